@@ -0,0 +1,512 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Bootstrapping of overnight index swap (OIS) discount curves.
+//!
+//! The instantaneous forward curve is modelled as
+//!
+//! $$
+//! f(t) = f_s(t) + \sum_j J_j \mathbb{1}_{t \geq \tau_j}
+//! $$
+//!
+//! where $f_s$ is continuous and piecewise linear between the pillars
+//! (the swap maturities), and $J_j$ are the jumps at the specified jump
+//! dates $\tau_j$ (e.g. central bank meeting dates, or the turn of the year).
+//!
+//! The pillar forwards and jump sizes are solved for together, by minimising
+//! the curvature of $f_s$ subject to every quote repricing to par.
+//! A step in the forward is therefore attributed to the jump dates,
+//! rather than smeared across the neighbouring pillars.
+
+use crate::data::CurveModel;
+use crate::error::RustQuantError;
+use crate::time::DayCountConvention;
+use nalgebra::{DMatrix, DVector};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Par rate quote for an overnight index swap with annual fixed payments.
+#[derive(Debug, Clone, Copy)]
+pub struct SwapQuote {
+    /// Maturity date of the swap.
+    pub maturity_date: Date,
+
+    /// Par (fixed) rate of the swap.
+    pub rate: f64,
+}
+
+/// Bootstrapper for OIS discount curves, with optional forward jumps.
+#[derive(Debug, Clone)]
+pub struct CurveBootstrapper {
+    /// Valuation date of the curve.
+    pub valuation_date: Date,
+
+    /// Calibration instruments.
+    pub quotes: Vec<SwapQuote>,
+
+    /// Dates at which the instantaneous forward is allowed to jump.
+    pub jump_dates: Vec<Date>,
+
+    /// Day count convention for the curve times.
+    pub day_count_convention: DayCountConvention,
+}
+
+/// Bootstrapped forward curve.
+#[derive(Debug, Clone)]
+pub struct BootstrappedCurve {
+    /// Valuation date of the curve.
+    pub valuation_date: Date,
+
+    /// Node times (in years), starting at zero, then one per pillar.
+    pub node_times: Vec<f64>,
+
+    /// Smooth instantaneous forward rates at the nodes.
+    pub node_forwards: Vec<f64>,
+
+    /// Jump times (in years).
+    pub jump_times: Vec<f64>,
+
+    /// Jump sizes of the instantaneous forward.
+    pub jump_sizes: Vec<f64>,
+
+    /// Day count convention for the curve times.
+    pub day_count_convention: DayCountConvention,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fixed leg payment times of a swap maturing at `T` (in years):
+/// annual, backwards from maturity, with a short first period.
+pub(crate) fn fixed_leg_times(T: f64) -> Vec<f64> {
+    let mut times: Vec<f64> = (0..)
+        .map(|k| T - k as f64)
+        .take_while(|&t| t > 1e-8)
+        .collect();
+    times.reverse();
+    times
+}
+
+impl CurveBootstrapper {
+    /// Create a new bootstrapper.
+    pub fn new(valuation_date: Date, quotes: Vec<SwapQuote>, jump_dates: Vec<Date>) -> Self {
+        Self {
+            valuation_date,
+            quotes,
+            jump_dates,
+            day_count_convention: DayCountConvention::default(),
+        }
+    }
+
+    /// Set the day count convention (Actual/Actual ISDA by default).
+    #[must_use]
+    pub fn with_day_count_convention(mut self, day_count_convention: DayCountConvention) -> Self {
+        self.day_count_convention = day_count_convention;
+        self
+    }
+
+    /// Bootstrap the curve.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no quotes, the quotes
+    ///   are not sorted by maturity, or a jump date is not after the valuation date.
+    /// - `RustQuantError::ComputationError` if the solver does not converge.
+    pub fn bootstrap(&self) -> Result<BootstrappedCurve, RustQuantError> {
+        const MAX_ITERATIONS: usize = 50;
+        const TOLERANCE: f64 = 1e-13;
+
+        // Small ridge so that the quadratic penalty is positive definite.
+        const RIDGE: f64 = 1e-10;
+
+        if self.quotes.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "At least one quote is required.".to_string(),
+            ));
+        }
+
+        let mut node_times = vec![0.0];
+        for quote in &self.quotes {
+            let t = self
+                .day_count_convention
+                .day_count_factor(self.valuation_date, quote.maturity_date);
+
+            if t <= *node_times.last().unwrap() {
+                return Err(RustQuantError::InvalidArgument(
+                    "Quotes must have increasing maturities after the valuation date.".to_string(),
+                ));
+            }
+
+            node_times.push(t);
+        }
+
+        let jump_times: Vec<f64> = self
+            .jump_dates
+            .iter()
+            .map(|&date| {
+                self.day_count_convention
+                    .day_count_factor(self.valuation_date, date)
+            })
+            .collect();
+
+        if jump_times.iter().any(|&t| t <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Jump dates must be after the valuation date.".to_string(),
+            ));
+        }
+
+        let n_nodes = node_times.len();
+        let n_params = n_nodes + jump_times.len();
+        let n_quotes = self.quotes.len();
+
+        let mut curve = BootstrappedCurve {
+            valuation_date: self.valuation_date,
+            node_forwards: vec![self.quotes[0].rate; n_nodes],
+            node_times,
+            jump_sizes: vec![0.0; jump_times.len()],
+            jump_times,
+            day_count_convention: self.day_count_convention,
+        };
+
+        // Curvature penalty: squared changes in slope of the smooth forward.
+        let mut H = DMatrix::<f64>::identity(n_params, n_params) * RIDGE;
+        for k in 1..n_nodes.saturating_sub(1) {
+            let h0 = curve.node_times[k] - curve.node_times[k - 1];
+            let h1 = curve.node_times[k + 1] - curve.node_times[k];
+
+            let mut row = DVector::<f64>::zeros(n_params);
+            row[k - 1] = 1.0 / h0;
+            row[k] = -1.0 / h0 - 1.0 / h1;
+            row[k + 1] = 1.0 / h1;
+
+            H += &row * row.transpose();
+        }
+
+        let schedules: Vec<Vec<f64>> = curve.node_times[1..]
+            .iter()
+            .map(|&T| fixed_leg_times(T))
+            .collect();
+
+        for _ in 0..MAX_ITERATIONS {
+            let params = curve.parameters();
+
+            // Residuals (swap PVs per unit notional) and their Jacobian.
+            let mut g = DVector::<f64>::zeros(n_quotes);
+            let mut A = DMatrix::<f64>::zeros(n_quotes, n_params);
+
+            for (i, (quote, times)) in self.quotes.iter().zip(&schedules).enumerate() {
+                let mut previous = 0.0;
+
+                for &t in times {
+                    let alpha = t - previous;
+                    let df = curve.discount_factor_at(t);
+                    let c = curve.integral_weights(t);
+
+                    g[i] += quote.rate * alpha * df;
+                    for p in 0..n_params {
+                        A[(i, p)] -= quote.rate * alpha * df * c[p];
+                    }
+
+                    previous = t;
+                }
+
+                // Floating leg: 1 - DF(T).
+                let T = *times.last().unwrap();
+                let df = curve.discount_factor_at(T);
+                let c = curve.integral_weights(T);
+
+                g[i] -= 1.0 - df;
+                for p in 0..n_params {
+                    A[(i, p)] -= df * c[p];
+                }
+            }
+
+            if g.amax() < TOLERANCE {
+                return Ok(curve);
+            }
+
+            // Minimise the penalty subject to the linearised repricing constraints.
+            let size = n_params + n_quotes;
+            let mut kkt = DMatrix::<f64>::zeros(size, size);
+            kkt.view_mut((0, 0), (n_params, n_params)).copy_from(&H);
+            kkt.view_mut((n_params, 0), (n_quotes, n_params))
+                .copy_from(&A);
+            kkt.view_mut((0, n_params), (n_params, n_quotes))
+                .copy_from(&A.transpose());
+
+            let mut rhs = DVector::<f64>::zeros(size);
+            rhs.rows_mut(n_params, n_quotes)
+                .copy_from(&(&A * &params - &g));
+
+            let solution = kkt
+                .lu()
+                .solve(&rhs)
+                .ok_or(RustQuantError::ComputationError(
+                    "Singular system while bootstrapping the curve.".to_string(),
+                ))?;
+
+            curve.set_parameters(solution.rows(0, n_params).as_slice());
+        }
+
+        Err(RustQuantError::ComputationError(
+            "Curve bootstrap did not converge.".to_string(),
+        ))
+    }
+}
+
+impl BootstrappedCurve {
+    /// Year fraction from the valuation date, used for the curve time.
+    fn year_fraction(&self, date: Date) -> f64 {
+        self.day_count_convention
+            .day_count_factor(self.valuation_date, date)
+    }
+
+    fn parameters(&self) -> DVector<f64> {
+        DVector::from_iterator(
+            self.node_forwards.len() + self.jump_sizes.len(),
+            self.node_forwards
+                .iter()
+                .chain(self.jump_sizes.iter())
+                .copied(),
+        )
+    }
+
+    fn set_parameters(&mut self, params: &[f64]) {
+        let n = self.node_forwards.len();
+
+        self.node_forwards.copy_from_slice(&params[..n]);
+        self.jump_sizes.copy_from_slice(&params[n..]);
+    }
+
+    /// Weights $c(t)$ such that $\int_0^t f(s) ds = c(t)^\top p$,
+    /// where $p$ stacks the node forwards and the jump sizes.
+    fn integral_weights(&self, t: f64) -> Vec<f64> {
+        let n = self.node_times.len();
+        let mut weights = vec![0.0; n + self.jump_sizes.len()];
+
+        for k in 0..n - 1 {
+            let (t0, t1) = (self.node_times[k], self.node_times[k + 1]);
+
+            if t <= t0 {
+                break;
+            }
+
+            let h = t1 - t0;
+            let s = t.min(t1) - t0;
+
+            weights[k] += s - s * s / (2.0 * h);
+            weights[k + 1] += s * s / (2.0 * h);
+        }
+
+        // Flat extrapolation beyond the last pillar.
+        weights[n - 1] += (t - self.node_times[n - 1]).max(0.0);
+
+        for (j, &tau) in self.jump_times.iter().enumerate() {
+            weights[n + j] = (t - tau).max(0.0);
+        }
+
+        weights
+    }
+
+    /// Instantaneous forward rate at time `t` (in years).
+    pub fn forward_rate_at(&self, t: f64) -> f64 {
+        let n = self.node_times.len();
+
+        let smooth = if t >= self.node_times[n - 1] {
+            self.node_forwards[n - 1]
+        } else {
+            let k = self.node_times.partition_point(|&x| x <= t).max(1);
+            let (t0, t1) = (self.node_times[k - 1], self.node_times[k]);
+            let (f0, f1) = (self.node_forwards[k - 1], self.node_forwards[k]);
+
+            f0 + (f1 - f0) * (t - t0) / (t1 - t0)
+        };
+
+        let jumps: f64 = self
+            .jump_times
+            .iter()
+            .zip(&self.jump_sizes)
+            .filter(|(&tau, _)| t >= tau)
+            .map(|(_, &size)| size)
+            .sum();
+
+        smooth + jumps
+    }
+
+    /// Discount factor at time `t` (in years).
+    pub fn discount_factor_at(&self, t: f64) -> f64 {
+        let integral: f64 = self
+            .integral_weights(t)
+            .iter()
+            .zip(self.node_forwards.iter().chain(self.jump_sizes.iter()))
+            .map(|(c, p)| c * p)
+            .sum();
+
+        (-integral).exp()
+    }
+}
+
+impl CurveModel for BootstrappedCurve {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.forward_rate_at(self.year_fraction(date))
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        let t = self.year_fraction(date);
+
+        if t <= 0.0 {
+            return self.forward_rate_at(0.0);
+        }
+
+        -self.discount_factor_at(t).ln() / t
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.discount_factor_at(self.year_fraction(date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bootstrap {
+    use super::*;
+    use crate::assert_approx_equal;
+    use time::{macros::date, Duration};
+
+    const VALUATION: Date = date!(2024 - 01 - 02);
+    const MEETING: Date = date!(2024 - 03 - 20);
+
+    fn year_fraction(start: Date, end: Date) -> f64 {
+        DayCountConvention::default().day_count_factor(start, end)
+    }
+
+    // True forward: 5.00%, stepping down 25bp at the meeting date.
+    fn true_curve() -> BootstrappedCurve {
+        BootstrappedCurve {
+            valuation_date: VALUATION,
+            node_times: vec![0.0, 10.0],
+            node_forwards: vec![0.05, 0.05],
+            jump_times: vec![year_fraction(VALUATION, MEETING)],
+            jump_sizes: vec![-0.0025],
+            day_count_convention: DayCountConvention::default(),
+        }
+    }
+
+    fn par_quotes() -> Vec<SwapQuote> {
+        let curve = true_curve();
+
+        [30, 61, 91, 182, 273, 365, 730, 1095, 1826]
+            .iter()
+            .map(|&days| {
+                let maturity_date = VALUATION + Duration::days(days);
+                let T = year_fraction(VALUATION, maturity_date);
+
+                let mut times: Vec<f64> = (0..)
+                    .map(|k| T - k as f64)
+                    .take_while(|&t| t > 1e-8)
+                    .collect();
+                times.reverse();
+
+                let mut annuity = 0.0;
+                let mut previous = 0.0;
+                for t in times {
+                    annuity += (t - previous) * curve.discount_factor_at(t);
+                    previous = t;
+                }
+
+                SwapQuote {
+                    maturity_date,
+                    rate: (1.0 - curve.discount_factor_at(T)) / annuity,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bootstrap_with_meeting_date_jump() {
+        let quotes = par_quotes();
+        let curve = CurveBootstrapper::new(VALUATION, quotes.clone(), vec![MEETING])
+            .bootstrap()
+            .unwrap();
+
+        // The forward steps at the meeting date.
+        let day = 1.0 / 366.0;
+        let tau = curve.jump_times[0];
+        assert_approx_equal!(curve.jump_sizes[0], -0.0025, 1e-6);
+        assert_approx_equal!(
+            curve.forward_rate_at(tau) - curve.forward_rate_at(tau - day),
+            -0.0025,
+            1e-6
+        );
+
+        // And is smooth elsewhere, including across the pillars.
+        for &t in curve.node_times[1..curve.node_times.len() - 1].iter() {
+            let step = curve.forward_rate_at(t + day) - curve.forward_rate_at(t - day);
+            assert!(step.abs() < 1e-6);
+        }
+
+        // The calibration instruments reprice to par.
+        for quote in &quotes {
+            let true_df = true_curve().discount_factor(quote.maturity_date);
+            assert_approx_equal!(curve.discount_factor(quote.maturity_date), true_df, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_without_jumps_smears_the_step() {
+        let curve = CurveBootstrapper::new(VALUATION, par_quotes(), vec![])
+            .bootstrap()
+            .unwrap();
+
+        let day = 1.0 / 366.0;
+        let tau = year_fraction(VALUATION, MEETING);
+        let step = curve.forward_rate_at(tau) - curve.forward_rate_at(tau - day);
+
+        assert!(step.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_day_count_convention() {
+        let quotes = par_quotes();
+        let curve = CurveBootstrapper::new(VALUATION, quotes.clone(), vec![MEETING])
+            .with_day_count_convention(DayCountConvention::Actual_365_Fixed)
+            .bootstrap()
+            .unwrap();
+
+        assert_eq!(
+            curve.day_count_convention,
+            DayCountConvention::Actual_365_Fixed
+        );
+
+        for (quote, &t) in quotes.iter().zip(&curve.node_times[1..]) {
+            let days = (quote.maturity_date - VALUATION).whole_days() as f64;
+
+            assert_eq!(t, days / 365.0);
+        }
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        let mut quotes = par_quotes();
+        quotes.swap(0, 1);
+
+        assert!(CurveBootstrapper::new(VALUATION, quotes, vec![])
+            .bootstrap()
+            .is_err());
+        assert!(CurveBootstrapper::new(VALUATION, vec![], vec![])
+            .bootstrap()
+            .is_err());
+    }
+}
@@ -0,0 +1,229 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Memoizing wrapper around a [`CurveModel`].
+//!
+//! Forward rates, spot rates, and discount factors are cached per date, so
+//! repeated queries (e.g. in bootstrapping or pricing loops) only evaluate
+//! the model once per date.
+//!
+//! Nothing is ever evicted: the cache holds one entry per distinct date
+//! queried, per quantity (roughly 40 bytes each, so about 100 KB for every
+//! business day over ten years). This is intended for the bounded date
+//! sets of a schedule or a pricing grid, not for unbounded streams of dates.
+//!
+//! Some models measure time from today rather than from a fixed valuation
+//! date (e.g. [`NelsonSiegel`](crate::models::NelsonSiegel) and
+//! [`NelsonSiegelSvensson`](crate::models::NelsonSiegelSvensson)), so the
+//! value for a given date changes from one day to the next. The cache is
+//! therefore dropped whenever it is queried on a later day than it was
+//! filled.
+
+use crate::data::CurveModel;
+use crate::time::today;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Curve model whose rates and discount factors are memoized by date.
+#[derive(Debug)]
+pub struct CachedCurve<M: CurveModel> {
+    /// The underlying curve model.
+    model: M,
+
+    /// The day the cached values were computed on.
+    as_of: RwLock<Date>,
+
+    forward_rates: RwLock<HashMap<Date, f64>>,
+    spot_rates: RwLock<HashMap<Date, f64>>,
+    discount_factors: RwLock<HashMap<Date, f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<M: CurveModel> CachedCurve<M> {
+    /// Wrap a curve model with an (initially empty) cache.
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            as_of: RwLock::new(today()),
+            forward_rates: RwLock::new(HashMap::new()),
+            spot_rates: RwLock::new(HashMap::new()),
+            discount_factors: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The underlying curve model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Total number of cached values.
+    pub fn len(&self) -> usize {
+        [
+            &self.forward_rates,
+            &self.spot_rates,
+            &self.discount_factors,
+        ]
+        .iter()
+        .map(|cache| cache.read().unwrap().len())
+        .sum()
+    }
+
+    /// Whether nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached values, e.g. to free the memory once a pricing run
+    /// is done. The model cannot change under the cache, so this is never
+    /// needed for the values to be correct.
+    pub fn clear(&self) {
+        for cache in [
+            &self.forward_rates,
+            &self.spot_rates,
+            &self.discount_factors,
+        ] {
+            cache.write().unwrap().clear();
+        }
+    }
+
+    /// Drop the cached values if they were computed before `day`.
+    fn roll_to(&self, day: Date) {
+        if *self.as_of.read().unwrap() == day {
+            return;
+        }
+
+        let mut as_of = self.as_of.write().unwrap();
+
+        if *as_of != day {
+            self.clear();
+            *as_of = day;
+        }
+    }
+
+    fn cached<F>(&self, cache: &RwLock<HashMap<Date, f64>>, date: Date, compute: F) -> f64
+    where
+        F: FnOnce() -> f64,
+    {
+        self.roll_to(today());
+
+        if let Some(&value) = cache.read().unwrap().get(&date) {
+            return value;
+        }
+
+        *cache.write().unwrap().entry(date).or_insert_with(compute)
+    }
+}
+
+impl<M: CurveModel> CurveModel for CachedCurve<M> {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.cached(&self.forward_rates, date, || self.model.forward_rate(date))
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.cached(&self.spot_rates, date, || self.model.spot_rate(date))
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.cached(&self.discount_factors, date, || {
+            self.model.discount_factor(date)
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cached_curve {
+    use super::*;
+    use crate::models::NelsonSiegelSvensson;
+    use crate::time::today;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use time::Duration;
+
+    /// Flat curve that counts how often it is evaluated.
+    struct CountingCurve {
+        calls: AtomicUsize,
+    }
+
+    impl CurveModel for CountingCurve {
+        fn forward_rate(&self, _date: Date) -> f64 {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            0.05
+        }
+
+        fn spot_rate(&self, _date: Date) -> f64 {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            0.05
+        }
+
+        fn discount_factor(&self, _date: Date) -> f64 {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            0.95
+        }
+    }
+
+    #[test]
+    fn test_cached_curve_memoizes() {
+        let curve = CachedCurve::new(CountingCurve {
+            calls: AtomicUsize::new(0),
+        });
+
+        let dates: Vec<Date> = (1..=10).map(|i| today() + Duration::days(i)).collect();
+
+        for _ in 0..100 {
+            for &date in &dates {
+                assert_eq!(curve.discount_factor(date), 0.95);
+                assert_eq!(curve.spot_rate(date), 0.05);
+            }
+        }
+
+        assert_eq!(curve.model().calls.load(Ordering::Relaxed), 20);
+        assert_eq!(curve.len(), 20);
+
+        // Values computed on an earlier day are dropped.
+        curve.roll_to(today() + Duration::days(1));
+        assert!(curve.is_empty());
+
+        curve.roll_to(today());
+        assert_eq!(curve.discount_factor(dates[0]), 0.95);
+        assert_eq!(curve.len(), 1);
+
+        curve.clear();
+        assert!(curve.is_empty());
+    }
+
+    #[test]
+    fn test_cached_curve_matches_model() {
+        let nss = NelsonSiegelSvensson::new(0.0806, -0.0031, -0.0625, -0.0198, 1.58, 0.15);
+        let cached = CachedCurve::new(nss);
+
+        let dates: Vec<Date> = (1..=360)
+            .map(|i| today() + Duration::days(30 * i))
+            .collect();
+
+        // Repeated queries return the model's values, computed once per date.
+        for _ in 0..3 {
+            for &date in &dates {
+                assert_eq!(cached.discount_factor(date), nss.discount_factor(date));
+            }
+        }
+
+        assert_eq!(cached.len(), dates.len());
+    }
+}
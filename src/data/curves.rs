@@ -26,19 +26,25 @@
 //! present in the curve. At that point, the curve is fit to the model and the
 //! rate is interpolated.
 
+use crate::data::bootstrap::{fixed_leg_times, BootstrappedCurve, CurveBootstrapper, SwapQuote};
+use crate::error::CurveError;
 use crate::math::{
     interpolation::{ExponentialInterpolator, Interpolator, LinearInterpolator},
     InterpolationIndex,
 };
 use crate::models::NelsonSiegelSvensson;
-use crate::time::{Calendar, DateRollingConvention, DayCountConvention};
+use crate::time::{Calendar, DateRollingConvention, DayCountConvention, Schedule};
 use argmin::{
     core::{CostFunction, Executor, State},
     solver::particleswarm::ParticleSwarm,
 };
 use derive_builder::Builder;
 use plotly::{color::NamedColor, common::Marker, common::Mode, Plot, Scatter};
-use std::{collections::BTreeMap, hash::Hash, iter::zip};
+use std::{
+    collections::BTreeMap,
+    hash::Hash,
+    iter::{once, zip},
+};
 use time::Date;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -55,6 +61,39 @@ pub trait CurveModel {
 
     /// Returns the discount factor for a given date.
     fn discount_factor(&self, date: time::Date) -> f64;
+
+    /// Checks the forward curve for negative rates on the given dates,
+    /// e.g. after calibration or interpolation.
+    ///
+    /// # Errors
+    /// - `CurveError::NoPoints` if the grid is empty.
+    /// - `CurveError::NegativeForwardRate` at the first date with a negative forward.
+    fn validate_no_negative_forwards(&self, grid: &[time::Date]) -> Result<(), CurveError> {
+        if grid.is_empty() {
+            return Err(CurveError::NoPoints);
+        }
+
+        match grid.iter().find(|&&date| self.forward_rate(date) < 0.0) {
+            Some(&date) => Err(CurveError::NegativeForwardRate(date)),
+            None => Ok(()),
+        }
+    }
+
+    /// Annuity (PVBP) of a fixed leg: $A = \sum_i \tau_i P(t_i)$, the present
+    /// value of one unit of rate paid on every period.
+    ///
+    /// The schedule dates are the period boundaries, starting with the
+    /// effective date, and the accruals $\tau_i$ are measured with
+    /// `day_count`. The par swap rate is then $(P(t_0) - P(t_n)) / A$.
+    fn annuity(&self, schedule: &Schedule, day_count: DayCountConvention) -> f64 {
+        schedule
+            .dates
+            .windows(2)
+            .map(|period| {
+                day_count.day_count_factor(period[0], period[1]) * self.discount_factor(period[1])
+            })
+            .sum()
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -372,7 +411,7 @@ macro_rules! impl_specific_curve_cost_function {
 }
 
 macro_rules! impl_specific_curve {
-    ($curve:ident, $curve_function:ident) => {
+    ($curve:ident, $curve_function:ident $(, $field:ident: $default:expr)*) => {
         impl<C> Curves<C> for $curve<Date, C>
         where
             C: Calendar + Clone,
@@ -427,6 +466,7 @@ macro_rules! impl_specific_curve {
                     nss: NelsonSiegelSvensson::default(),
                     fitted: false,
                     fitted_curve: None,
+                    $($field: $default,)*
                 }
             }
 
@@ -572,10 +612,161 @@ where
     /// Fitted curve.
     #[builder(default)]
     pub fitted_curve: Option<Curve<I>>,
+
+    /// Calibration instruments of a bootstrapped curve
+    /// (see [`DiscountCurve::from_instruments`]).
+    #[builder(default)]
+    pub quotes: Vec<SwapQuote>,
+
+    /// Forward curve solved for by the bootstrap, which interpolates
+    /// between the nodes for the [`CurveModel`] methods.
+    #[builder(default)]
+    pub bootstrapped: Option<BootstrappedCurve>,
 }
 
 impl_specific_curve_cost_function!(DiscountCurve, discount_factor);
-impl_specific_curve!(DiscountCurve, discount_factor);
+impl_specific_curve!(
+    DiscountCurve,
+    discount_factor,
+    quotes: Vec::new(),
+    bootstrapped: None
+);
+
+impl<C> DiscountCurve<Date, C>
+where
+    C: Calendar,
+{
+    /// Bootstrap a discount curve from par swap quotes, keeping the quotes.
+    ///
+    /// The quotes are overnight index swaps with annual fixed payments (see
+    /// [`SwapQuote`]), sorted by maturity. The forward curve is solved for
+    /// by a [`CurveBootstrapper`] (without jumps), with curve times measured
+    /// with `convention`. The nodes are the discount factors at the
+    /// valuation date and at each maturity, and the [`CurveModel`] methods
+    /// use the bootstrapped forward curve between them, so the quotes
+    /// reprice to par.
+    ///
+    /// The curve is then checked for negative forward rates at the pillars
+    /// (see [`CurveModel::validate_no_negative_forwards`]).
+    ///
+    /// # Errors
+    /// - `CurveError::NoPoints` if there are no quotes.
+    /// - `CurveError::UnorderedDate` if the maturities are not strictly
+    ///   increasing and after the valuation date.
+    /// - `CurveError::BootstrapFailed` if the bootstrap does not converge,
+    ///   e.g. if only a non-positive discount factor reprices a quote
+    ///   (a rate that is far too high).
+    /// - `CurveError::NegativeForwardRate` if the curve implies a negative
+    ///   forward rate (e.g. a rate that is far too low).
+    pub fn from_instruments(
+        valuation_date: Date,
+        quotes: &[SwapQuote],
+        convention: DayCountConvention,
+    ) -> Result<Self, CurveError> {
+        let last = quotes.last().ok_or(CurveError::NoPoints)?;
+
+        let mut previous = 0.0;
+
+        for quote in quotes {
+            let T = convention.day_count_factor(valuation_date, quote.maturity_date);
+
+            if T <= previous {
+                return Err(CurveError::UnorderedDate(quote.maturity_date));
+            }
+
+            previous = T;
+        }
+
+        let bootstrapped = CurveBootstrapper::new(valuation_date, quotes.to_vec(), Vec::new())
+            .with_day_count_convention(convention)
+            .bootstrap()
+            .map_err(|_| CurveError::BootstrapFailed(last.maturity_date))?;
+
+        let dates: Vec<Date> = once(valuation_date)
+            .chain(quotes.iter().map(|quote| quote.maturity_date))
+            .collect();
+        let discount_factors: Vec<f64> = bootstrapped
+            .node_times
+            .iter()
+            .map(|&t| bootstrapped.discount_factor_at(t))
+            .collect();
+
+        let curve = Self {
+            curve: Curve::<Date>::new_from_slice(&dates, &discount_factors),
+            calendar: None,
+            day_count_convention: Some(convention),
+            date_rolling_convention: None,
+            nss: NelsonSiegelSvensson::default(),
+            fitted: false,
+            fitted_curve: None,
+            quotes: quotes.to_vec(),
+            bootstrapped: Some(bootstrapped),
+        };
+
+        curve.validate_no_negative_forwards(&dates[1..])?;
+
+        Ok(curve)
+    }
+
+    /// Par rate of an overnight index swap with annual fixed payments
+    /// (as for a [`SwapQuote`]), from the valuation date to the maturity date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the curve was not bootstrapped (see [`DiscountCurve::from_instruments`]).
+    pub fn par_rate(&self, maturity_date: Date) -> f64 {
+        let curve = self.bootstrapped_curve();
+        let T = curve
+            .day_count_convention
+            .day_count_factor(curve.valuation_date, maturity_date);
+
+        let (annuity, _) = fixed_leg_times(T)
+            .into_iter()
+            .fold((0.0, 0.0), |(annuity, previous), t| {
+                (annuity + (t - previous) * curve.discount_factor_at(t), t)
+            });
+
+        (1.0 - curve.discount_factor_at(T)) / annuity
+    }
+
+    /// Par rate minus quoted rate of each calibration instrument, which is
+    /// zero (up to the solver's tolerance) for a bootstrapped curve.
+    pub fn repricing_errors(&self) -> Vec<f64> {
+        self.quotes
+            .iter()
+            .map(|quote| self.par_rate(quote.maturity_date) - quote.rate)
+            .collect()
+    }
+
+    fn bootstrapped_curve(&self) -> &BootstrappedCurve {
+        self.bootstrapped
+            .as_ref()
+            .expect("Only a bootstrapped DiscountCurve interpolates its nodes.")
+    }
+}
+
+/// The bootstrapped forward curve (see [`DiscountCurve::from_instruments`]),
+/// rather than the Nelson-Siegel-Svensson fit used by [`Curves::get_rate`].
+///
+/// # Panics
+///
+/// The methods panic if the curve was not bootstrapped.
+impl<C> CurveModel for DiscountCurve<Date, C>
+where
+    C: Calendar,
+{
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.bootstrapped_curve().forward_rate(date)
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.bootstrapped_curve().spot_rate(date)
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.bootstrapped_curve().discount_factor(date)
+    }
+}
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // SPOT CURVE
@@ -841,6 +1032,155 @@ where
 
 #[cfg(test)]
 mod tests_curves {
+    use super::*;
+    use crate::models::NelsonSiegel;
+    use crate::time::today;
+    use time::Duration;
+
+    /// Semi-annual fixed leg of a 5Y swap starting in one month.
+    fn fixed_leg() -> Schedule {
+        let dates: Vec<Date> = (0..=10)
+            .map(|i| today() + Duration::days(30 + 182 * i))
+            .collect();
+        let day_counting_convention = DayCountConvention::Thirty_360_ISDA;
+
+        Schedule {
+            day_count_factors: dates
+                .windows(2)
+                .map(|period| day_counting_convention.day_count_factor(period[0], period[1]))
+                .collect(),
+            dates,
+            day_counting_convention,
+            date_rolling_convention: DateRollingConvention::Actual,
+        }
+    }
+
+    #[test]
+    fn test_annuity_par_swap_rate() {
+        let curve = NelsonSiegel::new(4.0, -1.5, 1.0, 2.0);
+        let schedule = fixed_leg();
+        let annuity = curve.annuity(&schedule, schedule.day_counting_convention);
+
+        let df_start = curve.discount_factor(schedule.dates[0]);
+        let df_end = curve.discount_factor(*schedule.dates.last().unwrap());
+
+        // Quarterly floating leg, projected off the same curve.
+        let float_dates: Vec<Date> = (0..=20)
+            .map(|i| today() + Duration::days(30 + 91 * i))
+            .chain(std::iter::once(*schedule.dates.last().unwrap()))
+            .collect();
+        let float_leg: f64 = float_dates
+            .windows(2)
+            .filter(|period| period[0] < period[1])
+            .map(|period| {
+                let tau = DayCountConvention::Actual_360.day_count_factor(period[0], period[1]);
+                let (df_0, df_1) = (
+                    curve.discount_factor(period[0]),
+                    curve.discount_factor(period[1]),
+                );
+                let forward = (df_0 / df_1 - 1.0) / tau;
+
+                tau * forward * df_1
+            })
+            .sum();
+
+        let par_rate = float_leg / annuity;
+
+        assert!(annuity > 0.0);
+        assert!((par_rate - (df_start - df_end) / annuity).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_annuity_decreases_as_rates_rise() {
+        let curve = NelsonSiegel::new(4.0, -1.5, 1.0, 2.0);
+        let schedule = fixed_leg();
+
+        let annuities: Vec<f64> = [-100.0, 0.0, 100.0, 200.0]
+            .iter()
+            .map(|&bps| {
+                curve
+                    .parallel_shifted(bps)
+                    .annuity(&schedule, schedule.day_counting_convention)
+            })
+            .collect();
+
+        assert!(annuities.iter().all(|&annuity| annuity > 0.0));
+        assert!(annuities.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_discount_curve_from_instruments() {
+        use crate::time::north_america::united_states::UnitedStatesCalendar;
+        use time::macros::date;
+
+        type Bootstrapped = DiscountCurve<Date, UnitedStatesCalendar>;
+
+        let valuation_date = date!(2024 - 01 - 02);
+        let quotes = |rates: &[(u8, f64)]| -> Vec<SwapQuote> {
+            rates
+                .iter()
+                .map(|&(years, rate)| SwapQuote {
+                    maturity_date: valuation_date.replace_year(2024 + years as i32).unwrap(),
+                    rate,
+                })
+                .collect()
+        };
+        let bootstrap = |quotes: &[SwapQuote]| {
+            Bootstrapped::from_instruments(
+                valuation_date,
+                quotes,
+                DayCountConvention::Actual_365_Fixed,
+            )
+        };
+
+        // Consistent quotes reprice to par.
+        let consistent = quotes(&[
+            (1, 0.030),
+            (2, 0.032),
+            (3, 0.034),
+            (5, 0.036),
+            (7, 0.037),
+            (10, 0.038),
+        ]);
+        let curve = bootstrap(&consistent).unwrap();
+
+        assert_eq!(curve.quotes.len(), consistent.len());
+        assert!(curve.repricing_errors().iter().all(|e| e.abs() < 1e-10));
+
+        let discount_factors = curve.curve.values();
+        assert_eq!(discount_factors[0], 1.0);
+        assert!(discount_factors.windows(2).all(|pair| pair[1] < pair[0]));
+
+        // The nodes are the bootstrapped curve's discount factors.
+        let pillar = consistent[3].maturity_date;
+        assert_eq!(curve.discount_factor(pillar), discount_factors[4]);
+
+        // Off the pillars, the par rates lie between the neighbouring quotes.
+        let par_rate = curve.par_rate(date!(2028 - 01 - 02));
+        assert!(0.034 < par_rate && par_rate < 0.036);
+
+        // A later tenor with an impossibly low rate needs a negative forward.
+        let too_low = quotes(&[(1, 0.04), (2, 0.04), (5, 0.005)]);
+        assert!(matches!(
+            bootstrap(&too_low),
+            Err(CurveError::NegativeForwardRate(date)) if date == too_low[2].maturity_date
+        ));
+
+        // The earlier coupons alone are worth more than par.
+        let too_high = quotes(&[(1, 0.04), (5, 0.04), (6, 0.6)]);
+        assert!(matches!(
+            bootstrap(&too_high),
+            Err(CurveError::BootstrapFailed(date)) if date == too_high[2].maturity_date
+        ));
+
+        // No quotes, or maturities out of order.
+        assert!(matches!(bootstrap(&[]), Err(CurveError::NoPoints)));
+        assert!(matches!(
+            bootstrap(&quotes(&[(2, 0.03), (1, 0.03)])),
+            Err(CurveError::UnorderedDate(_))
+        ));
+    }
+
     // use super::*;
     // use crate::time::today;
     // use time::Duration;
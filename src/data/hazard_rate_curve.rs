@@ -0,0 +1,157 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Credit curve of piecewise-constant hazard rates.
+//!
+//! The hazard rate $\lambda_i$ applies on $(t_{i-1}, t_i]$, with $t_0 = 0$,
+//! and the last rate is extended flat after the last pillar. The survival
+//! probability is
+//!
+//! $$
+//! Q(t) = \exp\left( -\int_0^t \lambda(s) \, ds \right)
+//! $$
+//!
+//! Times are year fractions from the valuation date.
+
+use crate::error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Credit curve with piecewise-constant hazard rates.
+#[derive(Debug, Clone)]
+pub struct HazardRateCurve {
+    /// End of each hazard rate period (in years), strictly increasing.
+    times: Vec<f64>,
+
+    /// Hazard rate on each period.
+    hazard_rates: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HazardRateCurve {
+    /// Create a curve from the end times of the periods and their hazard rates.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no periods, there
+    ///   are not as many rates as times, the times are not positive and
+    ///   strictly increasing, or a rate is negative.
+    pub fn new(times: Vec<f64>, hazard_rates: Vec<f64>) -> Result<Self, RustQuantError> {
+        if times.is_empty() || times.len() != hazard_rates.len() {
+            return Err(RustQuantError::InvalidArgument(
+                "One hazard rate is required per period, and at least one period.".to_string(),
+            ));
+        }
+
+        if times[0] <= 0.0 || times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(RustQuantError::InvalidArgument(
+                "Times must be positive and strictly increasing.".to_string(),
+            ));
+        }
+
+        if hazard_rates.iter().any(|&rate| rate < 0.0 || rate.is_nan()) {
+            return Err(RustQuantError::InvalidArgument(
+                "Hazard rates must be non-negative.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            times,
+            hazard_rates,
+        })
+    }
+
+    /// Curve with the same hazard rate at all times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hazard_rate` is negative.
+    pub fn flat(hazard_rate: f64) -> Self {
+        Self::new(vec![1.0], vec![hazard_rate]).expect("Hazard rate must be non-negative.")
+    }
+
+    /// Hazard rate at time `t`.
+    pub fn hazard_rate(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&end| end < t);
+
+        self.hazard_rates[i.min(self.hazard_rates.len() - 1)]
+    }
+
+    /// Integrated hazard rate from 0 to `t`.
+    pub fn cumulative_hazard(&self, t: f64) -> f64 {
+        let mut start = 0.0;
+        let mut integral = 0.0;
+
+        for (i, (&end, &rate)) in self.times.iter().zip(&self.hazard_rates).enumerate() {
+            let last = i == self.times.len() - 1;
+
+            if t <= end || last {
+                return integral + rate * (t - start).max(0.0);
+            }
+
+            integral += rate * (end - start);
+            start = end;
+        }
+
+        integral
+    }
+
+    /// Probability of surviving to time `t`.
+    pub fn survival_probability(&self, t: f64) -> f64 {
+        (-self.cumulative_hazard(t)).exp()
+    }
+
+    /// Probability of defaulting in $(s, t]$.
+    pub fn default_probability(&self, s: f64, t: f64) -> f64 {
+        self.survival_probability(s) - self.survival_probability(t)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hazard_rate_curve {
+    use super::*;
+    use crate::assert_approx_equal;
+
+    #[test]
+    fn test_piecewise_survival_probability() {
+        let curve = HazardRateCurve::new(vec![1.0, 3.0, 5.0], vec![0.01, 0.02, 0.04]).unwrap();
+
+        assert_eq!(curve.survival_probability(0.0), 1.0);
+        assert_eq!(curve.hazard_rate(1.0), 0.01);
+        assert_eq!(curve.hazard_rate(2.0), 0.02);
+        assert_eq!(curve.hazard_rate(10.0), 0.04);
+
+        assert_approx_equal!(curve.survival_probability(2.0), (-0.03_f64).exp(), 1e-15);
+        assert_approx_equal!(curve.survival_probability(5.0), (-0.13_f64).exp(), 1e-15);
+
+        // Flat extrapolation after the last pillar.
+        assert_approx_equal!(curve.survival_probability(7.0), (-0.21_f64).exp(), 1e-15);
+        assert_approx_equal!(
+            curve.default_probability(2.0, 7.0),
+            (-0.03_f64).exp() - (-0.21_f64).exp(),
+            1e-15
+        );
+    }
+
+    #[test]
+    fn test_invalid_curves() {
+        assert!(HazardRateCurve::new(vec![], vec![]).is_err());
+        assert!(HazardRateCurve::new(vec![1.0, 2.0], vec![0.01]).is_err());
+        assert!(HazardRateCurve::new(vec![2.0, 1.0], vec![0.01, 0.02]).is_err());
+        assert!(HazardRateCurve::new(vec![1.0], vec![-0.01]).is_err());
+    }
+}
@@ -0,0 +1,378 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Zero curve interpolated by a natural cubic spline.
+//!
+//! The continuously compounded spot rate $r(t)$ is a natural cubic spline
+//! through the knots (twice continuously differentiable, with zero second
+//! derivative at both ends). The instantaneous forward is then
+//!
+//! $$
+//! f(t) = \frac{d}{dt} \left( t \, r(t) \right) = r(t) + t \, r'(t)
+//! $$
+//!
+//! and the discount factor is $e^{-r(t) t}$.
+//!
+//! Beyond the last knot the forward is held flat at its value there, so the
+//! spot rate tends to it smoothly. Before the first knot the spot rate is
+//! held flat at the first knot's rate.
+
+use crate::data::CurveModel;
+use crate::error::RustQuantError;
+use crate::time::DayCountConvention;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Zero curve interpolated by a natural cubic spline on the spot rates.
+#[derive(Debug, Clone)]
+pub struct InterpolatedCurve {
+    /// Valuation date of the curve.
+    pub valuation_date: Date,
+
+    /// Knots, as (date, continuously compounded spot rate), sorted by date.
+    knots: Vec<(Date, f64)>,
+
+    /// Day count convention for the knot times.
+    day_count_convention: DayCountConvention,
+
+    /// Knot times (in years).
+    times: Vec<f64>,
+
+    /// Second derivatives of the spline at the knots.
+    second_derivatives: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl InterpolatedCurve {
+    /// Fit the spline through the knots, with knot times measured from the
+    /// valuation date in the given day count convention.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are fewer than two knots,
+    ///   or the knot dates (or their times under the day count convention)
+    ///   are not strictly increasing and on or after the valuation date.
+    pub fn new(
+        valuation_date: Date,
+        knots: Vec<(Date, f64)>,
+        day_count_convention: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        if knots.len() < 2 {
+            return Err(RustQuantError::InvalidArgument(
+                "At least two knots are required.".to_string(),
+            ));
+        }
+
+        if knots[0].0 < valuation_date || knots.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Knot dates must be strictly increasing, from the valuation date on.".to_string(),
+            ));
+        }
+
+        let times: Vec<f64> = knots
+            .iter()
+            .map(|&(date, _)| day_count_convention.day_count_factor(valuation_date, date))
+            .collect();
+
+        if times[0] < 0.0 || times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Knot times must be strictly increasing under the {day_count_convention} day count."
+            )));
+        }
+        let rates: Vec<f64> = knots.iter().map(|&(_, rate)| rate).collect();
+        let second_derivatives = natural_spline_second_derivatives(&times, &rates);
+
+        Ok(Self {
+            valuation_date,
+            knots,
+            day_count_convention,
+            times,
+            second_derivatives,
+        })
+    }
+
+    /// The knots, as (date, spot rate).
+    pub fn knots(&self) -> &[(Date, f64)] {
+        &self.knots
+    }
+
+    /// The day count convention for the knot times.
+    pub fn day_count_convention(&self) -> DayCountConvention {
+        self.day_count_convention
+    }
+
+    /// Year fraction from the valuation date, used for the curve time.
+    fn year_fraction(&self, date: Date) -> f64 {
+        self.day_count_convention
+            .day_count_factor(self.valuation_date, date)
+    }
+
+    /// Spline value, first, and second derivative at time `t` on the
+    /// piece between knots `i` and `i + 1`.
+    fn segment(&self, i: usize, t: f64) -> (f64, f64, f64) {
+        let (t0, t1) = (self.times[i], self.times[i + 1]);
+        let (r0, r1) = (self.knots[i].1, self.knots[i + 1].1);
+        let (m0, m1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+
+        let h = t1 - t0;
+        let (a, b) = ((t1 - t) / h, (t - t0) / h);
+
+        let value = a * r0 + b * r1 + ((a.powi(3) - a) * m0 + (b.powi(3) - b) * m1) * h * h / 6.0;
+        let slope = (r1 - r0) / h + ((1.0 - 3.0 * a * a) * m0 + (3.0 * b * b - 1.0) * m1) * h / 6.0;
+        let curvature = a * m0 + b * m1;
+
+        (value, slope, curvature)
+    }
+
+    /// Spline value and slope at time `t` within the knots.
+    fn spline(&self, t: f64) -> (f64, f64) {
+        let i = self.times[1..self.times.len() - 1].partition_point(|&knot| knot <= t);
+        let (value, slope, _) = self.segment(i, t);
+
+        (value, slope)
+    }
+
+    /// Last knot time, spot rate, and (flat extrapolated) forward rate.
+    fn last_knot(&self) -> (f64, f64, f64) {
+        let n = self.times.len() - 1;
+        let (t_n, r_n) = (self.times[n], self.knots[n].1);
+        let (_, slope, _) = self.segment(n - 1, t_n);
+
+        (t_n, r_n, r_n + t_n * slope)
+    }
+
+    fn spot_rate_at(&self, t: f64) -> f64 {
+        let (t_n, r_n, f_n) = self.last_knot();
+
+        if t <= self.times[0] {
+            self.knots[0].1
+        } else if t <= t_n {
+            self.spline(t).0
+        } else {
+            (t_n * r_n + f_n * (t - t_n)) / t
+        }
+    }
+
+    fn forward_rate_at(&self, t: f64) -> f64 {
+        let (t_n, _, f_n) = self.last_knot();
+
+        if t <= self.times[0] {
+            self.knots[0].1
+        } else if t <= t_n {
+            let (value, slope) = self.spline(t);
+
+            value + t * slope
+        } else {
+            f_n
+        }
+    }
+}
+
+impl CurveModel for InterpolatedCurve {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.forward_rate_at(self.year_fraction(date))
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.spot_rate_at(self.year_fraction(date))
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        let t = self.year_fraction(date);
+
+        (-self.spot_rate_at(t) * t).exp()
+    }
+}
+
+/// Second derivatives at the knots of the natural cubic spline through
+/// `(x, y)`, solving the tridiagonal system by the Thomas algorithm.
+fn natural_spline_second_derivatives(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut m = vec![0.0; n];
+
+    if n < 3 {
+        return m;
+    }
+
+    // Forward elimination on the interior equations
+    // h_{i-1} m_{i-1} + 2 (h_{i-1} + h_i) m_i + h_i m_{i+1} = 6 (s_i - s_{i-1}).
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+
+    for i in 1..n - 1 {
+        let (h_lo, h_hi) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+        let rhs_i = 6.0 * ((y[i + 1] - y[i]) / h_hi - (y[i] - y[i - 1]) / h_lo);
+
+        if i == 1 {
+            diagonal[i] = 2.0 * (h_lo + h_hi);
+            rhs[i] = rhs_i;
+        } else {
+            let ratio = h_lo / diagonal[i - 1];
+
+            diagonal[i] = 2.0 * (h_lo + h_hi) - ratio * h_lo;
+            rhs[i] = rhs_i - ratio * rhs[i - 1];
+        }
+    }
+
+    // Back substitution, with m_0 = m_{n-1} = 0.
+    for i in (1..n - 1).rev() {
+        let h_hi = x[i + 1] - x[i];
+
+        m[i] = (rhs[i] - h_hi * m[i + 1]) / diagonal[i];
+    }
+
+    m
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_interpolated_curve {
+    use super::*;
+    use crate::assert_approx_equal;
+    use time::{macros::date, Duration};
+
+    const VALUATION: Date = date!(2024 - 01 - 02);
+
+    fn year_fraction(start: Date, end: Date) -> f64 {
+        DayCountConvention::default().day_count_factor(start, end)
+    }
+
+    fn curve() -> InterpolatedCurve {
+        let knots = [
+            (30, 0.052),
+            (182, 0.050),
+            (365, 0.047),
+            (730, 0.042),
+            (1826, 0.040),
+            (3652, 0.043),
+        ]
+        .iter()
+        .map(|&(days, rate)| (VALUATION + Duration::days(days), rate))
+        .collect();
+
+        InterpolatedCurve::new(VALUATION, knots, DayCountConvention::default()).unwrap()
+    }
+
+    #[test]
+    fn test_knots_are_reproduced() {
+        let curve = curve();
+
+        for &(date, rate) in curve.knots() {
+            assert_eq!(curve.spot_rate(date), rate);
+            assert_approx_equal!(
+                curve.discount_factor(date),
+                (-rate * year_fraction(VALUATION, date)).exp(),
+                1e-15
+            );
+        }
+    }
+
+    #[test]
+    fn test_spline_is_twice_continuously_differentiable() {
+        let curve = curve();
+        let n = curve.times.len();
+
+        // The pieces agree in value, slope, and curvature at interior knots.
+        for i in 1..n - 1 {
+            let left = curve.segment(i - 1, curve.times[i]);
+            let right = curve.segment(i, curve.times[i]);
+
+            assert_approx_equal!(left.0, right.0, 1e-14);
+            assert_approx_equal!(left.1, right.1, 1e-12);
+            assert_approx_equal!(left.2, right.2, 1e-10);
+        }
+
+        // Natural end conditions.
+        assert_eq!(curve.segment(0, curve.times[0]).2, 0.0);
+        assert_eq!(curve.segment(n - 2, curve.times[n - 1]).2, 0.0);
+    }
+
+    #[test]
+    fn test_forward_and_extrapolation() {
+        let curve = curve();
+        let (t_n, _, f_n) = curve.last_knot();
+
+        // The forward is the derivative of t r(t).
+        for t in [0.1, 0.75, 1.5, 3.0, 7.0, 9.9, 12.0, 30.0] {
+            let h = 1e-5;
+            let yield_integral = |t: f64| t * curve.spot_rate_at(t);
+
+            assert_approx_equal!(
+                curve.forward_rate_at(t),
+                (yield_integral(t + h) - yield_integral(t - h)) / (2.0 * h),
+                1e-8
+            );
+        }
+
+        // Flat forward beyond the last knot, continuous at it.
+        let last = curve.knots()[curve.knots().len() - 1].0;
+        for years in [1, 5, 20] {
+            let date = last + Duration::days(365 * years);
+            assert_eq!(curve.forward_rate(date), f_n);
+        }
+        assert_approx_equal!(curve.forward_rate_at(t_n - 1e-9), f_n, 1e-8);
+        assert_approx_equal!(
+            curve.spot_rate_at(t_n + 1e-9),
+            curve.spot_rate_at(t_n),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_day_count_convention() {
+        let knots: Vec<(Date, f64)> = curve().knots().to_vec();
+        let curve =
+            InterpolatedCurve::new(VALUATION, knots.clone(), DayCountConvention::Actual_360)
+                .unwrap();
+
+        assert_eq!(curve.day_count_convention(), DayCountConvention::Actual_360);
+
+        for (&(date, rate), &t) in knots.iter().zip(&curve.times) {
+            let days = (date - VALUATION).whole_days() as f64;
+
+            assert_eq!(t, days / 360.0);
+            assert_approx_equal!(
+                curve.discount_factor(date),
+                (-rate * days / 360.0).exp(),
+                1e-15
+            );
+        }
+
+        // 30E/360 counts the 30th and 31st of January as the same day.
+        let same_time = vec![(date!(2024 - 01 - 30), 0.05), (date!(2024 - 01 - 31), 0.05)];
+        assert!(matches!(
+            InterpolatedCurve::new(VALUATION, same_time, DayCountConvention::Thirty_E_360),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_knots() {
+        let one = vec![(VALUATION + Duration::days(30), 0.05)];
+        let unsorted = vec![
+            (VALUATION + Duration::days(60), 0.05),
+            (VALUATION + Duration::days(30), 0.05),
+        ];
+
+        for knots in [one, unsorted] {
+            assert!(matches!(
+                InterpolatedCurve::new(VALUATION, knots, DayCountConvention::default()),
+                Err(RustQuantError::InvalidArgument(_))
+            ));
+        }
+    }
+}
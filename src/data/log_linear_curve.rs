@@ -0,0 +1,332 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Discount curve interpolated linearly in the log discount factors.
+//!
+//! Between pillars $t_i < t < t_{i+1}$,
+//!
+//! $$
+//! \ln P(t) = \frac{t_{i+1} - t}{t_{i+1} - t_i} \ln P(t_i)
+//!          + \frac{t - t_i}{t_{i+1} - t_i} \ln P(t_{i+1})
+//! $$
+//!
+//! so the instantaneous forward $f = -\frac{d}{dt} \ln P$ is constant on
+//! each period, which is the usual convention for bootstrapped curves.
+//!
+//! The valuation date is an implicit pillar with $P = 1$, so the first period
+//! runs from the valuation date to the first pillar. The forwards of the first
+//! and last periods are extended flat before and after the pillars, and at a
+//! pillar the forward of the following period applies.
+
+use crate::data::CurveModel;
+use crate::error::RustQuantError;
+use crate::time::DayCountConvention;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Discount curve with log-linear interpolation of the discount factors
+/// (piecewise-constant forward rates).
+#[derive(Debug, Clone)]
+pub struct LogLinearDiscountCurve {
+    /// Valuation date of the curve.
+    pub valuation_date: Date,
+
+    /// Pillars, as (date, discount factor), sorted by date.
+    pillars: Vec<(Date, f64)>,
+
+    /// Day count convention for the node times.
+    day_count_convention: DayCountConvention,
+
+    /// Node times (in years), starting with the valuation date.
+    times: Vec<f64>,
+
+    /// Log discount factors at the nodes, starting with zero.
+    log_discount_factors: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LogLinearDiscountCurve {
+    /// Create a curve from its pillars, with node times measured from the
+    /// valuation date in the given day count convention.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no pillars, the pillar
+    ///   dates (or their times under the day count convention) are not
+    ///   strictly increasing and after the valuation date, or a discount
+    ///   factor is not positive.
+    pub fn new(
+        valuation_date: Date,
+        pillars: Vec<(Date, f64)>,
+        day_count_convention: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        if pillars.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "At least one pillar is required.".to_string(),
+            ));
+        }
+
+        if pillars[0].0 <= valuation_date || pillars.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Pillar dates must be strictly increasing, after the valuation date.".to_string(),
+            ));
+        }
+
+        if pillars.iter().any(|&(_, df)| df <= 0.0 || df.is_nan()) {
+            return Err(RustQuantError::InvalidArgument(
+                "Discount factors must be positive.".to_string(),
+            ));
+        }
+
+        let times: Vec<f64> = std::iter::once(0.0)
+            .chain(
+                pillars
+                    .iter()
+                    .map(|&(date, _)| day_count_convention.day_count_factor(valuation_date, date)),
+            )
+            .collect();
+
+        if times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Pillar times must be strictly increasing under the {day_count_convention} day count."
+            )));
+        }
+
+        let log_discount_factors = std::iter::once(0.0)
+            .chain(pillars.iter().map(|&(_, df)| df.ln()))
+            .collect();
+
+        Ok(Self {
+            valuation_date,
+            pillars,
+            day_count_convention,
+            times,
+            log_discount_factors,
+        })
+    }
+
+    /// The pillars, as (date, discount factor).
+    pub fn pillars(&self) -> &[(Date, f64)] {
+        &self.pillars
+    }
+
+    /// The day count convention for the node times.
+    pub fn day_count_convention(&self) -> DayCountConvention {
+        self.day_count_convention
+    }
+
+    /// Year fraction from the valuation date, used for the curve time.
+    fn year_fraction(&self, date: Date) -> f64 {
+        self.day_count_convention
+            .day_count_factor(self.valuation_date, date)
+    }
+
+    /// Index of the period containing time `t` (periods are closed on the
+    /// left), clamped to the first and last periods.
+    fn period(&self, t: f64) -> usize {
+        let n = self.times.len();
+
+        self.times[1..n - 1].partition_point(|&node| node <= t)
+    }
+
+    /// Constant forward rate on period `i`.
+    fn period_forward(&self, i: usize) -> f64 {
+        -(self.log_discount_factors[i + 1] - self.log_discount_factors[i])
+            / (self.times[i + 1] - self.times[i])
+    }
+
+    fn log_discount_factor_at(&self, t: f64) -> f64 {
+        let i = self.period(t);
+
+        self.log_discount_factors[i] - self.period_forward(i) * (t - self.times[i])
+    }
+
+    fn forward_rate_at(&self, t: f64) -> f64 {
+        self.period_forward(self.period(t))
+    }
+
+    fn spot_rate_at(&self, t: f64) -> f64 {
+        if t == 0.0 {
+            return self.forward_rate_at(0.0);
+        }
+
+        -self.log_discount_factor_at(t) / t
+    }
+}
+
+impl CurveModel for LogLinearDiscountCurve {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.forward_rate_at(self.year_fraction(date))
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.spot_rate_at(self.year_fraction(date))
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        // Exactly on a pillar, return its discount factor as given.
+        match self
+            .pillars
+            .binary_search_by_key(&date, |&(pillar, _)| pillar)
+        {
+            Ok(i) => self.pillars[i].1,
+            Err(_) => self.log_discount_factor_at(self.year_fraction(date)).exp(),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_log_linear_curve {
+    use super::*;
+    use crate::assert_approx_equal;
+    use time::{macros::date, Duration};
+
+    const VALUATION: Date = date!(2024 - 01 - 02);
+
+    fn year_fraction(start: Date, end: Date) -> f64 {
+        DayCountConvention::default().day_count_factor(start, end)
+    }
+
+    fn curve() -> LogLinearDiscountCurve {
+        let pillars = [
+            (91, 0.9875),
+            (182, 0.9752),
+            (365, 0.9520),
+            (730, 0.9100),
+            (1826, 0.8050),
+            (3652, 0.6500),
+        ]
+        .iter()
+        .map(|&(days, df)| (VALUATION + Duration::days(days), df))
+        .collect();
+
+        LogLinearDiscountCurve::new(VALUATION, pillars, DayCountConvention::default()).unwrap()
+    }
+
+    #[test]
+    fn test_pillars_are_reproduced() {
+        let curve = curve();
+
+        assert_eq!(curve.discount_factor(VALUATION), 1.0);
+
+        for &(date, df) in curve.pillars() {
+            assert_eq!(curve.discount_factor(date), df);
+            assert_approx_equal!(
+                curve.spot_rate(date),
+                -df.ln() / year_fraction(VALUATION, date),
+                1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_forward_is_constant_between_pillars() {
+        let curve = curve();
+
+        for pair in curve.pillars().windows(2) {
+            let ((start, df_0), (end, df_1)) = (pair[0], pair[1]);
+            let tau = year_fraction(start, end);
+            let forward = -(df_1 / df_0).ln() / tau;
+
+            // At the pillar the forward of the following period applies.
+            let days = (end - start).whole_days();
+            for day in 0..days {
+                let date = start + Duration::days(day);
+
+                assert_approx_equal!(curve.forward_rate(date), forward, 1e-12);
+            }
+
+            // And it is the log-slope of the discount factors in between.
+            let t = year_fraction(VALUATION, start) + 0.5 * tau;
+            let h = 1e-4;
+            let slope = -(curve.log_discount_factor_at(t + h)
+                - curve.log_discount_factor_at(t - h))
+                / (2.0 * h);
+            assert_approx_equal!(slope, forward, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_before_first_pillar() {
+        let curve = curve();
+        let (first, df) = curve.pillars()[0];
+        let forward = -df.ln() / year_fraction(VALUATION, first);
+
+        // Flat forward from the valuation date, so the spot rate is flat too.
+        for days in [0, 1, 30, 90] {
+            let date = VALUATION + Duration::days(days);
+
+            assert_approx_equal!(curve.forward_rate(date), forward, 1e-12);
+            assert_approx_equal!(curve.spot_rate(date), forward, 1e-12);
+        }
+
+        // Extrapolated back before the valuation date.
+        let date = VALUATION - Duration::days(10);
+        assert_approx_equal!(
+            curve.discount_factor(date),
+            (forward * year_fraction(date, VALUATION)).exp(),
+            1e-14
+        );
+    }
+
+    #[test]
+    fn test_day_count_convention() {
+        let pillars: Vec<(Date, f64)> = curve().pillars().to_vec();
+        let curve = LogLinearDiscountCurve::new(
+            VALUATION,
+            pillars.clone(),
+            DayCountConvention::Actual_365_Fixed,
+        )
+        .unwrap();
+
+        assert_eq!(
+            curve.day_count_convention(),
+            DayCountConvention::Actual_365_Fixed
+        );
+
+        for &(date, df) in &pillars {
+            let days = (date - VALUATION).whole_days() as f64;
+
+            assert_approx_equal!(curve.spot_rate(date), -df.ln() * 365.0 / days, 1e-14);
+        }
+
+        // 30E/360 counts the 30th and 31st of January as the same day.
+        let same_time = vec![(date!(2024 - 01 - 30), 0.99), (date!(2024 - 01 - 31), 0.98)];
+        assert!(matches!(
+            LogLinearDiscountCurve::new(VALUATION, same_time, DayCountConvention::Thirty_E_360),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_pillars() {
+        let after = |days| VALUATION + Duration::days(days);
+
+        for pillars in [
+            vec![],
+            vec![(VALUATION, 1.0)],
+            vec![(after(60), 0.99), (after(30), 0.995)],
+            vec![(after(30), 0.99), (after(60), 0.0)],
+        ] {
+            assert!(matches!(
+                LogLinearDiscountCurve::new(VALUATION, pillars, DayCountConvention::default()),
+                Err(RustQuantError::InvalidArgument(_))
+            ));
+        }
+    }
+}
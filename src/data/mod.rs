@@ -120,6 +120,33 @@ pub use yahoo::*;
 pub mod curves;
 pub use curves::*;
 
+/// Bootstrapping of OIS discount curves, with forward jumps at given dates.
+pub mod bootstrap;
+pub use bootstrap::*;
+
+pub mod cached_curve;
+pub use cached_curve::*;
+
+/// Zero curve interpolated by a natural cubic spline.
+pub mod interpolated_curve;
+pub use interpolated_curve::*;
+
+/// Discount curve interpolated linearly in the log discount factors.
+pub mod log_linear_curve;
+pub use log_linear_curve::*;
+
+/// Credit curve of piecewise-constant hazard rates.
+pub mod hazard_rate_curve;
+pub use hazard_rate_curve::*;
+
+/// Implied volatility surface and smile dynamics (sticky-strike/sticky-delta).
+pub mod volatility_surface;
+pub use volatility_surface::*;
+
+/// Interpolation of volatility smiles in strike, including arbitrage-free.
+pub mod smile_interpolation;
+pub use smile_interpolation::*;
+
 // /// Base surface data structure and implementations.
 // /// Surfaces are simply [Curve]s with an additional dimension.
 // /// For example, a volatility surface is a function of time and strike/moneyness.
@@ -0,0 +1,621 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Interpolation of an implied volatility smile in strike.
+//!
+//! Interpolating volatilities directly (e.g. linearly) can introduce
+//! butterfly arbitrage between the quotes: a negative implied density
+//! $\partial^2 C / \partial K^2$. The arbitrage-free smile instead fits a
+//! cubic spline to the (undiscounted) call prices, after Fengler (2009),
+//! whose second derivative, the implied density, is linear between the
+//! knots and non-negative at every knot, and so non-negative everywhere
+//! on the strike grid. Prices are fitted in the least-squares sense,
+//! weighted by $1 / \mathcal{V}$ so that the errors are in volatility
+//! terms, with a penalty on the roughness of the density.
+//!
+//! The spline is written in terms of non-negative unknowns only: the
+//! density at the knots, the probability masses below and above the grid,
+//! and the put price at the first knot and call price at the last, with
+//! the total mass of one (and the consistency of the prices at the ends)
+//! as equality constraints. It is then a non-negative least-squares
+//! problem, solved by the Lawson-Hanson active-set method.
+//!
+//! Outside the grid, the total implied variance is extended linearly in the
+//! log-moneyness, with a slope between zero and two (Lee's moment bound),
+//! so the total variance keeps increasing into both tails.
+
+use crate::error::RustQuantError;
+use crate::instruments::options::{implied_volatility, TypeFlag};
+use crate::math::distributions::{Distribution, Gaussian};
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Interpolation of a [`super::VolatilitySurface`]'s smiles in strike.
+#[derive(Debug, Clone, Default)]
+pub enum SmileInterpolation {
+    /// Linear in the volatilities, extended flat outside the quotes.
+    #[default]
+    Linear,
+
+    /// Arbitrage-free smile fitted in call-price space, one per expiry
+    /// (see [`super::VolatilitySurface::with_arbitrage_free_smiles`]).
+    ArbitrageFree(Vec<ArbitrageFreeSmile>),
+}
+
+/// Smile fitted by a cubic spline in (undiscounted) call-price space, with
+/// a non-negative implied density on its strike grid.
+#[derive(Debug, Clone)]
+pub struct ArbitrageFreeSmile {
+    /// Forward price of the underlying at the expiry.
+    pub forward: f64,
+
+    /// Time to expiry (in years).
+    pub expiry: f64,
+
+    // Knots, as moneyness K / F.
+    knots: Vec<f64>,
+
+    // Density (in moneyness) at the knots.
+    density: Vec<f64>,
+
+    // Put price at the first knot, and probability mass below it.
+    put_at_first: f64,
+    mass_below: f64,
+
+    // Total variance at the ends of the grid, and its slope (outwards, in
+    // the log-moneyness) beyond them.
+    left_wing: (f64, f64),
+    right_wing: (f64, f64),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Weight of the equality constraints relative to the fit.
+const EQUALITY_WEIGHT: f64 = 1e4;
+
+// Knots closer than this (in moneyness) are merged.
+const KNOT_TOLERANCE: f64 = 1e-8;
+
+// Floor on the (normalised) vega weighting the fit.
+const MIN_VEGA: f64 = 1e-3;
+
+// Log-moneyness step for the slopes of the wings.
+const WING_STEP: f64 = 1e-2;
+
+impl ArbitrageFreeSmile {
+    /// Fit a smile to quoted volatilities.
+    ///
+    /// # Arguments:
+    ///
+    /// * `forward` - Forward price of the underlying at the expiry.
+    /// * `expiry` - Time to expiry (in years).
+    /// * `strikes` - Quoted strikes, in increasing order.
+    /// * `volatilities` - Quoted implied volatilities.
+    /// * `grid` - Strikes at which the density is kept non-negative. The
+    ///   quoted strikes are added to it, and it must cover them.
+    /// * `smoothing` - Weight of the penalty on the roughness of the density,
+    ///   $\int (\partial^3 C / \partial K^3)^2 dK$, in moneyness terms.
+    ///   Small values (e.g. `1e-8`) reproduce arbitrage-free quotes.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if there are not as many
+    ///   volatilities as strikes.
+    /// - `RustQuantError::InvalidArgument` if there are no quotes, the
+    ///   strikes are not strictly increasing, the grid does not cover them,
+    ///   or an input is not positive.
+    /// - `RustQuantError::ComputationError` if the fitted prices have no
+    ///   implied volatility.
+    pub fn fit(
+        forward: f64,
+        expiry: f64,
+        strikes: &[f64],
+        volatilities: &[f64],
+        grid: &[f64],
+        smoothing: f64,
+    ) -> Result<Self, RustQuantError> {
+        if strikes.len() != volatilities.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let invalid = |message: &str| Err(RustQuantError::InvalidArgument(message.to_string()));
+
+        if strikes.is_empty() {
+            return invalid("A smile needs at least one quote.");
+        }
+        if strikes.windows(2).any(|w| w[0] >= w[1]) {
+            return invalid("Strikes must be strictly increasing.");
+        }
+        if forward <= 0.0 || expiry <= 0.0 || strikes[0] <= 0.0 || grid.iter().any(|&k| k <= 0.0) {
+            return invalid("Forward, expiry, and strikes must be positive.");
+        }
+        if volatilities.iter().any(|&v| v <= 0.0) || smoothing < 0.0 {
+            return invalid("Volatilities must be positive, and the smoothing non-negative.");
+        }
+
+        let (lowest, highest) = grid
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &k| {
+                (lo.min(k), hi.max(k))
+            });
+        if grid.is_empty() || lowest > strikes[0] || highest < strikes[strikes.len() - 1] {
+            return invalid("The strike grid must cover the quoted strikes.");
+        }
+
+        // Work in moneyness, with the quotes as knots.
+        let mut knots: Vec<f64> = grid.iter().chain(strikes).map(|&k| k / forward).collect();
+        knots.sort_by(f64::total_cmp);
+        knots.dedup_by(|b, a| *b - *a < KNOT_TOLERANCE);
+
+        let quotes: Vec<(f64, f64)> = strikes
+            .iter()
+            .zip(volatilities)
+            .map(|(&k, &v)| (k / forward, v))
+            .collect();
+
+        let (density, put_at_first, mass_below) = solve(&knots, &quotes, expiry, smoothing);
+
+        let mut smile = Self {
+            forward,
+            expiry,
+            knots,
+            density,
+            put_at_first,
+            mass_below,
+            left_wing: (0.0, 0.0),
+            right_wing: (0.0, 0.0),
+        };
+
+        let (first, last) = (smile.knots[0].ln(), smile.knots[smile.knots.len() - 1].ln());
+        let step = WING_STEP.min(0.5 * (last - first)).max(f64::EPSILON);
+
+        let w = |k: f64| smile.grid_total_variance(k);
+        let (w_first, w_last) = (w(first), w(last));
+        let left_slope = (w_first - w(first + step)) / step;
+        let right_slope = (w_last - w(last - step)) / step;
+
+        if ![w_first, w_last, left_slope, right_slope]
+            .iter()
+            .all(|w| w.is_finite())
+        {
+            return Err(RustQuantError::ComputationError(
+                "The fitted smile has no implied volatility at the ends of the grid.".to_string(),
+            ));
+        }
+
+        smile.left_wing = (w_first, left_slope.clamp(0.0, 2.0));
+        smile.right_wing = (w_last, right_slope.clamp(0.0, 2.0));
+
+        Ok(smile)
+    }
+
+    /// Implied volatility at the given strike.
+    pub fn volatility(&self, strike: f64) -> f64 {
+        (self.total_variance((strike / self.forward).ln()) / self.expiry).sqrt()
+    }
+
+    /// Total implied variance, $\sigma^2 T$, at the log-moneyness
+    /// $k = \ln(K / F)$.
+    pub fn total_variance(&self, log_moneyness: f64) -> f64 {
+        let k = log_moneyness;
+        let (first, last) = (self.knots[0].ln(), self.knots[self.knots.len() - 1].ln());
+
+        if k < first {
+            self.left_wing.0 + self.left_wing.1 * (first - k)
+        } else if k > last {
+            self.right_wing.0 + self.right_wing.1 * (k - last)
+        } else {
+            self.grid_total_variance(k)
+        }
+    }
+
+    /// Undiscounted call price at the given strike, on the strike grid
+    /// (from the spline) and outside it (from the extended volatilities).
+    pub fn call_price(&self, strike: f64) -> f64 {
+        let x = strike / self.forward;
+
+        if x < self.knots[0] || x > self.knots[self.knots.len() - 1] {
+            return self.forward * black_call(x, self.volatility(strike) * self.expiry.sqrt());
+        }
+
+        self.forward * (1.0 - x + self.put_price(x))
+    }
+
+    /// Implied (risk-neutral) density of the underlying at expiry, on the
+    /// strike grid: the second derivative of the undiscounted call price.
+    /// Zero outside the grid.
+    pub fn density(&self, strike: f64) -> f64 {
+        let x = strike / self.forward;
+        let n = self.knots.len();
+
+        if x < self.knots[0] || x > self.knots[n - 1] {
+            return 0.0;
+        }
+        if n == 1 {
+            return self.density[0] / self.forward;
+        }
+
+        let i = self
+            .knots
+            .partition_point(|&knot| knot <= x)
+            .clamp(1, n - 1);
+        let (x0, x1) = (self.knots[i - 1], self.knots[i]);
+        let (g0, g1) = (self.density[i - 1], self.density[i]);
+
+        (g0 + (g1 - g0) * (x - x0) / (x1 - x0)) / self.forward
+    }
+
+    /// Strike grid of the smile (including the quoted strikes).
+    pub fn strikes(&self) -> Vec<f64> {
+        self.knots.iter().map(|&x| x * self.forward).collect()
+    }
+
+    // Total variance implied by the spline at log-moneyness k on the grid.
+    fn grid_total_variance(&self, k: f64) -> f64 {
+        let x = k.exp();
+        let put = self.put_price(x);
+
+        // Out-of-the-money option: the put below the forward, else the call.
+        let (price, flag) = if x < 1.0 {
+            (put, TypeFlag::Put)
+        } else {
+            (1.0 - x + put, TypeFlag::Call)
+        };
+
+        implied_volatility(price, 1.0, x, self.expiry, 0.0, flag).powi(2) * self.expiry
+    }
+
+    // Undiscounted put price (per unit forward) at moneyness x on the grid.
+    fn put_price(&self, x: f64) -> f64 {
+        let x0 = self.knots[0];
+
+        self.put_at_first
+            + self.mass_below * (x - x0)
+            + self
+                .density
+                .iter()
+                .enumerate()
+                .map(|(k, g)| g * hat_ramp(&self.knots, k, x))
+                .sum::<f64>()
+    }
+}
+
+// Fit the spline: the density at the knots, the put price at the first
+// knot, and the mass below it.
+fn solve(
+    knots: &[f64],
+    quotes: &[(f64, f64)],
+    expiry: f64,
+    smoothing: f64,
+) -> (Vec<f64>, f64, f64) {
+    let n = knots.len();
+    let (x0, xn) = (knots[0], knots[n - 1]);
+
+    // Unknowns: put at the first knot (u), mass below (a), mass above (b),
+    // call at the last knot (v), a slack (s), and the density at the knots.
+    const U: usize = 0;
+    const A: usize = 1;
+    const B: usize = 2;
+    const V: usize = 3;
+    const S: usize = 4;
+    const G: usize = 5;
+
+    let rows = 3 + quotes.len() + n.saturating_sub(1);
+    let mut matrix = DMatrix::<f64>::zeros(rows, G + n);
+    let mut rhs = DVector::<f64>::zeros(rows);
+
+    // Total mass of one.
+    matrix[(0, A)] = EQUALITY_WEIGHT;
+    matrix[(0, B)] = EQUALITY_WEIGHT;
+    for k in 0..n {
+        matrix[(0, G + k)] = EQUALITY_WEIGHT * hat_mass(knots, k);
+    }
+    rhs[0] = EQUALITY_WEIGHT;
+
+    // The call at the last knot is the put there, plus the forward less the
+    // strike (put-call parity).
+    matrix[(1, U)] = EQUALITY_WEIGHT;
+    matrix[(1, A)] = EQUALITY_WEIGHT * (xn - x0);
+    matrix[(1, V)] = -EQUALITY_WEIGHT;
+    for k in 0..n {
+        matrix[(1, G + k)] = EQUALITY_WEIGHT * hat_ramp(knots, k, xn);
+    }
+    rhs[1] = EQUALITY_WEIGHT * (xn - 1.0);
+
+    // The put at the first knot pays at most the strike on the mass below.
+    matrix[(2, U)] = EQUALITY_WEIGHT;
+    matrix[(2, A)] = -EQUALITY_WEIGHT * x0;
+    matrix[(2, S)] = EQUALITY_WEIGHT;
+
+    // Quoted (undiscounted) put prices, weighted by one over the vega.
+    for (i, &(x, volatility)) in quotes.iter().enumerate() {
+        let total_volatility = volatility * expiry.sqrt();
+        let d1 = -x.ln() / total_volatility + 0.5 * total_volatility;
+        let weight = 1.0 / (Gaussian::default().pdf(d1) * expiry.sqrt()).max(MIN_VEGA);
+        let row = 3 + i;
+
+        matrix[(row, U)] = weight;
+        matrix[(row, A)] = weight * (x - x0);
+        for k in 0..n {
+            matrix[(row, G + k)] = weight * hat_ramp(knots, k, x);
+        }
+        rhs[row] = weight * (black_call(x, total_volatility) - 1.0 + x);
+    }
+
+    // Roughness of the density.
+    for j in 0..n.saturating_sub(1) {
+        let row = 3 + quotes.len() + j;
+        let weight = (smoothing / (knots[j + 1] - knots[j])).sqrt();
+
+        matrix[(row, G + j)] = -weight;
+        matrix[(row, G + j + 1)] = weight;
+    }
+
+    let solution = non_negative_least_squares(&matrix, &rhs);
+
+    (solution.as_slice()[G..].to_vec(), solution[U], solution[A])
+}
+
+// Mass of the k-th hat function on the knots (half-hats at the ends).
+fn hat_mass(knots: &[f64], k: usize) -> f64 {
+    let left = if k > 0 { knots[k] - knots[k - 1] } else { 0.0 };
+    let right = if k + 1 < knots.len() {
+        knots[k + 1] - knots[k]
+    } else {
+        0.0
+    };
+
+    0.5 * (left + right)
+}
+
+// The k-th hat function integrated twice from the first knot,
+// \int_{x_0}^x (x - s) \phi_k(s) ds.
+fn hat_ramp(knots: &[f64], k: usize, x: f64) -> f64 {
+    // \int_p^q (x - s)(alpha + beta s) ds, over the part of [p, q] below x.
+    let integral = |p: f64, q: f64, alpha: f64, beta: f64| {
+        let q = q.min(x);
+        if q <= p {
+            return 0.0;
+        }
+
+        let antiderivative =
+            |s: f64| x * alpha * s + (x * beta - alpha) * s * s / 2.0 - beta * s * s * s / 3.0;
+
+        antiderivative(q) - antiderivative(p)
+    };
+
+    let mut total = 0.0;
+
+    if k > 0 {
+        let (l, c) = (knots[k - 1], knots[k]);
+        total += integral(l, c, -l / (c - l), 1.0 / (c - l));
+    }
+    if k + 1 < knots.len() {
+        let (c, r) = (knots[k], knots[k + 1]);
+        total += integral(c, r, r / (r - c), -1.0 / (r - c));
+    }
+
+    total
+}
+
+// Undiscounted Black call price per unit forward, at moneyness x.
+fn black_call(x: f64, total_volatility: f64) -> f64 {
+    let n = Gaussian::default();
+    let d1 = -x.ln() / total_volatility + 0.5 * total_volatility;
+
+    n.cdf(d1) - x * n.cdf(d1 - total_volatility)
+}
+
+// Lawson-Hanson active-set method for min |Ax - b| subject to x >= 0.
+fn non_negative_least_squares(a: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64> {
+    let n = a.ncols();
+    let tolerance = 10.0 * f64::EPSILON * a.norm() * a.nrows().max(n) as f64;
+
+    let mut x = DVector::<f64>::zeros(n);
+    let mut passive = vec![false; n];
+
+    // Least squares on the passive columns.
+    let restricted = |passive: &[bool]| {
+        let columns: Vec<usize> = (0..n).filter(|&j| passive[j]).collect();
+        let sub = a.select_columns(&columns);
+        let z = sub
+            .svd(true, true)
+            .solve(b, 1e-14)
+            .expect("The SVD was computed with both U and V.");
+
+        let mut full = DVector::<f64>::zeros(n);
+        for (i, &j) in columns.iter().enumerate() {
+            full[j] = z[i];
+        }
+
+        full
+    };
+
+    for _ in 0..3 * n {
+        let w = a.transpose() * (b - a * &x);
+
+        let Some(entering) = (0..n)
+            .filter(|&j| !passive[j] && w[j] > tolerance)
+            .max_by(|&i, &j| w[i].total_cmp(&w[j]))
+        else {
+            break;
+        };
+        passive[entering] = true;
+
+        loop {
+            let z = restricted(&passive);
+
+            if (0..n).all(|j| !passive[j] || z[j] > 0.0) {
+                x = z;
+                break;
+            }
+
+            // Step towards z until a passive variable hits zero.
+            let alpha = (0..n)
+                .filter(|&j| passive[j] && z[j] <= 0.0)
+                .map(|j| x[j] / (x[j] - z[j]))
+                .fold(f64::INFINITY, f64::min);
+
+            x += alpha * (&z - &x);
+
+            for j in 0..n {
+                if passive[j] && x[j] <= f64::EPSILON * x.amax() {
+                    passive[j] = false;
+                    x[j] = 0.0;
+                }
+            }
+        }
+    }
+
+    x
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_smile_interpolation {
+    use super::*;
+    use crate::data::VolatilitySurface;
+
+    const FORWARD: f64 = 100.0;
+    const EXPIRY: f64 = 0.5;
+
+    // Quadratic smile in the log-moneyness, with a put skew.
+    fn clean_smile(strike: f64) -> f64 {
+        let k = (strike / FORWARD).ln();
+
+        0.2 - 0.15 * k + 0.4 * k * k
+    }
+
+    fn grid() -> Vec<f64> {
+        (0..=120).map(|i| 50.0 + i as f64).collect()
+    }
+
+    // Second differences of the Black prices at the smile's volatilities
+    // (zero up to rounding where the density vanishes).
+    fn min_butterfly(smile: &ArbitrageFreeSmile, strikes: &[f64]) -> f64 {
+        let call = |k: f64| FORWARD * black_call(k / FORWARD, smile.volatility(k) * EXPIRY.sqrt());
+
+        strikes
+            .windows(3)
+            .map(|w| {
+                (call(w[2]) - call(w[1])) / (w[2] - w[1])
+                    - (call(w[1]) - call(w[0])) / (w[1] - w[0])
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_clean_smile() {
+        let strikes = [70.0, 80.0, 90.0, 95.0, 100.0, 105.0, 110.0, 120.0, 135.0];
+        let volatilities: Vec<f64> = strikes.iter().map(|&k| clean_smile(k)).collect();
+
+        let smile =
+            ArbitrageFreeSmile::fit(FORWARD, EXPIRY, &strikes, &volatilities, &grid(), 1e-8)
+                .unwrap();
+
+        for (&k, &v) in strikes.iter().zip(&volatilities) {
+            assert!(
+                (smile.volatility(k) - v).abs() < 1e-4,
+                "{k}: {}",
+                smile.volatility(k)
+            );
+        }
+
+        let grid = grid();
+        assert!(grid.iter().all(|&k| smile.density(k) >= 0.0));
+        assert!(min_butterfly(&smile, &grid) > -1e-8);
+    }
+
+    #[test]
+    fn test_noisy_smile() {
+        let strikes: Vec<f64> = (0..=16).map(|i| 70.0 + 4.0 * i as f64).collect();
+
+        // Alternating noise of 50bp makes the linear smile arbitrageable.
+        let volatilities: Vec<f64> = strikes
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| clean_smile(k) + if i % 2 == 0 { 0.005 } else { -0.005 })
+            .collect();
+
+        let linear = VolatilitySurface::new(
+            FORWARD,
+            vec![EXPIRY],
+            strikes.clone(),
+            vec![volatilities.clone()],
+        )
+        .unwrap();
+        let linear_call = |k: f64| {
+            FORWARD * black_call(k / FORWARD, linear.volatility(k, EXPIRY) * EXPIRY.sqrt())
+        };
+        assert!(strikes
+            .windows(3)
+            .any(|w| linear_call(w[0]) - 2.0 * linear_call(w[1]) + linear_call(w[2]) < 0.0));
+
+        let smile =
+            ArbitrageFreeSmile::fit(FORWARD, EXPIRY, &strikes, &volatilities, &grid(), 1e-8)
+                .unwrap();
+
+        let grid = grid();
+        assert!(grid.iter().all(|&k| smile.density(k) >= 0.0));
+        assert!(min_butterfly(&smile, &grid) > -1e-8);
+
+        // Within the noise of the quotes.
+        for (&k, &v) in strikes.iter().zip(&volatilities) {
+            assert!(
+                (smile.volatility(k) - v).abs() < 0.0075,
+                "{k}: {}",
+                smile.volatility(k)
+            );
+        }
+    }
+
+    #[test]
+    fn test_wings() {
+        let strikes = [80.0, 90.0, 100.0, 110.0, 120.0];
+        let volatilities: Vec<f64> = strikes.iter().map(|&k| clean_smile(k)).collect();
+        let grid: Vec<f64> = (0..=12).map(|i| 70.0 + 5.0 * i as f64).collect();
+
+        let smile =
+            ArbitrageFreeSmile::fit(FORWARD, EXPIRY, &strikes, &volatilities, &grid, 1e-8).unwrap();
+
+        let (left, right) = ((70.0 / FORWARD).ln(), (130.0 / FORWARD).ln());
+
+        // Total variance increases outwards, at most as fast as Lee's bound.
+        let wing = |k: f64| smile.total_variance(k);
+        for i in 0..20 {
+            let step = 0.1 * i as f64;
+
+            assert!(wing(left - step - 0.1) >= wing(left - step));
+            assert!(wing(right + step + 0.1) >= wing(right + step));
+            assert!(wing(right + step + 0.1) - wing(right + step) <= 0.2 + 1e-12);
+        }
+
+        // Continuous at the ends of the grid.
+        assert!((wing(left - 1e-9) - wing(left)).abs() < 1e-8);
+        assert!((wing(right + 1e-9) - wing(right)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_smile() {
+        let fit = |strikes: &[f64], grid: &[f64]| {
+            ArbitrageFreeSmile::fit(FORWARD, EXPIRY, strikes, &[0.2, 0.2], grid, 0.0)
+        };
+
+        assert!(fit(&[90.0, 110.0], &[80.0, 100.0]).is_err());
+        assert!(fit(&[110.0, 90.0], &[80.0, 120.0]).is_err());
+        assert!(fit(&[90.0], &[80.0, 120.0]).is_err());
+    }
+}
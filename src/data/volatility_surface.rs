@@ -0,0 +1,307 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Implied volatility surface on a grid of expiries and strikes.
+//!
+//! Volatilities are interpolated in strike by the surface's
+//! [`SmileInterpolation`] (by default linearly, extrapolated flat), and
+//! linearly in total implied variance ($\sigma^2 T$) in expiry.
+//! Outside the expiries the surface is extrapolated flat.
+
+use super::{ArbitrageFreeSmile, SmileInterpolation};
+use crate::error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How the volatility smile moves when the spot moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StickyConvention {
+    /// The implied volatility of a fixed strike does not change with spot.
+    StickyStrike,
+
+    /// The implied volatility of a fixed moneyness (delta) does not change
+    /// with spot, i.e. the smile moves with the spot.
+    StickyDelta,
+}
+
+/// Implied volatility surface.
+#[derive(Debug, Clone)]
+pub struct VolatilitySurface {
+    /// Spot level at which the surface was quoted.
+    pub spot: f64,
+
+    /// Expiries (in years), in increasing order.
+    pub expiries: Vec<f64>,
+
+    /// Strikes, in increasing order.
+    pub strikes: Vec<f64>,
+
+    /// Implied volatilities, indexed as `volatilities[expiry][strike]`.
+    pub volatilities: Vec<Vec<f64>>,
+
+    /// Interpolation of the smiles in strike.
+    pub interpolation: SmileInterpolation,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl VolatilitySurface {
+    /// Create a new volatility surface.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the grid dimensions do not match.
+    /// - `RustQuantError::InvalidArgument` if the expiries or strikes
+    ///   are not strictly increasing, or if the grid is empty.
+    pub fn new(
+        spot: f64,
+        expiries: Vec<f64>,
+        strikes: Vec<f64>,
+        volatilities: Vec<Vec<f64>>,
+    ) -> Result<Self, RustQuantError> {
+        if expiries.is_empty() || strikes.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "Volatility surface must have at least one expiry and strike.".to_string(),
+            ));
+        }
+
+        if volatilities.len() != expiries.len()
+            || volatilities.iter().any(|row| row.len() != strikes.len())
+        {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        if expiries.windows(2).any(|w| w[0] >= w[1]) || strikes.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(RustQuantError::InvalidArgument(
+                "Expiries and strikes must be strictly increasing.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            spot,
+            expiries,
+            strikes,
+            volatilities,
+            interpolation: SmileInterpolation::default(),
+        })
+    }
+
+    /// The surface with each expiry's smile interpolated arbitrage-free
+    /// (see [`ArbitrageFreeSmile::fit`]).
+    ///
+    /// # Arguments:
+    ///
+    /// * `forwards` - Forward price of the underlying at each expiry.
+    /// * `grid` - Strikes at which the implied densities are kept
+    ///   non-negative. It must cover the surface's strikes.
+    /// * `smoothing` - Weight of the penalty on the roughness of the densities.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if there is not one forward per expiry.
+    /// - The errors of [`ArbitrageFreeSmile::fit`].
+    pub fn with_arbitrage_free_smiles(
+        mut self,
+        forwards: &[f64],
+        grid: &[f64],
+        smoothing: f64,
+    ) -> Result<Self, RustQuantError> {
+        if forwards.len() != self.expiries.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let smiles = forwards
+            .iter()
+            .zip(&self.expiries)
+            .zip(&self.volatilities)
+            .map(|((&forward, &expiry), volatilities)| {
+                ArbitrageFreeSmile::fit(
+                    forward,
+                    expiry,
+                    &self.strikes,
+                    volatilities,
+                    grid,
+                    smoothing,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.interpolation = SmileInterpolation::ArbitrageFree(smiles);
+
+        Ok(self)
+    }
+
+    /// Implied volatility for a given strike and expiry.
+    pub fn volatility(&self, strike: f64, expiry: f64) -> f64 {
+        let smile = |i: usize| match &self.interpolation {
+            SmileInterpolation::Linear => linear(&self.strikes, &self.volatilities[i], strike),
+            SmileInterpolation::ArbitrageFree(smiles) => smiles[i].volatility(strike),
+        };
+
+        let n = self.expiries.len();
+
+        if n == 1 || expiry <= self.expiries[0] {
+            return smile(0);
+        }
+
+        if expiry >= self.expiries[n - 1] {
+            return smile(n - 1);
+        }
+
+        let j = self.expiries.partition_point(|&t| t <= expiry);
+        let (t0, t1) = (self.expiries[j - 1], self.expiries[j]);
+        let (v0, v1) = (smile(j - 1), smile(j));
+
+        let w0 = v0 * v0 * t0;
+        let w1 = v1 * v1 * t1;
+        let w = w0 + (w1 - w0) * (expiry - t0) / (t1 - t0);
+
+        (w / expiry).sqrt()
+    }
+
+    /// Implied volatility for a given strike and expiry, when the spot has
+    /// moved from the quoting spot to `spot`, under the given convention.
+    pub fn volatility_at_spot(
+        &self,
+        strike: f64,
+        expiry: f64,
+        spot: f64,
+        convention: StickyConvention,
+    ) -> f64 {
+        match convention {
+            StickyConvention::StickyStrike => self.volatility(strike, expiry),
+            StickyConvention::StickyDelta => self.volatility(strike * self.spot / spot, expiry),
+        }
+    }
+
+    /// Slope of the smile, $\partial \sigma / \partial K$,
+    /// via a central finite difference.
+    pub fn smile_slope(&self, strike: f64, expiry: f64) -> f64 {
+        let h = 1e-4 * strike;
+
+        (self.volatility(strike + h, expiry) - self.volatility(strike - h, expiry)) / (2.0 * h)
+    }
+
+    /// Sensitivity of the implied volatility to the spot,
+    /// $\partial \sigma / \partial S$, at the quoting spot.
+    ///
+    /// - Sticky-strike: $0$.
+    /// - Sticky-delta: $-\frac{K}{S} \frac{\partial \sigma}{\partial K}$.
+    pub fn volatility_spot_sensitivity(
+        &self,
+        strike: f64,
+        expiry: f64,
+        convention: StickyConvention,
+    ) -> f64 {
+        match convention {
+            StickyConvention::StickyStrike => 0.0,
+            StickyConvention::StickyDelta => -strike / self.spot * self.smile_slope(strike, expiry),
+        }
+    }
+}
+
+/// Linear interpolation with flat extrapolation.
+fn linear(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let n = xs.len();
+
+    if n == 1 || x <= xs[0] {
+        return ys[0];
+    }
+
+    if x >= xs[n - 1] {
+        return ys[n - 1];
+    }
+
+    let i = xs.partition_point(|&xi| xi <= x);
+
+    ys[i - 1] + (ys[i] - ys[i - 1]) * (x - xs[i - 1]) / (xs[i] - xs[i - 1])
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_volatility_surface {
+    use super::*;
+    use crate::assert_approx_equal;
+
+    #[test]
+    fn test_volatility_interpolation() {
+        let surface = VolatilitySurface::new(
+            100.0,
+            vec![0.5, 1.0],
+            vec![90.0, 100.0, 110.0],
+            vec![vec![0.22, 0.20, 0.19], vec![0.24, 0.22, 0.21]],
+        )
+        .unwrap();
+
+        // On the grid.
+        assert_approx_equal!(surface.volatility(100.0, 0.5), 0.20, 1e-12);
+        assert_approx_equal!(surface.volatility(110.0, 1.0), 0.21, 1e-12);
+
+        // Linear in strike.
+        assert_approx_equal!(surface.volatility(95.0, 0.5), 0.21, 1e-12);
+
+        // Linear in total variance.
+        let w: f64 = 0.5 * (0.2 * 0.2 * 0.5 + 0.22 * 0.22 * 1.0);
+        assert_approx_equal!(surface.volatility(100.0, 0.75), (w / 0.75).sqrt(), 1e-12);
+
+        // Flat extrapolation.
+        assert_approx_equal!(surface.volatility(50.0, 2.0), 0.24, 1e-12);
+    }
+
+    #[test]
+    fn test_arbitrage_free_smiles() {
+        let strikes = vec![80.0, 90.0, 100.0, 110.0, 120.0];
+        let surface = VolatilitySurface::new(
+            100.0,
+            vec![0.5, 1.0],
+            strikes.clone(),
+            vec![
+                vec![0.26, 0.22, 0.2, 0.19, 0.19],
+                vec![0.25, 0.22, 0.205, 0.195, 0.19],
+            ],
+        )
+        .unwrap();
+
+        let grid: Vec<f64> = (0..=30).map(|i| 50.0 + 5.0 * i as f64).collect();
+        let arbitrage_free = surface
+            .clone()
+            .with_arbitrage_free_smiles(&[101.0, 102.0], &grid, 1e-8)
+            .unwrap();
+
+        // Close to the quotes, and linear in total variance in expiry.
+        for &strike in &strikes {
+            assert!(
+                (arbitrage_free.volatility(strike, 0.5) - surface.volatility(strike, 0.5)).abs()
+                    < 1e-3
+            );
+        }
+        let w = |t: f64| arbitrage_free.volatility(95.0, t).powi(2) * t;
+        assert_approx_equal!(w(0.75), 0.5 * (w(0.5) + w(1.0)), 1e-12);
+
+        assert!(surface
+            .with_arbitrage_free_smiles(&[101.0], &grid, 1e-8)
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_surface() {
+        assert!(
+            VolatilitySurface::new(100.0, vec![1.0], vec![90.0, 100.0], vec![vec![0.2]]).is_err()
+        );
+        assert!(
+            VolatilitySurface::new(100.0, vec![1.0], vec![100.0, 90.0], vec![vec![0.2, 0.2]])
+                .is_err()
+        );
+    }
+}
@@ -111,16 +111,60 @@ pub enum RustQuantError {
     /// Outside of interpolation range.
     #[error("Outside of interpolation range.")]
     OutsideOfRange,
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Simulation related errors
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// A simulated path failed.
+    #[error("{0}")]
+    Simulation(#[from] SimulationError),
+}
+
+/// Failure of a simulated path: a panic in the process (e.g. in its drift
+/// or diffusion), or a non-finite value.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Simulation failed on path {path} at step {step} (t = {time}, x = {state}): {reason}")]
+pub struct SimulationError {
+    /// Index of the path.
+    pub path: usize,
+
+    /// Time step at which the path failed.
+    pub step: usize,
+
+    /// Time at the start of the failed step.
+    pub time: f64,
+
+    /// State at the start of the failed step.
+    pub state: f64,
+
+    /// What went wrong: the panic message, or the non-finite value.
+    pub reason: String,
 }
 
 /// Curve error enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Error)]
 pub enum CurveError {
     /// The date is outside the curve's range.
+    #[error("The date is outside the curve's range.")]
     DateOutsideRange,
 
     /// The curve has no points.
+    #[error("The curve has no points.")]
     NoPoints,
+
+    /// The curve implies a negative forward rate at the date.
+    #[error("The curve implies a negative forward rate at {0}.")]
+    NegativeForwardRate(time::Date),
+
+    /// A quote's maturity is not after the valuation date and the
+    /// previous quote's maturity.
+    #[error("Quote maturities must be strictly increasing and after the valuation date: {0}.")]
+    UnorderedDate(time::Date),
+
+    /// The bootstrap did not converge for the quotes maturing up to the date
+    /// (the quotes are inconsistent, e.g. a rate that is far too high).
+    #[error("The bootstrap did not converge for the quotes maturing up to {0}.")]
+    BootstrapFailed(time::Date),
 }
 
 /// Create a `RustQuantError` with the text to include in the output.
@@ -0,0 +1,391 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Bond futures, deliverable into a basket of fixed-rate bonds.
+//!
+//! The short delivers any bond $i$ of the basket, and receives the invoice
+//! amount $F \cdot CF_i + AI_i$, with $CF_i$ the bond's conversion factor
+//! and $AI_i$ its accrued interest at delivery. All prices are quoted per
+//! 100 of face value.
+//!
+//! Each bond's forward clean price $B_i$ at delivery is implied by its
+//! discount curve, and the (theoretical) futures price is that of the
+//! cheapest-to-deliver (CTD) bond, $F = \min_i B_i / CF_i$. The value of the
+//! delivery options (quality, timing, wild card) is ignored.
+//!
+//! Given a futures price, the implied repo rate of a bond is the money
+//! market return of buying it now and delivering it into the future,
+//!
+//! $$
+//! r_i = \frac{F \cdot CF_i + AI_i + \sum_j c_j - P_i}{P_i \tau - \sum_j c_j \tau_j}
+//! $$
+//!
+//! where $P_i$ is the dirty price now, $c_j$ the coupons paid before
+//! delivery, $\tau_j$ the time from each coupon to delivery and $\tau$ the
+//! time to delivery. The CTD is the bond with the highest implied repo.
+
+use crate::data::CurveModel;
+use crate::instruments::bonds::fixed_rate_bond::FixedRateBond;
+use crate::instruments::Instrument;
+use crate::time::{today, DayCountConvention};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bond future with a deliverable basket.
+#[derive(Debug, Clone)]
+pub struct BondFuture<M: CurveModel> {
+    /// Deliverable bonds, each priced off its own curve.
+    pub basket: Vec<FixedRateBond<M>>,
+
+    /// Conversion factor of each bond in the basket.
+    pub conversion_factors: Vec<f64>,
+
+    /// Delivery date.
+    pub delivery_date: Date,
+
+    /// Day count convention of the repo rate.
+    pub repo_day_count_convention: DayCountConvention,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+/// Curve with its continuously compounded zero rates shifted in parallel.
+pub(crate) struct ShiftedCurve<'a, M: CurveModel> {
+    pub(crate) curve: &'a M,
+    pub(crate) anchor: Date,
+    pub(crate) shift: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<M: CurveModel> BondFuture<M> {
+    /// New bond future, valued today, with conversion factors for a
+    /// `notional_coupon` (see [`conversion_factor`]) and an Actual/360 repo.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the basket is empty, or a bond matures before delivery.
+    pub fn new(basket: Vec<FixedRateBond<M>>, delivery_date: Date, notional_coupon: f64) -> Self {
+        assert!(!basket.is_empty(), "The basket must not be empty.");
+        assert!(basket.iter().all(|bond| bond.maturity_date > delivery_date));
+
+        let conversion_factors = basket
+            .iter()
+            .map(|bond| conversion_factor(bond, notional_coupon, delivery_date))
+            .collect();
+
+        Self {
+            basket,
+            conversion_factors,
+            delivery_date,
+            repo_day_count_convention: DayCountConvention::Actual_360,
+            evaluation_date: None,
+        }
+    }
+
+    /// Forward clean price of the bond at `index` in the basket, at delivery.
+    pub fn forward_clean_price(&self, index: usize) -> f64 {
+        self.forward_clean_price_shifted(index, 0.0)
+    }
+
+    /// Theoretical futures price: the lowest converted forward price in the
+    /// basket, $\min_i B_i / CF_i$.
+    pub fn forward_price(&self) -> f64 {
+        self.forward_price_shifted(0.0)
+    }
+
+    /// Implied repo rate of the bond at `index`, delivered at `futures_price`.
+    pub fn implied_repo_rate(&self, index: usize, futures_price: f64) -> f64 {
+        let bond = &self.basket[index];
+        let valuation_date = self.valuation_date();
+        let per_hundred = 100.0 / bond.face_value;
+        let convention = self.repo_day_count_convention;
+
+        let dirty_price = bond.price_at(valuation_date) * per_hundred;
+        let invoice = futures_price * self.conversion_factors[index]
+            + bond.accrued_interest_at(self.delivery_date) * per_hundred;
+
+        let (coupons, weighted_coupons) = bond
+            .cash_flows_after(valuation_date)
+            .into_iter()
+            .filter(|&(payment, _)| payment <= self.delivery_date)
+            .fold((0.0, 0.0), |(sum, weighted), (payment, amount)| {
+                let amount = amount * per_hundred;
+                let tau = convention.day_count_factor(payment, self.delivery_date);
+
+                (sum + amount, weighted + amount * tau)
+            });
+
+        let tau = convention.day_count_factor(valuation_date, self.delivery_date);
+
+        (invoice + coupons - dirty_price) / (dirty_price * tau - weighted_coupons)
+    }
+
+    /// Index of the cheapest-to-deliver bond at `futures_price`: the bond
+    /// with the highest implied repo rate.
+    pub fn cheapest_to_deliver(&self, futures_price: f64) -> usize {
+        (0..self.basket.len())
+            .map(|index| (index, self.implied_repo_rate(index, futures_price)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap()
+    }
+
+    /// Forward DV01 of the bond at `index`: the fall in its forward clean
+    /// price for a one basis point parallel rise in its zero rates.
+    pub fn forward_dv01(&self, index: usize) -> f64 {
+        const BP: f64 = 1e-4;
+
+        0.5 * (self.forward_clean_price_shifted(index, -BP)
+            - self.forward_clean_price_shifted(index, BP))
+    }
+
+    /// DV01 of the futures price for a one basis point parallel rise in the
+    /// zero rates, with the CTD reselected on each side.
+    pub fn dv01(&self) -> f64 {
+        const BP: f64 = 1e-4;
+
+        0.5 * (self.forward_price_shifted(-BP) - self.forward_price_shifted(BP))
+    }
+
+    fn forward_price_shifted(&self, shift: f64) -> f64 {
+        (0..self.basket.len())
+            .map(|index| {
+                self.forward_clean_price_shifted(index, shift) / self.conversion_factors[index]
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn forward_clean_price_shifted(&self, index: usize, shift: f64) -> f64 {
+        let bond = shifted_bond(&self.basket[index], self.valuation_date(), shift);
+        let forward_dirty_price = bond.price_at(self.delivery_date);
+
+        (forward_dirty_price - bond.accrued_interest_at(self.delivery_date)) * 100.0
+            / bond.face_value
+    }
+}
+
+impl<M: CurveModel> Instrument for BondFuture<M> {
+    /// Theoretical futures price (the converted forward price of the CTD).
+    fn price(&self) -> f64 {
+        self.forward_price()
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "BondFuture"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.delivery_date)
+    }
+
+    /// The forward prices depend on the curves only, not on the valuation date.
+    fn price_as_of(&self, _valuation_date: Date) -> Option<f64> {
+        Some(self.forward_price())
+    }
+}
+
+impl<M: CurveModel> CurveModel for ShiftedCurve<'_, M> {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.curve.forward_rate(date) + self.shift
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.curve.spot_rate(date) + self.shift
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        let tau = DayCountConvention::default().day_count_factor(self.anchor, date);
+
+        self.curve.discount_factor(date) * (-self.shift * tau).exp()
+    }
+}
+
+/// Conversion factor of a bond: its clean price per unit of face value at
+/// delivery, at a flat yield of `notional_coupon` (compounded at the coupon
+/// frequency, as in [`FixedRateBond::price_from_yield`]).
+///
+/// Exchanges round the time to maturity (e.g. to whole quarters or months)
+/// before discounting; this uses the exact coupon schedule instead.
+pub fn conversion_factor<M: CurveModel>(
+    bond: &FixedRateBond<M>,
+    notional_coupon: f64,
+    delivery_date: Date,
+) -> f64 {
+    let bond = shifted_bond(bond, delivery_date, 0.0);
+
+    (bond.price_from_yield(notional_coupon) - bond.accrued_interest()) / bond.face_value
+}
+
+/// Copy of `bond`, valued at `anchor`, on its curve shifted by `shift`.
+fn shifted_bond<M: CurveModel>(
+    bond: &FixedRateBond<M>,
+    anchor: Date,
+    shift: f64,
+) -> FixedRateBond<ShiftedCurve<'_, M>> {
+    let curve = ShiftedCurve {
+        curve: &bond.curve,
+        anchor,
+        shift,
+    };
+
+    bond.with_curve(curve, anchor)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bond_future {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::time::Frequency;
+    use time::macros::date;
+
+    const VALUATION: Date = date!(2024 - 03 - 01);
+    const DELIVERY: Date = date!(2024 - 06 - 28);
+
+    /// Zero rate $a + b t$, continuously compounded on the default day count.
+    #[derive(Debug, Clone)]
+    struct LinearCurve {
+        level: f64,
+        slope: f64,
+    }
+
+    impl CurveModel for LinearCurve {
+        fn forward_rate(&self, date: Date) -> f64 {
+            let t = DayCountConvention::default().day_count_factor(VALUATION, date);
+
+            self.level + 2.0 * self.slope * t
+        }
+
+        fn spot_rate(&self, date: Date) -> f64 {
+            let t = DayCountConvention::default().day_count_factor(VALUATION, date);
+
+            self.level + self.slope * t
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            let t = DayCountConvention::default().day_count_factor(VALUATION, date);
+
+            (-self.spot_rate(date) * t).exp()
+        }
+    }
+
+    /// A short high-coupon bond and a long low-coupon bond, against a 6%
+    /// notional coupon.
+    fn future(level: f64, slope: f64) -> BondFuture<LinearCurve> {
+        let curve = LinearCurve { level, slope };
+        let bond = |coupon, issue, maturity| {
+            FixedRateBond::new(
+                100.0,
+                coupon,
+                Frequency::SemiAnnually,
+                issue,
+                maturity,
+                curve.clone(),
+            )
+        };
+
+        let basket = vec![
+            bond(0.08, date!(2021 - 02 - 15), date!(2031 - 02 - 15)),
+            bond(0.03, date!(2019 - 05 - 15), date!(2049 - 05 - 15)),
+        ];
+
+        let mut future = BondFuture::new(basket, DELIVERY, 0.06);
+        future.evaluation_date = Some(VALUATION);
+        future
+    }
+
+    fn converted_forward_prices(future: &BondFuture<LinearCurve>) -> Vec<f64> {
+        (0..2)
+            .map(|i| future.forward_clean_price(i) / future.conversion_factors[i])
+            .collect()
+    }
+
+    #[test]
+    fn test_conversion_factors() {
+        let future = future(0.04, 0.0);
+
+        // Above par for the 8% bond, below par for the 3% bond.
+        assert!(future.conversion_factors[0] > 1.0);
+        assert!(future.conversion_factors[1] < 1.0);
+
+        // At a 6% yield, both bonds deliver at the same futures price.
+        let at_notional = |index: usize| {
+            let bond = shifted_bond(&future.basket[index], DELIVERY, 0.0);
+            (bond.price_from_yield(0.06) - bond.accrued_interest())
+                / future.conversion_factors[index]
+        };
+        assert_approx_equal!(at_notional(0), 100.0, 1e-10);
+        assert_approx_equal!(at_notional(1), 100.0, 1e-10);
+    }
+
+    #[test]
+    fn test_cheapest_to_deliver_switches_when_curve_steepens() {
+        // Below the notional coupon, the short (low duration) bond is CTD.
+        let flat = future(0.04, 0.0);
+        let prices = converted_forward_prices(&flat);
+
+        assert!(prices[0] < prices[1]);
+        assert_eq!(flat.forward_price(), prices[0]);
+        assert_eq!(flat.cheapest_to_deliver(flat.forward_price()), 0);
+
+        // Raising the long end above the notional coupon makes the long
+        // (high duration) bond cheapest.
+        let steep = future(0.03, 0.004);
+        let prices = converted_forward_prices(&steep);
+
+        assert!(prices[1] < prices[0]);
+        assert_eq!(steep.forward_price(), prices[1]);
+        assert_eq!(steep.cheapest_to_deliver(steep.forward_price()), 1);
+
+        // Delivering the CTD at the theoretical price earns the repo rate
+        // implied by the curve, and every other bond earns less.
+        for future in [flat, steep] {
+            let price = future.forward_price();
+            let ctd = future.cheapest_to_deliver(price);
+            let repo = future.implied_repo_rate(ctd, price);
+
+            let tau = DayCountConvention::Actual_360.day_count_factor(VALUATION, DELIVERY);
+            let curve_repo = (1.0 / future.basket[ctd].curve.discount_factor(DELIVERY) - 1.0) / tau;
+
+            assert!((repo - curve_repo).abs() < 2e-4);
+            assert!(future.implied_repo_rate(1 - ctd, price) < repo);
+        }
+    }
+
+    #[test]
+    fn test_dv01_tracks_ctd() {
+        for (level, slope) in [(0.04, 0.0), (0.03, 0.004), (0.05, 0.001)] {
+            let future = future(level, slope);
+            let ctd = future.cheapest_to_deliver(future.forward_price());
+
+            let expected = future.forward_dv01(ctd) / future.conversion_factors[ctd];
+
+            assert!(future.dv01() > 0.0);
+            assert_approx_equal!(future.dv01(), expected, 1e-10);
+        }
+    }
+}
@@ -164,6 +164,19 @@ where
     fn instrument_type(&self) -> &'static str {
         "Coupon Bond"
     }
+
+    /// Returns the price as of another valuation date: the coupons paid
+    /// after it, with the discount curve held fixed.
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(
+            self.coupons
+                .iter()
+                .zip(self.discount_curve.curve.nodes.values())
+                .filter(|((&date, _), _)| date > valuation_date)
+                .map(|((_, coupon), df)| coupon * df)
+                .sum::<f64>(),
+        )
+    }
 }
 
 impl CouponBond2 {
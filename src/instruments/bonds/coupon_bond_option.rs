@@ -0,0 +1,474 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! European options on fixed-rate coupon bonds, under the one-factor
+//! Hull-White model fitted to the bond's discount curve.
+//!
+//! With $r(t) = x(t) + \varphi(t)$, where $x$ is an Ornstein-Uhlenbeck
+//! process started at zero and $\varphi$ fits the initial curve, the price at
+//! expiry $T$ of a zero-coupon bond maturing at $S$ is
+//!
+//! $$
+//! P(T, S) = \frac{P(0, S)}{P(0, T)} \exp \left( -B(T, S) x(T) - C(T, S) \right),
+//! \quad B(T, S) = \frac{1 - e^{-a (S - T)}}{a},
+//! $$
+//!
+//! with $C(T, S) = \frac{\sigma^2}{4a} (1 - e^{-2aT}) B^2 + \frac{\sigma^2}{2a^2} (1 - e^{-aT})^2 B$.
+//!
+//! The coupon bond, $\sum_i c_i P(T, S_i)$, is decreasing in $x(T)$, so
+//! (Jamshidian, 1989) an option on it with strike $K$ is a portfolio of
+//! options on the zero-coupon bonds, with strikes $K_i = P(T, S_i)$ at the
+//! critical state $x^*$ where the coupon bond is worth $K$.
+
+use crate::data::CurveModel;
+use crate::error::RustQuantError;
+use crate::instruments::bonds::fixed_rate_bond::FixedRateBond;
+use crate::instruments::options::TypeFlag;
+use crate::instruments::Instrument;
+use crate::math::distributions::{Distribution, Gaussian};
+use crate::models::HullWhite;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// European option on a fixed-rate coupon bond.
+#[derive(Debug, Clone)]
+pub struct CouponBondOption<M: CurveModel> {
+    /// The underlying bond, whose curve and valuation date are used.
+    pub bond: FixedRateBond<M>,
+
+    /// Expiry date of the option.
+    pub expiry: Date,
+
+    /// Strike, on the dirty price at expiry (of the cash flows after expiry).
+    pub strike: f64,
+
+    /// Call or put flag.
+    pub option_type: TypeFlag,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<M: CurveModel> CouponBondOption<M> {
+    /// New European option on a coupon bond.
+    pub fn new(bond: FixedRateBond<M>, expiry: Date, strike: f64, option_type: TypeFlag) -> Self {
+        Self {
+            bond,
+            expiry,
+            strike,
+            option_type,
+        }
+    }
+
+    /// Price under Hull-White, by Jamshidian's decomposition.
+    ///
+    /// Only the model's mean reversion (`alpha`) and volatility (`sigma`),
+    /// at $t = 0$, are used: the drift $\theta(t)$ is implied by the bond's
+    /// curve. Times are year fractions from the bond's valuation date, on
+    /// its day count convention.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidArgument` if the expiry is not between the valuation date
+    ///   and maturity, or the model parameters are not positive.
+    /// - `InvalidArgument`, naming the attainable range, if the strike is
+    ///   not a bond price at expiry for short rates within 100% of the
+    ///   fitted ones.
+    pub fn price_hull_white(&self, model: &HullWhite) -> Result<f64, RustQuantError> {
+        let (a, sigma) = (model.alpha.0(0.0), model.sigma.0(0.0));
+        let valuation_date = self.bond.valuation_date();
+
+        if !(a > 0.0 && sigma > 0.0) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Hull-White mean reversion ({a}) and volatility ({sigma}) must be positive."
+            )));
+        }
+
+        if !(valuation_date < self.expiry && self.expiry < self.bond.maturity_date) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Option expiry {} must be between the valuation date {} and maturity {}.",
+                self.expiry, valuation_date, self.bond.maturity_date
+            )));
+        }
+
+        let time = |date| {
+            self.bond
+                .day_count_convention
+                .day_count_factor(valuation_date, date)
+        };
+        let discount = |date| {
+            self.bond.curve.discount_factor(date) / self.bond.curve.discount_factor(valuation_date)
+        };
+
+        let T = time(self.expiry);
+        let p_T = discount(self.expiry);
+
+        // Each cash flow after expiry as (c_i, P(0, S_i), S_i, B(T, S_i), C(T, S_i)).
+        let cash_flows: Vec<(f64, f64, f64, f64, f64)> = self
+            .bond
+            .cash_flows_after(self.expiry)
+            .into_iter()
+            .map(|(date, amount)| {
+                let S = time(date);
+                let B = (1.0 - (-a * (S - T)).exp()) / a;
+                let C = sigma.powi(2) / (4.0 * a) * (1.0 - (-2.0 * a * T).exp()) * B * B
+                    + sigma.powi(2) / (2.0 * a * a) * (1.0 - (-a * T).exp()).powi(2) * B;
+
+                (amount, discount(date), S, B, C)
+            })
+            .collect();
+
+        // Zero-coupon bond prices at expiry, in state x.
+        let zero_coupon_bonds = |x: f64| {
+            cash_flows
+                .iter()
+                .map(move |&(_, p_S, _, B, C)| p_S / p_T * (-B * x - C).exp())
+        };
+        let bond_price = |x: f64| {
+            zero_coupon_bonds(x)
+                .zip(&cash_flows)
+                .map(|(p, &(amount, ..))| amount * p)
+                .sum::<f64>()
+        };
+
+        let x = self.critical_state(bond_price, |x| {
+            -zero_coupon_bonds(x)
+                .zip(&cash_flows)
+                .map(|(p, &(amount, _, _, B, _))| amount * B * p)
+                .sum::<f64>()
+        })?;
+
+        Ok(zero_coupon_bonds(x)
+            .zip(&cash_flows)
+            .map(|(strike, &(amount, p_S, S, _, _))| {
+                amount * zero_coupon_bond_option(p_T, p_S, T, S, strike, a, sigma, self.option_type)
+            })
+            .sum())
+    }
+
+    /// The state $x^*$ at which the bond is worth the strike at expiry:
+    /// Newton-Raphson, with bisection when a step leaves the bracket.
+    fn critical_state<F, G>(&self, bond_price: F, derivative: G) -> Result<f64, RustQuantError>
+    where
+        F: Fn(f64) -> f64,
+        G: Fn(f64) -> f64,
+    {
+        const TOLERANCE: f64 = 1e-14;
+        const MAX_ITERATIONS: usize = 200;
+
+        // The bond price is decreasing in the state.
+        let (mut lo, mut hi) = (-1.0, 1.0);
+        let (highest, lowest) = (bond_price(lo), bond_price(hi));
+
+        if !(self.strike > lowest && self.strike < highest) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Strike {} is outside the attainable bond prices at expiry [{lowest}, {highest}].",
+                self.strike
+            )));
+        }
+
+        let mut x = 0.0;
+
+        for _ in 0..MAX_ITERATIONS {
+            let diff = bond_price(x) - self.strike;
+
+            if diff.abs() < TOLERANCE * self.strike {
+                break;
+            }
+
+            if diff > 0.0 {
+                lo = x;
+            } else {
+                hi = x;
+            }
+
+            let newton = x - diff / derivative(x);
+
+            x = if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+
+            if hi - lo < TOLERANCE {
+                break;
+            }
+        }
+
+        Ok(x)
+    }
+}
+
+/// Hull-White price of a European option expiring at `T` on a zero-coupon
+/// bond maturing at `S`, given the discount factors to both.
+#[allow(clippy::too_many_arguments)]
+fn zero_coupon_bond_option(
+    p_T: f64,
+    p_S: f64,
+    T: f64,
+    S: f64,
+    strike: f64,
+    a: f64,
+    sigma: f64,
+    option_type: TypeFlag,
+) -> f64 {
+    let n = Gaussian::default();
+
+    let sigma_p = sigma * (1.0 - (-a * (S - T)).exp()) / a
+        * ((1.0 - (-2.0 * a * T).exp()) / (2.0 * a)).sqrt();
+    let h = (p_S / (strike * p_T)).ln() / sigma_p + 0.5 * sigma_p;
+
+    match option_type {
+        TypeFlag::Call => p_S * n.cdf(h) - strike * p_T * n.cdf(h - sigma_p),
+        TypeFlag::Put => strike * p_T * n.cdf(sigma_p - h) - p_S * n.cdf(-h),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_coupon_bond_option {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::time::{DayCountConvention, Frequency};
+    use time::macros::date;
+
+    const VALUATION: Date = date!(2024 - 01 - 15);
+
+    /// Upward-sloping zero curve, continuously compounded, on 30/360.
+    struct ZeroCurve;
+
+    impl ZeroCurve {
+        fn zero_rate(t: f64) -> f64 {
+            0.03 + 0.02 * (1.0 - (-t / 2.0).exp())
+        }
+
+        fn discount(t: f64) -> f64 {
+            (-Self::zero_rate(t) * t).exp()
+        }
+    }
+
+    impl CurveModel for ZeroCurve {
+        fn forward_rate(&self, date: Date) -> f64 {
+            self.spot_rate(date)
+        }
+
+        fn spot_rate(&self, date: Date) -> f64 {
+            Self::zero_rate(DayCountConvention::Thirty_360_ISDA.day_count_factor(VALUATION, date))
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            Self::discount(DayCountConvention::Thirty_360_ISDA.day_count_factor(VALUATION, date))
+        }
+    }
+
+    fn bond(frequency: Frequency) -> FixedRateBond<ZeroCurve> {
+        let mut bond = FixedRateBond::new(
+            100.0,
+            0.05,
+            frequency,
+            VALUATION,
+            date!(2029 - 01 - 15),
+            ZeroCurve,
+        );
+        bond.day_count_convention = DayCountConvention::Thirty_360_ISDA;
+        bond.evaluation_date = Some(VALUATION);
+        bond
+    }
+
+    /// Hull-White trinomial tree (Hull and White, 1994), fitted to the curve.
+    fn tree_price(
+        (a, sigma): (f64, f64),
+        cash_flows: &[(f64, f64)],
+        (expiry, strike, option_type): (f64, f64, TypeFlag),
+        dt: f64,
+    ) -> f64 {
+        let step = |t: f64| (t / dt).round() as usize;
+        let (n_expiry, n_total) = (step(expiry), step(cash_flows.last().unwrap().0));
+
+        let dR = sigma * (3.0 * dt).sqrt();
+        let j_max = (0.1835 / (a * dt)).ceil() as i64;
+        let M = -a * dt;
+
+        // Successor nodes and probabilities from node j.
+        let branches = |j: i64| -> [(i64, f64); 3] {
+            let (jM, jM2) = (j as f64 * M, (j as f64 * M).powi(2));
+
+            if j == j_max {
+                [
+                    (j, 7.0 / 6.0 + (jM2 + 3.0 * jM) / 2.0),
+                    (j - 1, -1.0 / 3.0 - jM2 - 2.0 * jM),
+                    (j - 2, 1.0 / 6.0 + (jM2 + jM) / 2.0),
+                ]
+            } else if j == -j_max {
+                [
+                    (j + 2, 1.0 / 6.0 + (jM2 - jM) / 2.0),
+                    (j + 1, -1.0 / 3.0 - jM2 + 2.0 * jM),
+                    (j, 7.0 / 6.0 + (jM2 - 3.0 * jM) / 2.0),
+                ]
+            } else {
+                [
+                    (j + 1, 1.0 / 6.0 + (jM2 + jM) / 2.0),
+                    (j, 2.0 / 3.0 - jM2),
+                    (j - 1, 1.0 / 6.0 + (jM2 - jM) / 2.0),
+                ]
+            }
+        };
+        let width = |m: usize| (m as i64).min(j_max);
+        let index = |m: usize, j: i64| (j + width(m)) as usize;
+
+        // Forward induction of Arrow-Debreu prices, fitting the shift at each step.
+        let mut alpha = vec![0.0; n_total];
+        let mut Q = vec![1.0];
+
+        for m in 0..n_total {
+            let sum: f64 = (-width(m)..=width(m))
+                .map(|j| Q[index(m, j)] * (-(j as f64) * dR * dt).exp())
+                .sum();
+            alpha[m] = (sum.ln() - ZeroCurve::discount((m + 1) as f64 * dt).ln()) / dt;
+
+            let mut next = vec![0.0; 2 * width(m + 1) as usize + 1];
+            for j in -width(m)..=width(m) {
+                let value = Q[index(m, j)] * (-(alpha[m] + j as f64 * dR) * dt).exp();
+
+                for (k, p) in branches(j) {
+                    next[index(m + 1, k)] += value * p;
+                }
+            }
+            Q = next;
+        }
+
+        // Backward induction of the bond, then of the option from expiry.
+        let cash_flow_at = |m: usize| -> f64 {
+            cash_flows
+                .iter()
+                .filter(|&&(t, _)| step(t) == m)
+                .map(|&(_, amount)| amount)
+                .sum()
+        };
+        let roll_back = |m: usize, values: &[f64]| -> Vec<f64> {
+            (-width(m)..=width(m))
+                .map(|j| {
+                    let continuation: f64 = branches(j)
+                        .iter()
+                        .map(|&(k, p)| p * values[index(m + 1, k)])
+                        .sum();
+
+                    (-(alpha[m] + j as f64 * dR) * dt).exp() * continuation
+                })
+                .collect()
+        };
+
+        let mut values = vec![cash_flow_at(n_total); 2 * width(n_total) as usize + 1];
+        for m in (n_expiry..n_total).rev() {
+            values = roll_back(m, &values);
+
+            if m > n_expiry {
+                values.iter_mut().for_each(|v| *v += cash_flow_at(m));
+            }
+        }
+
+        values = values
+            .iter()
+            .map(|&v| match option_type {
+                TypeFlag::Call => (v - strike).max(0.0),
+                TypeFlag::Put => (strike - v).max(0.0),
+            })
+            .collect();
+        for m in (0..n_expiry).rev() {
+            values = roll_back(m, &values);
+        }
+
+        values[0]
+    }
+
+    #[test]
+    fn test_single_cash_flow_is_zero_coupon_bond_option() {
+        let (a, sigma) = (0.1, 0.01);
+        let model = HullWhite::new(a, sigma, 0.0);
+        let (T, S) = (2.0, 5.0);
+
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            let option = CouponBondOption::new(
+                bond(Frequency::Zero),
+                date!(2026 - 01 - 15),
+                88.0,
+                option_type,
+            );
+
+            let (p_T, p_S) = (ZeroCurve::discount(T), ZeroCurve::discount(S));
+            let sigma_p = sigma / a
+                * (1.0 - (-a * (S - T)).exp())
+                * ((1.0 - (-2.0 * a * T).exp()) / (2.0 * a)).sqrt();
+            let h = (100.0 * p_S / (88.0 * p_T)).ln() / sigma_p + sigma_p / 2.0;
+            let n = Gaussian::default();
+
+            let expected = match option_type {
+                TypeFlag::Call => 100.0 * p_S * n.cdf(h) - 88.0 * p_T * n.cdf(h - sigma_p),
+                TypeFlag::Put => 88.0 * p_T * n.cdf(sigma_p - h) - 100.0 * p_S * n.cdf(-h),
+            };
+
+            assert_approx_equal!(option.price_hull_white(&model).unwrap(), expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_jamshidian_matches_tree() {
+        let (a, sigma) = (0.1, 0.01);
+        let model = HullWhite::new(a, sigma, 0.0);
+
+        // Coupons every half year from 2.5 to 5 years, after expiry at 2 years.
+        let cash_flows: Vec<(f64, f64)> = (5..=10)
+            .map(|i| (i as f64 / 2.0, if i == 10 { 102.5 } else { 2.5 }))
+            .collect();
+
+        for (strike, option_type) in [(98.0, TypeFlag::Call), (100.0, TypeFlag::Put)] {
+            let option = CouponBondOption::new(
+                bond(Frequency::SemiAnnually),
+                date!(2026 - 01 - 15),
+                strike,
+                option_type,
+            );
+
+            let jamshidian = option.price_hull_white(&model).unwrap();
+            let tree = tree_price((a, sigma), &cash_flows, (2.0, strike, option_type), 0.01);
+
+            assert!(jamshidian > 0.1);
+            assert_approx_equal!(jamshidian, tree, 5e-3);
+        }
+    }
+
+    #[test]
+    fn test_unattainable_strike() {
+        let model = HullWhite::new(0.1, 0.01, 0.0);
+        let option = CouponBondOption::new(
+            bond(Frequency::SemiAnnually),
+            date!(2026 - 01 - 15),
+            1e5,
+            TypeFlag::Call,
+        );
+
+        match option.price_hull_white(&model) {
+            Err(RustQuantError::InvalidArgument(message)) => {
+                assert!(message.contains("100000"), "{message}");
+                assert!(
+                    message.contains("attainable bond prices at expiry ["),
+                    "{message}"
+                );
+            }
+            other => panic!("Expected an error, got {other:?}"),
+        }
+    }
+}
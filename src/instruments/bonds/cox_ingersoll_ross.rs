@@ -78,6 +78,20 @@ impl Instrument for CoxIngersollRoss {
     fn instrument_type(&self) -> &'static str {
         "Zero Coupon Bond"
     }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.expiration_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(
+            Self {
+                evaluation_date: Some(valuation_date),
+                ..*self
+            }
+            .price(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,858 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Fixed-rate coupon bonds, priced off a discount curve.
+//!
+//! Coupon dates are rolled backwards from maturity in steps of
+//! `12 / frequency` months (clamped to month end), so an irregular period,
+//! if any, is the first one. `price` is the dirty (full) price; the clean
+//! (quoted) price excludes the coupon accrued since the last coupon date.
+
+use crate::data::CurveModel;
+use crate::instruments::bonds::bond_future::ShiftedCurve;
+use crate::instruments::{CarryRollDown, CashflowReport, CashflowRow, Instrument, RolledCurve};
+use crate::time::{today, DayCountConvention, Frequency};
+use time::{Date, Month};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fixed-rate coupon bond.
+#[derive(Debug, Clone)]
+pub struct FixedRateBond<M: CurveModel> {
+    /// Face (redemption) value.
+    pub face_value: f64,
+
+    /// Annual coupon rate, as a decimal (e.g. 0.06 for 6%).
+    pub coupon_rate: f64,
+
+    /// Coupon frequency. `Frequency::Zero` gives a zero-coupon bond.
+    pub coupon_frequency: Frequency,
+
+    /// Issue date (start of the first accrual period).
+    pub issue_date: Date,
+
+    /// Maturity date (last coupon and redemption).
+    pub maturity_date: Date,
+
+    /// Day count convention for accrued interest.
+    pub day_count_convention: DayCountConvention,
+
+    /// Curve used to discount the cash flows.
+    pub curve: M,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<M: CurveModel> FixedRateBond<M> {
+    /// New fixed-rate bond, valued today, with accrued interest on the
+    /// default day count convention.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `issue_date` is not before `maturity_date`, or if the
+    /// coupon period is not a whole number of months (i.e. the frequency
+    /// is not one of annual, semi-annual, tri-annual, quarterly, monthly,
+    /// or zero).
+    pub fn new(
+        face_value: f64,
+        coupon_rate: f64,
+        coupon_frequency: Frequency,
+        issue_date: Date,
+        maturity_date: Date,
+        curve: M,
+    ) -> Self {
+        assert!(issue_date < maturity_date);
+        assert!(
+            coupon_frequency == Frequency::Zero || 12 % (coupon_frequency as i64) == 0,
+            "Coupon period must be a whole number of months."
+        );
+
+        Self {
+            face_value,
+            coupon_rate,
+            coupon_frequency,
+            issue_date,
+            maturity_date,
+            day_count_convention: DayCountConvention::default(),
+            curve,
+            evaluation_date: None,
+        }
+    }
+
+    /// Coupon payment dates, from the first to maturity.
+    pub fn coupon_dates(&self) -> Vec<Date> {
+        if self.coupon_frequency == Frequency::Zero {
+            return vec![self.maturity_date];
+        }
+
+        let months = 12 / self.coupon_frequency as i32;
+
+        let mut dates: Vec<Date> = (0..)
+            .map(|i| shift_months(self.maturity_date, -i * months))
+            .take_while(|&date| date > self.issue_date)
+            .collect();
+
+        dates.reverse();
+        dates
+    }
+
+    /// Coupon paid on each coupon date.
+    pub fn coupon(&self) -> f64 {
+        match self.coupon_frequency {
+            Frequency::Zero => 0.0,
+            frequency => self.face_value * self.coupon_rate / frequency as i64 as f64,
+        }
+    }
+
+    /// Coupon accrued since the last coupon (or issue) date.
+    pub fn accrued_interest(&self) -> f64 {
+        self.accrued_interest_at(self.valuation_date())
+    }
+
+    /// Clean (quoted) price: the dirty price less accrued interest.
+    pub fn clean_price(&self) -> f64 {
+        self.price() - self.accrued_interest()
+    }
+
+    /// Cash flows (coupons, and the redemption at maturity) paid strictly
+    /// after `date`, as `(payment date, amount)`.
+    pub fn cash_flows_after(&self, date: Date) -> Vec<(Date, f64)> {
+        let coupon = self.coupon();
+
+        let mut cash_flows: Vec<(Date, f64)> = self
+            .coupon_dates()
+            .into_iter()
+            .filter(|&payment| payment > date)
+            .map(|payment| (payment, coupon))
+            .collect();
+
+        // The last coupon date is the maturity date.
+        if let Some((_, amount)) = cash_flows.last_mut() {
+            *amount += self.face_value;
+        }
+
+        cash_flows
+    }
+
+    /// All the cash flows of the bond, coupons and then the redemption,
+    /// with their present values as of the valuation date.
+    ///
+    /// The coupon rows show the bond's day count fraction for the accrual
+    /// period, but the amount is the regular coupon (see [`Self::coupon`]).
+    pub fn cashflow_report(&self) -> CashflowReport {
+        let valuation_date = self.valuation_date();
+        let valuation_discount_factor = self.curve.discount_factor(valuation_date);
+
+        let row = |payment_date, accrual_start, accrual_end, rate, amount| {
+            let is_past = payment_date <= valuation_date;
+            let discount_factor = (!is_past)
+                .then(|| self.curve.discount_factor(payment_date) / valuation_discount_factor);
+
+            CashflowRow {
+                payment_date,
+                accrual_start,
+                accrual_end,
+                day_count_fraction: self
+                    .day_count_convention
+                    .day_count_factor(accrual_start, accrual_end),
+                rate,
+                index: None,
+                amount,
+                discount_factor,
+                present_value: discount_factor.map_or(0.0, |df| amount * df),
+                is_past,
+            }
+        };
+
+        let mut rows = Vec::new();
+
+        if self.coupon_frequency != Frequency::Zero {
+            let mut accrual_start = self.issue_date;
+
+            for payment_date in self.coupon_dates() {
+                rows.push(row(
+                    payment_date,
+                    accrual_start,
+                    payment_date,
+                    Some(self.coupon_rate),
+                    self.coupon(),
+                ));
+                accrual_start = payment_date;
+            }
+        }
+
+        rows.push(row(
+            self.maturity_date,
+            self.maturity_date,
+            self.maturity_date,
+            None,
+            self.face_value,
+        ));
+
+        CashflowReport {
+            valuation_date,
+            rows,
+        }
+    }
+
+    /// Dirty price at a flat `yield_rate`, compounded at the coupon
+    /// frequency (annually for a zero-coupon bond), with times measured
+    /// from the valuation date on the bond's day count convention.
+    pub fn price_from_yield(&self, yield_rate: f64) -> f64 {
+        self.price_and_yield_derivative(yield_rate).0
+    }
+
+    /// Macaulay duration at a flat `yield_rate`: the present-value weighted
+    /// average time to the cash flows, in years.
+    pub fn macaulay_duration(&self, yield_rate: f64) -> f64 {
+        let (price, weighted_time, _) = self.yield_moments(yield_rate);
+
+        weighted_time / price
+    }
+
+    /// Modified duration at a flat `yield_rate`, $-\frac{1}{P} \frac{dP}{dy}$,
+    /// which is the Macaulay duration divided by $1 + y / f$.
+    pub fn modified_duration(&self, yield_rate: f64) -> f64 {
+        self.macaulay_duration(yield_rate) / self.yield_growth(yield_rate)
+    }
+
+    /// Convexity at a flat `yield_rate`, $\frac{1}{P} \frac{d^2P}{dy^2}$.
+    pub fn convexity(&self, yield_rate: f64) -> f64 {
+        let (price, _, weighted_time_squared) = self.yield_moments(yield_rate);
+
+        weighted_time_squared / (price * self.yield_growth(yield_rate).powi(2))
+    }
+
+    /// Yield to maturity: the flat yield at which [`Self::price_from_yield`]
+    /// reproduces the (dirty) `market_price`.
+    ///
+    /// Newton-Raphson, with the analytic derivative of the price, falls
+    /// back to bisection whenever a step leaves the bracketing interval.
+    /// Returns `None` if no yield between -50% and 100% reproduces the price.
+    pub fn yield_to_maturity(&self, market_price: f64) -> Option<f64> {
+        const TOLERANCE: f64 = 1e-12;
+        const MAX_ITERATIONS: usize = 100;
+
+        // The price is decreasing in the yield.
+        let (mut lo, mut hi) = (-0.5, 1.0);
+
+        if !(market_price <= self.price_from_yield(lo) && market_price >= self.price_from_yield(hi))
+        {
+            return None;
+        }
+
+        let mut yield_rate = self.coupon_rate.clamp(lo, hi);
+
+        for _ in 0..MAX_ITERATIONS {
+            let (price, derivative) = self.price_and_yield_derivative(yield_rate);
+            let diff = price - market_price;
+
+            if diff.abs() < TOLERANCE {
+                return Some(yield_rate);
+            }
+
+            if diff > 0.0 {
+                lo = yield_rate;
+            } else {
+                hi = yield_rate;
+            }
+
+            let newton = yield_rate - diff / derivative;
+
+            yield_rate = if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+
+            if hi - lo < TOLERANCE {
+                return Some(yield_rate);
+            }
+        }
+
+        Some(yield_rate)
+    }
+
+    /// Carry and roll-down from the valuation date to `horizon_date`, for a
+    /// position funded at `funding_rate` (simple, on Actual/360, as repo).
+    ///
+    /// The dates are settlement dates: the accrued interest is that at each
+    /// date, and the coupons paid in between are part of the carry (they are
+    /// not reinvested). The funding is on the dirty price, and the roll-down
+    /// is the change in clean price (see [`crate::instruments::carry_roll_down`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `horizon_date` is not after the valuation date and before
+    /// maturity.
+    pub fn carry_roll_down(&self, horizon_date: Date, funding_rate: f64) -> CarryRollDown {
+        const BP: f64 = 1e-4;
+
+        let valuation_date = self.valuation_date();
+        assert!(valuation_date < horizon_date && horizon_date < self.maturity_date);
+
+        let coupons: f64 = self
+            .cash_flows_after(valuation_date)
+            .into_iter()
+            .filter(|&(payment, _)| payment <= horizon_date)
+            .map(|(_, amount)| amount)
+            .sum();
+
+        let funding = self.price()
+            * funding_rate
+            * DayCountConvention::Actual_360.day_count_factor(valuation_date, horizon_date);
+
+        let carry =
+            coupons + self.accrued_interest_at(horizon_date) - self.accrued_interest() - funding;
+
+        let rolled_curve = RolledCurve {
+            curve: &self.curve,
+            roll: horizon_date - valuation_date,
+        };
+        let rolled = self.with_curve(rolled_curve, horizon_date);
+
+        let roll_down = rolled.clean_price() - self.clean_price();
+
+        let shifted_price = |shift| {
+            let curve = ShiftedCurve {
+                curve: &rolled.curve,
+                anchor: horizon_date,
+                shift,
+            };
+
+            self.with_curve(curve, horizon_date).price()
+        };
+
+        let risk = (shifted_price(-BP) - shifted_price(BP)) / (2.0 * BP);
+
+        CarryRollDown::new(valuation_date, horizon_date, carry, roll_down, risk)
+    }
+
+    /// Copy of the bond on `curve`, valued at `evaluation_date`.
+    pub(crate) fn with_curve<N: CurveModel>(
+        &self,
+        curve: N,
+        evaluation_date: Date,
+    ) -> FixedRateBond<N> {
+        FixedRateBond {
+            face_value: self.face_value,
+            coupon_rate: self.coupon_rate,
+            coupon_frequency: self.coupon_frequency,
+            issue_date: self.issue_date,
+            maturity_date: self.maturity_date,
+            day_count_convention: self.day_count_convention,
+            curve,
+            evaluation_date: Some(evaluation_date),
+        }
+    }
+
+    fn price_and_yield_derivative(&self, yield_rate: f64) -> (f64, f64) {
+        let (price, weighted_time, _) = self.yield_moments(yield_rate);
+
+        (price, -weighted_time / self.yield_growth(yield_rate))
+    }
+
+    /// Compounding frequency of the yield: the coupon frequency, or annual
+    /// for a zero-coupon bond.
+    fn yield_frequency(&self) -> f64 {
+        match self.coupon_frequency {
+            Frequency::Zero => 1.0,
+            frequency => frequency as i64 as f64,
+        }
+    }
+
+    /// Growth factor over one compounding period, $1 + y / f$.
+    fn yield_growth(&self, yield_rate: f64) -> f64 {
+        1.0 + yield_rate / self.yield_frequency()
+    }
+
+    /// Present values $v_i$ of the remaining cash flows at a flat yield, as
+    /// $(\sum_i v_i, \sum_i t_i v_i, \sum_i t_i (t_i + 1/f) v_i)$.
+    ///
+    /// Since $\frac{d v_i}{dy} = -t_i v_i / (1 + y/f)$, the second and
+    /// third sums give the first two yield derivatives of the price.
+    fn yield_moments(&self, yield_rate: f64) -> (f64, f64, f64) {
+        let frequency = self.yield_frequency();
+        let growth = self.yield_growth(yield_rate);
+        let date = self.valuation_date();
+
+        self.cash_flows_after(date).into_iter().fold(
+            (0.0, 0.0, 0.0),
+            |(price, weighted_time, weighted_time_squared), (payment, amount)| {
+                let t = self.day_count_convention.day_count_factor(date, payment);
+                let value = amount * growth.powf(-frequency * t);
+
+                (
+                    price + value,
+                    weighted_time + t * value,
+                    weighted_time_squared + t * (t + 1.0 / frequency) * value,
+                )
+            },
+        )
+    }
+
+    /// Dirty price as of `date`: cash flows strictly after `date`, discounted
+    /// on the curve and rolled forward to `date`.
+    pub(crate) fn price_at(&self, date: Date) -> f64 {
+        let value: f64 = self
+            .cash_flows_after(date)
+            .into_iter()
+            .map(|(payment, amount)| amount * self.curve.discount_factor(payment))
+            .sum();
+
+        value / self.curve.discount_factor(date)
+    }
+
+    pub(crate) fn accrued_interest_at(&self, date: Date) -> f64 {
+        if self.coupon_frequency == Frequency::Zero
+            || date <= self.issue_date
+            || date >= self.maturity_date
+        {
+            return 0.0;
+        }
+
+        let coupon_dates = self.coupon_dates();
+        let next = coupon_dates.partition_point(|&payment| payment <= date);
+        let end = coupon_dates[next];
+
+        // A short first period accrues from the issue date, at the rate of a
+        // regular period (the full first coupon is still paid).
+        let (start, period_start) = match next {
+            0 => (
+                self.issue_date,
+                shift_months(end, -12 / self.coupon_frequency as i32),
+            ),
+            _ => (coupon_dates[next - 1], coupon_dates[next - 1]),
+        };
+
+        let convention = self.day_count_convention;
+        let accrued = convention.day_count_factor(start, date);
+        let period = convention.day_count_factor(period_start, end);
+
+        self.coupon() * accrued / period
+    }
+}
+
+impl<M: CurveModel> Instrument for FixedRateBond<M> {
+    /// Dirty price of the bond.
+    fn price(&self) -> f64 {
+        self.price_at(self.valuation_date())
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "FixedRateBond"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.maturity_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(self.price_at(valuation_date))
+    }
+}
+
+/// Shift a date by a number of months, clamping the day to the month end.
+pub(crate) fn shift_months(date: Date, months: i32) -> Date {
+    let index = date.year() * 12 + date.month() as i32 - 1 + months;
+    let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u8 + 1);
+    let month = Month::try_from(month).unwrap();
+    let day = date.day().min(month.length(year));
+
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_fixed_rate_bond {
+    use super::*;
+    use crate::assert_approx_equal;
+    use time::macros::date;
+
+    /// Flat yield, compounded semi-annually, on a 30/360 basis from `anchor`.
+    struct FlatCurve {
+        anchor: Date,
+        yield_rate: f64,
+    }
+
+    impl CurveModel for FlatCurve {
+        fn forward_rate(&self, _date: Date) -> f64 {
+            self.yield_rate
+        }
+
+        fn spot_rate(&self, _date: Date) -> f64 {
+            self.yield_rate
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Thirty_360_ISDA.day_count_factor(self.anchor, date);
+
+            (1.0 + self.yield_rate / 2.0).powf(-2.0 * tau)
+        }
+    }
+
+    /// Continuously compounded zero rates $a + b (1 - e^{-\tau})$, on an
+    /// Actual/365 (Fixed) basis from `anchor`.
+    struct ZeroCurve {
+        anchor: Date,
+        level: f64,
+        slope: f64,
+    }
+
+    impl ZeroCurve {
+        fn zero_rate(&self, tau: f64) -> f64 {
+            self.level + self.slope * (1.0 - (-tau).exp())
+        }
+    }
+
+    impl CurveModel for ZeroCurve {
+        fn forward_rate(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(self.anchor, date);
+
+            self.zero_rate(tau) + tau * self.slope * (-tau).exp()
+        }
+
+        fn spot_rate(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(self.anchor, date);
+
+            self.zero_rate(tau)
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(self.anchor, date);
+
+            (-self.zero_rate(tau) * tau).exp()
+        }
+    }
+
+    fn fabozzi_bond(evaluation_date: Date) -> FixedRateBond<FlatCurve> {
+        // Fabozzi: 10-year 6% semi-annual bond, yielding 8%.
+        let mut bond = FixedRateBond::new(
+            100.0,
+            0.06,
+            Frequency::SemiAnnually,
+            date!(2020 - 01 - 15),
+            date!(2030 - 01 - 15),
+            FlatCurve {
+                anchor: date!(2020 - 01 - 15),
+                yield_rate: 0.08,
+            },
+        );
+        bond.day_count_convention = DayCountConvention::Thirty_360_ISDA;
+        bond.evaluation_date = Some(evaluation_date);
+        bond
+    }
+
+    #[test]
+    fn test_fixed_rate_bond_textbook_price() {
+        let bond = fabozzi_bond(date!(2020 - 01 - 15));
+
+        let dates = bond.coupon_dates();
+        assert_eq!(dates.len(), 20);
+        assert_eq!(dates[0], date!(2020 - 07 - 15));
+        assert_eq!(bond.instrument_type(), "FixedRateBond");
+        assert_eq!(bond.expiration_date(), Some(date!(2030 - 01 - 15)));
+
+        // Annuity of 3.0 for 20 periods at 4%, plus 100 discounted.
+        assert_approx_equal!(bond.price(), 86.409_674, 1e-6);
+        assert_eq!(bond.accrued_interest(), 0.0);
+        assert_eq!(bond.clean_price(), bond.price());
+    }
+
+    #[test]
+    fn test_fixed_rate_bond_accrued_interest() {
+        // Halfway through the first coupon period (90 / 180 days on 30/360).
+        let bond = fabozzi_bond(date!(2020 - 04 - 15));
+
+        assert_approx_equal!(bond.accrued_interest(), 1.5, 1e-12);
+
+        // The dirty price is the coupon-date price rolled forward half a period.
+        let dirty = 86.409_674 * 1.04_f64.sqrt();
+        assert_approx_equal!(bond.price(), dirty, 1e-5);
+        assert_approx_equal!(bond.clean_price(), dirty - 1.5, 1e-5);
+
+        // On a coupon date, that coupon has been paid and nothing has accrued.
+        let bond = fabozzi_bond(date!(2020 - 07 - 15));
+        assert_eq!(bond.accrued_interest(), 0.0);
+        assert_approx_equal!(bond.price(), 86.409_674 * 1.04 - 3.0, 1e-5);
+    }
+
+    #[test]
+    fn test_fixed_rate_bond_short_first_period() {
+        // Issued two months into a semi-annual period: the first coupon is
+        // paid in full, so accrual runs at the regular period's rate.
+        let mut bond = fabozzi_bond(date!(2020 - 04 - 15));
+        bond.issue_date = date!(2020 - 03 - 15);
+
+        assert_eq!(bond.coupon_dates()[0], date!(2020 - 07 - 15));
+        assert_approx_equal!(bond.accrued_interest(), 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_yield_to_maturity() {
+        // On the flat 8% curve, at or between coupon dates, the yield is 8%.
+        for date in [date!(2020 - 01 - 15), date!(2023 - 09 - 15)] {
+            let bond = fabozzi_bond(date);
+
+            let ytm = bond.yield_to_maturity(bond.price()).unwrap();
+            assert_approx_equal!(ytm, 0.08, 1e-10);
+        }
+
+        let bond = fabozzi_bond(date!(2021 - 05 - 03));
+
+        for market_price in [60.0, 86.0, 100.0, 125.0] {
+            let ytm = bond.yield_to_maturity(market_price).unwrap();
+
+            assert_approx_equal!(bond.price_from_yield(ytm), market_price, 1e-8);
+        }
+
+        // The modified duration is the relative price sensitivity.
+        let (y, h) = (0.07, 1e-6);
+        let derivative = (bond.price_from_yield(y + h) - bond.price_from_yield(y - h)) / (2.0 * h);
+        assert_approx_equal!(
+            bond.modified_duration(y),
+            -derivative / bond.price_from_yield(y),
+            1e-6
+        );
+
+        // No yield in range reproduces a negative or huge price.
+        assert!(bond.yield_to_maturity(-1.0).is_none());
+        assert!(bond.yield_to_maturity(1e6).is_none());
+    }
+
+    #[test]
+    fn test_duration_and_convexity() {
+        let bond = fabozzi_bond(date!(2021 - 05 - 03));
+        let (y, h) = (0.07, 1e-4);
+
+        let price = |y| bond.price_from_yield(y);
+        let second_derivative = (price(y + h) - 2.0 * price(y) + price(y - h)) / (h * h);
+
+        assert_approx_equal!(
+            bond.modified_duration(y),
+            bond.macaulay_duration(y) / (1.0 + y / 2.0),
+            1e-12
+        );
+        assert_approx_equal!(bond.convexity(y), second_derivative / price(y), 1e-4);
+
+        // A zero-coupon bond's Macaulay duration is its time to maturity.
+        for evaluation_date in [date!(2020 - 01 - 15), date!(2023 - 09 - 15)] {
+            let mut zero = fabozzi_bond(evaluation_date);
+            zero.coupon_frequency = Frequency::Zero;
+
+            let time_to_maturity = zero
+                .day_count_convention
+                .day_count_factor(evaluation_date, zero.maturity_date);
+
+            for y in [0.0, 0.03, 0.08] {
+                assert_approx_equal!(zero.macaulay_duration(y), time_to_maturity, 1e-12);
+                assert_approx_equal!(
+                    zero.convexity(y),
+                    time_to_maturity * (time_to_maturity + 1.0) / (1.0 + y).powi(2),
+                    1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cashflow_report() {
+        for evaluation_date in [
+            date!(2020 - 01 - 15),
+            date!(2023 - 09 - 15),
+            date!(2024 - 01 - 15),
+        ] {
+            let bond = fabozzi_bond(evaluation_date);
+            let report = bond.cashflow_report();
+
+            // 20 coupons and the redemption.
+            assert_eq!(report.rows.len(), 21);
+            assert_approx_equal!(report.present_value(), bond.price(), 1e-12);
+
+            for row in &report.rows {
+                assert_eq!(row.is_past, row.payment_date <= evaluation_date);
+
+                if row.is_past {
+                    assert_eq!(row.present_value, 0.0);
+                    assert!(row.discount_factor.is_none());
+                }
+            }
+        }
+
+        let report = fabozzi_bond(date!(2024 - 01 - 15)).cashflow_report();
+        let coupon = &report.rows[7];
+
+        // The coupon paid on the valuation date is past, the next is not.
+        assert_eq!(coupon.payment_date, date!(2024 - 01 - 15));
+        assert_eq!(coupon.accrual_start, date!(2023 - 07 - 15));
+        assert!(coupon.is_past && !report.rows[8].is_past);
+        assert_eq!(coupon.day_count_fraction, 0.5);
+        assert_eq!((coupon.rate, coupon.amount), (Some(0.06), 3.0));
+
+        let redemption = report.rows.last().unwrap();
+        assert_eq!((redemption.rate, redemption.amount), (None, 100.0));
+    }
+
+    #[test]
+    fn test_carry_roll_down_par_bond_flat_curve() {
+        // Annual coupons, with every period 365 days long.
+        let (valuation_date, horizon_date) = (date!(2025 - 01 - 15), date!(2026 - 01 - 15));
+        let (level, funding_rate) = (0.05_f64, 0.03);
+
+        // At the flat rate's annual yield, the bond is at par on its coupon dates.
+        let mut bond = FixedRateBond::new(
+            100.0,
+            level.exp() - 1.0,
+            Frequency::Annually,
+            valuation_date,
+            date!(2028 - 01 - 15),
+            ZeroCurve {
+                anchor: valuation_date,
+                level,
+                slope: 0.0,
+            },
+        );
+        bond.evaluation_date = Some(valuation_date);
+        assert_approx_equal!(bond.price(), 100.0, 1e-10);
+
+        let report = bond.carry_roll_down(horizon_date, funding_rate);
+
+        // Funded at par for 365 days, on Actual/360.
+        let funding = 100.0 * funding_rate * 365.0 / 360.0;
+
+        assert_approx_equal!(report.roll_down, 0.0, 1e-10);
+        assert_approx_equal!(report.carry, bond.coupon() - funding, 1e-10);
+        assert_approx_equal!(report.roll_down_yield, 0.0, 1e-12);
+        assert!(report.carry_yield > 0.0);
+    }
+
+    #[test]
+    fn test_carry_roll_down_steep_curve() {
+        let (valuation_date, horizon_date) = (date!(2025 - 01 - 15), date!(2026 - 01 - 15));
+
+        let curve = ZeroCurve {
+            anchor: valuation_date,
+            level: 0.01,
+            slope: 0.04,
+        };
+
+        let mut note = FixedRateBond::new(
+            100.0,
+            0.04,
+            Frequency::SemiAnnually,
+            valuation_date,
+            date!(2035 - 01 - 15),
+            curve,
+        );
+        note.evaluation_date = Some(valuation_date);
+
+        let report = note.carry_roll_down(horizon_date, 0.02);
+
+        let tau =
+            |date| DayCountConvention::Actual_365_Fixed.day_count_factor(valuation_date, date);
+        let (zero_rate, h) = (|tau| note.curve.zero_rate(tau), tau(horizon_date));
+
+        // Both dates are coupon dates, so the clean and dirty prices agree.
+        // At the horizon, each cash flow is discounted at today's zero rate
+        // for its remaining tenor.
+        let cash_flows = note.cash_flows_after(valuation_date);
+        let price_now: f64 = cash_flows
+            .iter()
+            .map(|&(payment, amount)| amount * (-zero_rate(tau(payment)) * tau(payment)).exp())
+            .sum();
+        let price_rolled: f64 = cash_flows
+            .iter()
+            .filter(|&&(payment, _)| payment > horizon_date)
+            .map(|&(payment, amount)| {
+                let tenor = tau(payment) - h;
+
+                amount * (-zero_rate(tenor) * tenor).exp()
+            })
+            .sum();
+
+        assert!(report.roll_down > 0.0);
+        assert_approx_equal!(report.roll_down, price_rolled - price_now, 1e-10);
+
+        // Rolling down an upward sloping curve lowers the yield.
+        assert!(report.roll_down_yield > 0.0);
+    }
+
+    #[test]
+    fn test_carry_roll_down_horizon_pnl() {
+        let curve = ZeroCurve {
+            anchor: date!(2025 - 01 - 15),
+            level: 0.01,
+            slope: 0.04,
+        };
+
+        let mut note = FixedRateBond::new(
+            100.0,
+            0.045,
+            Frequency::SemiAnnually,
+            date!(2025 - 01 - 15),
+            date!(2035 - 01 - 15),
+            curve,
+        );
+
+        // Mid-period, with a coupon in between.
+        let (valuation_date, horizon_date) = (date!(2025 - 03 - 10), date!(2025 - 11 - 20));
+        let funding_rate = 0.03;
+
+        note.evaluation_date = Some(valuation_date);
+        let report = note.carry_roll_down(horizon_date, funding_rate);
+
+        let coupons = note.coupon();
+        let funded = note.price()
+            * (1.0
+                + funding_rate
+                    * DayCountConvention::Actual_360
+                        .day_count_factor(valuation_date, horizon_date));
+
+        // Horizon P&L, with the curve rolled forward and shifted by `shift`.
+        let pnl = |shift| {
+            let rolled = RolledCurve {
+                curve: &note.curve,
+                roll: horizon_date - valuation_date,
+            };
+            let shifted = ShiftedCurve {
+                curve: &rolled,
+                anchor: horizon_date,
+                shift,
+            };
+
+            note.with_curve(shifted, horizon_date).price() + coupons - funded
+        };
+
+        assert_approx_equal!(report.total(), pnl(0.0), 1e-10);
+
+        // Rates rising by the breakeven wipe out the P&L, up to convexity.
+        let breakeven = report.breakeven_yield_change();
+        assert!(breakeven > 0.0);
+        assert!(pnl(breakeven).abs() < 0.02 * report.total());
+        assert!(pnl(1.5 * breakeven) < 0.0);
+    }
+}
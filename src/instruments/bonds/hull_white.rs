@@ -57,7 +57,7 @@ impl HullWhite {
         let t = (self.evaluation_date.unwrap_or(today).year() - today.year()) as f64;
         let T = (self.expiration_date.year() - today.year()) as f64;
 
-        let first = -1.0 * integrate(|u| (self.theta_t)(u) * self.B(), t, T);
+        let first = -integrate(|u| (self.theta_t)(u) * self.B(), t, T);
 
         let second = ((self.sigma).powi(2) / (2.0 * (self.a).powi(2))) * (self.B() - self.tau());
 
@@ -79,7 +79,7 @@ impl Instrument for HullWhite {
         assert!(self.a > 0.0);
         assert!(self.expiration_date >= self.evaluation_date.unwrap_or(today()));
 
-        self.A() * (-1.0 * self.B() * self.r_t).exp()
+        self.A() * (-self.B() * self.r_t).exp()
     }
 
     fn error(&self) -> Option<f64> {
@@ -93,6 +93,20 @@ impl Instrument for HullWhite {
     fn instrument_type(&self) -> &'static str {
         "Zero Coupon Bond"
     }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.expiration_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(
+            Self {
+                evaluation_date: Some(valuation_date),
+                ..*self
+            }
+            .price(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -6,3 +6,21 @@
 //      - LICENSE-APACHE.md
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// European options on coupon bonds.
+pub mod coupon_bond_option;
+
+/// Cox-Ingersoll-Ross zero-coupon bond.
+pub mod cox_ingersoll_ross;
+
+/// Hull-White zero-coupon bond.
+pub mod hull_white;
+
+/// Vasicek zero-coupon bond.
+pub mod vasicek;
+
+/// Fixed-rate coupon bond.
+pub mod fixed_rate_bond;
+
+/// Bond futures with a deliverable basket.
+pub mod bond_future;
@@ -21,6 +21,7 @@
 //! - `σ`: is the diffusion coefficient.
 
 use crate::instruments::Instrument;
+use crate::models::OrnsteinUhlenbeck;
 use crate::time::{today, DayCountConvention};
 use time::Date;
 
@@ -38,26 +39,21 @@ pub struct Vasicek {
 }
 
 impl Instrument for Vasicek {
+    /// Price from the affine bond formula of the Vasicek short rate model
+    /// (see [`OrnsteinUhlenbeck::vasicek_zcb_price`]). A bond past its
+    /// expiration date is valued at its face value of one.
     fn price(&self) -> f64 {
-        let k = self.k;
-        let theta = self.theta;
-        let sigma = self.sigma;
-        let r0 = self.r0;
-
         // Compute time to maturity.
         let tau = DayCountConvention::default().day_count_factor(
             self.evaluation_date.unwrap_or(today()),
             self.expiration_date,
         );
 
-        let B = || (1.0 - (-k * tau).exp()) / k;
-        let A = || {
-            (((B() - tau) * (k.powi(2) * theta - sigma.powi(2) / 2.0)) / k.powi(2)
-                - (sigma.powi(2) * B().powi(2)) / (4.0 * k))
-                .exp()
-        };
-
-        A() * (-B() * r0).exp()
+        OrnsteinUhlenbeck::new(self.theta, self.sigma, self.k).vasicek_zcb_price(
+            self.r0,
+            0.0,
+            tau.max(0.0),
+        )
 
         // Return the option price on the zero coupon bond?
         // let N = Gaussian::default();
@@ -86,6 +82,20 @@ impl Instrument for Vasicek {
     fn instrument_type(&self) -> &'static str {
         "Zero Coupon Bond"
     }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.expiration_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(
+            Self {
+                evaluation_date: Some(valuation_date),
+                ..*self
+            }
+            .price(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -96,19 +106,49 @@ mod tests_bond_vasicek {
 
     #[test]
     fn test_vasicek_zero_coupon_bond() {
-        let expiry_date = today() + time::Duration::days(365);
+        let evaluation_date = time::macros::date!(2023 - 01 - 02);
 
         let vasicek = Vasicek {
             r0: 0.03,
             k: 0.3,
             theta: 0.1,
             sigma: 0.03,
-            evaluation_date: None,
-            expiration_date: expiry_date,
+            evaluation_date: Some(evaluation_date),
+            expiration_date: evaluation_date + time::Duration::days(365),
         };
 
         let vasicek_price = vasicek.price();
 
-        assert_approx_equal!(vasicek_price, 0.9615, 1e-4);
+        assert_approx_equal!(vasicek_price, 0.96136, 1e-4);
+
+        // A matured bond is worth its face value.
+        assert_eq!(
+            vasicek.price_as_of(evaluation_date + time::Duration::days(400)),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_vasicek_theta_is_daily_carry() {
+        let evaluation_date = time::macros::date!(2024 - 03 - 01);
+
+        // No volatility and theta = r0, so the yield curve is flat at r0.
+        let vasicek = Vasicek {
+            r0: 0.04,
+            k: 0.3,
+            theta: 0.04,
+            sigma: 0.0,
+            evaluation_date: Some(evaluation_date),
+            expiration_date: evaluation_date + time::Duration::days(730),
+        };
+
+        let one_day = DayCountConvention::default()
+            .day_count_factor(evaluation_date, evaluation_date.next_day().unwrap());
+
+        assert_approx_equal!(
+            vasicek.theta().unwrap(),
+            vasicek.price() * ((0.04 * one_day).exp() - 1.0),
+            1e-12
+        );
     }
 }
@@ -0,0 +1,110 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Carry and roll-down of curve instruments over a holding horizon.
+//!
+//! Holding a position from the valuation date $t_0$ to a horizon date $t_1$,
+//! with the curve unchanged, earns
+//!
+//! - the carry: the coupons (or fixed leg) accrued and received, less the
+//!   funding (or floating leg) accrued at the funding rate, and
+//! - the roll-down: the change in clean value as the instrument slides down
+//!   the curve, valued at $t_1$ on the curve rolled forward by $t_1 - t_0$,
+//!   so that the rate at each tenor is the same as today.
+//!
+//! Each is also given in yield terms, divided by the instrument's sensitivity
+//! to a parallel shift of the (continuously compounded) zero rates at the
+//! horizon. Their sum is the breakeven: the rise in rates over the horizon
+//! that, to first order, wipes out the carry and roll-down.
+
+use crate::data::CurveModel;
+use time::{Date, Duration};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Carry and roll-down of a position over a holding horizon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarryRollDown {
+    /// Start of the horizon (the valuation date).
+    pub valuation_date: Date,
+
+    /// End of the horizon.
+    pub horizon_date: Date,
+
+    /// Coupon accrual less funding accrual, in price terms.
+    pub carry: f64,
+
+    /// Change in clean value on the rolled curve, in price terms.
+    pub roll_down: f64,
+
+    /// Carry in yield terms (as a decimal).
+    pub carry_yield: f64,
+
+    /// Roll-down in yield terms (as a decimal).
+    pub roll_down_yield: f64,
+}
+
+/// Curve rolled forward by `roll`: its rates at each tenor are those of
+/// `curve` at the same tenor `roll` earlier.
+pub(crate) struct RolledCurve<'a, M: CurveModel> {
+    pub(crate) curve: &'a M,
+    pub(crate) roll: Duration,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CarryRollDown {
+    /// Report from the carry and roll-down in price terms, and `risk`, the
+    /// fall in value per unit parallel rise in the zero rates at the horizon.
+    pub(crate) fn new(
+        valuation_date: Date,
+        horizon_date: Date,
+        carry: f64,
+        roll_down: f64,
+        risk: f64,
+    ) -> Self {
+        Self {
+            valuation_date,
+            horizon_date,
+            carry,
+            roll_down,
+            carry_yield: carry / risk,
+            roll_down_yield: roll_down / risk,
+        }
+    }
+
+    /// Carry plus roll-down: the horizon P&L if the curve is unchanged.
+    pub fn total(&self) -> f64 {
+        self.carry + self.roll_down
+    }
+
+    /// Parallel rise in the zero rates over the horizon that wipes out
+    /// the carry and roll-down (to first order), as a decimal.
+    pub fn breakeven_yield_change(&self) -> f64 {
+        self.carry_yield + self.roll_down_yield
+    }
+}
+
+impl<M: CurveModel> CurveModel for RolledCurve<'_, M> {
+    fn forward_rate(&self, date: Date) -> f64 {
+        self.curve.forward_rate(date - self.roll)
+    }
+
+    fn spot_rate(&self, date: Date) -> f64 {
+        self.curve.spot_rate(date - self.roll)
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.curve.discount_factor(date - self.roll)
+    }
+}
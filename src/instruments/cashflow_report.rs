@@ -0,0 +1,231 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Projected cash flows of an instrument, for checking its price.
+//!
+//! Each row is one payment, with its accrual period, rate, amount, and
+//! present value at the valuation date. Payments on or before the valuation
+//! date are kept in the report, flagged as past, but have no present value,
+//! so the present values sum to the instrument's price.
+
+use crate::error::RustQuantError;
+use polars::prelude::*;
+use std::fmt;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// One cash flow of a [CashflowReport].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashflowRow {
+    /// Payment date.
+    pub payment_date: Date,
+
+    /// Start of the accrual period.
+    pub accrual_start: Date,
+
+    /// End of the accrual period.
+    pub accrual_end: Date,
+
+    /// Day count fraction of the accrual period.
+    pub day_count_fraction: f64,
+
+    /// Annual rate accrued, as a decimal (none for principal flows).
+    pub rate: Option<f64>,
+
+    /// Where a floating rate comes from: the index and its fixing, or the
+    /// curve it is projected off (none for fixed rates).
+    pub index: Option<String>,
+
+    /// Amount paid.
+    pub amount: f64,
+
+    /// Discount factor from the payment date to the valuation date
+    /// (none for past payments).
+    pub discount_factor: Option<f64>,
+
+    /// Present value at the valuation date (zero for past payments).
+    pub present_value: f64,
+
+    /// Whether the payment is on or before the valuation date.
+    pub is_past: bool,
+}
+
+/// Projected cash flows of an instrument, in payment date order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashflowReport {
+    /// Valuation date of the present values.
+    pub valuation_date: Date,
+
+    /// The cash flows.
+    pub rows: Vec<CashflowRow>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CashflowReport {
+    /// Total present value of the future cash flows.
+    pub fn present_value(&self) -> f64 {
+        self.rows.iter().map(|row| row.present_value).sum()
+    }
+
+    /// Convert the report to a `DataFrame`, with one row per cash flow and
+    /// columns `payment_date`, `accrual_start`, `accrual_end` (dates),
+    /// `day_count_fraction`, `rate` (floats), `index` (string), `amount`,
+    /// `discount_factor`, `present_value` (floats), and `is_past` (boolean).
+    pub fn to_dataframe(&self) -> Result<DataFrame, RustQuantError> {
+        let unix_epoch = time::OffsetDateTime::UNIX_EPOCH.date();
+
+        let dates = |name: &str, date: fn(&CashflowRow) -> Date| {
+            let days: Vec<i32> = self
+                .rows
+                .iter()
+                .map(|row| (date(row) - unix_epoch).whole_days() as i32)
+                .collect();
+
+            Series::new(name, days).cast(&DataType::Date)
+        };
+
+        let columns = vec![
+            dates("payment_date", |row| row.payment_date)?,
+            dates("accrual_start", |row| row.accrual_start)?,
+            dates("accrual_end", |row| row.accrual_end)?,
+            Series::new(
+                "day_count_fraction",
+                self.column(|row| row.day_count_fraction),
+            ),
+            Series::new("rate", self.column(|row| row.rate)),
+            Series::new("index", self.column(|row| row.index.clone())),
+            Series::new("amount", self.column(|row| row.amount)),
+            Series::new("discount_factor", self.column(|row| row.discount_factor)),
+            Series::new("present_value", self.column(|row| row.present_value)),
+            Series::new("is_past", self.column(|row| row.is_past)),
+        ];
+
+        Ok(DataFrame::new(columns)?)
+    }
+
+    fn column<T>(&self, field: impl Fn(&CashflowRow) -> T) -> Vec<T> {
+        self.rows.iter().map(field).collect()
+    }
+}
+
+impl fmt::Display for CashflowReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Cash flows as of {}:", self.valuation_date)?;
+        writeln!(
+            f,
+            "Payment     Start       End              DCF       Rate          Amount          DF              PV  Index"
+        )?;
+
+        for row in &self.rows {
+            let rate = row
+                .rate
+                .map_or(String::from("-"), |rate| format!("{:.5}%", 100.0 * rate));
+            let discount_factor = match (row.is_past, row.discount_factor) {
+                (false, Some(df)) => format!("{df:.8}"),
+                _ => String::from("past"),
+            };
+
+            writeln!(
+                f,
+                "{:<10}  {:<10}  {:<10}  {:>8.6}  {:>9}  {:>14.6}  {:>10}  {:>14.6}  {}",
+                row.payment_date,
+                row.accrual_start,
+                row.accrual_end,
+                row.day_count_fraction,
+                rate,
+                row.amount,
+                discount_factor,
+                row.present_value,
+                row.index.as_deref().unwrap_or("-"),
+            )?;
+        }
+
+        write!(f, "Present value: {:.6}", self.present_value())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cashflow_report {
+    use super::*;
+    use time::macros::date;
+
+    fn report() -> CashflowReport {
+        let row = |payment_date, is_past: bool, index: Option<&str>| CashflowRow {
+            payment_date,
+            accrual_start: date!(2024 - 01 - 15),
+            accrual_end: payment_date,
+            day_count_fraction: 0.5,
+            rate: Some(0.05),
+            index: index.map(String::from),
+            amount: 2.5,
+            discount_factor: (!is_past).then_some(0.98),
+            present_value: if is_past { 0.0 } else { 2.45 },
+            is_past,
+        };
+
+        CashflowReport {
+            valuation_date: date!(2024 - 09 - 01),
+            rows: vec![
+                row(date!(2024 - 07 - 15), true, None),
+                row(date!(2025 - 01 - 15), false, Some("SOFR 2024-12-13")),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_dataframe_schema() {
+        let df = report().to_dataframe().unwrap();
+
+        let columns: Vec<(&str, DataType)> = df
+            .get_columns()
+            .iter()
+            .map(|series| (series.name(), series.dtype().clone()))
+            .collect();
+
+        assert_eq!(
+            columns,
+            vec![
+                ("payment_date", DataType::Date),
+                ("accrual_start", DataType::Date),
+                ("accrual_end", DataType::Date),
+                ("day_count_fraction", DataType::Float64),
+                ("rate", DataType::Float64),
+                ("index", DataType::String),
+                ("amount", DataType::Float64),
+                ("discount_factor", DataType::Float64),
+                ("present_value", DataType::Float64),
+                ("is_past", DataType::Boolean),
+            ]
+        );
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.column("discount_factor").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let report = report();
+        let table = report.to_string();
+
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.contains("SOFR 2024-12-13"));
+        assert!(table.contains("past"));
+        assert!(table.ends_with("Present value: 2.450000"));
+        assert_eq!(report.present_value(), 2.45);
+    }
+}
@@ -0,0 +1,235 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Dividend future: pays the dividends of a share going ex in a calendar
+//! year, at settlement.
+//!
+//! A cash dividend contributes its amount, and a proportional dividend
+//! $\delta$ its expected amount $\delta F(t^-)$, with $F(t^-)$ the forward
+//! just before its ex-date (see [`DividendSchedule`]).
+
+use super::dividends::{growth_factor, DividendSchedule};
+use crate::data::CurveModel;
+use crate::instruments::Instrument;
+use crate::time::today;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Future on the dividends of a share going ex in a calendar year.
+#[derive(Debug, Clone)]
+pub struct DividendFuture<D: CurveModel, B: CurveModel> {
+    /// Spot price of the share.
+    pub spot: f64,
+
+    /// Calendar year of the ex-dates.
+    pub year: i32,
+
+    /// Date the realised dividends are paid.
+    pub settlement_date: Date,
+
+    /// Dividends of the share.
+    pub dividends: DividendSchedule,
+
+    /// Funding curve, used to grow the forward and discount the payoff.
+    pub discount_curve: D,
+
+    /// Borrow (repo) spread curve of the share.
+    pub borrow_curve: B,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D: CurveModel, B: CurveModel> DividendFuture<D, B> {
+    /// New dividend future, valued today.
+    pub fn new(
+        spot: f64,
+        year: i32,
+        settlement_date: Date,
+        dividends: DividendSchedule,
+        discount_curve: D,
+        borrow_curve: B,
+    ) -> Self {
+        Self {
+            spot,
+            year,
+            settlement_date,
+            dividends,
+            discount_curve,
+            borrow_curve,
+            evaluation_date: None,
+        }
+    }
+
+    /// Expected dividends going ex in the year, from the valuation date on:
+    /// the fair futures price. Dividends already ex are fixed and excluded.
+    pub fn expected_dividends(&self) -> f64 {
+        self.expected_dividends_from(self.valuation_date())
+    }
+
+    fn expected_dividends_from(&self, valuation_date: Date) -> f64 {
+        let Some(last) = self
+            .dividends
+            .dividends()
+            .iter()
+            .rev()
+            .find(|dividend| dividend.ex_date.year() <= self.year)
+        else {
+            return 0.0;
+        };
+
+        let mut total = 0.0;
+
+        self.dividends.forward(
+            self.spot,
+            valuation_date,
+            last.ex_date,
+            |date| {
+                growth_factor(
+                    &self.discount_curve,
+                    &self.borrow_curve,
+                    valuation_date,
+                    date,
+                )
+            },
+            |dividend, forward| {
+                if dividend.ex_date.year() == self.year {
+                    total += dividend.paid(forward);
+                }
+            },
+        );
+
+        total
+    }
+
+    fn price_at(&self, valuation_date: Date) -> f64 {
+        let discount = self.discount_curve.discount_factor(self.settlement_date)
+            / self.discount_curve.discount_factor(valuation_date);
+
+        self.expected_dividends_from(valuation_date) * discount
+    }
+}
+
+impl<D: CurveModel, B: CurveModel> Instrument for DividendFuture<D, B> {
+    /// Present value of the expected dividends, paid at settlement.
+    fn price(&self) -> f64 {
+        self.price_at(self.valuation_date())
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "DividendFuture"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.settlement_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(self.price_at(valuation_date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_dividend_future {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::instruments::equities::{Dividend, EquityForward};
+    use crate::time::DayCountConvention;
+    use time::macros::date;
+
+    const VALUATION: Date = date!(2024 - 01 - 02);
+
+    /// Flat, continuously compounded rate on the default day count.
+    struct FlatCurve(f64);
+
+    impl CurveModel for FlatCurve {
+        fn forward_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn spot_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            (-self.0 * DayCountConvention::default().day_count_factor(VALUATION, date)).exp()
+        }
+    }
+
+    #[test]
+    fn test_dividend_future_expected_dividends() {
+        let ex_date = date!(2025 - 06 - 13);
+        let schedule = DividendSchedule::new(vec![
+            Dividend::cash(date!(2025 - 12 - 12), 1.5),
+            Dividend::proportional(ex_date, 0.02),
+            Dividend::cash(date!(2024 - 06 - 14), 1.0),
+            Dividend::cash(date!(2026 - 03 - 13), 1.0),
+        ]);
+        let settlement_date = date!(2025 - 12 - 19);
+
+        let mut future = DividendFuture::new(
+            100.0,
+            2025,
+            settlement_date,
+            schedule.clone(),
+            FlatCurve(0.04),
+            FlatCurve(0.005),
+        );
+        future.evaluation_date = Some(VALUATION);
+
+        // The proportional dividend pays 2% of the forward just before it.
+        let mut forward = EquityForward::new(
+            100.0,
+            0.0,
+            ex_date.previous_day().unwrap(),
+            schedule,
+            FlatCurve(0.04),
+            FlatCurve(0.005),
+        );
+        forward.evaluation_date = Some(VALUATION);
+        let before = forward.forward_price()
+            * growth_factor(
+                &FlatCurve(0.04),
+                &FlatCurve(0.005),
+                forward.maturity_date,
+                ex_date,
+            );
+
+        let expected = 0.02 * before + 1.5;
+
+        assert_approx_equal!(future.expected_dividends(), expected, 1e-12);
+        assert_approx_equal!(
+            future.price(),
+            expected * FlatCurve(0.04).discount_factor(settlement_date),
+            1e-12
+        );
+
+        // Once the year's dividends have gone ex, nothing is left to pay.
+        assert_eq!(future.price_as_of(date!(2025 - 12 - 15)), Some(0.0));
+    }
+}
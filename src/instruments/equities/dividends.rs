@@ -0,0 +1,152 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Discrete dividend schedules, and the equity forward they imply.
+//!
+//! Between ex-dates the forward grows at the funding rate less the
+//! borrow (repo) spread:
+//!
+//! $$
+//! G(t, u) = \frac{P(t)}{P(u)} \frac{B(u)}{B(t)}
+//! $$
+//!
+//! with $P$ the discount factors of the funding curve and $B$ those of the
+//! borrow curve. On each ex-date the forward drops by the cash amount $D$,
+//! or by the fraction $\delta$ for a proportional dividend.
+
+use crate::data::CurveModel;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Amount of a discrete dividend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DividendAmount {
+    /// Fixed cash amount per share ($D$).
+    Cash(f64),
+
+    /// Fraction of the share price just before the ex-date ($\delta$).
+    Proportional(f64),
+}
+
+/// Discrete dividend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dividend {
+    /// Ex-dividend date: the share price drops by the dividend on this date.
+    pub ex_date: Date,
+
+    /// Amount of the dividend.
+    pub amount: DividendAmount,
+}
+
+/// Dividends of a share, sorted by ex-date.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DividendSchedule {
+    dividends: Vec<Dividend>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Dividend {
+    /// Cash dividend of `amount` per share.
+    #[must_use]
+    pub fn cash(ex_date: Date, amount: f64) -> Self {
+        Self {
+            ex_date,
+            amount: DividendAmount::Cash(amount),
+        }
+    }
+
+    /// Dividend of a `fraction` of the share price.
+    #[must_use]
+    pub fn proportional(ex_date: Date, fraction: f64) -> Self {
+        Self {
+            ex_date,
+            amount: DividendAmount::Proportional(fraction),
+        }
+    }
+
+    /// Cash paid, given the share (forward) price just before the ex-date.
+    #[must_use]
+    pub fn paid(&self, price_before: f64) -> f64 {
+        match self.amount {
+            DividendAmount::Cash(amount) => amount,
+            DividendAmount::Proportional(fraction) => fraction * price_before,
+        }
+    }
+}
+
+impl DividendSchedule {
+    /// Create a schedule from dividends in any order.
+    #[must_use]
+    pub fn new(mut dividends: Vec<Dividend>) -> Self {
+        dividends.sort_by_key(|dividend| dividend.ex_date);
+
+        Self { dividends }
+    }
+
+    /// The dividends, sorted by ex-date.
+    #[must_use]
+    pub fn dividends(&self) -> &[Dividend] {
+        &self.dividends
+    }
+
+    /// Forward price for `date`, from `spot` at `valuation_date`, given the
+    /// growth factor `growth(u)` from the valuation date to `u`.
+    ///
+    /// Dividends going ex after the valuation date and on or before `date`
+    /// are deducted; `on_dividend` is called with each one and the forward
+    /// just before its ex-date.
+    pub(crate) fn forward<G, F>(
+        &self,
+        spot: f64,
+        valuation_date: Date,
+        date: Date,
+        growth: G,
+        mut on_dividend: F,
+    ) -> f64
+    where
+        G: Fn(Date) -> f64,
+        F: FnMut(&Dividend, f64),
+    {
+        let mut forward = spot;
+        let mut previous = 1.0;
+
+        for dividend in self
+            .dividends
+            .iter()
+            .filter(|d| d.ex_date > valuation_date && d.ex_date <= date)
+        {
+            let current = growth(dividend.ex_date);
+            forward *= current / previous;
+            previous = current;
+
+            on_dividend(dividend, forward);
+            forward -= dividend.paid(forward);
+        }
+
+        forward * growth(date) / previous
+    }
+}
+
+/// Growth factor of the forward from `from` to `to`: funding on the
+/// discount curve, less the borrow spread.
+pub(crate) fn growth_factor<D, B>(discount_curve: &D, borrow_curve: &B, from: Date, to: Date) -> f64
+where
+    D: CurveModel,
+    B: CurveModel,
+{
+    discount_curve.discount_factor(from) / discount_curve.discount_factor(to)
+        * borrow_curve.discount_factor(to)
+        / borrow_curve.discount_factor(from)
+}
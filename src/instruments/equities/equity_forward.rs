@@ -0,0 +1,292 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Equity forward contract, on a share with discrete dividends and a
+//! borrow (repo) cost.
+//!
+//! The forward price is the spot grown at the funding rate less the
+//! borrow spread, net of the dividends going ex before maturity (see
+//! [`DividendSchedule`]). This is the forward to use for options on the
+//! share, so that they are consistent with the forward contract.
+
+use super::dividends::{growth_factor, DividendSchedule};
+use crate::data::CurveModel;
+use crate::instruments::Instrument;
+use crate::time::{today, DayCountConvention};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Long forward contract on a share: buys it at the delivery price at maturity.
+#[derive(Debug, Clone)]
+pub struct EquityForward<D: CurveModel, B: CurveModel> {
+    /// Spot price of the share.
+    pub spot: f64,
+
+    /// Delivery (strike) price.
+    pub delivery_price: f64,
+
+    /// Maturity (delivery) date.
+    pub maturity_date: Date,
+
+    /// Dividends of the share.
+    pub dividends: DividendSchedule,
+
+    /// Funding curve, used to grow the forward and discount the payoff.
+    pub discount_curve: D,
+
+    /// Borrow (repo) spread curve: its discount factors are those of the
+    /// spread earned by lending the share.
+    pub borrow_curve: B,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D: CurveModel, B: CurveModel> EquityForward<D, B> {
+    /// New equity forward, valued today.
+    pub fn new(
+        spot: f64,
+        delivery_price: f64,
+        maturity_date: Date,
+        dividends: DividendSchedule,
+        discount_curve: D,
+        borrow_curve: B,
+    ) -> Self {
+        Self {
+            spot,
+            delivery_price,
+            maturity_date,
+            dividends,
+            discount_curve,
+            borrow_curve,
+            evaluation_date: None,
+        }
+    }
+
+    /// Forward price for delivery at maturity.
+    pub fn forward_price(&self) -> f64 {
+        self.forward_price_at(self.maturity_date)
+    }
+
+    /// Forward price for delivery on `date`.
+    pub fn forward_price_at(&self, date: Date) -> f64 {
+        self.forward_from(self.valuation_date(), date)
+    }
+
+    /// Flat, continuously compounded borrow spread (on the default day count)
+    /// that, in place of the borrow curve, reproduces `forward_price` at
+    /// maturity.
+    ///
+    /// Returns `None` if no spread between -100% and 100% does.
+    pub fn implied_borrow(&self, forward_price: f64) -> Option<f64> {
+        const TOLERANCE: f64 = 1e-14;
+        const MAX_ITERATIONS: usize = 200;
+
+        let valuation_date = self.valuation_date();
+        let forward = |spread: f64| {
+            self.dividends.forward(
+                self.spot,
+                valuation_date,
+                self.maturity_date,
+                |date| {
+                    let tau = DayCountConvention::default().day_count_factor(valuation_date, date);
+
+                    self.discount_curve.discount_factor(valuation_date)
+                        / self.discount_curve.discount_factor(date)
+                        * (-spread * tau).exp()
+                },
+                |_, _| {},
+            )
+        };
+
+        // The forward is decreasing in the borrow spread.
+        let (mut lo, mut hi) = (-1.0, 1.0);
+
+        if !(forward_price <= forward(lo) && forward_price >= forward(hi)) {
+            return None;
+        }
+
+        for _ in 0..MAX_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+
+            if forward(mid) > forward_price {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+
+            if hi - lo < TOLERANCE {
+                break;
+            }
+        }
+
+        Some(0.5 * (lo + hi))
+    }
+
+    fn forward_from(&self, valuation_date: Date, date: Date) -> f64 {
+        self.dividends.forward(
+            self.spot,
+            valuation_date,
+            date,
+            |u| growth_factor(&self.discount_curve, &self.borrow_curve, valuation_date, u),
+            |_, _| {},
+        )
+    }
+
+    fn price_at(&self, valuation_date: Date) -> f64 {
+        let discount = self.discount_curve.discount_factor(self.maturity_date)
+            / self.discount_curve.discount_factor(valuation_date);
+
+        (self.forward_from(valuation_date, self.maturity_date) - self.delivery_price) * discount
+    }
+}
+
+impl<D: CurveModel, B: CurveModel> Instrument for EquityForward<D, B> {
+    /// Value of the long forward, $(F - K) P(T)$.
+    fn price(&self) -> f64 {
+        self.price_at(self.valuation_date())
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "EquityForward"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.maturity_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(self.price_at(valuation_date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_equity_forward {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::instruments::equities::Dividend;
+    use time::macros::date;
+
+    const VALUATION: Date = date!(2024 - 01 - 02);
+    const MATURITY: Date = date!(2025 - 01 - 02);
+
+    /// Flat, continuously compounded rate on the default day count.
+    struct FlatCurve(f64);
+
+    impl CurveModel for FlatCurve {
+        fn forward_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn spot_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            (-self.0 * DayCountConvention::default().day_count_factor(VALUATION, date)).exp()
+        }
+    }
+
+    fn years(date: Date) -> f64 {
+        DayCountConvention::default().day_count_factor(VALUATION, date)
+    }
+
+    fn forward(dividends: Vec<Dividend>, borrow: f64) -> EquityForward<FlatCurve, FlatCurve> {
+        let mut forward = EquityForward::new(
+            100.0,
+            100.0,
+            MATURITY,
+            DividendSchedule::new(dividends),
+            FlatCurve(0.04),
+            FlatCurve(borrow),
+        );
+        forward.evaluation_date = Some(VALUATION);
+        forward
+    }
+
+    #[test]
+    fn test_forward_without_dividends() {
+        let plain = forward(vec![], 0.0);
+        let T = years(MATURITY);
+
+        assert_approx_equal!(plain.forward_price(), 100.0 * (0.04 * T).exp(), 1e-12);
+        assert_approx_equal!(plain.price(), 100.0 - 100.0 * (-0.04 * T).exp(), 1e-12);
+
+        // The borrow spread reduces the growth rate.
+        let borrowed = forward(vec![], 0.01);
+        assert_approx_equal!(borrowed.forward_price(), 100.0 * (0.03 * T).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_cash_dividend_reduces_forward() {
+        let ex_date = date!(2024 - 06 - 14);
+        let plain = forward(vec![], 0.0);
+
+        let with_dividend = forward(vec![Dividend::cash(ex_date, 2.5)], 0.0);
+        let compounded = 2.5 * (0.04 * (years(MATURITY) - years(ex_date))).exp();
+
+        assert_approx_equal!(
+            plain.forward_price() - with_dividend.forward_price(),
+            compounded,
+            1e-12
+        );
+
+        // Before the ex-date, or after maturity, the dividend is not deducted.
+        assert_eq!(
+            with_dividend.forward_price_at(date!(2024 - 06 - 13)),
+            plain.forward_price_at(date!(2024 - 06 - 13))
+        );
+        let after_maturity = forward(vec![Dividend::cash(date!(2025 - 03 - 14), 2.5)], 0.0);
+        assert_eq!(after_maturity.forward_price(), plain.forward_price());
+
+        // A proportional dividend scales the forward.
+        let proportional = forward(vec![Dividend::proportional(ex_date, 0.02)], 0.0);
+        assert_approx_equal!(
+            proportional.forward_price(),
+            0.98 * plain.forward_price(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_implied_borrow_round_trip() {
+        let dividends = vec![
+            Dividend::cash(date!(2024 - 03 - 15), 1.0),
+            Dividend::proportional(date!(2024 - 09 - 13), 0.015),
+        ];
+
+        for borrow in [-0.005, 0.0, 0.0125, 0.05] {
+            let contract = forward(dividends.clone(), borrow);
+            let implied = contract.implied_borrow(contract.forward_price()).unwrap();
+
+            assert_approx_equal!(implied, borrow, 1e-12);
+        }
+
+        assert!(forward(dividends, 0.0).implied_borrow(1e6).is_none());
+    }
+}
@@ -10,6 +10,18 @@
 use super::{currency::Currency, Ticker};
 use crate::iso::isin::ISIN;
 
+/// Discrete dividend schedules.
+pub mod dividends;
+pub use dividends::*;
+
+/// Dividend futures.
+pub mod dividend_future;
+pub use dividend_future::*;
+
+/// Equity forwards.
+pub mod equity_forward;
+pub use equity_forward::*;
+
 /// Equity instrument.
 pub struct Equity {
     /// The ticker symbol.
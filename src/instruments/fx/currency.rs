@@ -116,6 +116,11 @@ impl Instrument for Currency {
     fn instrument_type(&self) -> &'static str {
         self.name
     }
+
+    /// A unit of currency is worth one on any date.
+    fn price_as_of(&self, _valuation_date: time::Date) -> Option<f64> {
+        Some(self.price())
+    }
 }
 
 impl CurrencyPair {
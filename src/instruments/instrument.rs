@@ -27,4 +27,48 @@ pub trait Instrument {
 
     /// Instrument type.
     fn instrument_type(&self) -> &'static str;
+
+    /// Returns the expiration (maturity) date, if the instrument has one.
+    fn expiration_date(&self) -> Option<time::Date> {
+        None
+    }
+
+    /// Returns the price as of another valuation date,
+    /// holding all market data fixed.
+    ///
+    /// Returns `None` if the instrument cannot be repriced as of
+    /// another date (the default).
+    fn price_as_of(&self, _valuation_date: time::Date) -> Option<f64> {
+        None
+    }
+
+    /// Returns the price in a shocked market as of another valuation date:
+    /// with the underlying at `underlying_price` (if given), and volatilities
+    /// and interest rates shifted by `volatility_shift` and `rate_shift`.
+    ///
+    /// Returns `None` if the instrument cannot be repriced in a shocked
+    /// market (the default), rather than ignoring the shocks.
+    fn price_in_market(
+        &self,
+        _valuation_date: time::Date,
+        _underlying_price: Option<f64>,
+        _volatility_shift: f64,
+        _rate_shift: f64,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// Returns the theta (time decay) of the instrument: the change in value
+    /// from rolling the valuation date forward by one day,
+    /// holding all market data fixed.
+    ///
+    /// Returns `None` if the instrument cannot be repriced as of another
+    /// date, or if the valuation date has no next day.
+    ///
+    /// Instruments with an analytic theta should override this.
+    fn theta(&self) -> Option<f64> {
+        let date = self.valuation_date();
+
+        Some(self.price_as_of(date.next_day()?)? - self.price_as_of(date)?)
+    }
 }
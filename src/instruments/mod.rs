@@ -68,6 +68,18 @@ pub use fx::*;
 pub mod equities;
 pub use equities::*;
 
+/// Interest rate instruments.
+pub mod rates;
+pub use rates::*;
+
+/// Projected cash flows of an instrument.
+pub mod cashflow_report;
+pub use cashflow_report::*;
+
+/// Carry and roll-down of bonds and swaps over a holding horizon.
+pub mod carry_roll_down;
+pub use carry_roll_down::*;
+
 /// Ticker symbol.
 pub mod ticker;
 pub use ticker::*;
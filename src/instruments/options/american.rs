@@ -0,0 +1,296 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! American vanilla options, priced on a Cox-Ross-Rubinstein binomial tree.
+
+use super::{ExerciseFlag, OptionContract, TypeFlag};
+use crate::instruments::Payoff;
+use crate::time::{today, DayCountConvention};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// American vanilla option (exercisable at any time up to expiry).
+#[derive(Debug, Clone)]
+pub struct AmericanVanillaOption {
+    /// The option contract.
+    /// The exercise flag must be [`ExerciseFlag::American`].
+    pub contract: OptionContract,
+
+    /// Strike price of the option.
+    pub strike: f64,
+
+    /// Evaluation date (optional, defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Payoff for AmericanVanillaOption {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        match self.contract.type_flag {
+            TypeFlag::Call => (underlying - self.strike).max(0.0),
+            TypeFlag::Put => (self.strike - underlying).max(0.0),
+        }
+    }
+}
+
+impl AmericanVanillaOption {
+    /// Create a new American vanilla option.
+    pub fn new(contract: OptionContract, strike: f64, evaluation_date: Option<Date>) -> Self {
+        Self {
+            contract,
+            strike,
+            evaluation_date,
+        }
+    }
+
+    /// Times (in years) from the evaluation date to the start and the end
+    /// of the exercise window. The start is zero if the window has opened.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract's exercise flag is not American.
+    fn exercise_window(&self) -> (f64, f64) {
+        match self.contract.exercise_flag {
+            ExerciseFlag::American { start, end } => {
+                let evaluation_date = self.evaluation_date.unwrap_or(today());
+                let year_fraction = |date: Date| {
+                    DayCountConvention::default().day_count_factor(evaluation_date, date)
+                };
+
+                (year_fraction(start).max(0.0), year_fraction(end))
+            }
+            _ => panic!("AmericanVanillaOption requires an American exercise flag."),
+        }
+    }
+
+    /// Time to expiry (in years), from the evaluation date to the end of
+    /// the exercise window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract's exercise flag is not American.
+    #[must_use]
+    pub fn year_fraction(&self) -> f64 {
+        self.exercise_window().1
+    }
+
+    /// Cox-Ross-Rubinstein binomial tree price.
+    ///
+    /// At each node in the exercise window the continuation value is
+    /// compared with the intrinsic value (the [`Payoff`]), so early exercise
+    /// is optimal wherever the latter is larger. The tree runs from the
+    /// evaluation date to the end of the window.
+    ///
+    /// # Arguments:
+    ///
+    /// * `spot` - The underlying asset price.
+    /// * `rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The continuous dividend yield.
+    /// * `volatility` - The underlying asset's volatility.
+    /// * `n_steps` - Number of time steps in the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_steps` is zero, the exercise flag is not American, the
+    /// option has expired, or the time step is too coarse for the
+    /// volatility, so that the risk-neutral up probability is not in (0, 1).
+    #[must_use]
+    pub fn price_binomial(
+        &self,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+        n_steps: usize,
+    ) -> f64 {
+        self.price_crr(spot, rate, dividend_yield, volatility, n_steps, true)
+    }
+
+    // Backward induction through the tree, with or without early exercise.
+    fn price_crr(
+        &self,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+        n_steps: usize,
+        early_exercise: bool,
+    ) -> f64 {
+        assert!(n_steps > 0);
+
+        let (start, end) = self.exercise_window();
+        assert!(end > 0.0, "The option has expired.");
+
+        let dt = end / n_steps as f64;
+        let u = (volatility * dt.sqrt()).exp();
+        let d = 1.0 / u;
+        let p = (((rate - dividend_yield) * dt).exp() - d) / (u - d);
+        let df = (-rate * dt).exp();
+
+        assert!(
+            0.0 < p && p < 1.0,
+            "The up probability {p} is not in (0, 1): use more steps or a higher volatility."
+        );
+
+        // Underlying price after `j` steps, `i` of them up.
+        let node = |j: usize, i: usize| spot * u.powi(i as i32) * d.powi((j - i) as i32);
+
+        let mut values: Vec<f64> = (0..=n_steps)
+            .map(|i| self.payoff(node(n_steps, i)))
+            .collect();
+
+        for j in (0..n_steps).rev() {
+            for i in 0..=j {
+                let continuation = df * (p * values[i + 1] + (1.0 - p) * values[i]);
+
+                values[i] = if early_exercise && j as f64 * dt >= start {
+                    continuation.max(self.payoff(node(j, i)))
+                } else {
+                    continuation
+                };
+            }
+        }
+
+        values[0]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_american {
+    use super::*;
+    use crate::instruments::options::{BlackScholesMerton, OptionContractBuilder};
+    use time::macros::date;
+
+    fn american(type_flag: TypeFlag, strike: f64) -> AmericanVanillaOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::American {
+                start: date!(2023 - 01 - 01),
+                end: date!(2024 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        AmericanVanillaOption::new(contract, strike, Some(date!(2023 - 01 - 01)))
+    }
+
+    #[test]
+    fn test_american_put_benchmarks() {
+        // Longstaff and Schwartz (2001), Table 1: K = 40, r = 0.06, T = 1.
+        let put = american(TypeFlag::Put, 40.0);
+
+        for (spot, volatility, expected) in [
+            (36.0, 0.2, 4.478),
+            (40.0, 0.2, 2.314),
+            (44.0, 0.2, 1.110),
+            (36.0, 0.4, 7.101),
+        ] {
+            let price = put.price_binomial(spot, 0.06, 0.0, volatility, 1000);
+            let european = put.price_crr(spot, 0.06, 0.0, volatility, 1000, false);
+
+            assert!(
+                (price - expected).abs() < 0.01,
+                "{spot}, {volatility}: {price}"
+            );
+            assert!(price > european);
+        }
+    }
+
+    #[test]
+    fn test_american_call_without_dividends_is_european() {
+        let call = american(TypeFlag::Call, 100.0);
+        let (spot, rate, volatility) = (100.0, 0.05, 0.2);
+
+        let price = call.price_binomial(spot, rate, 0.0, volatility, 1000);
+        let european = call.price_crr(spot, rate, 0.0, volatility, 1000, false);
+
+        let analytic = BlackScholesMerton::new(
+            rate,
+            spot,
+            100.0,
+            volatility,
+            rate,
+            Some(date!(2023 - 01 - 01)),
+            date!(2024 - 01 - 01),
+            TypeFlag::Call,
+        )
+        .price();
+
+        // Early exercise of a call is never optimal without dividends.
+        assert!((price - european).abs() < 1e-10);
+        assert!((price - analytic).abs() < 0.01);
+
+        // With a large enough dividend yield it can be.
+        let price = call.price_binomial(spot, rate, 0.1, volatility, 1000);
+        let european = call.price_crr(spot, rate, 0.1, volatility, 1000, false);
+
+        assert!(price > european + 0.01);
+    }
+
+    #[test]
+    fn test_american_put_measured_from_evaluation_date() {
+        let (spot, rate, volatility) = (40.0, 0.06, 0.2);
+        let put = american(TypeFlag::Put, 40.0);
+
+        // Half-way through the window, the tree covers the remaining half year.
+        let later = AmericanVanillaOption {
+            evaluation_date: Some(date!(2023 - 07 - 02)),
+            ..put.clone()
+        };
+        let mut contract = put.contract.clone();
+        contract.exercise_flag = ExerciseFlag::American {
+            start: date!(2023 - 07 - 02),
+            end: date!(2024 - 01 - 01),
+        };
+        let half_year = AmericanVanillaOption::new(contract, 40.0, Some(date!(2023 - 07 - 02)));
+
+        assert!((later.year_fraction() - 0.5).abs() < 0.01);
+        assert_eq!(
+            later.price_binomial(spot, rate, 0.0, volatility, 500),
+            half_year.price_binomial(spot, rate, 0.0, volatility, 500)
+        );
+
+        // A window that opens after the evaluation date is worth less than
+        // one that is already open, but more than European exercise.
+        let forward_start = AmericanVanillaOption {
+            evaluation_date: Some(date!(2022 - 07 - 02)),
+            ..half_year
+        };
+        let mut contract = put.contract;
+        contract.exercise_flag = ExerciseFlag::American {
+            start: date!(2022 - 07 - 02),
+            end: date!(2024 - 01 - 01),
+        };
+        let open = AmericanVanillaOption::new(contract, 40.0, Some(date!(2022 - 07 - 02)));
+        let european = forward_start.price_crr(spot, rate, 0.0, volatility, 500, false);
+        let price = forward_start.price_binomial(spot, rate, 0.0, volatility, 500);
+
+        assert!(european < price);
+        assert!(price < open.price_binomial(spot, rate, 0.0, volatility, 500));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in (0, 1)")]
+    fn test_american_coarse_tree_panics() {
+        // With a 50% carry and 1% volatility, one step has e^{r dt} > u.
+        let _ = american(TypeFlag::Put, 40.0).price_binomial(40.0, 0.5, 0.0, 0.01, 1);
+    }
+}
@@ -0,0 +1,262 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Basket options: a call or put on the weighted sum of several underlyings
+//! at expiry, $B(T) = \sum_i w_i S_i(T)$.
+//!
+//! Besides Monte Carlo, the option can be priced with Levy's (1992)
+//! approximation, which replaces $B(T)$ by a lognormal variable with the
+//! same first two moments,
+//!
+//! $$
+//! M_1 = \sum_i w_i S_i e^{\mu_i T}, \quad
+//! M_2 = \sum_{i,j} w_i w_j S_i S_j e^{(\mu_i + \mu_j + \rho_{ij} \sigma_i \sigma_j) T},
+//! $$
+//!
+//! and prices it with Black's formula at the volatility
+//! $\sigma_B^2 = \ln(M_2 / M_1^2) / T$. For a single asset this is exactly
+//! the Black-Scholes price.
+
+use super::{OptionContract, TypeFlag};
+use crate::instruments::Payoff;
+use crate::math::distributions::{Distribution, Gaussian};
+use crate::pricer::MonteCarloEstimate;
+use crate::stochastics::{MultiGeometricBrownianMotion, StochasticProcessConfig};
+
+/// Basket option.
+#[derive(Debug, Clone)]
+pub struct BasketOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Weight of each underlying in the basket.
+    pub weights: Vec<f64>,
+
+    /// Strike price of the option on the basket.
+    pub strike: f64,
+}
+
+impl Payoff for BasketOption {
+    /// The underlyings' values at expiry.
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        assert_eq!(underlying.len(), self.weights.len());
+
+        let basket: f64 = self
+            .weights
+            .iter()
+            .zip(&underlying)
+            .map(|(w, s)| w * s)
+            .sum();
+
+        match self.contract.type_flag {
+            TypeFlag::Call => (basket - self.strike).max(0.0),
+            TypeFlag::Put => (self.strike - basket).max(0.0),
+        }
+    }
+}
+
+impl BasketOption {
+    /// Price the option by Monte Carlo over correlated paths of the
+    /// underlyings, discounting the average payoff.
+    ///
+    /// # Arguments:
+    /// * `process` - Correlated (risk-neutral) GBM of the underlyings.
+    /// * `x_0` - The underlyings' initial values.
+    /// * `config` - Simulation config: `t_n - t_0` is the time to expiry (in
+    ///   years); `config.x_0` is not used.
+    /// * `rate` - The interest rate used to discount the payoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `process` does not have one asset per weight.
+    pub fn price_monte_carlo(
+        &self,
+        process: &MultiGeometricBrownianMotion,
+        x_0: &[f64],
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate {
+        assert_eq!(process.dimension(), self.weights.len());
+
+        let (n_steps, m_paths) = (config.n_steps, config.m_paths);
+
+        let out = process.euler_maruyama(x_0, config);
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        let (sum, sum_of_squares) = (0..m_paths).fold((0.0, 0.0), |(sum, sum_of_squares), j| {
+            let terminal = out.paths.iter().map(|asset| asset[j][n_steps]).collect();
+            let value = df * self.payoff(terminal);
+
+            (sum + value, sum_of_squares + value * value)
+        });
+
+        MonteCarloEstimate::from_sums(sum, sum_of_squares, m_paths)
+    }
+
+    /// Price the option with Levy's lognormal moment-matching approximation.
+    ///
+    /// # Arguments:
+    /// * `process` - Correlated (risk-neutral) GBM of the underlyings.
+    /// * `x_0` - The underlyings' initial values.
+    /// * `t_n` - Time to expiry (in years).
+    /// * `rate` - The interest rate used to discount the payoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `process` does not have one asset per weight, or if the
+    /// expected basket value is not positive.
+    pub fn price_moment_matching(
+        &self,
+        process: &MultiGeometricBrownianMotion,
+        x_0: &[f64],
+        t_n: f64,
+        rate: f64,
+    ) -> f64 {
+        let n = self.weights.len();
+        assert_eq!(process.dimension(), n);
+        assert_eq!(x_0.len(), n);
+
+        let (mu, sigma, rho) = (&process.mu, &process.sigma, &process.correlation);
+
+        // Weighted forwards of the underlyings.
+        let forwards: Vec<f64> = (0..n)
+            .map(|i| self.weights[i] * x_0[i] * (mu[i] * t_n).exp())
+            .collect();
+
+        let m_1: f64 = forwards.iter().sum();
+        let m_2: f64 = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                forwards[i] * forwards[j] * (rho[(i, j)] * sigma[i] * sigma[j] * t_n).exp()
+            })
+            .sum();
+
+        assert!(m_1 > 0.0, "The expected basket value must be positive.");
+
+        let std_dev = (m_2 / (m_1 * m_1)).ln().sqrt();
+        let d1 = ((m_1 / self.strike).ln() + 0.5 * std_dev * std_dev) / std_dev;
+        let d2 = d1 - std_dev;
+
+        let n = Gaussian::default();
+        let df = (-rate * t_n).exp();
+
+        match self.contract.type_flag {
+            TypeFlag::Call => df * (m_1 * n.cdf(d1) - self.strike * n.cdf(d2)),
+            TypeFlag::Put => df * (self.strike * n.cdf(-d2) - m_1 * n.cdf(-d1)),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_basket {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::instruments::options::{ExerciseFlag, OptionContractBuilder};
+    use nalgebra::DMatrix;
+
+    fn basket(type_flag: TypeFlag, weights: Vec<f64>, strike: f64) -> BasketOption {
+        BasketOption {
+            contract: OptionContractBuilder::default()
+                .type_flag(type_flag)
+                .exercise_flag(ExerciseFlag::European {
+                    expiry: time::macros::date!(2025 - 01 - 01),
+                })
+                .build()
+                .unwrap(),
+            weights,
+            strike,
+        }
+    }
+
+    #[test]
+    fn test_single_asset_basket_is_vanilla() {
+        let (s, k, r, sigma, t): (f64, f64, f64, f64, f64) = (100.0, 95.0, 0.05, 0.25, 1.0);
+
+        let process =
+            MultiGeometricBrownianMotion::new(vec![r], vec![sigma], DMatrix::identity(1, 1))
+                .unwrap();
+
+        // Black-Scholes.
+        let n = Gaussian::default();
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+        let call = s * n.cdf(d1) - k * (-r * t).exp() * n.cdf(d2);
+        let put = call - s + k * (-r * t).exp();
+
+        for (type_flag, vanilla) in [(TypeFlag::Call, call), (TypeFlag::Put, put)] {
+            let option = basket(type_flag, vec![1.0], k);
+
+            assert_approx_equal!(
+                option.price_moment_matching(&process, &[s], t, r),
+                vanilla,
+                1e-10
+            );
+
+            let config = StochasticProcessConfig::new(s, 0.0, t, 1, 100_000, true).with_seed(1);
+            let estimate = option.price_monte_carlo(&process, &[s], &config, r);
+            assert!((estimate.value - vanilla).abs() < 4.0 * estimate.standard_error);
+        }
+
+        // A weight scales the underlying: w max(S - K / w, 0).
+        let half = basket(TypeFlag::Call, vec![0.5], 50.0);
+        let full = basket(TypeFlag::Call, vec![1.0], 100.0);
+        assert_approx_equal!(
+            half.price_moment_matching(&process, &[s], t, r),
+            0.5 * full.price_moment_matching(&process, &[s], t, r),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_two_asset_basket_against_moment_matching() {
+        let (r, t) = (0.03, 1.0);
+        let x_0 = [100.0, 100.0];
+
+        for rho in [-0.3, 0.0, 0.5, 0.9] {
+            let process = MultiGeometricBrownianMotion::new(
+                vec![r, r],
+                vec![0.2, 0.3],
+                DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0]),
+            )
+            .unwrap();
+
+            for (type_flag, strike) in [(TypeFlag::Call, 100.0), (TypeFlag::Put, 95.0)] {
+                let option = basket(type_flag, vec![0.5, 0.5], strike);
+
+                let approximation = option.price_moment_matching(&process, &x_0, t, r);
+                let config =
+                    StochasticProcessConfig::new(0.0, 0.0, t, 1, 100_000, true).with_seed(7);
+                let estimate = option.price_monte_carlo(&process, &x_0, &config, r);
+
+                // Levy's approximation is within a few percent for
+                // near-the-money options on a two-asset basket, but it
+                // overprices the puts, the more so as the correlation turns
+                // negative and the basket is further from lognormal (by
+                // about 4.5% at rho = -0.3).
+                let bias = match type_flag {
+                    TypeFlag::Put if rho < 0.0 => 0.06,
+                    _ => 0.02,
+                };
+
+                assert!(
+                    (estimate.value - approximation).abs()
+                        < 4.0 * estimate.standard_error + bias * approximation,
+                    "{rho} {type_flag:?}: {} vs {approximation}",
+                    estimate.value
+                );
+            }
+        }
+    }
+}
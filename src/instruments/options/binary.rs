@@ -8,8 +8,9 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::instruments::Payoff;
+use time::Date;
 
-use super::{BinaryType, OptionContract, TypeFlag};
+use super::{BinaryType, BlackScholesMerton, ExerciseFlag, OptionContract, TypeFlag};
 
 /// Binary option.
 #[derive(Debug, Clone)]
@@ -24,6 +25,23 @@ pub struct BinaryOption {
     pub binary_type: BinaryType,
 }
 
+/// Digital option: pays a fixed amount of cash (cash-or-nothing), or of
+/// units of the underlying (asset-or-nothing), if it expires in the money.
+#[derive(Debug, Clone)]
+pub struct DigitalOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Strike price of the option.
+    pub strike: f64,
+
+    /// Cash amount, or number of units of the underlying, paid on exercise.
+    pub payout: f64,
+
+    /// Type of digital option.
+    pub binary_type: BinaryType,
+}
+
 impl Payoff for BinaryOption {
     type Underlying = f64;
 
@@ -52,3 +70,235 @@ impl Payoff for BinaryOption {
         }
     }
 }
+
+impl Payoff for DigitalOption {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let in_the_money = match self.contract.type_flag {
+            TypeFlag::Call => underlying > self.strike,
+            TypeFlag::Put => underlying < self.strike,
+        };
+
+        match (in_the_money, self.binary_type) {
+            (false, _) => 0.0,
+            (true, BinaryType::CashOrNothing) => self.payout,
+            (true, BinaryType::AssetOrNothing) => self.payout * underlying,
+        }
+    }
+}
+
+impl DigitalOption {
+    /// Create a new digital option.
+    pub fn new(
+        contract: OptionContract,
+        strike: f64,
+        payout: f64,
+        binary_type: BinaryType,
+    ) -> Self {
+        Self {
+            contract,
+            strike,
+            payout,
+            binary_type,
+        }
+    }
+
+    /// Analytic price under the generalised Black-Scholes-Merton model
+    /// (see [`BlackScholesMerton::digital_price`]).
+    ///
+    /// # Arguments:
+    ///
+    /// * `valuation_date` - The date of the price.
+    /// * `spot` - The underlying asset price.
+    /// * `rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The continuous dividend yield.
+    /// * `volatility` - The underlying asset's volatility.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option is not European.
+    #[must_use]
+    pub fn price(
+        &self,
+        valuation_date: Date,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> f64 {
+        self.black_scholes_merton(valuation_date, spot, rate, dividend_yield, volatility)
+            .digital_price(self.binary_type, self.payout)
+    }
+
+    /// Analytic delta, with the arguments of [`DigitalOption::price`].
+    #[must_use]
+    pub fn delta(
+        &self,
+        valuation_date: Date,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> f64 {
+        self.black_scholes_merton(valuation_date, spot, rate, dividend_yield, volatility)
+            .digital_delta(self.binary_type, self.payout)
+    }
+
+    /// Analytic gamma, with the arguments of [`DigitalOption::price`].
+    #[must_use]
+    pub fn gamma(
+        &self,
+        valuation_date: Date,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> f64 {
+        self.black_scholes_merton(valuation_date, spot, rate, dividend_yield, volatility)
+            .digital_gamma(self.binary_type, self.payout)
+    }
+
+    fn black_scholes_merton(
+        &self,
+        valuation_date: Date,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> BlackScholesMerton {
+        let ExerciseFlag::European { expiry } = self.contract.exercise_flag else {
+            panic!("Only European digital options have an analytic price.");
+        };
+
+        BlackScholesMerton::new(
+            rate - dividend_yield,
+            spot,
+            self.strike,
+            volatility,
+            rate,
+            Some(valuation_date),
+            expiry,
+            self.contract.type_flag,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests_digital_option {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::instruments::OptionContractBuilder;
+    use crate::time::DayCountConvention;
+    use time::macros::date;
+    use time::Duration;
+
+    const VALUATION_DATE: Date = date!(2024 - 01 - 01);
+
+    fn digital(type_flag: TypeFlag, expiry: Date, binary_type: BinaryType) -> DigitalOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European { expiry })
+            .build()
+            .unwrap();
+
+        DigitalOption::new(contract, 100.0, 10.0, binary_type)
+    }
+
+    #[test]
+    fn test_digital_put_call_parity() {
+        let expiry = date!(2024 - 07 - 01);
+        let (spot, rate, dividend_yield, volatility) = (95.0, 0.05, 0.02, 0.3);
+        let t = DayCountConvention::default().day_count_factor(VALUATION_DATE, expiry);
+
+        let price = |type_flag, binary_type| {
+            digital(type_flag, expiry, binary_type).price(
+                VALUATION_DATE,
+                spot,
+                rate,
+                dividend_yield,
+                volatility,
+            )
+        };
+
+        // A call and a put together always pay out.
+        let cash = price(TypeFlag::Call, BinaryType::CashOrNothing)
+            + price(TypeFlag::Put, BinaryType::CashOrNothing);
+        let asset = price(TypeFlag::Call, BinaryType::AssetOrNothing)
+            + price(TypeFlag::Put, BinaryType::AssetOrNothing);
+
+        assert_approx_equal!(cash, 10.0 * (-rate * t).exp(), 1e-12);
+        assert_approx_equal!(asset, 10.0 * spot * (-dividend_yield * t).exp(), 1e-10);
+
+        // A vanilla call is long the asset digital and short the cash
+        // digital paying the strike.
+        let vanilla = BlackScholesMerton::new(
+            rate - dividend_yield,
+            spot,
+            100.0,
+            volatility,
+            rate,
+            Some(VALUATION_DATE),
+            expiry,
+            TypeFlag::Call,
+        );
+        let call = (price(TypeFlag::Call, BinaryType::AssetOrNothing)
+            - 100.0 * price(TypeFlag::Call, BinaryType::CashOrNothing))
+            / 10.0;
+
+        assert_approx_equal!(call, vanilla.price(), 1e-10);
+    }
+
+    #[test]
+    fn test_digital_greeks() {
+        let expiry = date!(2024 - 10 - 01);
+        let (rate, dividend_yield, volatility) = (0.04, 0.01, 0.25);
+        let h = 1e-3;
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            for binary_type in [BinaryType::CashOrNothing, BinaryType::AssetOrNothing] {
+                let option = digital(type_flag, expiry, binary_type);
+
+                for spot in [80.0, 100.0, 120.0] {
+                    let at =
+                        |spot| option.price(VALUATION_DATE, spot, rate, dividend_yield, volatility);
+                    let delta =
+                        option.delta(VALUATION_DATE, spot, rate, dividend_yield, volatility);
+                    let gamma =
+                        option.gamma(VALUATION_DATE, spot, rate, dividend_yield, volatility);
+
+                    assert_approx_equal!(delta, (at(spot + h) - at(spot - h)) / (2.0 * h), 1e-6);
+                    assert_approx_equal!(
+                        gamma,
+                        (at(spot + h) - 2.0 * at(spot) + at(spot - h)) / (h * h),
+                        1e-4
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_digital_delta_near_expiry() {
+        let (rate, volatility) = (0.03, 0.2);
+
+        let delta = |days: i64, spot: f64| {
+            digital(
+                TypeFlag::Call,
+                VALUATION_DATE + Duration::days(days),
+                BinaryType::CashOrNothing,
+            )
+            .delta(VALUATION_DATE, spot, rate, 0.0, volatility)
+        };
+
+        // At the money, the delta grows like 1 / sqrt(T) as expiry nears,
+        let deltas: Vec<f64> = [360, 90, 22, 5, 1].map(|days| delta(days, 100.0)).to_vec();
+
+        assert!(deltas.windows(2).all(|w| w[0] < w[1]));
+        assert!((delta(90, 100.0) / delta(360, 100.0) - 2.0).abs() < 0.05);
+
+        // while away from the strike it vanishes.
+        assert!(delta(1, 90.0) < 1e-12 && delta(1, 110.0) < 1e-12);
+        assert!(delta(1, 110.0) < delta(90, 110.0));
+    }
+}
@@ -20,12 +20,21 @@
 //!     - Asay 1982 margined futures option model.
 //! - b = r_d - r_f
 //!     - Garman and Kohlhagen 1983 currency option model.
+//!
+//! At the boundaries $\sigma \sqrt{T} \to 0$ (zero volatility or expiry) and
+//! $S \to 0$ the terminal price is the forward $F = S e^{bT}$, so the price is
+//! the discounted intrinsic value, $N(d_1)$ and $N(d_2)$ become the step
+//! function $H(F - K)$ (one half at the forward), and the $N'(d_1)$ terms
+//! vanish. Below `boundary_epsilon`, the price and greeks take these limits
+//! explicitly rather than dividing by zero.
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::instruments::options::TypeFlag;
+use crate::data::{StickyConvention, VolatilitySurface};
+use crate::instruments::options::barrier::reiner_rubinstein;
+use crate::instruments::options::{BarrierType, BinaryType, TypeFlag};
 use crate::instruments::Instrument;
 use crate::math::distributions::{Distribution, Gaussian};
 use crate::time::{today, DayCountConvention};
@@ -69,6 +78,45 @@ pub struct BlackScholesMerton {
 
     /// Call or put flag.
     pub option_type: TypeFlag,
+
+    /// Threshold on $\sigma \sqrt{T}$ and $S$ below which the deterministic
+    /// limits are used (defaults to `RUSTQUANT_EPSILON`).
+    #[builder(default = "crate::RUSTQUANT_EPSILON")]
+    pub boundary_epsilon: f64,
+}
+
+/// Sensitivities of a generalised Black-Scholes-Merton European option,
+/// computed together by [`BlackScholesMerton::greeks`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackScholesMertonGreeks {
+    /// Delta, $\partial V / \partial S$.
+    pub delta: f64,
+    /// Gamma, $\partial^2 V / \partial S^2$.
+    pub gamma: f64,
+    /// Speed, $\partial^3 V / \partial S^3$.
+    pub speed: f64,
+    /// Colour, $\partial^2 V / \partial S^2 \partial T$.
+    pub colour: f64,
+    /// Vanna, $\partial^2 V / \partial S \partial \sigma$.
+    pub vanna: f64,
+    /// Charm, $\partial^2 V / \partial S \partial T$.
+    pub charm: f64,
+    /// Zomma, $\partial^3 V / \partial S^2 \partial \sigma$.
+    pub zomma: f64,
+    /// Vega, $\partial V / \partial \sigma$.
+    pub vega: f64,
+    /// Vomma, $\partial^2 V / \partial \sigma^2$.
+    pub vomma: f64,
+    /// Ultima, $\partial^3 V / \partial \sigma^3$.
+    pub ultima: f64,
+    /// Vega bleed, $\partial^2 V / \partial \sigma \partial T$.
+    pub vega_bleed: f64,
+    /// Theta (per year, as [`BlackScholesMerton::theta`]).
+    pub theta: f64,
+    /// Rho, $\partial V / \partial r$.
+    pub rho: f64,
+    /// Phi, the sensitivity to the dividend yield (or foreign rate).
+    pub phi: f64,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -96,6 +144,48 @@ impl Instrument for BlackScholesMerton {
     fn instrument_type(&self) -> &'static str {
         "Black-Scholes-Merton European Option"
     }
+
+    /// Returns the expiration date.
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.expiration_date)
+    }
+
+    /// Returns the price as of another valuation date.
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(
+            Self {
+                evaluation_date: Some(valuation_date),
+                ..*self
+            }
+            .price(),
+        )
+    }
+
+    /// Returns the price in a shocked market
+    /// (see [`BlackScholesMerton::price_in_market`]).
+    fn price_in_market(
+        &self,
+        valuation_date: Date,
+        underlying_price: Option<f64>,
+        volatility_shift: f64,
+        rate_shift: f64,
+    ) -> Option<f64> {
+        Some(BlackScholesMerton::price_in_market(
+            self,
+            valuation_date,
+            underlying_price,
+            volatility_shift,
+            rate_shift,
+        ))
+    }
+
+    /// Returns the (analytic) theta over one day.
+    fn theta(&self) -> Option<f64> {
+        let date = self.valuation_date();
+        let one_day = DayCountConvention::default().day_count_factor(date, date.next_day()?);
+
+        Some(BlackScholesMerton::theta(self) * one_day)
+    }
 }
 
 impl BlackScholesMerton {
@@ -121,13 +211,48 @@ impl BlackScholesMerton {
             evaluation_date,
             expiration_date,
             option_type,
+            boundary_epsilon: crate::RUSTQUANT_EPSILON,
+        }
+    }
+
+    /// Price in a shocked market as of another valuation date: with the
+    /// underlying at `underlying_price` (if given), and the volatility and
+    /// risk-free rate shifted by `volatility_shift` and `rate_shift`.
+    /// The rate shift moves the cost of carry too, as for $b = r - q$.
+    #[must_use]
+    pub fn price_in_market(
+        &self,
+        valuation_date: Date,
+        underlying_price: Option<f64>,
+        volatility_shift: f64,
+        rate_shift: f64,
+    ) -> f64 {
+        Self {
+            evaluation_date: Some(valuation_date),
+            underlying_price: underlying_price.unwrap_or(self.underlying_price),
+            volatility: self.volatility + volatility_shift,
+            risk_free_rate: self.risk_free_rate + rate_shift,
+            cost_of_carry: self.cost_of_carry + rate_shift,
+            ..*self
         }
+        .price()
     }
 
     /// Generalised Black-Scholes European Option Price.
     #[must_use]
     pub fn price(&self) -> f64 {
         let (S, K, _, r, b) = self.unpack();
+
+        if let Some((_, T)) = self.deterministic_limit() {
+            let forward = S * (b * T).exp();
+
+            return (-r * T).exp()
+                * match self.option_type {
+                    TypeFlag::Call => (forward - K).max(0.0),
+                    TypeFlag::Put => (K - forward).max(0.0),
+                };
+        }
+
         let T = self.year_fraction();
         let (d1, d2) = self.d1_d2();
         let n = Gaussian::default();
@@ -140,6 +265,137 @@ impl BlackScholesMerton {
         }
     }
 
+    /// Price of a continuously monitored single-barrier option on the same
+    /// underlying, strike, expiry, and type, by the Reiner-Rubinstein
+    /// formulas. The `rebate` is paid at expiry if a knock-in option is
+    /// never knocked in, and when the barrier is hit for a knock-out option.
+    ///
+    /// If the barrier is already breached, a knock-in option is the vanilla
+    /// option, and a knock-out option pays its rebate immediately.
+    #[must_use]
+    pub fn barrier_price(&self, barrier_type: BarrierType, barrier: f64, rebate: f64) -> f64 {
+        let (S, K, v, r, b) = self.unpack();
+
+        let breached = match barrier_type {
+            BarrierType::UpAndIn | BarrierType::UpAndOut => S >= barrier,
+            BarrierType::DownAndIn | BarrierType::DownAndOut => S <= barrier,
+        };
+
+        match (breached, barrier_type) {
+            (true, BarrierType::UpAndIn | BarrierType::DownAndIn) => self.price(),
+            (true, BarrierType::UpAndOut | BarrierType::DownAndOut) => rebate,
+            (false, _) => reiner_rubinstein(
+                self.option_type,
+                barrier_type,
+                (S, K, barrier, rebate),
+                self.year_fraction(),
+                r,
+                b,
+                v,
+            ),
+        }
+    }
+
+    /// Price of a digital option on the same underlying, strike, expiry,
+    /// and type, paying `payout` if it finishes in the money:
+    /// - cash-or-nothing: $P e^{-rT} N(\pm d_2)$, the discounted
+    ///   probability of exercise times the cash payout,
+    /// - asset-or-nothing: $P S e^{(b-r)T} N(\pm d_1)$, the discounted
+    ///   expected asset value on exercise times the number of units paid.
+    #[must_use]
+    pub fn digital_price(&self, binary_type: BinaryType, payout: f64) -> f64 {
+        let (S, _, _, r, b) = self.unpack();
+
+        let (h, T) = match self.deterministic_limit() {
+            Some((h, T)) => (h, T),
+            None => {
+                let (d1, d2) = self.d1_d2();
+                let d = match binary_type {
+                    BinaryType::CashOrNothing => d2,
+                    BinaryType::AssetOrNothing => d1,
+                };
+
+                (Gaussian::default().cdf(d), self.year_fraction())
+            }
+        };
+
+        let probability = match self.option_type {
+            TypeFlag::Call => h,
+            TypeFlag::Put => 1.0 - h,
+        };
+
+        payout
+            * probability
+            * match binary_type {
+                BinaryType::CashOrNothing => (-r * T).exp(),
+                BinaryType::AssetOrNothing => S * ((b - r) * T).exp(),
+            }
+    }
+
+    /// Delta of the digital option (see [`BlackScholesMerton::digital_price`]).
+    ///
+    /// The cash-or-nothing delta is the discounted density at the strike,
+    /// $P e^{-rT} n(d_2) / (S \sigma \sqrt{T})$ for a call, which grows
+    /// without bound near the strike as the expiry approaches.
+    #[must_use]
+    pub fn digital_delta(&self, binary_type: BinaryType, payout: f64) -> f64 {
+        let (S, _, v, r, b) = self.unpack();
+
+        if self.deterministic_limit().is_some() {
+            return match binary_type {
+                BinaryType::CashOrNothing => 0.0,
+                BinaryType::AssetOrNothing => self.digital_price(binary_type, payout) / S,
+            };
+        }
+
+        let T = self.year_fraction();
+        let (d1, d2) = self.d1_d2();
+        let n = Gaussian::default();
+        let vol_sqrt_t = v * T.sqrt();
+
+        // Call delta; the put's is the negative (plus the forward's delta
+        // for asset-or-nothing), by parity.
+        let (call, forward) = match binary_type {
+            BinaryType::CashOrNothing => ((-r * T).exp() * n.pdf(d2) / (S * vol_sqrt_t), 0.0),
+            BinaryType::AssetOrNothing => {
+                let carry = ((b - r) * T).exp();
+
+                (carry * (n.cdf(d1) + n.pdf(d1) / vol_sqrt_t), carry)
+            }
+        };
+
+        payout
+            * match self.option_type {
+                TypeFlag::Call => call,
+                TypeFlag::Put => forward - call,
+            }
+    }
+
+    /// Gamma of the digital option (see [`BlackScholesMerton::digital_price`]).
+    #[must_use]
+    pub fn digital_gamma(&self, binary_type: BinaryType, payout: f64) -> f64 {
+        let (S, _, v, r, b) = self.unpack();
+
+        if self.deterministic_limit().is_some() {
+            return 0.0;
+        }
+
+        let T = self.year_fraction();
+        let (d1, d2) = self.d1_d2();
+        let n = Gaussian::default();
+
+        let call = match binary_type {
+            BinaryType::CashOrNothing => -(-r * T).exp() * n.pdf(d2) * d1 / (S * S * v * v * T),
+            BinaryType::AssetOrNothing => -((b - r) * T).exp() * n.pdf(d1) * d2 / (S * v * v * T),
+        };
+
+        payout
+            * match self.option_type {
+                TypeFlag::Call => call,
+                TypeFlag::Put => -call,
+            }
+    }
+
     /// Implied volatility.
     pub fn implied_volatility(&self, price: f64) -> f64 {
         crate::instruments::options::implied_volatility(
@@ -152,6 +408,88 @@ impl BlackScholesMerton {
         )
     }
 
+    /// Implied volatility via a safeguarded Newton-Raphson iteration.
+    ///
+    /// The iteration is seeded with the Brenner-Subrahmanyam (1988)
+    /// approximation, $\sigma_0 = \sqrt{2 \pi / T} \cdot C / (S e^{(b-r)T})$,
+    /// and falls back to bisection when vega is near zero or a Newton
+    /// step leaves the bracketing interval.
+    ///
+    /// Unlike [`BlackScholesMerton::implied_volatility`], this supports any
+    /// cost of carry, and returns `None` if the price lies outside the
+    /// no-arbitrage bounds, or if the iteration stops (the bracket
+    /// collapses or the iterations run out) without repricing it.
+    #[must_use]
+    pub fn implied_volatility_newton(&self, market_price: f64) -> Option<f64> {
+        const TOLERANCE: f64 = 1e-12;
+        // Residual accepted once the bracket has collapsed, where the
+        // price is only resolved to about vega times the bracket width.
+        const PRICE_TOLERANCE: f64 = 1e-8;
+        const MAX_ITERATIONS: usize = 100;
+        const MIN_VEGA: f64 = 1e-8;
+
+        let (S, K, _, r, b) = self.unpack();
+        let T = self.year_fraction();
+
+        let forward = S * ((b - r) * T).exp();
+        let strike = K * (-r * T).exp();
+
+        let (lower, upper) = match self.option_type {
+            TypeFlag::Call => ((forward - strike).max(0.0), forward),
+            TypeFlag::Put => ((strike - forward).max(0.0), strike),
+        };
+
+        if !(T > 0.0 && market_price > lower && market_price < upper) {
+            return None;
+        }
+
+        let mut bsm = BlackScholesMerton::new(
+            b,
+            S,
+            K,
+            0.0,
+            r,
+            self.evaluation_date,
+            self.expiration_date,
+            self.option_type,
+        );
+
+        // The price is increasing in volatility, so we maintain a bracket.
+        let (mut lo, mut hi) = (1e-8, 10.0);
+
+        let seed = (2.0 * std::f64::consts::PI / T).sqrt() * market_price / forward;
+        bsm.volatility = seed.clamp(1e-4, 5.0);
+
+        for _ in 0..MAX_ITERATIONS {
+            let diff = bsm.price() - market_price;
+
+            if diff.abs() < TOLERANCE {
+                return Some(bsm.volatility);
+            }
+
+            if diff > 0.0 {
+                hi = bsm.volatility;
+            } else {
+                lo = bsm.volatility;
+            }
+
+            let vega = bsm.vega();
+            let newton = bsm.volatility - diff / vega;
+
+            bsm.volatility = if vega > MIN_VEGA && newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+
+            if hi - lo < TOLERANCE {
+                break;
+            }
+        }
+
+        ((bsm.price() - market_price).abs() < PRICE_TOLERANCE).then_some(bsm.volatility)
+    }
+
     /// Compute the year fraction between two dates.
     #[must_use]
     pub fn year_fraction(&self) -> f64 {
@@ -175,6 +513,30 @@ impl BlackScholesMerton {
         (d1, d2)
     }
 
+    // Limits at the boundaries (see the module docs): the common limit of
+    // N(d1) and N(d2), and the time to expiry floored at zero.
+    // `None` away from the boundaries.
+    fn deterministic_limit(&self) -> Option<(f64, f64)> {
+        let (S, K, v, _, b) = self.unpack();
+        let T = self.year_fraction().max(0.0);
+        let eps = self.boundary_epsilon;
+
+        if S > eps && v * T.sqrt() > eps {
+            return None;
+        }
+
+        let forward = S * (b * T).exp();
+        let step = if forward > K {
+            1.0
+        } else if forward < K {
+            0.0
+        } else {
+            0.5
+        };
+
+        Some((step, T))
+    }
+
     // Unpack struct to get option parameters.
     #[must_use]
     fn unpack(&self) -> (f64, f64, f64, f64, f64) {
@@ -190,67 +552,65 @@ impl BlackScholesMerton {
     /// Delta of generalised Black-Scholes European Option.
     #[must_use]
     pub fn delta(&self) -> f64 {
-        let (_, _, _, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let d1 = self.d1_d2().0;
-        let n = Gaussian::default();
+        self.greeks().delta
+    }
 
-        match self.option_type {
-            TypeFlag::Call => ((b - r) * T).exp() * n.cdf(d1),
-            TypeFlag::Put => ((b - r) * T).exp() * (n.cdf(d1) - 1.0),
-        }
+    /// Smile-adjusted delta of generalised Black-Scholes European Option.
+    ///
+    /// Adds the volatility-change term to the Black delta:
+    /// $$
+    /// \Delta_{smile} = \Delta + \mathcal{V} \frac{\partial \sigma}{\partial S}
+    /// $$
+    /// where $\partial \sigma / \partial S$ is zero under sticky-strike,
+    /// and $-\frac{K}{S} \frac{\partial \sigma}{\partial K}$ under sticky-delta.
+    #[must_use]
+    pub fn smile_adjusted_delta(
+        &self,
+        surface: &VolatilitySurface,
+        convention: StickyConvention,
+    ) -> f64 {
+        let dsigma_dS = surface.volatility_spot_sensitivity(
+            self.strike_price,
+            self.year_fraction(),
+            convention,
+        );
+
+        self.delta() + self.vega() * dsigma_dS
     }
 
     /// Vanna of generalised Black-Scholes European Option.
     /// Also known as DdeltaDvol.
     #[must_use]
     pub fn vanna(&self) -> f64 {
-        let (_, _, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, d2) = self.d1_d2();
-        let n = Gaussian::default();
-
-        -((b - r) * T).exp() * n.pdf(d1) * d2 / v
+        self.greeks().vanna
     }
 
     /// Charm of generalised Black-Scholes European Option.
     /// Also known as DdeltaDtime, delta decay or delta bleed.
     #[must_use]
     pub fn charm(&self) -> f64 {
-        let (_, _, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, d2) = self.d1_d2();
-        let n = Gaussian::default();
-
-        match self.option_type {
-            TypeFlag::Call => {
-                ((b - r) * T).exp()
-                    * (n.pdf(d1) * ((b / (v * T.sqrt())) - (d2 / (2.0 * T))) + (b - r) * n.cdf(d1))
-            }
-            TypeFlag::Put => {
-                ((b - r) * T).exp()
-                    * (n.pdf(d1) * ((b / (v * T.sqrt())) - (d2 / (2.0 * T))) - (b - r) * n.cdf(-d1))
-            }
-        }
+        self.greeks().charm
     }
 
     /// Lambda of generalised Black-Scholes European Option.
     /// Also known as elasticity or leverage.
+    /// Zero if the option is worthless.
     #[must_use]
     pub fn lambda(&self) -> f64 {
-        self.delta() * self.underlying_price / self.price()
+        let price = self.price();
+
+        if price == 0.0 {
+            return 0.0;
+        }
+
+        self.delta() * self.underlying_price / price
     }
 
     /// Gamma of generalised Black-Scholes European Option.
     /// Also known as convexity.
     #[must_use]
     pub fn gamma(&self) -> f64 {
-        let n = Gaussian::default();
-        let (S, _, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, _) = self.d1_d2();
-
-        ((b - r) * T).exp() * n.pdf(d1) / (S * v * T.sqrt())
+        self.greeks().gamma
     }
 
     /// Gamma percent of generalised Black-Scholes European Option.
@@ -263,8 +623,7 @@ impl BlackScholesMerton {
     /// Also known as DgammaDvol.
     #[must_use]
     pub fn zomma(&self) -> f64 {
-        let (d1, d2) = self.d1_d2();
-        self.gamma() * ((d1 * d2 - 1.0) / self.volatility)
+        self.greeks().zomma
     }
 
     /// Zomma percent of generalised Black-Scholes European Option.
@@ -277,134 +636,75 @@ impl BlackScholesMerton {
     /// Also known as DgammaDspot.
     #[must_use]
     pub fn speed(&self) -> f64 {
-        let (S, _, v, _, _) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, _) = self.d1_d2();
-
-        let gamma = self.gamma();
-
-        -gamma * (1.0 + d1 / (v * T.sqrt())) / S
+        self.greeks().speed
     }
 
     /// Colour of generalised Black-Scholes European Option.
     /// Also known as DgammaDtime.
     #[must_use]
     pub fn colour(&self) -> f64 {
-        let (_, _, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, d2) = self.d1_d2();
-
-        let gamma = self.gamma();
-
-        gamma * (r - b + b * d1 / (v * T.sqrt()) + (1.0 - d1 * d2) / (2.0 * T))
+        self.greeks().colour
     }
 
     /// Vega of generalised Black-Scholes European Option.
     /// Also known as zeta.
     #[must_use]
     pub fn vega(&self) -> f64 {
-        let (S, _, _, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, _) = self.d1_d2();
-
-        let n = Gaussian::default();
-
-        S * ((b - r) * T).exp() * n.pdf(d1) * T.sqrt()
+        self.greeks().vega
     }
 
     /// Vomma of generalised Black-Scholes European Option.
     /// Also known as DvegaDvol.
     #[must_use]
     pub fn vomma(&self) -> f64 {
-        let (d1, d2) = self.d1_d2();
-
-        self.vega() * d1 * d2 / self.volatility
+        self.greeks().vomma
     }
 
     /// Ultima of generalised Black-Scholes European Option.
     /// Also known as DvommaDvol.
     #[must_use]
     pub fn ultima(&self) -> f64 {
-        let (d1, d2) = self.d1_d2();
-
-        (self.vomma() / self.volatility) * (d1 * d2 - d1 / d2 + d2 / d1 - 1.0)
+        self.greeks().ultima
     }
 
     /// Vega Bleed of the generalised Black-Scholes European option.
     /// Also known as DvegaDtime.
     #[must_use]
     pub fn vega_bleed(&self) -> f64 {
-        let (_, _, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, d2) = self.d1_d2();
-
-        self.vega() * (r - b + b * d1 / (v * T.sqrt()) - (d1 * d2 + 1.0) / (2.0 * T))
+        self.greeks().vega_bleed
     }
 
     /// Theta of the generalised Black-Scholes European option.
     /// Also known as Expected Bleed.
     #[must_use]
     pub fn theta(&self) -> f64 {
-        let (S, K, v, r, b) = self.unpack();
-        let T = self.year_fraction();
-        let (d1, d2) = self.d1_d2();
-
-        let n = Gaussian::default();
-
-        match self.option_type {
-            TypeFlag::Call => {
-                -S * ((b - r) * T).exp() * n.pdf(d1) * v / (2.0 * T.sqrt())
-                    - (b - r) * S * ((b - r) * T).exp() * n.cdf(d1)
-                    - r * K * (-r * T).exp() * n.cdf(d2)
-            }
-            TypeFlag::Put => {
-                -S * ((b - r) * T).exp() * n.pdf(d1) * v / (2.0 * T.sqrt())
-                    + (b - r) * S * ((b - r) * T).exp() * n.cdf(-d1)
-                    + r * K * (-r * T).exp() * n.cdf(-d2)
-            }
-        }
+        self.greeks().theta
     }
 
     /// Rho of the generalised Black-Scholes European option.
     #[must_use]
     pub fn rho(&self) -> f64 {
-        let T = self.year_fraction();
-
-        match self.option_type {
-            TypeFlag::Call => {
-                self.strike_price
-                    * T
-                    * (-self.risk_free_rate * T).exp()
-                    * Gaussian::default().cdf(self.d1_d2().1)
-            }
-            TypeFlag::Put => {
-                -self.strike_price
-                    * T
-                    * (-self.risk_free_rate * T).exp()
-                    * Gaussian::default().cdf(-self.d1_d2().1)
-            }
-        }
+        self.greeks().rho
     }
 
     /// Phi of the generalised Black-Scholes European option.
     /// Also known as Rho-2.
     #[must_use]
     pub fn phi(&self) -> f64 {
-        let (S, _, _, r, b) = self.unpack();
-        let T = self.year_fraction();
-
-        let (d1, _) = self.d1_d2();
-
-        match self.option_type {
-            TypeFlag::Call => -T * S * ((b - r) * T).exp() * Gaussian::default().cdf(d1),
-            TypeFlag::Put => T * S * ((b - r) * T).exp() * Gaussian::default().cdf(-d1),
-        }
+        self.greeks().phi
     }
 
     /// Zeta of the generalised Black-Scholes European option.
     /// Also known as the in-the-money probability.
     #[must_use]
     pub fn zeta(&self) -> f64 {
+        if let Some((h, _)) = self.deterministic_limit() {
+            return match self.option_type {
+                TypeFlag::Call => h,
+                TypeFlag::Put => 1.0 - h,
+            };
+        }
+
         let n = Gaussian::default();
 
         match self.option_type {
@@ -417,6 +717,15 @@ impl BlackScholesMerton {
     /// Also known as Dual Delta or Discounted Probability.
     #[must_use]
     pub fn strike_delta(&self) -> f64 {
+        if let Some((h, T)) = self.deterministic_limit() {
+            let discount = (-self.risk_free_rate * T).exp();
+
+            return match self.option_type {
+                TypeFlag::Call => -discount * h,
+                TypeFlag::Put => discount * (1.0 - h),
+            };
+        }
+
         let n = Gaussian::default();
 
         let T = self.year_fraction();
@@ -430,12 +739,150 @@ impl BlackScholesMerton {
     /// Strike Gamma of the generalised Black-Scholes European option.
     #[must_use]
     pub fn strike_gamma(&self) -> f64 {
+        if self.deterministic_limit().is_some() {
+            return 0.0;
+        }
+
         let n = Gaussian::default();
         let T = self.year_fraction();
 
         n.pdf(self.d1_d2().1) * (-self.risk_free_rate * T).exp()
             / (self.strike_price * self.volatility * T.sqrt())
     }
+
+    /// All the sensitivities in [`BlackScholesMertonGreeks`] at once,
+    /// sharing the year fraction, $d_1$, $d_2$, and the normal density and
+    /// distribution terms. The individual getters (e.g.
+    /// [`BlackScholesMerton::delta`]) read their value from here, so when
+    /// several are needed it is cheaper to call this once.
+    #[must_use]
+    pub fn greeks(&self) -> BlackScholesMertonGreeks {
+        let (S, K, v, r, b) = self.unpack();
+
+        // +1 for calls, -1 for puts.
+        let sign = match self.option_type {
+            TypeFlag::Call => 1.0,
+            TypeFlag::Put => -1.0,
+        };
+
+        // In-the-money probabilities N(±d1) and N(±d2), and d1 and d2
+        // away from the boundaries, where the density terms vanish.
+        let (T, N1, N2, d) = match self.deterministic_limit() {
+            Some((h, T)) => {
+                let p = if sign > 0.0 { h } else { 1.0 - h };
+
+                (T, p, p, None)
+            }
+            None => {
+                let T = self.year_fraction();
+                let (d1, d2) = self.d1_d2();
+                let n = Gaussian::default();
+
+                (T, n.cdf(sign * d1), n.cdf(sign * d2), Some((d1, d2)))
+            }
+        };
+
+        let carry = ((b - r) * T).exp();
+        let discount = (-r * T).exp();
+
+        let mut greeks = BlackScholesMertonGreeks {
+            delta: sign * carry * N1,
+            gamma: 0.0,
+            speed: 0.0,
+            colour: 0.0,
+            vanna: 0.0,
+            charm: sign * carry * (b - r) * N1,
+            zomma: 0.0,
+            vega: 0.0,
+            vomma: 0.0,
+            ultima: 0.0,
+            vega_bleed: 0.0,
+            theta: -sign * ((b - r) * S * carry * N1 + r * K * discount * N2),
+            rho: sign * K * T * discount * N2,
+            phi: -sign * T * S * carry * N1,
+        };
+
+        if let Some((d1, d2)) = d {
+            let v_sqrt_T = v * T.sqrt();
+            let pdf = Gaussian::default().pdf(d1);
+
+            let gamma = carry * pdf / (S * v_sqrt_T);
+            let vega = S * carry * pdf * T.sqrt();
+            let vomma = vega * d1 * d2 / v;
+
+            greeks.gamma = gamma;
+            greeks.speed = -gamma * (1.0 + d1 / v_sqrt_T) / S;
+            greeks.colour = gamma * (r - b + b * d1 / v_sqrt_T + (1.0 - d1 * d2) / (2.0 * T));
+            greeks.vanna = -carry * pdf * d2 / v;
+            greeks.charm += carry * pdf * (b / v_sqrt_T - d2 / (2.0 * T));
+            greeks.zomma = gamma * (d1 * d2 - 1.0) / v;
+            greeks.vega = vega;
+            greeks.vomma = vomma;
+            greeks.ultima = (vomma / v) * (d1 * d2 - d1 / d2 + d2 / d1 - 1.0);
+            greeks.vega_bleed = vega * (r - b + b * d1 / v_sqrt_T - (d1 * d2 + 1.0) / (2.0 * T));
+            greeks.theta -= S * carry * pdf * v / (2.0 * T.sqrt());
+        }
+
+        greeks
+    }
+
+    /// Risk-neutral density of the spot at horizon `t` (in years),
+    /// evaluated at `spot_level`: the lognormal density of
+    /// $S_t = S e^{(b - \sigma^2 / 2) t + \sigma W_t}$.
+    ///
+    /// The density is the same for calls and puts; the price of either is
+    /// $e^{-rT} \int \text{payoff}(x) f(x) dx$ at $t = T$.
+    /// Zero when the distribution collapses onto the forward
+    /// (see the module docs).
+    #[must_use]
+    pub fn terminal_density(&self, spot_level: f64, t: f64) -> f64 {
+        match self.terminal_z_score(spot_level, t) {
+            Some((z, total_volatility)) => {
+                Gaussian::default().pdf(z) / (spot_level * total_volatility)
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Risk-neutral probability that the spot at horizon `t` (in years)
+    /// is at most `spot_level`. At $t = T$ and the strike, this is the
+    /// probability of a put finishing in the money, and one minus that of
+    /// a call (see [`BlackScholesMerton::zeta`]).
+    #[must_use]
+    pub fn terminal_cdf(&self, spot_level: f64, t: f64) -> f64 {
+        if spot_level <= 0.0 {
+            return 0.0;
+        }
+
+        match self.terminal_z_score(spot_level, t) {
+            Some((z, _)) => Gaussian::default().cdf(z),
+            None => {
+                let forward = self.underlying_price * (self.cost_of_carry * t.max(0.0)).exp();
+
+                if spot_level >= forward {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    // Standardised log spot level at horizon t, and the total volatility.
+    // `None` for non-positive levels, and at the boundaries.
+    fn terminal_z_score(&self, spot_level: f64, t: f64) -> Option<(f64, f64)> {
+        let (S, _, v, _, b) = self.unpack();
+        let total_volatility = v * t.max(0.0).sqrt();
+        let eps = self.boundary_epsilon;
+
+        if spot_level <= 0.0 || S <= eps || total_volatility <= eps {
+            return None;
+        }
+
+        let z = ((spot_level / S).ln() - (b - 0.5 * v * v) * t) / total_volatility;
+
+        Some((z, total_volatility))
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -446,6 +893,7 @@ impl BlackScholesMerton {
 mod tests_black_scholes_merton {
     use super::*;
     use crate::assert_approx_equal;
+    use crate::math::integrate;
     use crate::RUSTQUANT_EPSILON;
     use time::Duration;
 
@@ -480,4 +928,368 @@ mod tests_black_scholes_merton {
         );
         assert_approx_equal!(bsm.price(), 2.456571166461579, RUSTQUANT_EPSILON);
     }
+
+    #[test]
+    fn implied_volatility_newton_round_trip() {
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            for (strike, volatility, days) in [
+                (100.0, 0.2, 365),
+                (80.0, 0.35, 30),
+                (130.0, 0.15, 730),
+                (150.0, 0.25, 182),
+                (60.0, 0.6, 91),
+            ] {
+                let bsm = BlackScholesMerton::new(
+                    0.03,
+                    100.0,
+                    strike,
+                    volatility,
+                    0.05,
+                    None,
+                    today() + Duration::days(days),
+                    option_type,
+                );
+
+                let iv = bsm.implied_volatility_newton(bsm.price()).unwrap();
+
+                assert_approx_equal!(iv, volatility, 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn implied_volatility_newton_arbitrage_bounds() {
+        let bsm = BlackScholesMerton::new(
+            0.05,
+            100.0,
+            90.0,
+            0.2,
+            0.05,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Call,
+        );
+
+        // Above the spot price.
+        assert!(bsm.implied_volatility_newton(100.5).is_none());
+
+        // Below the (discounted) intrinsic value.
+        assert!(bsm.implied_volatility_newton(5.0).is_none());
+        assert!(bsm.implied_volatility_newton(-1.0).is_none());
+
+        // Within the bounds, but above the price at the largest volatility
+        // searched, so the iteration stops without repricing it.
+        let widest = BlackScholesMerton {
+            volatility: 10.0,
+            ..bsm
+        };
+        assert!(widest.price() < 100.0 - 1e-7);
+        assert!(bsm.implied_volatility_newton(100.0 - 1e-7).is_none());
+    }
+
+    #[test]
+    fn smile_adjusted_delta() {
+        // Linear skew: -0.1 vol points per unit of strike.
+        let strikes = vec![80.0, 90.0, 100.0, 110.0, 120.0];
+        let smile = strikes
+            .iter()
+            .map(|k| 0.2 - 0.001 * (k - 100.0))
+            .collect::<Vec<f64>>();
+        let surface = VolatilitySurface::new(100.0, vec![1.0], strikes, vec![smile]).unwrap();
+
+        let bsm = BlackScholesMerton::new(
+            0.05,
+            100.0,
+            95.0,
+            surface.volatility(95.0, 1.0),
+            0.05,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Call,
+        );
+
+        let sticky_strike = bsm.smile_adjusted_delta(&surface, StickyConvention::StickyStrike);
+        let sticky_delta = bsm.smile_adjusted_delta(&surface, StickyConvention::StickyDelta);
+
+        assert_approx_equal!(sticky_strike, bsm.delta(), RUSTQUANT_EPSILON);
+        assert_approx_equal!(
+            sticky_delta - bsm.delta(),
+            bsm.vega() * (-95.0 / 100.0) * (-0.001),
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_theta_matches_repricing() {
+        let evaluation_date = time::macros::date!(2024 - 03 - 01);
+
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            let bsm = BlackScholesMerton::new(
+                0.02,
+                100.0,
+                105.0,
+                0.25,
+                0.05,
+                Some(evaluation_date),
+                evaluation_date + Duration::days(182),
+                option_type,
+            );
+
+            let price_as_of = |date| Instrument::price_as_of(&bsm, date).unwrap();
+            let one_day =
+                price_as_of(evaluation_date.next_day().unwrap()) - price_as_of(evaluation_date);
+            let theta = Instrument::theta(&bsm).unwrap();
+
+            assert_approx_equal!(price_as_of(evaluation_date), bsm.price(), 1e-12);
+            assert_approx_equal!(theta, one_day, 1e-4);
+            assert!(theta < 0.0);
+        }
+    }
+
+    fn boundary_option(
+        strike: f64,
+        spot: f64,
+        volatility: f64,
+        days: i64,
+        option_type: TypeFlag,
+    ) -> BlackScholesMerton {
+        let evaluation_date = time::macros::date!(2024 - 03 - 01);
+
+        BlackScholesMerton::new(
+            0.02,
+            spot,
+            strike,
+            volatility,
+            0.05,
+            Some(evaluation_date),
+            evaluation_date + Duration::days(days),
+            option_type,
+        )
+    }
+
+    fn outputs(bsm: &BlackScholesMerton) -> [f64; 21] {
+        [
+            bsm.price(),
+            bsm.delta(),
+            bsm.vanna(),
+            bsm.charm(),
+            bsm.lambda(),
+            bsm.gamma(),
+            bsm.gamma_percent(),
+            bsm.zomma(),
+            bsm.zomma_percent(),
+            bsm.speed(),
+            bsm.colour(),
+            bsm.vega(),
+            bsm.vomma(),
+            bsm.ultima(),
+            bsm.vega_bleed(),
+            bsm.theta(),
+            bsm.rho(),
+            bsm.phi(),
+            bsm.zeta(),
+            bsm.strike_delta(),
+            bsm.strike_gamma(),
+        ]
+    }
+
+    #[test]
+    fn test_boundaries_are_finite() {
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            for strike in [90.0, 100.0, 110.0] {
+                for spot in [0.0, 1e-12, 100.0] {
+                    for volatility in [0.3, 1e-3, 1e-6, 1e-9, 0.0] {
+                        for days in [365, 1, 0, -1] {
+                            let bsm = boundary_option(strike, spot, volatility, days, option_type);
+
+                            assert!(
+                                outputs(&bsm).iter().all(|x| x.is_finite()),
+                                "{:?}",
+                                (option_type, strike, spot, volatility, days, outputs(&bsm))
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundaries_are_continuous() {
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            for strike in [90.0, 110.0] {
+                // sigma -> 0 at one year: the N'(d1) terms vanish long before
+                // the threshold, so the limits are matched to within rounding.
+                let limit = outputs(&boundary_option(strike, 100.0, 0.0, 365, option_type));
+
+                for volatility in [1e-3, 1e-5, 1e-7] {
+                    let near = outputs(&boundary_option(
+                        strike,
+                        100.0,
+                        volatility,
+                        365,
+                        option_type,
+                    ));
+
+                    for (x, y) in near.iter().zip(&limit) {
+                        assert_approx_equal!(x, y, 1e-9);
+                    }
+                }
+
+                // t -> 0 at 20% volatility: one day from expiry, the values
+                // differ from the limits by one day of carry and discounting.
+                let limit = outputs(&boundary_option(strike, 100.0, 0.2, 0, option_type));
+                let near = outputs(&boundary_option(strike, 100.0, 0.2, 1, option_type));
+
+                for i in [0, 1, 5, 11, 18, 19] {
+                    assert_approx_equal!(near[i], limit[i], 1e-2);
+                }
+            }
+
+            // At the forward, N(d1) tends to one half.
+            let mut bsm = boundary_option(100.0, 100.0, 0.0, 365, option_type);
+            bsm.strike_price = 100.0 * (0.02 * bsm.year_fraction()).exp();
+            assert_eq!(bsm.zeta(), 0.5);
+            assert_eq!(bsm.price(), 0.0);
+        }
+
+        // S = 0: the call is worthless and the put is the discounted strike.
+        let put = boundary_option(100.0, 0.0, 0.2, 365, TypeFlag::Put);
+        assert_approx_equal!(
+            put.price(),
+            100.0 * (-0.05 * put.year_fraction()).exp(),
+            1e-12
+        );
+        assert_eq!(
+            boundary_option(100.0, 0.0, 0.2, 365, TypeFlag::Call).price(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_mid_range_values_unchanged() {
+        let call = boundary_option(105.0, 100.0, 0.25, 184, TypeFlag::Call);
+        let put = boundary_option(105.0, 100.0, 0.25, 184, TypeFlag::Put);
+
+        // The reference values came from another erfc implementation,
+        // so they agree to about 1e-12 relative, not to the last bit.
+        for (value, expected) in [
+            (call.price(), 5.317054841183989),
+            (call.delta(), 0.44161370066362077),
+            (call.gamma(), 0.021983041855397106),
+            (call.vega(), 27.628959708969038),
+            (call.theta(), -7.487075239079638),
+            (call.rho(), 19.528289621401008),
+            (put.price(), 9.207486855328753),
+            (put.delta(), -0.5434174953683545),
+            (put.lambda(), -5.901908999781589),
+        ] {
+            assert_approx_equal!(value, expected, 1e-12 * expected.abs());
+        }
+    }
+
+    #[test]
+    fn test_terminal_density_reprices_options() {
+        for (option_type, strike) in [(TypeFlag::Call, 95.0), (TypeFlag::Put, 110.0)] {
+            let bsm = boundary_option(strike, 100.0, 0.3, 273, option_type);
+            let (T, r) = (bsm.year_fraction(), bsm.risk_free_rate);
+            let density = |x: f64| bsm.terminal_density(x, T);
+
+            // E[g(S_T)] in log spot y, where the integrand is smooth on
+            // either side of the strike. The quadrature is composite, over
+            // 20 panels, for accuracy.
+            let expectation = |g: &dyn Fn(f64) -> f64, a: f64, b: f64| {
+                let width = (b - a) / 20.0;
+
+                (0..20)
+                    .map(|i| {
+                        let a = a + i as f64 * width;
+                        integrate(|y| g(y.exp()) * density(y.exp()) * y.exp(), a, a + width)
+                    })
+                    .sum::<f64>()
+            };
+            let (lower, upper) = (100_f64.ln() - 3.0, 100_f64.ln() + 3.0);
+
+            let expected_payoff = match option_type {
+                TypeFlag::Call => expectation(&|x| x - strike, strike.ln(), upper),
+                TypeFlag::Put => expectation(&|x| strike - x, lower, strike.ln()),
+            };
+
+            assert_approx_equal!((-r * T).exp() * expected_payoff, bsm.price(), 1e-8);
+            assert_approx_equal!(expectation(&|_| 1.0, lower, upper), 1.0, 1e-10);
+
+            // Breeden-Litzenberger: f(K) = e^{rT} d^2 C / dK^2.
+            assert_approx_equal!(density(strike), (r * T).exp() * bsm.strike_gamma(), 1e-12);
+
+            let itm = match option_type {
+                TypeFlag::Call => 1.0 - bsm.terminal_cdf(strike, T),
+                TypeFlag::Put => bsm.terminal_cdf(strike, T),
+            };
+            assert_approx_equal!(itm, bsm.zeta(), 1e-12);
+            assert_approx_equal!(
+                bsm.terminal_cdf(strike, T),
+                expectation(&|_| 1.0, lower, strike.ln()),
+                1e-10
+            );
+        }
+
+        // Without volatility, all the mass is at the forward.
+        let bsm = boundary_option(100.0, 100.0, 0.0, 365, TypeFlag::Call);
+        let forward = 100.0 * (0.02 * bsm.year_fraction()).exp();
+        assert_eq!(bsm.terminal_density(forward, bsm.year_fraction()), 0.0);
+        assert_eq!(bsm.terminal_cdf(forward - 1e-6, bsm.year_fraction()), 0.0);
+        assert_eq!(bsm.terminal_cdf(forward + 1e-6, bsm.year_fraction()), 1.0);
+    }
+
+    #[test]
+    fn barrier_in_out_parity() {
+        use BarrierType::*;
+
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            for strike in [90.0, 100.0, 110.0] {
+                let bsm = boundary_option(strike, 100.0, 0.25, 182, option_type);
+                let vanilla = bsm.price();
+
+                for (knock_in, knock_out, barrier) in
+                    [(UpAndIn, UpAndOut, 120.0), (DownAndIn, DownAndOut, 85.0)]
+                {
+                    let price_in = bsm.barrier_price(knock_in, barrier, 0.0);
+                    let price_out = bsm.barrier_price(knock_out, barrier, 0.0);
+
+                    assert!(price_in >= 0.0 && price_out >= 0.0);
+                    assert_approx_equal!(price_in + price_out, vanilla, 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn barrier_breached_at_inception() {
+        use BarrierType::*;
+
+        let bsm = boundary_option(100.0, 100.0, 0.25, 182, TypeFlag::Put);
+
+        // Knock-in options are already the vanilla option.
+        assert_eq!(bsm.barrier_price(UpAndIn, 95.0, 3.0), bsm.price());
+        assert_eq!(bsm.barrier_price(DownAndIn, 100.0, 3.0), bsm.price());
+
+        // Knock-out options pay their rebate immediately.
+        assert_eq!(bsm.barrier_price(UpAndOut, 100.0, 3.0), 3.0);
+        assert_eq!(bsm.barrier_price(DownAndOut, 105.0, 0.0), 0.0);
+
+        // Otherwise the prices are the Reiner-Rubinstein formulas.
+        let (S, K, v, r, b) = bsm.unpack();
+        assert_eq!(
+            bsm.barrier_price(DownAndOut, 95.0, 3.0),
+            reiner_rubinstein(
+                TypeFlag::Put,
+                DownAndOut,
+                (S, K, 95.0, 3.0),
+                bsm.year_fraction(),
+                r,
+                b,
+                v
+            )
+        );
+    }
 }
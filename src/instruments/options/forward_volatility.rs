@@ -0,0 +1,384 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Forward-starting volatility instruments: forward variance swaps and
+//! forward volatility agreements (FVAs), on the variance realised between
+//! $T_1$ and $T_2$ but entered today.
+//!
+//! Variance is additive, so the fair strike of a forward variance swap
+//! follows from the variance swap term structure by subtracting total
+//! variances:
+//!
+//! $$
+//! F(T_1, T_2) = \frac{\sigma_{VS}^2(T_2) T_2 - \sigma_{VS}^2(T_1) T_1}{T_2 - T_1}
+//! $$
+//!
+//! Volatility is not: since the square root is concave, the fair strike of
+//! an FVA is below $\sqrt{F}$, by a convexity adjustment that depends on
+//! how uncertain the realised variance $V$ is. To second order,
+//!
+//! $$
+//! E\left[\sqrt{V}\right] \approx \sqrt{F} - \frac{\operatorname{Var}(V)}{8 F^{3/2}}
+//! $$
+
+use crate::error::RustQuantError;
+use crate::math::integration::integrate;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Term structure of fair variance swap strikes.
+///
+/// The total variance $\sigma_{VS}^2(T) T$ is interpolated linearly between
+/// the maturities (starting from zero today), so forward variances are
+/// constant between them, and the last one is extended flat.
+#[derive(Debug, Clone)]
+pub struct VarianceSwapCurve {
+    /// Maturities (in years), positive and strictly increasing.
+    maturities: Vec<f64>,
+
+    /// Total variance at each maturity.
+    total_variances: Vec<f64>,
+}
+
+/// Forward variance swap: pays `notional` times the variance realised
+/// between `start` and `end` (annualised) less the strike, at `end`.
+#[derive(Debug, Clone)]
+pub struct ForwardVarianceSwap {
+    /// Start of the observation period (in years).
+    pub start: f64,
+
+    /// End of the observation period (in years).
+    pub end: f64,
+
+    /// Strike, as an annualised variance (e.g. 0.04 for 20% volatility).
+    pub strike: f64,
+
+    /// Variance notional.
+    pub notional: f64,
+}
+
+/// Forward volatility agreement: pays `notional` times the volatility
+/// realised between `start` and `end` (annualised) less the strike, at
+/// `end`.
+#[derive(Debug, Clone)]
+pub struct ForwardVolatilityAgreement {
+    /// Start of the observation period (in years).
+    pub start: f64,
+
+    /// End of the observation period (in years).
+    pub end: f64,
+
+    /// Strike, as an annualised volatility (e.g. 0.2 for 20%).
+    pub strike: f64,
+
+    /// Vega notional.
+    pub notional: f64,
+}
+
+/// Assumption on the volatility of volatility, which sets the uncertainty
+/// of the realised variance and so the convexity adjustment of an FVA.
+#[derive(Debug, Clone, Copy)]
+pub enum VolOfVol {
+    /// The volatility is lognormal, $d\sigma_t = \omega \sigma_t dW_t$.
+    ///
+    /// The realised volatility is approximated by the geometric average of
+    /// $\sigma_t$ over the period, whose log has variance
+    /// $\omega^2 (T_1 + (T_2 - T_1) / 3)$, giving
+    /// $E[\sqrt{V}] = \sqrt{F} e^{-\omega^2 (T_1 + (T_2 - T_1) / 3) / 2}$.
+    Lognormal {
+        /// Volatility of the volatility ($\omega$).
+        vol_of_vol: f64,
+    },
+
+    /// The variance follows Heston's square-root process,
+    /// $dv_t = \kappa (\theta - v_t) dt + \xi \sqrt{v_t} dW_t$, from which
+    /// the variance of the realised variance is computed exactly. Only its
+    /// variance is used: its mean is the forward variance of the curve.
+    Heston {
+        /// Current variance ($v_0$).
+        initial_variance: f64,
+        /// Mean reversion speed ($\kappa$), positive.
+        mean_reversion: f64,
+        /// Long-run variance ($\theta$).
+        long_run_variance: f64,
+        /// Volatility of the variance ($\xi$).
+        vol_of_vol: f64,
+    },
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl VarianceSwapCurve {
+    /// Create a curve from `(maturity, fair variance swap volatility)` pairs.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no strikes, the
+    ///   maturities are not positive and strictly increasing, a volatility
+    ///   is negative, or the total variance decreases with maturity (so a
+    ///   forward variance would be negative).
+    pub fn new(strikes: &[(f64, f64)]) -> Result<Self, RustQuantError> {
+        if strikes.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "At least one variance swap strike is required.".to_string(),
+            ));
+        }
+
+        if strikes[0].0 <= 0.0 || strikes.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Maturities must be positive and strictly increasing.".to_string(),
+            ));
+        }
+
+        if strikes.iter().any(|&(_, vol)| vol < 0.0 || vol.is_nan()) {
+            return Err(RustQuantError::InvalidArgument(
+                "Volatilities must be non-negative.".to_string(),
+            ));
+        }
+
+        let (maturities, total_variances): (Vec<f64>, Vec<f64>) = strikes
+            .iter()
+            .map(|&(maturity, vol)| (maturity, vol * vol * maturity))
+            .unzip();
+
+        if total_variances.windows(2).any(|w| w[0] > w[1]) {
+            return Err(RustQuantError::InvalidArgument(
+                "Total variance must not decrease with maturity.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            maturities,
+            total_variances,
+        })
+    }
+
+    /// Total variance $\sigma_{VS}^2(t) t$ up to time `t`.
+    pub fn total_variance(&self, t: f64) -> f64 {
+        // The segment ending at pillar `i`, extended after the last pillar.
+        let i = self
+            .maturities
+            .partition_point(|&maturity| maturity < t)
+            .min(self.maturities.len() - 1);
+
+        let (t_0, w_0) = match i {
+            0 => (0.0, 0.0),
+            _ => (self.maturities[i - 1], self.total_variances[i - 1]),
+        };
+        let (t_1, w_1) = (self.maturities[i], self.total_variances[i]);
+
+        w_0 + (w_1 - w_0) * (t - t_0) / (t_1 - t_0)
+    }
+
+    /// Annualised forward variance between `start` and `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is not after `start`.
+    pub fn forward_variance(&self, start: f64, end: f64) -> f64 {
+        assert!(start < end, "The forward period must end after it starts.");
+
+        (self.total_variance(end) - self.total_variance(start)) / (end - start)
+    }
+}
+
+impl ForwardVarianceSwap {
+    /// Fair strike: the forward variance between the start and the end.
+    pub fn fair_strike(&self, curve: &VarianceSwapCurve) -> f64 {
+        curve.forward_variance(self.start, self.end)
+    }
+
+    /// Present value, discounting the payment at `end` at the constant
+    /// `rate`.
+    pub fn price(&self, curve: &VarianceSwapCurve, rate: f64) -> f64 {
+        self.notional * (self.fair_strike(curve) - self.strike) * (-rate * self.end).exp()
+    }
+}
+
+impl ForwardVolatilityAgreement {
+    /// Fair strike: the expected realised volatility, which is the square
+    /// root of the forward variance plus the (negative)
+    /// [`Self::convexity_adjustment`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Heston mean reversion is not positive.
+    pub fn fair_strike(&self, curve: &VarianceSwapCurve, vol_of_vol: VolOfVol) -> f64 {
+        let forward_variance = curve.forward_variance(self.start, self.end);
+        let tau = self.end - self.start;
+
+        match vol_of_vol {
+            VolOfVol::Lognormal { vol_of_vol } => {
+                let log_variance = vol_of_vol * vol_of_vol * (self.start + tau / 3.0);
+
+                forward_variance.sqrt() * (-0.5 * log_variance).exp()
+            }
+            VolOfVol::Heston { .. } if forward_variance == 0.0 => 0.0,
+            VolOfVol::Heston {
+                initial_variance,
+                mean_reversion,
+                long_run_variance,
+                vol_of_vol,
+            } => {
+                assert!(mean_reversion > 0.0, "Mean reversion must be positive.");
+
+                let (v_0, kappa, theta, xi) = (
+                    initial_variance,
+                    mean_reversion,
+                    long_run_variance,
+                    vol_of_vol,
+                );
+
+                // Var(v_s), and Cov(v_s, v_t) = exp(-kappa (t - s)) Var(v_s)
+                // for s < t, integrated over s < t in the period.
+                let variance = |s: f64| {
+                    let decay = (-kappa * s).exp();
+
+                    xi * xi / kappa
+                        * (v_0 * (decay - decay * decay) + 0.5 * theta * (1.0 - decay).powi(2))
+                };
+                let covariance = integrate(
+                    |s| variance(s) * (1.0 - (-kappa * (self.end - s)).exp()) / kappa,
+                    self.start,
+                    self.end,
+                );
+                let realised_variance_variance = 2.0 * covariance / (tau * tau);
+
+                forward_variance.sqrt()
+                    - realised_variance_variance / (8.0 * forward_variance.powf(1.5))
+            }
+        }
+    }
+
+    /// Convexity adjustment: the fair strike less the square root of the
+    /// forward variance, which is never positive.
+    pub fn convexity_adjustment(&self, curve: &VarianceSwapCurve, vol_of_vol: VolOfVol) -> f64 {
+        self.fair_strike(curve, vol_of_vol) - curve.forward_variance(self.start, self.end).sqrt()
+    }
+
+    /// Present value, discounting the payment at `end` at the constant
+    /// `rate`.
+    pub fn price(&self, curve: &VarianceSwapCurve, vol_of_vol: VolOfVol, rate: f64) -> f64 {
+        self.notional
+            * (self.fair_strike(curve, vol_of_vol) - self.strike)
+            * (-rate * self.end).exp()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_forward_volatility {
+    use super::*;
+    use crate::assert_approx_equal;
+
+    fn curve() -> VarianceSwapCurve {
+        VarianceSwapCurve::new(&[(0.25, 0.22), (0.5, 0.21), (1.0, 0.2), (2.0, 0.21)]).unwrap()
+    }
+
+    const HESTON: VolOfVol = VolOfVol::Heston {
+        initial_variance: 0.04,
+        mean_reversion: 1.5,
+        long_run_variance: 0.045,
+        vol_of_vol: 0.5,
+    };
+
+    #[test]
+    fn test_forward_variance_additivity() {
+        let curve = curve();
+
+        // Pillars, points between them, and after the last one.
+        for (t_1, t_2) in [(0.5, 1.0), (0.3, 0.8), (0.1, 1.7), (1.0, 3.0), (2.5, 4.0)] {
+            let near = curve.forward_variance(0.0, t_1) * t_1;
+            let forward = curve.forward_variance(t_1, t_2) * (t_2 - t_1);
+
+            assert_approx_equal!(
+                near + forward,
+                curve.forward_variance(0.0, t_2) * t_2,
+                1e-14
+            );
+        }
+
+        assert_approx_equal!(curve.total_variance(1.0), 0.04, 1e-15);
+        assert_approx_equal!(
+            curve.forward_variance(1.0, 2.0),
+            2.0 * 0.21 * 0.21 - 0.04,
+            1e-15
+        );
+
+        let swap = ForwardVarianceSwap {
+            start: 1.0,
+            end: 2.0,
+            strike: 0.04,
+            notional: 100.0,
+        };
+        assert_approx_equal!(
+            swap.price(&curve, 0.05),
+            100.0 * (0.0482 - 0.04) * (-0.1_f64).exp(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_zero_vol_of_vol() {
+        let curve = curve();
+        let fva = ForwardVolatilityAgreement {
+            start: 0.5,
+            end: 1.5,
+            strike: 0.2,
+            notional: 1.0,
+        };
+        let forward_vol = curve.forward_variance(0.5, 1.5).sqrt();
+
+        let lognormal = VolOfVol::Lognormal { vol_of_vol: 0.0 };
+        let heston = VolOfVol::Heston {
+            initial_variance: 0.04,
+            mean_reversion: 1.5,
+            long_run_variance: 0.045,
+            vol_of_vol: 0.0,
+        };
+
+        assert_eq!(fva.fair_strike(&curve, lognormal), forward_vol);
+        assert_eq!(fva.fair_strike(&curve, heston), forward_vol);
+    }
+
+    #[test]
+    fn test_convexity_adjustment_is_negative() {
+        let curve = curve();
+        let fva = |start: f64| ForwardVolatilityAgreement {
+            start,
+            end: start + 0.5,
+            strike: 0.2,
+            notional: 1.0,
+        };
+
+        for vol_of_vol in [VolOfVol::Lognormal { vol_of_vol: 0.8 }, HESTON] {
+            let adjustments: Vec<f64> = [0.0, 0.5, 1.0]
+                .iter()
+                .map(|&start| fva(start).convexity_adjustment(&curve, vol_of_vol))
+                .collect();
+
+            // Negative, but well below the ~21% forward volatility.
+            assert!(
+                adjustments.iter().all(|&a| a < 0.0 && a > -0.1),
+                "{adjustments:?}"
+            );
+        }
+
+        // More vol of vol, larger adjustment.
+        let lognormal =
+            |vol_of_vol| fva(1.0).convexity_adjustment(&curve, VolOfVol::Lognormal { vol_of_vol });
+        assert!(lognormal(1.0) < lognormal(0.5));
+    }
+}
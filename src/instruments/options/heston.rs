@@ -0,0 +1,760 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{
+    instruments::TypeFlag,
+    math::integrate,
+    pricer::MonteCarloEstimate,
+    time::{today, DayCountConvention},
+};
+use num::Complex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Semi-analytic (Fourier) pricer for European options under the Heston
+/// model, with sensitivities to the model parameters.
+///
+/// The sensitivities are central finite differences of [`HestonPricer::price`],
+/// bumping one parameter by [`HestonPricer::PARAMETER_BUMP`] (one-sided
+/// at the edge of its domain). These are the hedge ratios of a volatility
+/// book against moves in the calibrated parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct HestonPricer {
+    /// Initial asset value ($S_0$).
+    pub initial_price: f64,
+
+    /// Initial variance value ($v_0$).
+    pub initial_variance: f64,
+
+    /// Strike price ($K$).
+    pub strike_price: f64,
+
+    /// Risk-free rate ($r$).
+    pub risk_free_rate: f64,
+
+    /// Dividend yield ($q$).
+    pub dividend_yield: f64,
+
+    /// Correlation between the two Brownian motions ($\rho$).
+    pub correlation: f64,
+
+    /// Volatility-of-volatility ($\sigma$).
+    pub volatility_of_volatility: f64,
+
+    /// Mean reversion rate of the variance ($\kappa$).
+    pub mean_reversion_rate: f64,
+
+    /// Long run mean of the variance ($\theta$).
+    pub long_run_variance: f64,
+
+    /// Evaluation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+
+    /// Expiration date.
+    pub expiration_date: Date,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+}
+
+/// Monte-Carlo pricer for European options under the Heston model,
+/// with Greeks computed in the same simulation pass.
+#[derive(Debug, Clone, Copy)]
+pub struct HestonMonteCarlo {
+    /// Initial asset value ($S_0$).
+    pub initial_price: f64,
+
+    /// Initial variance value ($v_0$).
+    pub initial_variance: f64,
+
+    /// Strike price ($K$).
+    pub strike_price: f64,
+
+    /// Risk-free rate ($r$).
+    pub risk_free_rate: f64,
+
+    /// Dividend yield ($q$).
+    pub dividend_yield: f64,
+
+    /// Correlation between the two Brownian motions ($\rho$).
+    pub correlation: f64,
+
+    /// Volatility-of-volatility ($\sigma$).
+    pub volatility_of_volatility: f64,
+
+    /// Mean reversion rate of the variance ($\kappa$).
+    pub mean_reversion_rate: f64,
+
+    /// Long run mean of the variance ($\theta$).
+    pub long_run_variance: f64,
+
+    /// Evaluation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+
+    /// Expiration date.
+    pub expiration_date: Date,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Number of time steps.
+    pub n_steps: usize,
+
+    /// Number of simulated paths.
+    pub n_paths: usize,
+
+    /// Seed for the random number generator.
+    pub seed: u64,
+}
+
+/// Monte-Carlo price and Greeks under the Heston model.
+#[derive(Debug, Clone, Copy)]
+pub struct HestonGreeks {
+    /// Option price.
+    pub price: MonteCarloEstimate,
+
+    /// Delta, $\partial V / \partial S_0$.
+    pub delta: MonteCarloEstimate,
+
+    /// Sensitivity to the initial variance, $\partial V / \partial v_0$.
+    pub initial_variance_vega: MonteCarloEstimate,
+
+    /// Sensitivity to the volatility-of-volatility, $\partial V / \partial \sigma$.
+    pub volatility_of_volatility_vega: MonteCarloEstimate,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Heston model for option pricing.
+#[allow(clippy::too_many_arguments)]
+#[must_use]
+pub fn heston(
+    S0: f64,    // Initial asset value.
+    V0: f64,    // Initial variance value.
+    K: f64,     // Strike price.
+    r: f64,     // Risk-free rate.
+    q: f64,     // Dividend yield.
+    rho: f64,   // Correlation between the two Brownian motions.
+    sigma: f64, // Volatility-of-volatility.
+    kappa: f64, // Mean reversion rate in the variance process' drift term.
+    theta: f64, // Long run mean of the variance process.
+    evaluation_date: Option<Date>,
+    expiration_date: Date,
+) -> (f64, f64) {
+    // Time to expiry.
+
+    let tau = DayCountConvention::default()
+        .day_count_factor(evaluation_date.unwrap_or(today()), expiration_date);
+
+    // Market price of volatility risk (set to 0 for simplicity).
+    // Should probably include, though, since for equity options it has been shown
+    // to be non-zero (Lamoureux & Lastrapes, 1993).
+    let lambda = 0.0;
+
+    // i = sqrt(-1). Used frequently, so assign here.
+    let i: Complex<f64> = Complex::i();
+
+    let u = |j: u8| -> f64 {
+        match j {
+            1 => 0.5,
+            2 => -0.5,
+            _ => panic!("`j` should be: 1 or 2."),
+        }
+    };
+
+    let b = |j: u8| -> f64 {
+        match j {
+            1 => kappa + lambda - rho * sigma,
+            2 => kappa + lambda,
+            _ => panic!("`j` should be: 1 or 2."),
+        }
+    };
+
+    let d = |j: u8, phi: f64| -> Complex<f64> {
+        ((rho * sigma * i * phi - b(j)).powi(2)
+            - sigma.powi(2) * (2.0 * u(j) * i * phi - phi.powi(2)))
+        .sqrt()
+    };
+
+    let g = |j: u8, phi: f64| -> Complex<f64> {
+        assert!(j == 1 || j == 2);
+
+        (b(j) - rho * sigma * i * phi + d(j, phi)) / (b(j) - rho * sigma * i * phi - d(j, phi))
+    };
+
+    let C = |j: u8, phi: f64| -> Complex<f64> {
+        assert!(j == 1 || j == 2);
+
+        (r - q) * i * phi * tau
+            + (kappa * theta / sigma.powi(2))
+                * ((b(j) - rho * sigma * i * phi + d(j, phi)) * tau
+                    - 2.0 * ((1.0 - g(j, phi) * (d(j, phi) * tau).exp()) / (1.0 - g(j, phi))).ln())
+    };
+
+    let D = |j: u8, phi: f64| -> Complex<f64> {
+        assert!(j == 1 || j == 2);
+
+        ((b(j) - rho * sigma * i * phi + d(j, phi)) * (1.0 - (d(j, phi) * tau).exp()))
+            / (sigma.powi(2) * (1.0 - g(j, phi) * (d(j, phi) * tau).exp()))
+    };
+
+    // The Heston characteristic functions.
+    let f = |j: u8, phi: f64| -> Complex<f64> {
+        assert!(j == 1 || j == 2);
+
+        (C(j, phi) + D(j, phi) * V0 + i * phi * S0.ln()).exp()
+    };
+
+    // These functions return the integrand for P1 and P2.
+    let Re1 = |phi: f64| -> f64 {
+        let j = 1;
+
+        (f(j, phi) * (-i * phi * K.ln()).exp() / (i * phi)).re
+    };
+    let Re2 = |phi: f64| -> f64 {
+        let j = 2;
+
+        (f(j, phi) * (-i * phi * K.ln()).exp() / (i * phi)).re
+    };
+
+    // Integration bounds given in Fabrice D. Rouah's book (see tests).
+    // The integral decays rapidly so 50 is probably enough.
+    let P1 = 0.5 + std::f64::consts::FRAC_1_PI * integrate(Re1, 0.00001, 50.0);
+    let P2 = 0.5 + std::f64::consts::FRAC_1_PI * integrate(Re2, 0.00001, 50.0);
+
+    // Price call, then use put-call-parity for the put.
+    let call = S0 * (-q * tau).exp() * P1 - K * (-r * tau).exp() * P2;
+    let put = call + K * (-r * tau).exp() - S0 * (-q * tau).exp();
+
+    (call, put)
+}
+
+impl HestonPricer {
+    /// Absolute bump applied to a parameter for its sensitivity.
+    pub const PARAMETER_BUMP: f64 = 1e-4;
+
+    /// Option price, from the Fourier inversion in [`heston`].
+    #[must_use]
+    pub fn price(&self) -> f64 {
+        let (call, put) = heston(
+            self.initial_price,
+            self.initial_variance,
+            self.strike_price,
+            self.risk_free_rate,
+            self.dividend_yield,
+            self.correlation,
+            self.volatility_of_volatility,
+            self.mean_reversion_rate,
+            self.long_run_variance,
+            self.evaluation_date,
+            self.expiration_date,
+        );
+
+        match self.option_type {
+            TypeFlag::Call => call,
+            TypeFlag::Put => put,
+        }
+    }
+
+    /// Sensitivity to the initial variance, $\partial V / \partial v_0$.
+    #[must_use]
+    pub fn vega_v0(&self) -> f64 {
+        self.sensitivity(|p| &mut p.initial_variance, (0.0, f64::INFINITY))
+    }
+
+    /// Sensitivity to the mean reversion rate, $\partial V / \partial \kappa$.
+    #[must_use]
+    pub fn sensitivity_kappa(&self) -> f64 {
+        self.sensitivity(|p| &mut p.mean_reversion_rate, (0.0, f64::INFINITY))
+    }
+
+    /// Sensitivity to the long run variance, $\partial V / \partial \theta$.
+    #[must_use]
+    pub fn sensitivity_theta(&self) -> f64 {
+        self.sensitivity(|p| &mut p.long_run_variance, (0.0, f64::INFINITY))
+    }
+
+    /// Sensitivity to the volatility-of-volatility, $\partial V / \partial \sigma$.
+    #[must_use]
+    pub fn sensitivity_sigma(&self) -> f64 {
+        self.sensitivity(|p| &mut p.volatility_of_volatility, (0.0, f64::INFINITY))
+    }
+
+    /// Sensitivity to the correlation, $\partial V / \partial \rho$.
+    #[must_use]
+    pub fn sensitivity_rho(&self) -> f64 {
+        self.sensitivity(|p| &mut p.correlation, (-1.0, 1.0))
+    }
+
+    /// Central difference of the price in one parameter, with the bumped
+    /// values kept within its `domain`.
+    fn sensitivity<F>(&self, parameter: F, (lower, upper): (f64, f64)) -> f64
+    where
+        F: Fn(&mut Self) -> &mut f64,
+    {
+        let mut up = *self;
+        let mut down = *self;
+
+        let value = *parameter(&mut up);
+        let (value_up, value_down) = (
+            (value + Self::PARAMETER_BUMP).min(upper),
+            (value - Self::PARAMETER_BUMP).max(lower),
+        );
+
+        *parameter(&mut up) = value_up;
+        *parameter(&mut down) = value_down;
+
+        (up.price() - down.price()) / (value_up - value_down)
+    }
+}
+
+impl HestonMonteCarlo {
+    /// Time to expiry in years.
+    fn year_fraction(&self) -> f64 {
+        DayCountConvention::default().day_count_factor(
+            self.evaluation_date.unwrap_or(today()),
+            self.expiration_date,
+        )
+    }
+
+    /// Discounted payoff for a terminal asset value.
+    fn discounted_payoff(&self, terminal_price: f64, discount_factor: f64) -> f64 {
+        discount_factor
+            * match self.option_type {
+                TypeFlag::Call => (terminal_price - self.strike_price).max(0.0),
+                TypeFlag::Put => (self.strike_price - terminal_price).max(0.0),
+            }
+    }
+
+    /// Price, delta, and vegas via likelihood-ratio weights,
+    /// using a full-truncation Euler scheme.
+    ///
+    /// - Delta uses the weight for the log-spot conditional on the variance
+    ///   path, which is Gaussian with variance $(1 - \rho^2) \int_0^T v_t dt$.
+    /// - The $v_0$ sensitivity uses the score of the first (bivariate normal)
+    ///   transition density, so its variance grows like $1 / \Delta t$.
+    /// - The $\sigma$ sensitivity sums the score of every transition density;
+    ///   steps where the truncated variance is zero are deterministic in
+    ///   $\sigma$ and contribute nothing.
+    ///
+    /// Requires $|\rho| < 1$.
+    #[must_use]
+    pub fn likelihood_ratio_greeks(&self) -> HestonGreeks {
+        let T = self.year_fraction();
+        let dt = T / self.n_steps as f64;
+        let df = (-self.risk_free_rate * T).exp();
+
+        let S0 = self.initial_price;
+        let v0 = self.initial_variance;
+        let rho = self.correlation;
+        let sigma = self.volatility_of_volatility;
+        let kappa = self.mean_reversion_rate;
+        let theta = self.long_run_variance;
+        let carry = self.risk_free_rate - self.dividend_yield;
+        let rho_bar = (1.0 - rho * rho).sqrt();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        // Sums and sums of squares of: price, delta, v0-vega, sigma-vega.
+        let mut sums = [0.0; 4];
+        let mut sums_sq = [0.0; 4];
+
+        for _ in 0..self.n_paths {
+            let mut x = S0.ln();
+            let mut v = v0;
+
+            let mut integrated_variance = 0.0;
+            let mut independent_noise = 0.0;
+            let mut score_v0 = 0.0;
+            let mut score_sigma = 0.0;
+
+            for step in 0..self.n_steps {
+                let z1: f64 = rng.sample(StandardNormal);
+                let z2: f64 = rng.sample(StandardNormal);
+
+                let v_plus = v.max(0.0);
+                let sqrt_vdt = (v_plus * dt).sqrt();
+
+                if step == 0 {
+                    let dmu_x = -0.5 * dt;
+                    let dmu_v = 1.0 - kappa * dt;
+
+                    score_v0 = (z1 * z1 + z2 * z2 - 2.0) / (2.0 * v0)
+                        + (z1 * (sigma * dmu_x - rho * dmu_v) + z2 * rho_bar * dmu_v)
+                            / (sqrt_vdt * rho_bar * sigma);
+                }
+
+                if v_plus > 0.0 {
+                    score_sigma += (z2 * z2 - 1.0) / sigma - rho * z1 * z2 / (rho_bar * sigma);
+                }
+
+                x += (carry - 0.5 * v_plus) * dt + sqrt_vdt * (rho * z2 + rho_bar * z1);
+                v += kappa * (theta - v_plus) * dt + sigma * sqrt_vdt * z2;
+
+                integrated_variance += v_plus * dt;
+                independent_noise += sqrt_vdt * z1;
+            }
+
+            let payoff = self.discounted_payoff(x.exp(), df);
+            let weight_delta = independent_noise / (S0 * rho_bar * integrated_variance);
+
+            let samples = [
+                payoff,
+                payoff * weight_delta,
+                payoff * score_v0,
+                payoff * score_sigma,
+            ];
+
+            for i in 0..4 {
+                sums[i] += samples[i];
+                sums_sq[i] += samples[i] * samples[i];
+            }
+        }
+
+        let estimate = |i: usize| MonteCarloEstimate::from_sums(sums[i], sums_sq[i], self.n_paths);
+
+        HestonGreeks {
+            price: estimate(0),
+            delta: estimate(1),
+            initial_variance_vega: estimate(2),
+            volatility_of_volatility_vega: estimate(3),
+        }
+    }
+
+    /// Pathwise delta using Andersen's (2008) Quadratic-Exponential scheme.
+    ///
+    /// The variance path does not depend on $S_0$, so
+    /// $\partial S_T / \partial S_0 = S_T / S_0$.
+    #[must_use]
+    pub fn pathwise_delta(&self) -> MonteCarloEstimate {
+        // Switching level between the quadratic and exponential branches.
+        const PSI_CRITICAL: f64 = 1.5;
+
+        let T = self.year_fraction();
+        let dt = T / self.n_steps as f64;
+        let df = (-self.risk_free_rate * T).exp();
+
+        let S0 = self.initial_price;
+        let K = self.strike_price;
+        let rho = self.correlation;
+        let sigma = self.volatility_of_volatility;
+        let kappa = self.mean_reversion_rate;
+        let theta = self.long_run_variance;
+        let carry = self.risk_free_rate - self.dividend_yield;
+
+        let e = (-kappa * dt).exp();
+
+        // Log-spot discretisation coefficients (central, gamma_1 = gamma_2 = 0.5).
+        let k0 = -rho * kappa * theta * dt / sigma;
+        let k1 = 0.5 * dt * (kappa * rho / sigma - 0.5) - rho / sigma;
+        let k2 = 0.5 * dt * (kappa * rho / sigma - 0.5) + rho / sigma;
+        let k3 = 0.5 * dt * (1.0 - rho * rho);
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+
+        for _ in 0..self.n_paths {
+            let mut x = S0.ln();
+            let mut v = self.initial_variance;
+
+            for _ in 0..self.n_steps {
+                let m = theta + (v - theta) * e;
+                let s2 = v * sigma * sigma * e * (1.0 - e) / kappa
+                    + theta * sigma * sigma * (1.0 - e).powi(2) / (2.0 * kappa);
+                let psi = s2 / (m * m);
+
+                let v_next = if psi <= PSI_CRITICAL {
+                    let b2 = 2.0 / psi - 1.0 + (2.0 / psi).sqrt() * (2.0 / psi - 1.0).sqrt();
+                    let a = m / (1.0 + b2);
+                    let z: f64 = rng.sample(StandardNormal);
+
+                    a * (b2.sqrt() + z).powi(2)
+                } else {
+                    let p = (psi - 1.0) / (psi + 1.0);
+                    let beta = (1.0 - p) / m;
+                    let u: f64 = rng.gen();
+
+                    if u <= p {
+                        0.0
+                    } else {
+                        ((1.0 - p) / (1.0 - u)).ln() / beta
+                    }
+                };
+
+                let z: f64 = rng.sample(StandardNormal);
+
+                x += carry * dt + k0 + k1 * v + k2 * v_next + (k3 * (v + v_next)).sqrt() * z;
+                v = v_next;
+            }
+
+            let S_T = x.exp();
+
+            let sample = df
+                * S_T
+                * match self.option_type {
+                    TypeFlag::Call if S_T > K => 1.0,
+                    TypeFlag::Put if S_T < K => -1.0,
+                    _ => 0.0,
+                }
+                / S0;
+
+            sum += sample;
+            sum_sq += sample * sample;
+        }
+
+        MonteCarloEstimate::from_sums(sum, sum_sq, self.n_paths)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/*
+TEST VALUES
+From: "The Heston Model and Its Extensions in MATLAB and C#"" by Fabrice D. Rouah.
+
+""
+For example, the price a 6-month European put with strike K = 100 on a
+dividend-paying stock with spot price S = 100 and yield q = 0.02,
+when the risk-free rate is r = 0.03 and using the parameters
+κ =5, σ =0.5, ρ =−0.8, θ =v0 =0.05, and λ=0,
+along with the integration grid φ ∈ [0.00001, 50] in increments of 0.001 is 5.7590.
+The price of the call with identical features is 6.2528.
+If there is no dividend yield so that q = 0, then as expected,
+the put price decreases, to 5.3790, and the call price increases, to 6.8678.
+""
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_approx_equal, RUSTQUANT_EPSILON};
+    use time::{macros::date, Duration};
+
+    #[test]
+    fn test_heston_options() {
+        // 6 Month expiry (183 / 366 days in a leap year).
+        let evaluation_date = Some(date!(2024 - 01 - 01));
+        let expiry_date = date!(2024 - 01 - 01) + Duration::days(183);
+
+        let heston1 = heston(
+            100.0,
+            0.05,
+            100.0,
+            0.03,
+            0.02,
+            -0.8,
+            0.5,
+            5.0,
+            0.05,
+            evaluation_date,
+            expiry_date,
+        );
+        // Call price.
+        assert_approx_equal!(heston1.0, 6.2528189954900455, RUSTQUANT_EPSILON);
+        // Put price.
+        assert_approx_equal!(heston1.1, 5.759029580879499, RUSTQUANT_EPSILON);
+
+        // WITHOUT DIVIDEND YIELD.
+        let heston2 = heston(
+            100.0,
+            0.05,
+            100.0,
+            0.03,
+            0.0,
+            -0.8,
+            0.5,
+            5.0,
+            0.05,
+            evaluation_date,
+            expiry_date,
+        );
+        // Call price.
+        assert_approx_equal!(heston2.0, 6.867834818545035, RUSTQUANT_EPSILON);
+        // Put price.
+        assert_approx_equal!(heston2.1, 5.379028778851293, RUSTQUANT_EPSILON);
+    }
+
+    fn monte_carlo(strike_price: f64) -> HestonMonteCarlo {
+        HestonMonteCarlo {
+            initial_price: 100.0,
+            initial_variance: 0.05,
+            strike_price,
+            risk_free_rate: 0.03,
+            dividend_yield: 0.02,
+            correlation: -0.8,
+            volatility_of_volatility: 0.5,
+            mean_reversion_rate: 5.0,
+            long_run_variance: 0.05,
+            evaluation_date: Some(date!(2024 - 01 - 01)),
+            expiration_date: date!(2024 - 07 - 02),
+            option_type: TypeFlag::Call,
+            n_steps: 50,
+            n_paths: 50_000,
+            seed: 42,
+        }
+    }
+
+    // Central finite difference of the Fourier call price.
+    fn fourier_call(mc: &HestonMonteCarlo, dS: f64, dv0: f64, dsigma: f64) -> f64 {
+        heston(
+            mc.initial_price + dS,
+            mc.initial_variance + dv0,
+            mc.strike_price,
+            mc.risk_free_rate,
+            mc.dividend_yield,
+            mc.correlation,
+            mc.volatility_of_volatility + dsigma,
+            mc.mean_reversion_rate,
+            mc.long_run_variance,
+            mc.evaluation_date,
+            mc.expiration_date,
+        )
+        .0
+    }
+
+    #[test]
+    fn test_heston_monte_carlo_greeks() {
+        // ATM and 20% OTM calls.
+        for strike in [100.0, 120.0] {
+            let mc = monte_carlo(strike);
+
+            let h = 1e-2;
+            let delta =
+                (fourier_call(&mc, h, 0.0, 0.0) - fourier_call(&mc, -h, 0.0, 0.0)) / (2.0 * h);
+            let vega_v0 =
+                (fourier_call(&mc, 0.0, 1e-4, 0.0) - fourier_call(&mc, 0.0, -1e-4, 0.0)) / 2e-4;
+            let vega_sigma =
+                (fourier_call(&mc, 0.0, 0.0, 1e-4) - fourier_call(&mc, 0.0, 0.0, -1e-4)) / 2e-4;
+
+            let lr = mc.likelihood_ratio_greeks();
+            let pw = mc.pathwise_delta();
+
+            for estimate in [
+                lr.price,
+                lr.delta,
+                lr.initial_variance_vega,
+                lr.volatility_of_volatility_vega,
+                pw,
+            ] {
+                assert!(estimate.variance.is_finite() && estimate.variance > 0.0);
+            }
+
+            assert!(
+                (lr.price.value - fourier_call(&mc, 0.0, 0.0, 0.0)).abs()
+                    < 4.0 * lr.price.standard_error
+            );
+            assert!((lr.delta.value - delta).abs() < 4.0 * lr.delta.standard_error);
+            assert!((pw.value - delta).abs() < 4.0 * pw.standard_error);
+            assert!(
+                (lr.initial_variance_vega.value - vega_v0).abs()
+                    < 4.0 * lr.initial_variance_vega.standard_error
+            );
+            assert!(
+                (lr.volatility_of_volatility_vega.value - vega_sigma).abs()
+                    < 4.0 * lr.volatility_of_volatility_vega.standard_error
+            );
+
+            // The pathwise estimator should have lower variance,
+            // and the two estimators should agree within their combined error.
+            assert!(pw.variance < lr.delta.variance);
+            assert!(
+                (lr.delta.value - pw.value).abs()
+                    < 4.0 * lr.delta.standard_error.hypot(pw.standard_error)
+            );
+        }
+    }
+
+    #[test]
+    fn test_heston_pricer_sensitivities() {
+        let call = HestonPricer {
+            initial_price: 100.0,
+            initial_variance: 0.05,
+            strike_price: 105.0,
+            risk_free_rate: 0.03,
+            dividend_yield: 0.02,
+            correlation: -0.8,
+            volatility_of_volatility: 0.5,
+            mean_reversion_rate: 5.0,
+            long_run_variance: 0.05,
+            evaluation_date: Some(date!(2024 - 01 - 01)),
+            expiration_date: date!(2024 - 07 - 02),
+            option_type: TypeFlag::Call,
+        };
+
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            let pricer = HestonPricer {
+                option_type,
+                ..call
+            };
+
+            assert!(pricer.vega_v0() > 0.0);
+            assert!(pricer.sensitivity_theta() > 0.0);
+
+            // Independent reference: Richardson extrapolation of central
+            // differences with steps well below `PARAMETER_BUMP`, accurate
+            // to O(h^4). The long run variance has the largest curvature,
+            // where the bumped sensitivity is off by about 4e-5 (relative).
+            let difference = |bump: fn(&mut HestonPricer, f64)| {
+                let central = |h: f64| {
+                    let (mut up, mut down) = (pricer, pricer);
+                    bump(&mut up, h);
+                    bump(&mut down, -h);
+
+                    (up.price() - down.price()) / (2.0 * h)
+                };
+
+                (4.0 * central(1e-5) - central(2e-5)) / 3.0
+            };
+
+            let pairs = [
+                (pricer.vega_v0(), difference(|p, h| p.initial_variance += h)),
+                (
+                    pricer.sensitivity_kappa(),
+                    difference(|p, h| p.mean_reversion_rate += h),
+                ),
+                (
+                    pricer.sensitivity_theta(),
+                    difference(|p, h| p.long_run_variance += h),
+                ),
+                (
+                    pricer.sensitivity_sigma(),
+                    difference(|p, h| p.volatility_of_volatility += h),
+                ),
+                (
+                    pricer.sensitivity_rho(),
+                    difference(|p, h| p.correlation += h),
+                ),
+            ];
+
+            for (sensitivity, expected) in pairs {
+                assert_approx_equal!(sensitivity, expected, 1e-4 * expected.abs().max(1.0));
+            }
+        }
+
+        // The correlation bump stays within [-1, 1].
+        for correlation in [-1.0, 1.0] {
+            let pricer = HestonPricer {
+                correlation,
+                ..call
+            };
+            assert!(pricer.sensitivity_rho().is_finite());
+        }
+    }
+}
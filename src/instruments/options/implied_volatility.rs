@@ -28,6 +28,8 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use super::TypeFlag;
+use crate::autodiff::{Arity, Variable};
+use crate::error::RustQuantError;
 use crate::math::distributions::{gaussian::Gaussian, Distribution};
 use errorfunctions::RealErrorFunctions;
 
@@ -110,6 +112,57 @@ pub fn implied_volatility(price: f64, S: f64, K: f64, T: f64, r: f64, flag: Type
     )
 }
 
+/// Implied volatility of a price that is a `Variable`, so that gradients
+/// propagate through the inversion (e.g. to the parameters of the model
+/// that produced the price).
+///
+/// The solver itself is not differentiated: by the implicit function
+/// theorem, $\partial \sigma / \partial p = (\partial P / \partial p) / \mathcal{V}$
+/// for any parameter $p$ of the price, so the implied volatility is
+/// recorded on the graph with partial $1 / \mathcal{V}$ (the Black-Scholes vega)
+/// with respect to the price.
+///
+/// # Errors
+///
+/// `RustQuantError::ComputationError` if there is no finite implied volatility,
+/// or the vega is too small for the gradient to be meaningful
+/// (e.g. for prices at or very near the intrinsic value).
+pub fn implied_volatility_ad<'v>(
+    price: Variable<'v>,
+    S: f64,
+    K: f64,
+    T: f64,
+    r: f64,
+    flag: TypeFlag,
+) -> Result<Variable<'v>, RustQuantError> {
+    let sigma = implied_volatility(price.value, S, K, T, r, flag);
+
+    if !sigma.is_finite() {
+        return Err(RustQuantError::ComputationError(format!(
+            "No implied volatility for price {}.",
+            price.value
+        )));
+    }
+
+    let d1 = ((S / K).ln() + (r + 0.5 * sigma * sigma) * T) / (sigma * T.sqrt());
+    let vega = S * T.sqrt() * ONE_OVER_SQRT_TWO_PI * (-0.5 * d1 * d1).exp();
+
+    // Zero sigma gives a NaN d1, and so vega.
+    if vega.is_nan() || vega <= f64::EPSILON.sqrt() * S {
+        return Err(RustQuantError::ComputationError(format!(
+            "Vega ({vega}) is too small to differentiate the implied volatility."
+        )));
+    }
+
+    Ok(Variable {
+        graph: price.graph,
+        value: sigma,
+        index: price
+            .graph
+            .push(Arity::Unary, &[price.index], &[1.0 / vega]),
+    })
+}
+
 /// A convenience function to calculate the implied volatility.
 /// It is a wrapper around `implied_volatility` function.
 pub fn iv(price: f64, S: f64, K: f64, T: f64, r: f64, flag: TypeFlag) -> f64 {
@@ -1214,3 +1267,98 @@ mod test_lets_be_rational {
         assert!(!is_zero(0.1));
     }
 }
+
+#[cfg(test)]
+mod tests_implied_volatility_ad {
+    use super::*;
+    use crate::autodiff::{Accumulate, Gradient, Graph};
+    use crate::instruments::options::heston;
+    use crate::time::DayCountConvention;
+    use time::macros::date;
+
+    #[test]
+    fn test_implied_volatility_ad_price_gradient() {
+        let (S, K, T, r) = (100.0, 110.0, 0.89, 0.03);
+
+        let g = Graph::new();
+        let price = g.var(12.3);
+
+        let sigma = implied_volatility_ad(price, S, K, T, r, TypeFlag::Call).unwrap();
+        let gradient = sigma.accumulate();
+
+        let d1 =
+            ((S / K).ln() + (r + 0.5 * sigma.value * sigma.value) * T) / (sigma.value * T.sqrt());
+        let vega = S * T.sqrt() * (-0.5 * d1 * d1).exp() / (2.0 * std::f64::consts::PI).sqrt();
+
+        assert_eq!(
+            sigma.value,
+            implied_volatility(12.3, S, K, T, r, TypeFlag::Call)
+        );
+        assert!((gradient.wrt(&price) - 1.0 / vega).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_implied_volatility_ad_heston_chain_rule() {
+        let (S, K, r) = (100.0, 100.0, 0.03);
+        let (start, end) = (date!(2024 - 01 - 01), date!(2025 - 01 - 01));
+        let T = DayCountConvention::default().day_count_factor(start, end);
+
+        // (v_0, theta, kappa, rho, sigma)
+        let parameters = [0.05, 0.04, 1.5, -0.6, 0.4];
+
+        let heston_call =
+            |p: &[f64]| heston(S, p[0], K, r, 0.0, p[3], p[4], p[2], p[1], Some(start), end).0;
+        let implied = |p: &[f64]| implied_volatility(heston_call(p), S, K, T, r, TypeFlag::Call);
+
+        // Central difference of `f` with respect to parameter `i`.
+        let bumped = |f: &dyn Fn(&[f64]) -> f64, i: usize| {
+            let h = 1e-5;
+            let (mut up, mut down) = (parameters, parameters);
+            up[i] += h;
+            down[i] -= h;
+
+            (f(&up) - f(&down)) / (2.0 * h)
+        };
+
+        // Record the Heston price on the graph as its first-order expansion
+        // in the parameters, so the price has the Heston gradient.
+        let g = Graph::new();
+        let variables = g.vars(&parameters);
+
+        let mut price = g.var(heston_call(&parameters));
+        for (i, variable) in variables.iter().enumerate() {
+            price += bumped(&heston_call, i) * (*variable - parameters[i]);
+        }
+
+        let sigma = implied_volatility_ad(price, S, K, T, r, TypeFlag::Call).unwrap();
+        let gradient = sigma.accumulate();
+
+        // The implicit function theorem matches differencing through the solver.
+        for (i, variable) in variables.iter().enumerate() {
+            let expected = bumped(&implied, i);
+
+            assert!(
+                (gradient.wrt(variable) - expected).abs() < 1e-6 * (1.0 + expected.abs()),
+                "{i}: {} vs {expected}",
+                gradient.wrt(variable)
+            );
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility_ad_near_intrinsic() {
+        let (S, K, T, r) = (100.0, 80.0, 0.25, 0.0);
+        let g = Graph::new();
+
+        // A price barely above intrinsic has a tiny implied volatility and vega.
+        let price = g.var(20.0 + 1e-12);
+        let result = implied_volatility_ad(price, S, K, T, r, TypeFlag::Call);
+        assert!(matches!(result, Err(RustQuantError::ComputationError(_))));
+
+        // Exactly intrinsic, and below intrinsic.
+        for value in [20.0, 19.0] {
+            let price = g.var(value);
+            assert!(implied_volatility_ad(price, S, K, T, r, TypeFlag::Call).is_err());
+        }
+    }
+}
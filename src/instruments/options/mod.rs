@@ -7,6 +7,10 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+/// American option pricers.
+pub mod american;
+pub use american::*;
+
 /// Asian option pricers.
 pub mod asian;
 pub use asian::*;
@@ -15,6 +19,10 @@ pub use asian::*;
 // pub mod bachelier;
 // pub use bachelier::*;
 
+/// Basket options.
+pub mod basket;
+pub use basket::*;
+
 /// Barrier option pricers.
 pub mod barrier;
 pub use barrier::*;
@@ -33,9 +41,9 @@ pub use black_scholes_merton::*;
 // /// Forward start options pricers.
 // pub mod forward_start;
 
-// /// Heston model option pricer.
-// pub mod heston;
-// pub use heston::*;
+/// Heston model option pricer.
+pub mod heston;
+pub use heston::*;
 
 /// Implied volatility functions.
 pub mod implied_volatility;
@@ -49,6 +57,14 @@ pub use lookback::*;
 // pub mod merton_jump_diffusion;
 // pub use merton_jump_diffusion::*;
 
+/// Range accrual (corridor) notes.
+pub mod range_accrual;
+pub use range_accrual::*;
+
+/// Risk-neutral density implied by option prices.
+pub mod risk_neutral_density;
+pub use risk_neutral_density::*;
+
 /// Base option traits.
 pub mod option_contract;
 pub use option_contract::*;
@@ -60,10 +76,21 @@ pub use power::*;
 /// Finite Difference Pricer
 pub mod finite_difference_pricer;
 
+/// Two-dimensional (ADI) finite difference pricer.
+pub mod finite_difference_2d;
+
 /// Option flags.
 pub mod option_flags;
 pub use option_flags::*;
 
+/// Option strategies quoted by delta (risk reversals, butterflies).
+pub mod option_strategy;
+pub use option_strategy::*;
+
+/// Two-asset barrier options.
+pub mod two_asset_barrier;
+pub use two_asset_barrier::*;
+
 /// Vanilla option.
 pub mod vanilla;
 pub use vanilla::*;
@@ -75,3 +102,7 @@ pub use supershare::*;
 /// Log contracts and options.
 pub mod log;
 pub use log::*;
+
+/// Forward variance swaps and forward volatility agreements.
+pub mod forward_volatility;
+pub use forward_volatility::*;
@@ -27,3 +27,85 @@ pub struct OptionContract {
     #[builder(default)]
     pub settlement_flag: Option<SettlementFlag>,
 }
+
+/// Pricing engine suited to an option contract.
+/// See [`OptionContract::recommended_engine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineChoice {
+    /// Closed-form price (e.g. Black-Scholes).
+    Analytic,
+
+    /// Binomial tree, checking for early exercise at every node.
+    Binomial,
+
+    /// Longstaff-Schwartz least-squares Monte Carlo.
+    LeastSquaresMonteCarlo,
+}
+
+impl OptionContract {
+    /// The engine that applies to the contract:
+    /// - analytic for European exercise,
+    /// - binomial for American exercise,
+    /// - least-squares Monte Carlo for Bermudan exercise, or a floating
+    ///   strike (which depends on the path of the underlying).
+    #[must_use]
+    pub fn recommended_engine(&self) -> EngineChoice {
+        if let Some(StrikeFlag::Floating) = self.strike_flag {
+            return EngineChoice::LeastSquaresMonteCarlo;
+        }
+
+        match self.exercise_flag {
+            ExerciseFlag::European { .. } => EngineChoice::Analytic,
+            ExerciseFlag::American { .. } => EngineChoice::Binomial,
+            ExerciseFlag::Bermudan { .. } => EngineChoice::LeastSquaresMonteCarlo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_option_contract {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_recommended_engine() {
+        let contract = |exercise_flag, strike_flag| OptionContract {
+            type_flag: TypeFlag::Put,
+            exercise_flag,
+            strike_flag,
+            settlement_flag: None,
+        };
+
+        let european = ExerciseFlag::European {
+            expiry: date!(2025 - 01 - 01),
+        };
+        let american = ExerciseFlag::American {
+            start: date!(2024 - 01 - 01),
+            end: date!(2025 - 01 - 01),
+        };
+        let bermudan = ExerciseFlag::Bermudan {
+            exercise_dates: vec![date!(2024 - 07 - 01), date!(2025 - 01 - 01)],
+        };
+
+        assert_eq!(
+            contract(european.clone(), None).recommended_engine(),
+            EngineChoice::Analytic
+        );
+        assert_eq!(
+            contract(european.clone(), Some(StrikeFlag::Fixed)).recommended_engine(),
+            EngineChoice::Analytic
+        );
+        assert_eq!(
+            contract(american, None).recommended_engine(),
+            EngineChoice::Binomial
+        );
+        assert_eq!(
+            contract(bermudan, None).recommended_engine(),
+            EngineChoice::LeastSquaresMonteCarlo
+        );
+        assert_eq!(
+            contract(european, Some(StrikeFlag::Floating)).recommended_engine(),
+            EngineChoice::LeastSquaresMonteCarlo
+        );
+    }
+}
@@ -103,6 +103,23 @@ pub enum BarrierType {
     DownAndIn,
 }
 
+/// How often the barrier of a barrier option is monitored.
+#[derive(Clone, Debug, Default)]
+pub enum BarrierMonitoring {
+    /// Monitored continuously.
+    #[default]
+    Continuous,
+
+    /// Monitored at each daily close (252 trading days per year).
+    Daily,
+
+    /// Monitored weekly (52 weeks per year).
+    Weekly,
+
+    /// Monitored on the given dates.
+    Dates(Vec<Date>),
+}
+
 /// Binary type enum.
 #[derive(Debug, Clone, Copy)]
 pub enum BinaryType {
@@ -0,0 +1,328 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Option strategies quoted by delta, as traded on a volatility surface.
+//!
+//! - A risk reversal is long the $\Delta$ call and short the $-\Delta$ put.
+//!   It is quoted as $\sigma_{\Delta C} - \sigma_{\Delta P}$, the skew of
+//!   the smile.
+//! - A (vol) butterfly is long the $\Delta$ strangle and short the
+//!   at-the-money (delta-neutral) straddle. It is quoted as
+//!   $\frac{1}{2}(\sigma_{\Delta C} + \sigma_{\Delta P}) - \sigma_{ATM}$,
+//!   the curvature of the smile.
+//!
+//! Strikes are found from Black-Scholes spot deltas at the surface's own
+//! volatility at each strike, so they are consistent with the smile.
+
+use super::{vanilla, TypeFlag, VanillaMarket};
+use crate::data::VolatilitySurface;
+use crate::math::distributions::{Distribution, Gaussian};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// One European option in a strategy, priced off a volatility surface.
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyLeg {
+    /// Call or put.
+    pub type_flag: TypeFlag,
+
+    /// Strike of the option.
+    pub strike: f64,
+
+    /// Number of options held (negative if short).
+    pub quantity: f64,
+
+    /// Implied volatility of the surface at the strike.
+    pub volatility: f64,
+
+    /// Black-Scholes price of one option.
+    pub price: f64,
+
+    /// Black-Scholes vega of one option.
+    pub vega: f64,
+}
+
+/// Portfolio of European options on the same underlying and expiry.
+#[derive(Debug, Clone)]
+pub struct OptionStrategy {
+    /// The options in the strategy.
+    pub legs: Vec<StrategyLeg>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StrategyLeg {
+    /// Option of the given type and strike, priced at the surface's
+    /// volatility at that strike.
+    ///
+    /// The market is read as in [`super::VanillaOption::reconcile_monte_carlo`]:
+    /// `config.x_0` is the spot and `t_n - t_0` the time to expiry, which
+    /// is also the expiry looked up on the surface.
+    pub fn new(
+        type_flag: TypeFlag,
+        strike: f64,
+        quantity: f64,
+        market: &VanillaMarket,
+        surface: &VolatilitySurface,
+    ) -> Self {
+        let config = &market.config;
+        let (s, k, r) = (config.x_0, strike, market.risk_free_rate);
+        let t = config.t_n - config.t_0;
+        let v = surface.volatility(k, t);
+        let df = (-r * t).exp();
+
+        let n = Gaussian::default();
+        let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * t.sqrt());
+        let d2 = d1 - v * t.sqrt();
+
+        let price = match type_flag {
+            TypeFlag::Call => s * n.cdf(d1) - k * df * n.cdf(d2),
+            TypeFlag::Put => k * df * n.cdf(-d2) - s * n.cdf(-d1),
+        };
+
+        Self {
+            type_flag,
+            strike,
+            quantity,
+            volatility: v,
+            price,
+            vega: s * n.pdf(d1) * t.sqrt(),
+        }
+    }
+
+    /// Option of the given type at the strike with the given spot delta
+    /// (on the smile), priced off the surface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delta is not in (0, 1) for a call, or in (-1, 0)
+    /// for a put.
+    pub fn with_delta(
+        type_flag: TypeFlag,
+        delta: f64,
+        quantity: f64,
+        market: &VanillaMarket,
+        surface: &VolatilitySurface,
+    ) -> Self {
+        let t = market.config.t_n - market.config.t_0;
+        let strike = vanilla::strike_for_delta(type_flag, delta, market, |strike| {
+            surface.volatility(strike, t)
+        });
+
+        Self::new(type_flag, strike, quantity, market, surface)
+    }
+}
+
+impl OptionStrategy {
+    /// Create a new strategy from its legs.
+    pub fn new(legs: Vec<StrategyLeg>) -> Self {
+        Self { legs }
+    }
+
+    /// Risk reversal: long one call at spot delta `delta` and short one
+    /// put at spot delta `-delta`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delta` is not in (0, 1).
+    pub fn risk_reversal(delta: f64, market: &VanillaMarket, surface: &VolatilitySurface) -> Self {
+        Self::new(vec![
+            StrategyLeg::with_delta(TypeFlag::Call, delta, 1.0, market, surface),
+            StrategyLeg::with_delta(TypeFlag::Put, -delta, -1.0, market, surface),
+        ])
+    }
+
+    /// Vol butterfly: long one call at spot delta `delta` and one put at
+    /// spot delta `-delta`, short one call and one put at the at-the-money
+    /// strike, where the straddle is delta-neutral.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delta` is not in (0, 0.5).
+    pub fn vol_butterfly(delta: f64, market: &VanillaMarket, surface: &VolatilitySurface) -> Self {
+        assert!(
+            0.0 < delta && delta < 0.5,
+            "The wings of a butterfly must have a delta in (0, 0.5)."
+        );
+
+        // The call and put deltas of a straddle cancel when N(d1) = 1/2.
+        let atm = StrategyLeg::with_delta(TypeFlag::Call, 0.5, -1.0, market, surface);
+
+        Self::new(vec![
+            StrategyLeg::with_delta(TypeFlag::Call, delta, 1.0, market, surface),
+            StrategyLeg::with_delta(TypeFlag::Put, -delta, 1.0, market, surface),
+            atm,
+            StrategyLeg::new(TypeFlag::Put, atm.strike, -1.0, market, surface),
+        ])
+    }
+
+    /// Value of the strategy.
+    pub fn price(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.quantity * leg.price).sum()
+    }
+
+    /// Vega of the strategy.
+    pub fn vega(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.quantity * leg.vega).sum()
+    }
+
+    /// Volatility quote of the strategy: the quantity-weighted volatility
+    /// of its legs, per option held long,
+    /// $\sum_i q_i \sigma_i / \sum_{q_i > 0} q_i$.
+    ///
+    /// This is $\sigma_{\Delta C} - \sigma_{\Delta P}$ for a risk reversal,
+    /// and $\frac{1}{2}(\sigma_{\Delta C} + \sigma_{\Delta P}) - \sigma_{ATM}$
+    /// for a butterfly.
+    pub fn volatility_quote(&self) -> f64 {
+        let long = self
+            .legs
+            .iter()
+            .map(|leg| leg.quantity.max(0.0))
+            .sum::<f64>();
+
+        self.legs
+            .iter()
+            .map(|leg| leg.quantity * leg.volatility)
+            .sum::<f64>()
+            / long
+    }
+
+    /// The strategy scaled so that its long legs have a total vega of
+    /// `vega_notional` (the convention for quoting size in volatility
+    /// trading).
+    #[must_use]
+    pub fn with_vega_notional(&self, vega_notional: f64) -> Self {
+        let long_vega = self
+            .legs
+            .iter()
+            .filter(|leg| leg.quantity > 0.0)
+            .map(|leg| leg.quantity * leg.vega)
+            .sum::<f64>();
+        let scale = vega_notional / long_vega;
+
+        Self::new(
+            self.legs
+                .iter()
+                .map(|&leg| StrategyLeg {
+                    quantity: scale * leg.quantity,
+                    ..leg
+                })
+                .collect(),
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_option_strategy {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::models::GeometricBrownianMotion;
+    use crate::stochastics::StochasticProcessConfig;
+
+    const SPOT: f64 = 100.0;
+    const RATE: f64 = 0.02;
+    const EXPIRY: f64 = 0.5;
+
+    fn market() -> VanillaMarket {
+        VanillaMarket {
+            risk_free_rate: RATE,
+            process: GeometricBrownianMotion::new(RATE, 0.2),
+            config: StochasticProcessConfig::new(SPOT, 0.0, EXPIRY, 1, 1, false),
+        }
+    }
+
+    fn smile(strikes: Vec<f64>, volatilities: Vec<f64>) -> VolatilitySurface {
+        VolatilitySurface::new(SPOT, vec![EXPIRY], strikes, vec![volatilities]).unwrap()
+    }
+
+    #[test]
+    fn test_risk_reversal_follows_skew() {
+        let market = market();
+        let strikes = vec![70.0, 85.0, 100.0, 115.0, 130.0];
+
+        let flat = smile(strikes.clone(), vec![0.2; 5]);
+        let call_skew = smile(strikes.clone(), vec![0.18, 0.19, 0.2, 0.21, 0.22]);
+        let put_skew = smile(strikes, vec![0.26, 0.23, 0.2, 0.18, 0.17]);
+
+        let rr = |surface| OptionStrategy::risk_reversal(0.25, &market, surface);
+        let (flat, call_skew, put_skew) = (rr(&flat), rr(&call_skew), rr(&put_skew));
+
+        // The legs are at the 25-delta strikes on the smile.
+        for leg in &put_skew.legs {
+            let delta = vanilla::black_delta(leg.type_flag, leg.strike, leg.volatility, &market);
+            assert_approx_equal!(delta.abs(), 0.25, 1e-10);
+        }
+
+        assert_approx_equal!(flat.volatility_quote(), 0.0, 1e-12);
+        assert!(call_skew.volatility_quote() > 0.0);
+        assert!(put_skew.volatility_quote() < 0.0);
+
+        // Richer calls (puts) make the risk reversal worth more (less).
+        assert!(call_skew.price() > flat.price());
+        assert!(put_skew.price() < flat.price());
+    }
+
+    #[test]
+    fn test_symmetric_smile() {
+        let market = market();
+        let (wing, atm): (f64, f64) = (0.22, 0.2);
+
+        // A smile with the same volatility at the 25-delta call and put
+        // strikes, and its minimum at the delta-neutral straddle strike.
+        let forward = SPOT * (RATE * EXPIRY).exp();
+        let d1 = Gaussian::default().inv_cdf(0.75);
+        let strike =
+            |d1: f64, v: f64| forward * (v * v * EXPIRY / 2.0 - d1 * v * EXPIRY.sqrt()).exp();
+
+        let surface = smile(
+            vec![strike(d1, wing), strike(0.0, atm), strike(-d1, wing)],
+            vec![wing, atm, wing],
+        );
+
+        let rr = OptionStrategy::risk_reversal(0.25, &market, &surface);
+        let fly = OptionStrategy::vol_butterfly(0.25, &market, &surface);
+
+        assert_approx_equal!(rr.volatility_quote(), 0.0, 1e-8);
+        assert_approx_equal!(fly.volatility_quote(), wing - atm, 1e-8);
+
+        // The straddle is at the delta-neutral strike, and the wings are
+        // richer than on a flat smile at the at-the-money volatility.
+        let flat = smile(vec![SPOT], vec![atm]);
+        let flat_fly = OptionStrategy::vol_butterfly(0.25, &market, &flat);
+
+        assert_approx_equal!(fly.legs[2].strike, strike(0.0, atm), 1e-6);
+        assert_approx_equal!(flat_fly.volatility_quote(), 0.0, 1e-12);
+        assert!(fly.price() > flat_fly.price());
+    }
+
+    #[test]
+    fn test_vega_notional() {
+        let market = market();
+        let surface = smile(vec![80.0, 100.0, 120.0], vec![0.25, 0.2, 0.19]);
+
+        let rr = OptionStrategy::risk_reversal(0.25, &market, &surface);
+        let scaled = rr.with_vega_notional(10_000.0);
+
+        assert_approx_equal!(
+            scaled.legs[0].quantity * scaled.legs[0].vega,
+            10_000.0,
+            1e-8
+        );
+        assert_approx_equal!(scaled.volatility_quote(), rr.volatility_quote(), 1e-12);
+        assert_approx_equal!(scaled.price(), scaled.legs[0].quantity * rr.price(), 1e-8);
+    }
+}
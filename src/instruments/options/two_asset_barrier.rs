@@ -0,0 +1,166 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Two-asset barrier options: a barrier on the first (monitoring) asset
+//! knocks the option on the second (payoff) asset in or out.
+
+use super::{BarrierType, OptionContract, TypeFlag};
+use crate::instruments::Payoff;
+use crate::pricer::MonteCarloEstimate;
+use crate::stochastics::{MultiGeometricBrownianMotion, StochasticProcessConfig};
+
+/// Two-asset barrier option.
+#[derive(Debug, Clone)]
+pub struct TwoAssetBarrierOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Barrier type (up-and-out, down-and-out, up-and-in, down-and-in),
+    /// applied to the monitoring asset.
+    pub barrier_type: BarrierType,
+
+    /// Barrier level on the monitoring asset.
+    pub barrier: f64,
+
+    /// Strike price of the option on the payoff asset.
+    pub strike: f64,
+}
+
+impl Payoff for TwoAssetBarrierOption {
+    /// The monitoring asset's path, and the payoff asset's terminal value.
+    type Underlying = (Vec<f64>, f64);
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let (monitor, terminal) = underlying;
+        let b = self.barrier;
+
+        let payoff = match self.contract.type_flag {
+            TypeFlag::Call => (terminal - self.strike).max(0.0),
+            TypeFlag::Put => (self.strike - terminal).max(0.0),
+        };
+
+        let hit = match self.barrier_type {
+            BarrierType::UpAndOut | BarrierType::UpAndIn => monitor.iter().any(|&x| x >= b),
+            BarrierType::DownAndOut | BarrierType::DownAndIn => monitor.iter().any(|&x| x <= b),
+        };
+
+        let alive = match self.barrier_type {
+            BarrierType::UpAndOut | BarrierType::DownAndOut => !hit,
+            BarrierType::UpAndIn | BarrierType::DownAndIn => hit,
+        };
+
+        if alive {
+            payoff
+        } else {
+            0.0
+        }
+    }
+}
+
+impl TwoAssetBarrierOption {
+    /// Price the option by Monte Carlo over correlated paths, where asset 0
+    /// of `process` is the monitoring asset and asset 1 the payoff asset.
+    /// The barrier is monitored discretely, at each time step.
+    ///
+    /// # Arguments:
+    /// * `process` - Two-asset correlated (risk-neutral) GBM.
+    /// * `x_0` - The assets' initial values.
+    /// * `config` - Simulation config: `t_n - t_0` is the time to expiry (in
+    ///   years), and its `n_steps` the barrier monitoring steps; `config.x_0`
+    ///   is not used.
+    /// * `rate` - The interest rate used to discount the payoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `process` does not have exactly two assets.
+    pub fn price_monte_carlo(
+        &self,
+        process: &MultiGeometricBrownianMotion,
+        x_0: &[f64],
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate {
+        assert_eq!(process.dimension(), 2);
+
+        let (n_steps, m_paths) = (config.n_steps, config.m_paths);
+
+        let out = process.euler_maruyama(x_0, config);
+        let df = (-rate * (config.t_n - config.t_0)).exp();
+
+        let (sum, sum_of_squares) = out.paths[0].iter().zip(&out.paths[1]).fold(
+            (0.0, 0.0),
+            |(sum, sum_of_squares), (monitor, asset)| {
+                let value = df * self.payoff((monitor.clone(), asset[n_steps]));
+
+                (sum + value, sum_of_squares + value * value)
+            },
+        );
+
+        MonteCarloEstimate::from_sums(sum, sum_of_squares, m_paths)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_two_asset_barrier {
+    use super::*;
+    use crate::instruments::options::{ExerciseFlag, OptionContractBuilder};
+    use crate::math::distributions::{Distribution, Gaussian};
+    use nalgebra::DMatrix;
+
+    fn up_and_out_call(barrier: f64, strike: f64) -> TwoAssetBarrierOption {
+        TwoAssetBarrierOption {
+            contract: OptionContractBuilder::default()
+                .type_flag(TypeFlag::Call)
+                .exercise_flag(ExerciseFlag::European {
+                    expiry: time::macros::date!(2025 - 01 - 01),
+                })
+                .build()
+                .unwrap(),
+            barrier_type: BarrierType::UpAndOut,
+            barrier,
+            strike,
+        }
+    }
+
+    #[test]
+    fn test_two_asset_barrier_limits() {
+        let (r, t, k): (f64, f64, f64) = (0.05, 1.0, 100.0);
+        let (s_1, s_2, sigma_1, sigma_2) = (100.0, 100.0, 0.2, 0.3);
+
+        let process = MultiGeometricBrownianMotion::new(
+            vec![r, r],
+            vec![sigma_1, sigma_2],
+            DMatrix::identity(2, 2),
+        )
+        .unwrap();
+
+        // Black-Scholes call on the payoff asset alone.
+        let n = Gaussian::default();
+        let d1 = ((s_2 / k).ln() + (r + 0.5 * sigma_2 * sigma_2) * t) / (sigma_2 * t.sqrt());
+        let d2 = d1 - sigma_2 * t.sqrt();
+        let vanilla = s_2 * n.cdf(d1) - k * (-r * t).exp() * n.cdf(d2);
+
+        let config = StochasticProcessConfig::new(s_1, 0.0, t, 50, 20_000, true).with_seed(11);
+        let price = |barrier| {
+            up_and_out_call(barrier, k).price_monte_carlo(&process, &[s_1, s_2], &config, r)
+        };
+
+        // A distant barrier is never hit.
+        let distant = price(1e6);
+        assert!((distant.value - vanilla).abs() < 4.0 * distant.standard_error);
+
+        // A near barrier knocks out most paths.
+        let near = price(110.0);
+        assert!(near.value < 0.6 * vanilla);
+    }
+}
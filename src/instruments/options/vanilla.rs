@@ -7,8 +7,25 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::{OptionContract, TypeFlag};
+use super::{
+    AmericanVanillaOption, BlackScholesMerton, EngineChoice, ExerciseFlag, OptionContract, TypeFlag,
+};
 use crate::instruments::Payoff;
+use crate::math::brent::Brent;
+use crate::math::distributions::{Distribution, Gaussian};
+use crate::math::rootfinder::{Rootfinder, RootfinderData};
+use crate::models::GeometricBrownianMotion;
+use crate::pricer::{MonteCarloEstimate, StochasticMesh};
+use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+use crate::time::DayCountConvention;
+use time::Date;
+
+// Settings of the engines in `VanillaOption::price`.
+const BINOMIAL_STEPS: usize = 1000;
+const LSM_EXERCISE_DATES: usize = 50;
+const LSM_REGRESSION_PATHS: usize = 20_000;
+const LSM_PATHS: usize = 50_000;
+const LSM_SEED: u64 = 42;
 
 /// Vanilla option.
 #[derive(Debug, Clone)]
@@ -20,6 +37,46 @@ pub struct VanillaOption {
     pub strike: f64,
 }
 
+/// Market and simulation inputs for reconciling a vanilla option's
+/// Monte Carlo price against its analytic (Black-Scholes) price.
+pub struct VanillaMarket {
+    /// Risk-free rate, used for discounting and the analytic price.
+    pub risk_free_rate: f64,
+
+    /// Simulated underlying. This is only risk-neutral (and so consistent
+    /// with the analytic price) if its drift is the risk-free rate.
+    /// The volatility is assumed constant.
+    pub process: GeometricBrownianMotion,
+
+    /// Simulation config: `x_0` is the underlying price, and `t_n - t_0`
+    /// the time to expiry (in years).
+    pub config: StochasticProcessConfig,
+}
+
+impl VanillaMarket {
+    /// Black-Scholes price and spot delta of an option on the market's
+    /// underlying, at the given strike and volatility.
+    ///
+    /// The time to expiry is the simulation horizon `t_n - t_0`, rather than
+    /// a year fraction between dates, so the formulas are kept here instead
+    /// of going through [`BlackScholesMerton`].
+    fn black_scholes(&self, type_flag: TypeFlag, strike: f64, volatility: f64) -> (f64, f64) {
+        let config = &self.config;
+        let (s, k, r, v) = (config.x_0, strike, self.risk_free_rate, volatility);
+        let t = config.t_n - config.t_0;
+        let df = (-r * t).exp();
+
+        let n = Gaussian::default();
+        let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * t.sqrt());
+        let d2 = d1 - v * t.sqrt();
+
+        match type_flag {
+            TypeFlag::Call => (s * n.cdf(d1) - k * df * n.cdf(d2), n.cdf(d1)),
+            TypeFlag::Put => (k * df * n.cdf(-d2) - s * n.cdf(-d1), n.cdf(d1) - 1.0),
+        }
+    }
+}
+
 impl Payoff for VanillaOption {
     type Underlying = f64;
 
@@ -36,11 +93,242 @@ impl VanillaOption {
     pub fn new(contract: OptionContract, strike: f64) -> Self {
         Self { contract, strike }
     }
+
+    /// Price the option with the engine its contract calls for (see
+    /// [`OptionContract::recommended_engine`]), with Black-Scholes dynamics
+    /// for the underlying:
+    /// - [`EngineChoice::Analytic`] uses the Black-Scholes-Merton formula,
+    /// - [`EngineChoice::Binomial`] uses a Cox-Ross-Rubinstein tree of 1000
+    ///   steps, as [`AmericanVanillaOption::price_binomial`],
+    /// - [`EngineChoice::LeastSquaresMonteCarlo`] uses the (seeded)
+    ///   Longstaff-Schwartz estimate of a [`StochasticMesh`] over 50,000
+    ///   paths, exercisable on the exercise dates after the valuation date
+    ///   (or 50 equally spaced dates for American exercise).
+    ///
+    /// # Arguments:
+    ///
+    /// * `valuation_date` - The date of the price.
+    /// * `spot` - The underlying asset price.
+    /// * `rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The continuous dividend yield.
+    /// * `volatility` - The underlying asset's volatility.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option has expired.
+    #[must_use]
+    pub fn price(
+        &self,
+        valuation_date: Date,
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> f64 {
+        let expiry = match &self.contract.exercise_flag {
+            ExerciseFlag::European { expiry } => *expiry,
+            ExerciseFlag::American { end, .. } => *end,
+            ExerciseFlag::Bermudan { exercise_dates } => *exercise_dates
+                .iter()
+                .max()
+                .expect("A Bermudan option needs exercise dates."),
+        };
+        assert!(valuation_date < expiry, "The option has expired.");
+
+        let year_fraction =
+            |date: Date| DayCountConvention::default().day_count_factor(valuation_date, date);
+
+        match self.contract.recommended_engine() {
+            EngineChoice::Analytic => BlackScholesMerton::new(
+                rate - dividend_yield,
+                spot,
+                self.strike,
+                volatility,
+                rate,
+                Some(valuation_date),
+                expiry,
+                self.contract.type_flag,
+            )
+            .price(),
+            EngineChoice::Binomial => {
+                AmericanVanillaOption::new(self.contract.clone(), self.strike, Some(valuation_date))
+                    .price_binomial(spot, rate, dividend_yield, volatility, BINOMIAL_STEPS)
+            }
+            EngineChoice::LeastSquaresMonteCarlo => {
+                let exercise_times: Vec<f64> = match &self.contract.exercise_flag {
+                    ExerciseFlag::American { .. } => {
+                        let t = year_fraction(expiry);
+
+                        (1..=LSM_EXERCISE_DATES)
+                            .map(|i| t * i as f64 / LSM_EXERCISE_DATES as f64)
+                            .collect()
+                    }
+                    ExerciseFlag::European { .. } => vec![year_fraction(expiry)],
+                    ExerciseFlag::Bermudan { exercise_dates } => {
+                        let mut dates: Vec<Date> = exercise_dates
+                            .iter()
+                            .copied()
+                            .filter(|&date| date > valuation_date)
+                            .collect();
+                        dates.sort();
+                        dates.dedup();
+
+                        dates.into_iter().map(year_fraction).collect()
+                    }
+                };
+
+                StochasticMesh::new(
+                    GeometricBrownianMotion::new(rate - dividend_yield, volatility),
+                    vec![spot],
+                    exercise_times,
+                    rate,
+                    1,
+                )
+                .with_seed(LSM_SEED)
+                .low_estimate(
+                    &|state: &[f64]| self.payoff(state[0]),
+                    LSM_REGRESSION_PATHS,
+                    LSM_PATHS,
+                )
+                .value
+            }
+        }
+    }
+
+    /// Check the Monte Carlo price of a European option against the
+    /// analytic Black-Scholes price.
+    ///
+    /// Returns the discrepancy (Monte Carlo minus analytic price) if it is
+    /// more than `tolerance` Monte Carlo standard errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option is not European.
+    pub fn reconcile_monte_carlo(&self, market: &VanillaMarket, tolerance: f64) -> Result<(), f64> {
+        assert!(
+            matches!(self.contract.exercise_flag, ExerciseFlag::European { .. }),
+            "Only European options have an analytic price."
+        );
+
+        let config = &market.config;
+        let v = market.process.sigma.0(config.t_0);
+        let df = (-market.risk_free_rate * (config.t_n - config.t_0)).exp();
+
+        let (analytic, _) = market.black_scholes(self.contract.type_flag, self.strike, v);
+
+        let (sum, sum_of_squares) = market
+            .process
+            .simulate_terminal(config)
+            .into_iter()
+            .map(|x| df * self.payoff(x))
+            .fold((0.0, 0.0), |(sum, sum_of_squares), value| {
+                (sum + value, sum_of_squares + value * value)
+            });
+
+        let estimate = MonteCarloEstimate::from_sums(sum, sum_of_squares, config.m_paths);
+        let discrepancy = estimate.value - analytic;
+
+        if discrepancy.abs() <= tolerance * estimate.standard_error {
+            Ok(())
+        } else {
+            Err(discrepancy)
+        }
+    }
+
+    /// Strike at which the option has the given Black(-Scholes) spot delta,
+    /// as used for quoting by delta.
+    ///
+    /// The market is read as in [`VanillaOption::reconcile_monte_carlo`]:
+    /// `config.x_0` is the spot, `t_n - t_0` the time to expiry, and the
+    /// process volatility at `t_0` the Black volatility.
+    ///
+    /// Delta decreases monotonically in the strike from 1 to 0 for calls,
+    /// and from 0 to -1 for puts, so the target must lie in the option's
+    /// own region. Within it the delta is inverted by Brent's method in
+    /// the log-strike, starting from the forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target delta is not in (0, 1) for a call, or in (-1, 0)
+    /// for a put.
+    pub fn strike_for_delta(&self, target_delta: f64, market: &VanillaMarket) -> f64 {
+        let config = &market.config;
+        let v = market.process.sigma.0(config.t_0);
+
+        strike_for_delta(self.contract.type_flag, target_delta, market, |_| v)
+    }
+}
+
+/// Strike at which an option of the given type has the given Black-Scholes
+/// spot delta, when the volatility at strike $K$ is `volatility(K)`.
+///
+/// See [`VanillaOption::strike_for_delta`]. The search is bracketed using
+/// the volatility at the forward.
+///
+/// # Panics
+///
+/// Panics if the target delta is not in (0, 1) for a call, or in (-1, 0)
+/// for a put.
+pub(crate) fn strike_for_delta<F>(
+    type_flag: TypeFlag,
+    target_delta: f64,
+    market: &VanillaMarket,
+    volatility: F,
+) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let (lower, upper) = match type_flag {
+        TypeFlag::Call => (0.0, 1.0),
+        TypeFlag::Put => (-1.0, 0.0),
+    };
+    assert!(
+        lower < target_delta && target_delta < upper,
+        "Target delta must be in ({lower}, {upper})."
+    );
+
+    let config = &market.config;
+    let t = config.t_n - config.t_0;
+    let ln_forward = config.x_0.ln() + market.risk_free_rate * t;
+    let v = volatility(ln_forward.exp());
+
+    // Deltas this far out (|d1| of about 40) are zero or one in f64.
+    let width = 40.0 * v * t.sqrt();
+    let data = RootfinderData::new(
+        1e-12,
+        0.1 * v * t.sqrt(),
+        ln_forward - width,
+        ln_forward + width,
+        true,
+    );
+
+    let mut solver = Brent::new(
+        |ln_strike: f64| {
+            let strike = ln_strike.exp();
+
+            black_delta(type_flag, strike, volatility(strike), market) - target_delta
+        },
+        ln_forward,
+        data,
+    );
+
+    solver.solve().exp()
+}
+
+/// Black-Scholes spot delta at the given strike and volatility.
+pub(crate) fn black_delta(
+    type_flag: TypeFlag,
+    strike: f64,
+    volatility: f64,
+    market: &VanillaMarket,
+) -> f64 {
+    market.black_scholes(type_flag, strike, volatility).1
 }
 
 #[cfg(test)]
 mod test_vanilla_option_monte_carlo {
     use super::*;
+    use crate::assert_approx_equal;
     use crate::instruments::AsianOption;
     use crate::instruments::AveragingMethod;
     use crate::instruments::StrikeFlag;
@@ -73,13 +361,226 @@ mod test_vanilla_option_monte_carlo {
         let process = GeometricBrownianMotion::new(interest_rate, volatility);
 
         let config =
-            StochasticProcessConfig::new(underlying, 0.0, time_to_maturity, 1, 1_000_000, true);
+            StochasticProcessConfig::new(underlying, 0.0, time_to_maturity, 1, 1_000_000, true)
+                .with_seed(1);
 
         let start = Instant::now();
         let price = option.price_monte_carlo(&process, &config, interest_rate);
         println!("Elapsed time: {:?}", start.elapsed());
 
         println!("Price: {}", price);
+
+        // The paths are streamed, so the serial price is the same.
+        let serial = StochasticProcessConfig {
+            parallel: false,
+            ..config
+        };
+
+        assert_eq!(
+            price,
+            option.price_monte_carlo(&process, &serial, interest_rate)
+        );
+    }
+
+    #[test]
+    fn test_vanilla_option_terminal_fast_path() {
+        use crate::instruments::options::BlackScholesMerton;
+
+        let (underlying, strike, interest_rate, volatility) = (100.0, 100.0, 0.05, 0.2);
+
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        let option = VanillaOption::new(contract, strike);
+        let process = GeometricBrownianMotion::new(interest_rate, volatility);
+
+        let config =
+            StochasticProcessConfig::new(underlying, 0.0, 1.0, 1, 1_000_000, true).with_seed(2);
+        let price = option.price_monte_carlo(&process, &config, interest_rate);
+
+        // A single exact step reproduces the Black-Scholes price.
+        let analytic = BlackScholesMerton::new(
+            interest_rate,
+            underlying,
+            strike,
+            volatility,
+            interest_rate,
+            Some(date!(2024 - 01 - 01)),
+            date!(2025 - 01 - 01),
+            TypeFlag::Call,
+        )
+        .price();
+
+        assert!((price - analytic).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_strike_for_delta() {
+        let (spot, rate, volatility, expiry) = (100.0, 0.03, 0.25, 0.5);
+
+        let market = VanillaMarket {
+            risk_free_rate: rate,
+            process: GeometricBrownianMotion::new(rate, volatility),
+            config: StochasticProcessConfig::new(spot, 0.0, expiry, 1, 1, false),
+        };
+        let option = |type_flag: TypeFlag| {
+            let contract = OptionContractBuilder::default()
+                .type_flag(type_flag)
+                .exercise_flag(ExerciseFlag::European {
+                    expiry: date!(2025 - 01 - 01),
+                })
+                .build()
+                .unwrap();
+
+            VanillaOption::new(contract, 100.0)
+        };
+
+        let (call, put) = (option(TypeFlag::Call), option(TypeFlag::Put));
+
+        for target in [0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95] {
+            let strike = call.strike_for_delta(target, &market);
+            assert!(
+                (black_delta(TypeFlag::Call, strike, volatility, &market) - target).abs() < 1e-10
+            );
+
+            let strike = put.strike_for_delta(-target, &market);
+            assert!(
+                (black_delta(TypeFlag::Put, strike, volatility, &market) + target).abs() < 1e-10
+            );
+        }
+
+        // Deltas fall with the strike: a 25-delta call is above the 25-delta put.
+        assert!(call.strike_for_delta(0.25, &market) > put.strike_for_delta(-0.25, &market));
+
+        // The 50-delta call is at the forward, up to the convexity term:
+        // K = F exp(sigma^2 T / 2).
+        let forward = spot * (rate * expiry).exp();
+        let atm = call.strike_for_delta(0.5, &market);
+
+        assert!((atm / forward - 1.0).abs() < volatility * volatility * expiry);
+        assert!((atm - forward * (0.5 * volatility * volatility * expiry).exp()).abs() < 1e-8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Target delta must be in")]
+    fn test_strike_for_delta_outside_region() {
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Put)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+        let market = VanillaMarket {
+            risk_free_rate: 0.03,
+            process: GeometricBrownianMotion::new(0.03, 0.2),
+            config: StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 1, false),
+        };
+
+        // A put's delta is negative.
+        VanillaOption::new(contract, 100.0).strike_for_delta(0.25, &market);
+    }
+
+    #[test]
+    fn test_reconcile_monte_carlo() {
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        let option = VanillaOption::new(contract, 100.0);
+
+        let market = |drift| VanillaMarket {
+            risk_free_rate: 0.05,
+            process: GeometricBrownianMotion::new(drift, 0.2),
+            config: StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 50_000, true).with_seed(3),
+        };
+
+        assert_eq!(option.reconcile_monte_carlo(&market(0.05), 4.0), Ok(()));
+
+        // Simulating with the wrong rate overprices the call.
+        let discrepancy = option
+            .reconcile_monte_carlo(&market(0.08), 4.0)
+            .unwrap_err();
+
+        assert!(discrepancy > 1.0, "{discrepancy}");
+    }
+
+    #[test]
+    fn test_price_dispatch() {
+        let (spot, rate, volatility) = (100.0, 0.05, 0.2);
+        let valuation_date = date!(2024 - 01 - 01);
+        let expiry = date!(2025 - 01 - 01);
+
+        let option = |type_flag, exercise_flag| {
+            VanillaOption::new(
+                OptionContractBuilder::default()
+                    .type_flag(type_flag)
+                    .exercise_flag(exercise_flag)
+                    .build()
+                    .unwrap(),
+                100.0,
+            )
+        };
+        let price =
+            |option: VanillaOption| option.price(valuation_date, spot, rate, 0.0, volatility);
+
+        let european = ExerciseFlag::European { expiry };
+        let american = ExerciseFlag::American {
+            start: date!(2023 - 06 - 01),
+            end: expiry,
+        };
+        let bermudan = ExerciseFlag::Bermudan {
+            exercise_dates: vec![
+                date!(2023 - 10 - 01),
+                date!(2024 - 04 - 01),
+                date!(2024 - 07 - 01),
+                date!(2024 - 10 - 01),
+                expiry,
+            ],
+        };
+
+        // European options get the Black-Scholes price.
+        let european_call = price(option(TypeFlag::Call, european.clone()));
+        let european_put = price(option(TypeFlag::Put, european));
+        let analytic = BlackScholesMerton::new(
+            rate,
+            spot,
+            100.0,
+            volatility,
+            rate,
+            Some(valuation_date),
+            expiry,
+            TypeFlag::Call,
+        )
+        .price();
+
+        assert_eq!(european_call, analytic);
+        assert_approx_equal!(european_call, 10.450_583_572_185_565, 1e-10);
+
+        // Early exercise is worth nothing for a call without dividends, and
+        // something for a put (whose American price is about 6.09).
+        let american_call = price(option(TypeFlag::Call, american.clone()));
+        let american_put = price(option(TypeFlag::Put, american));
+
+        assert_approx_equal!(american_call, european_call, 1e-2);
+        assert_approx_equal!(american_put, 6.09, 1e-2);
+
+        // Exercising quarterly is worth less than at any time.
+        let bermudan_put = price(option(TypeFlag::Put, bermudan));
+
+        assert!(
+            european_put < bermudan_put && bermudan_put < american_put,
+            "{european_put} {bermudan_put} {american_put}"
+        );
     }
 
     #[test]
@@ -0,0 +1,293 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Overnight index futures (e.g. 3M SOFR or ESTR futures).
+//!
+//! The contract settles at $100 (1 - R)$, where $R$ is the overnight rate
+//! compounded over the reference period $[S, E)$:
+//!
+//! $$
+//! R = \frac{1}{\tau(S, E)} \left( \prod_i (1 + r_i \tau_i) - 1 \right)
+//! $$
+//!
+//! The rate $r_i$ is fixed on each business day $d_i$ of the period, and
+//! accrues until the next business day (or $E$), so a Friday fixing
+//! accrues over the weekend.
+//!
+//! Fixings not yet published are projected off the curve: from the first
+//! missing fixing date $d_k$ to $E$ the compounded growth is
+//! $P(d_k) / P(E)$. No convexity adjustment is applied.
+
+use crate::data::CurveModel;
+use crate::instruments::Instrument;
+use crate::time::{today, Calendar, DayCountConvention};
+use std::collections::BTreeMap;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Future on an overnight rate compounded over a reference period.
+pub struct OvernightIndexFuture<C: Calendar, M: CurveModel> {
+    /// Start of the reference period (first fixing date).
+    pub reference_start: Date,
+
+    /// End of the reference period (exclusive).
+    pub reference_end: Date,
+
+    /// Published fixings, by fixing date, as decimals.
+    pub fixings: BTreeMap<Date, f64>,
+
+    /// Fixing calendar of the index.
+    pub calendar: C,
+
+    /// Day count convention of the index (Actual/360 for SOFR and ESTR).
+    pub day_count_convention: DayCountConvention,
+
+    /// Curve used to project the fixings not yet published.
+    pub curve: M,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<C: Calendar, M: CurveModel> OvernightIndexFuture<C, M> {
+    /// New overnight index future, valued today, on Actual/360.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reference_start` is not before `reference_end`.
+    pub fn new(
+        reference_start: Date,
+        reference_end: Date,
+        fixings: BTreeMap<Date, f64>,
+        calendar: C,
+        curve: M,
+    ) -> Self {
+        assert!(reference_start < reference_end);
+
+        Self {
+            reference_start,
+            reference_end,
+            fixings,
+            calendar,
+            day_count_convention: DayCountConvention::Actual_360,
+            curve,
+            evaluation_date: None,
+        }
+    }
+
+    /// Fixing dates of the reference period, with the accrual period of each.
+    pub fn fixing_periods(&self) -> Vec<(Date, f64)> {
+        let last = self.reference_end.previous_day().unwrap();
+        let dates = self
+            .calendar
+            .all_business_days_between(self.reference_start, last);
+
+        dates
+            .iter()
+            .enumerate()
+            .map(|(i, &date)| {
+                let next = dates.get(i + 1).copied().unwrap_or(self.reference_end);
+
+                (date, self.day_count_convention.day_count_factor(date, next))
+            })
+            .collect()
+    }
+
+    /// Compounded rate over the reference period, as a decimal.
+    pub fn compounded_rate(&self) -> f64 {
+        self.compounded_rate_at(self.valuation_date())
+    }
+
+    /// Compounded rate, with the fixings missing as of `valuation_date`
+    /// projected off the curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fixing before `valuation_date` has not been supplied.
+    fn compounded_rate_at(&self, valuation_date: Date) -> f64 {
+        let mut growth = 1.0;
+
+        for (date, accrual) in self.fixing_periods() {
+            match self.fixings.get(&date) {
+                Some(rate) => growth *= 1.0 + rate * accrual,
+                None => {
+                    assert!(date >= valuation_date, "Missing fixing for {date}.");
+
+                    growth *= self.curve.discount_factor(date)
+                        / self.curve.discount_factor(self.reference_end);
+                    break;
+                }
+            }
+        }
+
+        let tau = self
+            .day_count_convention
+            .day_count_factor(self.reference_start, self.reference_end);
+
+        (growth - 1.0) / tau
+    }
+
+    /// Futures price as of `valuation_date`.
+    fn price_at(&self, valuation_date: Date) -> f64 {
+        100.0 * (1.0 - self.compounded_rate_at(valuation_date))
+    }
+}
+
+impl<C: Calendar, M: CurveModel> Instrument for OvernightIndexFuture<C, M> {
+    /// Futures price, $100 (1 - R)$.
+    fn price(&self) -> f64 {
+        self.price_at(self.valuation_date())
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "OvernightIndexFuture"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.reference_end)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(self.price_at(valuation_date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_overnight_index_future {
+    use super::*;
+    use crate::assert_approx_equal;
+    use crate::time::north_america::united_states::UnitedStatesCalendar;
+    use time::macros::date;
+
+    // March 2024 IMM quarter: 13 weeks, with Memorial Day (27 May) inside.
+    const START: Date = date!(2024 - 03 - 20);
+    const END: Date = date!(2024 - 06 - 19);
+
+    /// Flat, continuously compounded rate on Actual/360.
+    struct FlatCurve(f64);
+
+    impl CurveModel for FlatCurve {
+        fn forward_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn spot_rate(&self, _date: Date) -> f64 {
+            self.0
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            (-self.0 * DayCountConvention::Actual_360.day_count_factor(START, date)).exp()
+        }
+    }
+
+    fn future(
+        fixings: BTreeMap<Date, f64>,
+    ) -> OvernightIndexFuture<UnitedStatesCalendar, FlatCurve> {
+        OvernightIndexFuture::new(START, END, fixings, UnitedStatesCalendar, FlatCurve(0.05))
+    }
+
+    #[test]
+    fn test_settlement_from_fixings() {
+        // 5.31% up to the end of April, 5.33% after.
+        let switch = date!(2024 - 05 - 01);
+        let rate = |date: Date| if date < switch { 0.0531 } else { 0.0533 };
+
+        let fixings = UnitedStatesCalendar
+            .all_business_days_between(START, END.previous_day().unwrap())
+            .into_iter()
+            .map(|date| (date, rate(date)))
+            .collect();
+
+        let mut future = future(fixings);
+        future.evaluation_date = Some(END);
+
+        // Before May: 6 weeks, each of four 1-day and one 3-day (Friday)
+        // periods. From May: 7 weeks, less Memorial Day, whose Friday
+        // fixing accrues over 4 days.
+        let (a, b): (f64, f64) = (0.0531 / 360.0, 0.0533 / 360.0);
+        let growth = (1.0 + a).powi(24)
+            * (1.0 + 3.0 * a).powi(6)
+            * (1.0 + b).powi(27)
+            * (1.0 + 3.0 * b).powi(6)
+            * (1.0 + 4.0 * b);
+        let compounded = (growth - 1.0) * 360.0 / 91.0;
+
+        assert_eq!(future.fixing_periods().len(), 64);
+        assert_approx_equal!(future.compounded_rate(), compounded, 1e-13);
+        assert_approx_equal!(future.price(), 100.0 * (1.0 - compounded), 1e-12);
+    }
+
+    #[test]
+    fn test_projection_of_missing_fixings() {
+        // Before the period, the whole rate is projected off the curve.
+        let mut future = future(BTreeMap::new());
+        future.evaluation_date = Some(date!(2024 - 03 - 01));
+
+        let growth = (0.05_f64 * 91.0 / 360.0).exp();
+        assert_approx_equal!(
+            future.compounded_rate(),
+            (growth - 1.0) * 360.0 / 91.0,
+            1e-14
+        );
+
+        // Halfway through, the published fixings are compounded with the
+        // projected growth from the first missing fixing date.
+        let valuation = date!(2024 - 05 - 06);
+        let fixings: BTreeMap<Date, f64> = future
+            .fixing_periods()
+            .into_iter()
+            .filter(|(date, _)| *date < valuation)
+            .map(|(date, _)| (date, 0.06))
+            .collect();
+        future.fixings = fixings.clone();
+        future.evaluation_date = Some(valuation);
+
+        let fixed: f64 = future
+            .fixing_periods()
+            .iter()
+            .filter(|(date, _)| fixings.contains_key(date))
+            .map(|(_, accrual)| 1.0 + 0.06 * accrual)
+            .product();
+        let projected = (0.05_f64 * 44.0 / 360.0).exp();
+
+        assert_approx_equal!(
+            future.compounded_rate(),
+            (fixed * projected - 1.0) * 360.0 / 91.0,
+            1e-14
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing fixing")]
+    fn test_missing_past_fixing() {
+        let mut future = future(BTreeMap::new());
+        future.evaluation_date = Some(date!(2024 - 04 - 01));
+
+        future.compounded_rate();
+    }
+}
@@ -0,0 +1,528 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Overnight index swaps, receiving a fixed rate against the compounded
+//! overnight rate (pass a negative notional to pay fixed).
+//!
+//! Fixed payment dates are rolled backwards from maturity in steps of
+//! `12 / frequency` months (clamped to month end), so an irregular period,
+//! if any, is the first one. The overnight leg compounded over $[S, E]$ is
+//! worth $P(S) - P(E)$, so from a date $t$ the clean value of the swap is
+//!
+//! $$
+//! V(t) = N \left( K A(t) - \frac{P(\max(S, t)) - P(E)}{P(t)} \right)
+//! $$
+//!
+//! where $A(t)$ is the annuity of the fixed periods, each accruing from the
+//! later of its start and $t$. Both legs' accruals before $t$ are excluded.
+
+use crate::data::CurveModel;
+use crate::instruments::bonds::bond_future::ShiftedCurve;
+use crate::instruments::bonds::fixed_rate_bond::shift_months;
+use crate::instruments::{CarryRollDown, CashflowReport, CashflowRow, Instrument, RolledCurve};
+use crate::time::{today, DateRollingConvention, DayCountConvention, Frequency, Schedule};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fixed-for-overnight swap, receiving the fixed leg.
+#[derive(Debug, Clone)]
+pub struct OvernightIndexSwap<M: CurveModel> {
+    /// Notional (negative to pay fixed).
+    pub notional: f64,
+
+    /// Fixed rate, as a decimal.
+    pub fixed_rate: f64,
+
+    /// Fixed leg payment frequency.
+    pub fixed_frequency: Frequency,
+
+    /// Start date (of the first accrual period on both legs).
+    pub start_date: Date,
+
+    /// Maturity date (last payment on both legs).
+    pub maturity_date: Date,
+
+    /// Day count convention of the fixed leg.
+    pub day_count_convention: DayCountConvention,
+
+    /// Curve used to discount the cash flows and project the overnight rate.
+    pub curve: M,
+
+    /// Valuation date (defaults to today).
+    pub evaluation_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<M: CurveModel> OvernightIndexSwap<M> {
+    /// New overnight index swap, valued today, with the fixed leg on
+    /// Actual/360.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_date` is not before `maturity_date`, or if the
+    /// payment period is not a whole number of months.
+    pub fn new(
+        notional: f64,
+        fixed_rate: f64,
+        fixed_frequency: Frequency,
+        start_date: Date,
+        maturity_date: Date,
+        curve: M,
+    ) -> Self {
+        assert!(start_date < maturity_date);
+        assert!(
+            fixed_frequency != Frequency::Zero && 12 % (fixed_frequency as i64) == 0,
+            "Payment period must be a whole number of months."
+        );
+
+        Self {
+            notional,
+            fixed_rate,
+            fixed_frequency,
+            start_date,
+            maturity_date,
+            day_count_convention: DayCountConvention::Actual_360,
+            curve,
+            evaluation_date: None,
+        }
+    }
+
+    /// Fixed leg payment dates, from the first to maturity.
+    pub fn payment_dates(&self) -> Vec<Date> {
+        let months = 12 / self.fixed_frequency as i32;
+
+        let mut dates: Vec<Date> = (0..)
+            .map(|i| shift_months(self.maturity_date, -i * months))
+            .take_while(|&date| date > self.start_date)
+            .collect();
+
+        dates.reverse();
+        dates
+    }
+
+    /// Annuity (PV01 per unit notional and unit rate) of the fixed leg.
+    pub fn annuity(&self) -> f64 {
+        self.annuity_at(self.valuation_date())
+    }
+
+    /// Fixed rate at which the swap is worth zero.
+    pub fn par_rate(&self) -> f64 {
+        let date = self.valuation_date();
+
+        self.overnight_leg_at(date) / self.annuity_at(date)
+    }
+
+    /// Carry and roll-down from the valuation date to `horizon_date`, with
+    /// the overnight rate fixed at `funding_rate` over the horizon (simple,
+    /// on Actual/360).
+    ///
+    /// The carry is the fixed leg accrued less the overnight leg accrued, and
+    /// the roll-down is the change in clean value
+    /// (see [`crate::instruments::carry_roll_down`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `horizon_date` is not after the valuation date and before
+    /// maturity.
+    pub fn carry_roll_down(&self, horizon_date: Date, funding_rate: f64) -> CarryRollDown {
+        const BP: f64 = 1e-4;
+
+        let valuation_date = self.valuation_date();
+        assert!(valuation_date < horizon_date && horizon_date < self.maturity_date);
+
+        let accrual_start = valuation_date.max(self.start_date);
+        let overnight_accrual = if accrual_start < horizon_date {
+            DayCountConvention::Actual_360.day_count_factor(accrual_start, horizon_date)
+        } else {
+            0.0
+        };
+
+        let carry = self.notional
+            * (self.fixed_rate * self.fixed_accrual(valuation_date, horizon_date)
+                - funding_rate * overnight_accrual);
+
+        let rolled_curve = RolledCurve {
+            curve: &self.curve,
+            roll: horizon_date - valuation_date,
+        };
+        let rolled = self.with_curve(rolled_curve, horizon_date);
+
+        let roll_down = rolled.price() - self.price();
+
+        let shifted_value = |shift| {
+            let curve = ShiftedCurve {
+                curve: &rolled.curve,
+                anchor: horizon_date,
+                shift,
+            };
+
+            self.with_curve(curve, horizon_date).price()
+        };
+
+        let risk = (shifted_value(-BP) - shifted_value(BP)) / (2.0 * BP);
+
+        CarryRollDown::new(valuation_date, horizon_date, carry, roll_down, risk)
+    }
+
+    /// All the cash flows of the swap, each fixed payment followed by the
+    /// overnight payment for the same period, with their present values as
+    /// of the valuation date.
+    ///
+    /// The overnight rates are projected off the curve. Since the swap is
+    /// valued clean, the period running over the valuation date accrues
+    /// from the valuation date on both legs.
+    pub fn cashflow_report(&self) -> CashflowReport {
+        let valuation_date = self.valuation_date();
+        let valuation_discount_factor = self.curve.discount_factor(valuation_date);
+
+        let mut rows = Vec::new();
+
+        for (start, end) in self.periods() {
+            let is_past = end <= valuation_date;
+            let accrual_start = if is_past {
+                start
+            } else {
+                start.max(valuation_date)
+            };
+            let day_count_fraction = self
+                .day_count_convention
+                .day_count_factor(accrual_start, end);
+            let discount_factor =
+                (!is_past).then(|| self.curve.discount_factor(end) / valuation_discount_factor);
+
+            // Growth of the overnight rate compounded over the period.
+            let growth =
+                self.curve.discount_factor(accrual_start) / self.curve.discount_factor(end);
+
+            let row = |rate, index, amount: f64| CashflowRow {
+                payment_date: end,
+                accrual_start,
+                accrual_end: end,
+                day_count_fraction,
+                rate: Some(rate),
+                index,
+                amount,
+                discount_factor,
+                present_value: discount_factor.map_or(0.0, |df| amount * df),
+                is_past,
+            };
+
+            rows.push(row(
+                self.fixed_rate,
+                None,
+                self.notional * self.fixed_rate * day_count_fraction,
+            ));
+            rows.push(row(
+                (growth - 1.0) / day_count_fraction,
+                Some(String::from("Overnight rate, projected off the curve")),
+                -self.notional * (growth - 1.0),
+            ));
+        }
+
+        CashflowReport {
+            valuation_date,
+            rows,
+        }
+    }
+
+    /// Fixed periods, as `(accrual start, payment date)`.
+    fn periods(&self) -> Vec<(Date, Date)> {
+        let dates = self.payment_dates();
+
+        let starts = std::iter::once(self.start_date).chain(dates.iter().copied());
+
+        starts.zip(dates.iter().copied()).collect()
+    }
+
+    /// Day count fraction of the fixed leg accrued between `from` and `to`.
+    fn fixed_accrual(&self, from: Date, to: Date) -> f64 {
+        self.periods()
+            .into_iter()
+            .map(|(start, end)| (start.max(from), end.min(to)))
+            .filter(|(start, end)| start < end)
+            .map(|(start, end)| self.day_count_convention.day_count_factor(start, end))
+            .sum()
+    }
+
+    /// Annuity of the fixed periods remaining at `date`, each accruing from
+    /// the later of its start and `date`, discounted to `date`.
+    fn annuity_at(&self, date: Date) -> f64 {
+        let remaining: Vec<(Date, Date)> = self
+            .periods()
+            .into_iter()
+            .filter(|&(_, end)| end > date)
+            .collect();
+
+        let Some(&(first_start, _)) = remaining.first() else {
+            return 0.0;
+        };
+
+        let dates: Vec<Date> = std::iter::once(first_start.max(date))
+            .chain(remaining.iter().map(|&(_, end)| end))
+            .collect();
+
+        let schedule = Schedule {
+            day_count_factors: dates
+                .windows(2)
+                .map(|period| {
+                    self.day_count_convention
+                        .day_count_factor(period[0], period[1])
+                })
+                .collect(),
+            dates,
+            day_counting_convention: self.day_count_convention,
+            date_rolling_convention: DateRollingConvention::Actual,
+        };
+
+        self.curve.annuity(&schedule, self.day_count_convention) / self.curve.discount_factor(date)
+    }
+
+    /// Value at `date`, per unit notional, of the overnight leg from `date`.
+    fn overnight_leg_at(&self, date: Date) -> f64 {
+        let start = self.start_date.max(date);
+
+        (self.curve.discount_factor(start) - self.curve.discount_factor(self.maturity_date))
+            / self.curve.discount_factor(date)
+    }
+
+    /// Clean value of the swap as of `valuation_date`.
+    fn price_at(&self, valuation_date: Date) -> f64 {
+        self.notional
+            * (self.fixed_rate * self.annuity_at(valuation_date)
+                - self.overnight_leg_at(valuation_date))
+    }
+
+    /// Copy of the swap on `curve`, valued at `evaluation_date`.
+    fn with_curve<N: CurveModel>(&self, curve: N, evaluation_date: Date) -> OvernightIndexSwap<N> {
+        OvernightIndexSwap {
+            notional: self.notional,
+            fixed_rate: self.fixed_rate,
+            fixed_frequency: self.fixed_frequency,
+            start_date: self.start_date,
+            maturity_date: self.maturity_date,
+            day_count_convention: self.day_count_convention,
+            curve,
+            evaluation_date: Some(evaluation_date),
+        }
+    }
+}
+
+impl<M: CurveModel> Instrument for OvernightIndexSwap<M> {
+    /// Clean value of the swap.
+    fn price(&self) -> f64 {
+        self.price_at(self.valuation_date())
+    }
+
+    fn error(&self) -> Option<f64> {
+        None
+    }
+
+    fn valuation_date(&self) -> Date {
+        self.evaluation_date.unwrap_or(today())
+    }
+
+    fn instrument_type(&self) -> &'static str {
+        "OvernightIndexSwap"
+    }
+
+    fn expiration_date(&self) -> Option<Date> {
+        Some(self.maturity_date)
+    }
+
+    fn price_as_of(&self, valuation_date: Date) -> Option<f64> {
+        Some(self.price_at(valuation_date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_overnight_index_swap {
+    use super::*;
+    use crate::assert_approx_equal;
+    use time::macros::date;
+
+    const START: Date = date!(2025 - 01 - 15);
+
+    /// Continuously compounded zero rates $a + b \tau$, on Actual/365 (Fixed).
+    struct LinearCurve {
+        level: f64,
+        slope: f64,
+    }
+
+    impl CurveModel for LinearCurve {
+        fn forward_rate(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(START, date);
+
+            self.level + 2.0 * self.slope * tau
+        }
+
+        fn spot_rate(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(START, date);
+
+            self.level + self.slope * tau
+        }
+
+        fn discount_factor(&self, date: Date) -> f64 {
+            let tau = DayCountConvention::Actual_365_Fixed.day_count_factor(START, date);
+
+            (-(self.level + self.slope * tau) * tau).exp()
+        }
+    }
+
+    /// Annual swap, valued at its start, at its par rate.
+    fn par_swap(maturity_date: Date, slope: f64) -> OvernightIndexSwap<LinearCurve> {
+        let curve = LinearCurve { level: 0.02, slope };
+
+        let mut swap =
+            OvernightIndexSwap::new(1e6, 0.0, Frequency::Annually, START, maturity_date, curve);
+        swap.evaluation_date = Some(START);
+        swap.fixed_rate = swap.par_rate();
+        swap
+    }
+
+    #[test]
+    fn test_par_swap() {
+        let swap = par_swap(date!(2030 - 01 - 15), 0.002);
+
+        assert_eq!(swap.payment_dates().len(), 5);
+        assert_approx_equal!(swap.price(), 0.0, 1e-8);
+
+        // The overnight leg telescopes to P(S) - P(E).
+        let annuity: f64 = swap
+            .payment_dates()
+            .iter()
+            .zip(std::iter::once(START).chain(swap.payment_dates()))
+            .map(|(&end, start)| {
+                DayCountConvention::Actual_360.day_count_factor(start, end)
+                    * swap.curve.discount_factor(end)
+            })
+            .sum();
+
+        assert_approx_equal!(swap.annuity(), annuity, 1e-12);
+        assert_approx_equal!(
+            swap.par_rate(),
+            (1.0 - swap.curve.discount_factor(swap.maturity_date)) / annuity,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_cashflow_report() {
+        let mut swap = par_swap(date!(2030 - 01 - 15), 0.002);
+        swap.fixed_rate += 0.001;
+
+        for evaluation_date in [START, date!(2026 - 01 - 15), date!(2027 - 06 - 01)] {
+            swap.evaluation_date = Some(evaluation_date);
+            let report = swap.cashflow_report();
+
+            // A fixed and an overnight payment for each of the 5 periods.
+            assert_eq!(report.rows.len(), 10);
+            assert_approx_equal!(report.present_value(), swap.price(), 1e-8);
+
+            for row in &report.rows {
+                assert_eq!(row.is_past, row.payment_date <= evaluation_date);
+            }
+        }
+
+        // Seasoned: the current period accrues from the valuation date.
+        let current = &swap.cashflow_report().rows[4];
+        assert_eq!(current.accrual_start, date!(2027 - 06 - 01));
+        assert_eq!(current.payment_date, date!(2028 - 01 - 15));
+        assert_eq!(current.rate, Some(swap.fixed_rate));
+        assert!(current.index.is_none() && swap.cashflow_report().rows[5].index.is_some());
+    }
+
+    #[test]
+    fn test_carry_roll_down() {
+        // Every period is 365 days, so rolling the 3Y swap forward by a year
+        // gives a 2Y swap today, with the same fixed rate.
+        let horizon_date = date!(2026 - 01 - 15);
+        let funding_rate = 0.015;
+
+        let swap = par_swap(date!(2028 - 01 - 15), 0.004);
+        let report = swap.carry_roll_down(horizon_date, funding_rate);
+
+        let mut rolled = par_swap(date!(2027 - 01 - 15), 0.004);
+        rolled.fixed_rate = swap.fixed_rate;
+
+        // Receiving fixed rolls down an upward sloping curve.
+        assert!(report.roll_down > 0.0);
+        assert_approx_equal!(report.roll_down, rolled.price() - swap.price(), 1e-6);
+
+        // The fixed rate against the overnight rate, over 365 days.
+        assert_approx_equal!(
+            report.carry,
+            1e6 * (swap.fixed_rate - funding_rate) * 365.0 / 360.0,
+            1e-6
+        );
+
+        // In yield terms, the roll-down is close to the difference in par rates.
+        let par_difference = swap.fixed_rate - rolled.par_rate();
+        assert!((report.roll_down_yield / par_difference - 1.0).abs() < 0.1);
+
+        // On a flat curve there is no roll-down.
+        let flat = par_swap(date!(2028 - 01 - 15), 0.0);
+        assert_approx_equal!(
+            flat.carry_roll_down(horizon_date, funding_rate).roll_down,
+            0.0,
+            1e-6
+        );
+    }
+
+    #[test]
+    fn test_carry_roll_down_horizon_pnl() {
+        let mut swap = par_swap(date!(2035 - 01 - 15), 0.002);
+
+        // Seasoned, and over a payment date.
+        let (valuation_date, horizon_date) = (date!(2025 - 09 - 01), date!(2026 - 04 - 20));
+        let funding_rate = 0.025;
+
+        swap.evaluation_date = Some(valuation_date);
+        let report = swap.carry_roll_down(horizon_date, funding_rate);
+
+        // Fixed accrual over the two periods spanned, less the overnight leg.
+        let tau = |from, to| DayCountConvention::Actual_360.day_count_factor(from, to);
+        let payment_date = date!(2026 - 01 - 15);
+
+        let accrued = swap.fixed_rate
+            * (tau(valuation_date, payment_date) + tau(payment_date, horizon_date))
+            - funding_rate * tau(valuation_date, horizon_date);
+
+        // Horizon P&L, with the curve rolled forward and shifted by `shift`.
+        let pnl = |shift| {
+            let rolled = RolledCurve {
+                curve: &swap.curve,
+                roll: horizon_date - valuation_date,
+            };
+            let shifted = ShiftedCurve {
+                curve: &rolled,
+                anchor: horizon_date,
+                shift,
+            };
+
+            swap.with_curve(shifted, horizon_date).price() + swap.notional * accrued - swap.price()
+        };
+
+        assert_approx_equal!(report.carry, swap.notional * accrued, 1e-6);
+        assert_approx_equal!(report.total(), pnl(0.0), 1e-6);
+
+        // Rates rising by the breakeven wipe out the P&L, up to convexity.
+        let breakeven = report.breakeven_yield_change();
+        assert!(pnl(breakeven).abs() < 0.02 * report.total().abs());
+    }
+}
@@ -35,6 +35,10 @@ pub use gamma::*;
 pub mod gaussian;
 pub use gaussian::*;
 
+/// Non-central chi-squared distribution.
+pub mod non_central_chi_squared;
+pub use non_central_chi_squared::*;
+
 /// Poisson distribution.
 pub mod poisson;
 pub use poisson::*;
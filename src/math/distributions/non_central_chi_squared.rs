@@ -0,0 +1,343 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::{
+    error::RustQuantError,
+    math::{distributions::Distribution, integrate},
+};
+use num::Complex;
+use rand::Rng;
+use statrs::function::gamma::{gamma_lr, ln_gamma};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Non-central Chi-Squared distribution: X ~ ChiSq(k, lambda)
+///
+/// The density and distribution functions are evaluated as
+/// Poisson($\lambda / 2$) mixtures of central Chi-Squared distributions.
+pub struct NonCentralChiSquared {
+    /// k: degrees of freedom (need not be an integer).
+    k: f64,
+
+    /// lambda: non-centrality parameter.
+    lambda: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl NonCentralChiSquared {
+    /// New instance of a non-central Chi-Squared distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::math::distributions::*;
+    ///
+    /// let chi = NonCentralChiSquared::new(2.5, 1.0);
+    ///
+    /// assert_eq!(chi.mean(), 3.5);
+    /// ```
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive or `lambda` is negative.
+    #[must_use]
+    pub fn new(k: f64, lambda: f64) -> Self {
+        assert!(k > 0.0);
+        assert!(lambda >= 0.0);
+
+        Self { k, lambda }
+    }
+
+    /// Draw a single variate using the given random number generator.
+    ///
+    /// For $k > 1$ this uses $X = (Z + \sqrt{\lambda})^2 + \chi^2_{k-1}$,
+    /// otherwise the Poisson mixture $X = \chi^2_{k + 2N}$, $N \sim Poisson(\lambda / 2)$.
+    pub fn sample_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // IMPORT HERE TO AVOID CLASH WITH
+        // `RustQuant::distributions::Distribution`
+        use rand_distr::{ChiSquared, Distribution, Poisson, StandardNormal};
+
+        if self.k > 1.0 {
+            let z: f64 = rng.sample(StandardNormal);
+            let chi = ChiSquared::new(self.k - 1.0).unwrap().sample(rng);
+
+            (z + self.lambda.sqrt()).powi(2) + chi
+        } else {
+            let n = if self.lambda > 0.0 {
+                Poisson::new(0.5 * self.lambda).unwrap().sample(rng)
+            } else {
+                0.0
+            };
+
+            ChiSquared::new(self.k + 2.0 * n).unwrap().sample(rng)
+        }
+    }
+
+    /// Sum of a function of `j` weighted by Poisson(lambda / 2) probabilities,
+    /// truncated once the remaining Poisson mass is negligible.
+    fn poisson_mixture<F: Fn(f64) -> f64>(&self, f: F) -> f64 {
+        let mu = 0.5 * self.lambda;
+
+        if mu == 0.0 {
+            return f(0.0);
+        }
+
+        let mut sum = 0.0;
+        let mut mass = 0.0;
+        let mut j = 0.0;
+
+        while mass < 1.0 - 1e-15 && j < 10_000.0 {
+            let weight = (-mu + j * mu.ln() - ln_gamma(j + 1.0)).exp();
+
+            sum += weight * f(j);
+            mass += weight;
+            j += 1.0;
+        }
+
+        sum
+    }
+}
+
+impl Distribution for NonCentralChiSquared {
+    /// Characteristic function of the non-central Chi-Squared distribution.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let i: Complex<f64> = Complex::i();
+        let denominator = 1.0 - 2.0 * i * t;
+
+        (i * self.lambda * t / denominator).exp() * denominator.powf(-self.k / 2.0)
+    }
+
+    /// Probability density function of the non-central Chi-Squared distribution.
+    fn pdf(&self, x: f64) -> f64 {
+        assert!(x >= 0.0);
+
+        self.poisson_mixture(|j| {
+            let nu = 0.5 * self.k + j;
+
+            ((nu - 1.0) * x.ln() - 0.5 * x - nu * 2_f64.ln() - ln_gamma(nu)).exp()
+        })
+    }
+
+    /// Probability mass function (equal to the density).
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Cumulative distribution function of the non-central Chi-Squared distribution.
+    fn cdf(&self, x: f64) -> f64 {
+        assert!(x >= 0.0);
+
+        self.poisson_mixture(|j| gamma_lr(0.5 * self.k + j, 0.5 * x))
+    }
+
+    /// Inverse (quantile) distribution function, via bisection.
+    fn inv_cdf(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+
+        let mut lo = 0.0;
+        let mut hi = self.mean() + 20.0 * self.variance().sqrt();
+
+        while hi - lo > 1e-10 {
+            let mid = 0.5 * (lo + hi);
+
+            if self.cdf(mid) < p {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        0.5 * (lo + hi)
+    }
+
+    /// Mean of the non-central Chi-Squared distribution.
+    fn mean(&self) -> f64 {
+        self.k + self.lambda
+    }
+
+    /// Median of the non-central Chi-Squared distribution (numerical).
+    fn median(&self) -> f64 {
+        self.inv_cdf(0.5)
+    }
+
+    /// Mode of the non-central Chi-Squared distribution (numerical).
+    ///
+    /// Zero for $k < 2$, where the density is unbounded at the origin.
+    /// Otherwise the (unimodal) density is maximised by golden-section
+    /// search on $[\max(k - 2, 0), k + \lambda]$, which brackets the mode.
+    fn mode(&self) -> f64 {
+        if self.k < 2.0 {
+            return 0.0;
+        }
+
+        let ratio = 0.5 * (5_f64.sqrt() - 1.0);
+
+        let mut lo = (self.k - 2.0).max(0.0);
+        let mut hi = self.k + self.lambda;
+
+        while hi - lo > 1e-10 {
+            let left = hi - ratio * (hi - lo);
+            let right = lo + ratio * (hi - lo);
+
+            if self.pdf(left) < self.pdf(right) {
+                lo = left;
+            } else {
+                hi = right;
+            }
+        }
+
+        0.5 * (lo + hi)
+    }
+
+    /// Variance of the non-central Chi-Squared distribution.
+    fn variance(&self) -> f64 {
+        2.0 * (self.k + 2.0 * self.lambda)
+    }
+
+    /// Skewness of the non-central Chi-Squared distribution.
+    fn skewness(&self) -> f64 {
+        2_f64.powf(1.5) * (self.k + 3.0 * self.lambda) / (self.k + 2.0 * self.lambda).powf(1.5)
+    }
+
+    /// (Excess) kurtosis of the non-central Chi-Squared distribution.
+    fn kurtosis(&self) -> f64 {
+        12.0 * (self.k + 4.0 * self.lambda) / (self.k + 2.0 * self.lambda).powi(2)
+    }
+
+    /// Entropy of the non-central Chi-Squared distribution (numerical).
+    ///
+    /// Integrates $-f \ln f$ by quadrature, split at the mode, up to
+    /// twenty standard deviations above the mean.
+    fn entropy(&self) -> f64 {
+        let integrand = |x: f64| {
+            let f = self.pdf(x);
+
+            if f > 0.0 {
+                -f * f.ln()
+            } else {
+                0.0
+            }
+        };
+
+        let mode = self.mode();
+        let upper = self.mean() + 20.0 * self.variance().sqrt();
+
+        integrate(integrand, 0.0, mode) + integrate(integrand, mode, upper)
+    }
+
+    /// Moment-generating function of the non-central Chi-Squared distribution.
+    fn mgf(&self, t: f64) -> f64 {
+        assert!(t < 0.5);
+
+        (self.lambda * t / (1.0 - 2.0 * t)).exp() * (1.0 - 2.0 * t).powf(-self.k / 2.0)
+    }
+
+    /// Generates a random sample from a non-central Chi-Squared distribution.
+    fn sample(&self, n: usize) -> Result<Vec<f64>, RustQuantError> {
+        assert!(n > 0);
+
+        let mut rng = rand::thread_rng();
+
+        Ok((0..n).map(|_| self.sample_with_rng(&mut rng)).collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_non_central_chi_squared {
+    use super::*;
+    use crate::math::distributions::{ChiSquared, Gaussian};
+    use crate::math::Statistic;
+
+    #[test]
+    fn test_non_central_chi_squared_distribution_function() {
+        // With one degree of freedom, X = (Z + sqrt(lambda))^2, so
+        // F(x) = N(sqrt(x) - sqrt(lambda)) - N(-sqrt(x) - sqrt(lambda)).
+        let (lambda, gaussian) = (2.0_f64, Gaussian::default());
+        let dist = NonCentralChiSquared::new(1.0, lambda);
+
+        for x in [0.5_f64, 1.0, 3.0, 8.0] {
+            let expected =
+                gaussian.cdf(x.sqrt() - lambda.sqrt()) - gaussian.cdf(-x.sqrt() - lambda.sqrt());
+
+            assert_approx_equal!(dist.cdf(x), expected, 1e-6);
+        }
+
+        // Zero non-centrality reduces to the central distribution.
+        let central = NonCentralChiSquared::new(3.0, 0.0);
+        for x in [0.5, 1.0, 3.0, 8.0] {
+            assert_approx_equal!(central.cdf(x), ChiSquared::new(3).cdf(x), 1e-10);
+            assert_approx_equal!(central.pdf(x), ChiSquared::new(3).pdf(x), 1e-10);
+        }
+
+        assert_approx_equal!(dist.inv_cdf(dist.cdf(3.0)), 3.0, 1e-8);
+    }
+
+    #[test]
+    fn test_non_central_chi_squared_sample_moments() {
+        for (k, lambda) in [(0.4, 3.0), (2.5, 1.5)] {
+            let dist = NonCentralChiSquared::new(k, lambda);
+            let sample = dist.sample(100_000).unwrap();
+
+            assert_approx_equal!(sample.mean(), dist.mean(), 0.05);
+            assert_approx_equal!(sample.variance(), dist.variance(), 0.3);
+            assert!(sample.iter().all(|&x| x >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_non_central_chi_squared_mode_and_entropy() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        // Zero non-centrality reduces to the central distribution.
+        for k in [1, 3, 4, 7] {
+            let central = NonCentralChiSquared::new(k as f64, 0.0);
+
+            assert_approx_equal!(central.mode(), ChiSquared::new(k).mode(), 1e-6);
+            assert_approx_equal!(central.entropy(), ChiSquared::new(k).entropy(), 1e-6);
+        }
+
+        for (k, lambda) in [(0.4, 3.0), (2.5, 1.5), (4.0, 10.0)] {
+            let dist = NonCentralChiSquared::new(k, lambda);
+
+            // The density is largest at the mode.
+            let mode = dist.mode();
+            if mode > 0.0 {
+                assert!(dist.pdf(mode) > dist.pdf(mode - 1e-3));
+                assert!(dist.pdf(mode) > dist.pdf(mode + 1e-3));
+            }
+
+            // The entropy is E[-ln f(X)], estimated within its standard error.
+            let mut rng = StdRng::seed_from_u64(3);
+            let log_density: Vec<f64> = (0..100_000)
+                .map(|_| -dist.pdf(dist.sample_with_rng(&mut rng)).ln())
+                .collect();
+
+            let standard_error = (log_density.variance() / 100_000.0).sqrt();
+
+            assert!(
+                (log_density.mean() - dist.entropy()).abs() < 4.0 * standard_error,
+                "{k} {lambda}: {} vs {}",
+                log_density.mean(),
+                dist.entropy()
+            );
+        }
+
+        // Below two degrees of freedom the mode is zero, and with two it
+        // leaves zero once lambda > 2.
+        assert_eq!(NonCentralChiSquared::new(1.5, 5.0).mode(), 0.0);
+        assert!(NonCentralChiSquared::new(2.0, 1.0).mode() < 1e-8);
+        assert!(NonCentralChiSquared::new(2.0, 4.0).mode() > 0.5);
+    }
+}
@@ -0,0 +1,402 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Estimation of the Hurst exponent of a path, e.g. a simulated
+//! [`FractionalBrownianMotion`](crate::models::FractionalBrownianMotion)
+//! or a series of historical log prices.
+//!
+//! Each method measures how a fluctuation statistic grows with the scale
+//! $n$ (as $n^H$), and regresses its logarithm on $\log n$:
+//!
+//! - Rescaled range (Hurst, 1951): the range of the cumulative deviations
+//!   from the mean of each block of $n$ increments, divided by their
+//!   standard deviation. The small-sample bias is removed with the
+//!   Anis-Lloyd-Peters expected value for independent increments, but the
+//!   estimate is still pulled towards 1/2 by a few hundredths for strongly
+//!   persistent or anti-persistent paths.
+//! - Detrended fluctuation analysis (Peng et al., 1994): the root mean
+//!   square deviation of the cumulative sum of the demeaned increments
+//!   from a linear trend fitted to each block of $n$.
+//! - Variogram: the root mean square change of the path over $n$ steps,
+//!   for lags up to 64. This matches the scaling of fractional Brownian
+//!   motion exactly and has the smallest variance, but unlike DFA it is
+//!   not robust to trends in the path.
+//!
+//! ```rust
+//! use RustQuant::math::*;
+//!
+//! // A random walk has H = 1/2.
+//! let mut x = 0.0_f64;
+//! let path: Vec<f64> = (0..1024_u32)
+//!     .map(|i| {
+//!         x += if (i.wrapping_mul(2654435761) >> 16) % 2 == 0 { 1.0 } else { -1.0 };
+//!         x
+//!     })
+//!     .collect();
+//!
+//! let estimate = estimate_hurst_with(&path, HurstMethod::DetrendedFluctuation).unwrap();
+//! let (lower, upper) = estimate.confidence_interval(0.95);
+//!
+//! assert!(lower < estimate.hurst && estimate.hurst < upper);
+//! assert!(estimate_hurst(&path[..64]).is_err());
+//! ```
+
+use crate::error::RustQuantError;
+use crate::math::distributions::{Distribution, Gaussian};
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::PI;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Minimum number of points in a path for the Hurst exponent to be
+/// estimated, so that there are enough scales to regress over.
+pub const HURST_MINIMUM_LENGTH: usize = 128;
+
+/// Method used to estimate the Hurst exponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HurstMethod {
+    /// Rescaled range (R/S) analysis, with the Anis-Lloyd-Peters correction.
+    RescaledRange,
+
+    /// Detrended fluctuation analysis (DFA), with linear detrending.
+    #[default]
+    DetrendedFluctuation,
+
+    /// Variogram: the root mean square change of the path over each lag.
+    Variogram,
+}
+
+/// Estimate of the Hurst exponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HurstEstimate {
+    /// Method used for the estimate.
+    pub method: HurstMethod,
+
+    /// Estimated Hurst exponent.
+    pub hurst: f64,
+
+    /// Standard error of the estimate, from the log-log regression.
+    pub standard_error: f64,
+
+    /// Scales (block sizes) regressed over.
+    pub scales: Vec<usize>,
+
+    /// Fluctuation statistic at each scale: the corrected rescaled range,
+    /// the detrended fluctuation, or the root mean square change.
+    pub fluctuations: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HurstEstimate {
+    /// Normal confidence interval for the Hurst exponent at the given
+    /// level (e.g. 0.95), from the standard error of the regression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is not in (0, 1).
+    #[must_use]
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        assert!(0.0 < level && level < 1.0);
+
+        let z = Gaussian::default().inv_cdf(0.5 + 0.5 * level);
+
+        (
+            self.hurst - z * self.standard_error,
+            self.hurst + z * self.standard_error,
+        )
+    }
+}
+
+/// Estimate the Hurst exponent of a path by detrended fluctuation analysis.
+///
+/// # Errors
+///
+/// - `RustQuantError::InvalidArgument` if the path has fewer than
+///   [`HURST_MINIMUM_LENGTH`] points, or non-finite values.
+/// - `RustQuantError::ComputationError` if the path does not fluctuate.
+pub fn estimate_hurst(path: &[f64]) -> Result<f64, RustQuantError> {
+    Ok(estimate_hurst_with(path, HurstMethod::default())?.hurst)
+}
+
+/// Estimate the Hurst exponent of a path, with its standard error.
+///
+/// # Errors
+///
+/// - `RustQuantError::InvalidArgument` if the path has fewer than
+///   [`HURST_MINIMUM_LENGTH`] points, or non-finite values.
+/// - `RustQuantError::ComputationError` if the path does not fluctuate.
+pub fn estimate_hurst_with(
+    path: &[f64],
+    method: HurstMethod,
+) -> Result<HurstEstimate, RustQuantError> {
+    if path.len() < HURST_MINIMUM_LENGTH {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "The path has {} points, but at least {HURST_MINIMUM_LENGTH} are needed.",
+            path.len()
+        )));
+    }
+
+    if !path.iter().all(|x| x.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "The path has non-finite values.".to_string(),
+        ));
+    }
+
+    let increments: Vec<f64> = path.windows(2).map(|w| w[1] - w[0]).collect();
+    let n = increments.len();
+
+    let (scales, fluctuations, offset): (Vec<usize>, Vec<f64>, f64) = match method {
+        HurstMethod::RescaledRange => {
+            let scales = log_spaced_scales(8, n / 4);
+            let fluctuations = scales
+                .iter()
+                .map(|&scale| rescaled_range(&increments, scale) / expected_rescaled_range(scale))
+                .collect();
+
+            // The corrected statistic grows as n^{H - 1/2}.
+            (scales, fluctuations, 0.5)
+        }
+        HurstMethod::DetrendedFluctuation => {
+            let scales = log_spaced_scales(16, n / 4);
+            let fluctuations = scales
+                .iter()
+                .map(|&scale| detrended_fluctuation(&increments, scale))
+                .collect();
+
+            (scales, fluctuations, 0.0)
+        }
+        HurstMethod::Variogram => {
+            let scales = log_spaced_scales(1, (n / 16).min(64));
+            let fluctuations = scales.iter().map(|&lag| variogram(path, lag)).collect();
+
+            (scales, fluctuations, 0.0)
+        }
+    };
+
+    if !fluctuations.iter().all(|f| f.is_finite() && *f > 0.0) {
+        return Err(RustQuantError::ComputationError(
+            "The path does not fluctuate at every scale.".to_string(),
+        ));
+    }
+
+    let log_scales: Vec<f64> = scales.iter().map(|&s| (s as f64).ln()).collect();
+    let log_fluctuations: Vec<f64> = fluctuations.iter().map(|f| f.ln()).collect();
+    let (slope, standard_error) = regression_slope(&log_scales, &log_fluctuations);
+
+    Ok(HurstEstimate {
+        method,
+        hurst: offset + slope,
+        standard_error,
+        scales,
+        fluctuations,
+    })
+}
+
+// Up to 20 distinct, roughly log-spaced scales from `min` to `max`.
+fn log_spaced_scales(min: usize, max: usize) -> Vec<usize> {
+    let count = 20;
+    let ratio = (max as f64 / min as f64).ln();
+
+    let mut scales: Vec<usize> = (0..count)
+        .map(|i| (min as f64 * (ratio * i as f64 / (count - 1) as f64).exp()).round() as usize)
+        .collect();
+    scales.dedup();
+
+    scales
+}
+
+// Mean of R/S over the non-overlapping blocks of `scale` increments
+// (blocks without variation are skipped).
+fn rescaled_range(increments: &[f64], scale: usize) -> f64 {
+    let (mut sum, mut count) = (0.0, 0);
+
+    for block in increments.chunks_exact(scale) {
+        let mean = block.iter().sum::<f64>() / scale as f64;
+
+        let (mut cumulative, mut min, mut max, mut sum_of_squares) = (0.0, 0.0_f64, 0.0_f64, 0.0);
+        for x in block {
+            cumulative += x - mean;
+            min = min.min(cumulative);
+            max = max.max(cumulative);
+            sum_of_squares += (x - mean) * (x - mean);
+        }
+
+        let std_dev = (sum_of_squares / scale as f64).sqrt();
+
+        if std_dev > 0.0 {
+            sum += (max - min) / std_dev;
+            count += 1;
+        }
+    }
+
+    sum / count as f64
+}
+
+// Anis-Lloyd expected R/S of `n` independent Gaussian increments,
+// with the Peters (n - 1/2) / n correction.
+fn expected_rescaled_range(n: usize) -> f64 {
+    let n = n as f64;
+    let sum: f64 = (1..n as usize)
+        .map(|i| ((n - i as f64) / i as f64).sqrt())
+        .sum();
+
+    (n - 0.5) / n * (ln_gamma(0.5 * (n - 1.0)) - ln_gamma(0.5 * n)).exp() / PI.sqrt() * sum
+}
+
+// Root mean square deviation of the profile (cumulative sum of the
+// demeaned increments) from a linear trend in each block of `scale`.
+fn detrended_fluctuation(increments: &[f64], scale: usize) -> f64 {
+    let mean = increments.iter().sum::<f64>() / increments.len() as f64;
+    let profile: Vec<f64> = increments
+        .iter()
+        .scan(0.0, |y, x| {
+            *y += x - mean;
+            Some(*y)
+        })
+        .collect();
+
+    // Centred time index of a block, and its sum of squares.
+    let t_mean = 0.5 * (scale as f64 - 1.0);
+    let t_sum_of_squares: f64 = (0..scale).map(|t| (t as f64 - t_mean).powi(2)).sum();
+
+    let blocks = profile.chunks_exact(scale);
+    let n_blocks = blocks.len();
+
+    let residual_variance: f64 = blocks
+        .map(|block| {
+            let y_mean = block.iter().sum::<f64>() / scale as f64;
+            let slope = block
+                .iter()
+                .enumerate()
+                .map(|(t, y)| (t as f64 - t_mean) * (y - y_mean))
+                .sum::<f64>()
+                / t_sum_of_squares;
+
+            block
+                .iter()
+                .enumerate()
+                .map(|(t, y)| (y - y_mean - slope * (t as f64 - t_mean)).powi(2))
+                .sum::<f64>()
+                / scale as f64
+        })
+        .sum();
+
+    (residual_variance / n_blocks as f64).sqrt()
+}
+
+// Root mean square change of the path over `lag` steps.
+fn variogram(path: &[f64], lag: usize) -> f64 {
+    let sum_of_squares: f64 = path.windows(lag + 1).map(|w| (w[lag] - w[0]).powi(2)).sum();
+
+    (sum_of_squares / (path.len() - lag) as f64).sqrt()
+}
+
+// Least squares slope of `ys` on `xs`, and its standard error.
+fn regression_slope(xs: &[f64], ys: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    let x_mean = xs.iter().sum::<f64>() / n;
+    let y_mean = ys.iter().sum::<f64>() / n;
+
+    let sxx: f64 = xs.iter().map(|x| (x - x_mean).powi(2)).sum();
+    let sxy: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - x_mean) * (y - y_mean))
+        .sum();
+
+    let slope = sxy / sxx;
+    let residual_sum_of_squares: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (y - y_mean - slope * (x - x_mean)).powi(2))
+        .sum();
+
+    let standard_error = (residual_sum_of_squares / (n - 2.0) / sxx).sqrt();
+
+    (slope, standard_error)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hurst {
+    use super::*;
+    use crate::models::FractionalBrownianMotion;
+    use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+
+    fn fbm_path(hurst: f64, seed: u64) -> Vec<f64> {
+        let fbm = FractionalBrownianMotion::new(hurst, Default::default());
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 1 << 16, 1, false).with_seed(seed);
+
+        fbm.simulate_path(&config, 0)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (hurst, seed) in [(0.2, 1), (0.5, 2), (0.8, 3)] {
+            let path = fbm_path(hurst, seed);
+
+            // Rescaled range keeps a small bias towards 1/2.
+            for (method, tolerance) in [
+                (HurstMethod::RescaledRange, 0.08),
+                (HurstMethod::DetrendedFluctuation, 0.05),
+                (HurstMethod::Variogram, 0.05),
+            ] {
+                let estimate = estimate_hurst_with(&path, method).unwrap();
+
+                assert!(
+                    (estimate.hurst - hurst).abs() < tolerance,
+                    "H = {hurst}, {method:?}: {}",
+                    estimate.hurst
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_confidence_interval() {
+        let estimate = estimate_hurst_with(&fbm_path(0.5, 4), HurstMethod::default()).unwrap();
+        let (lower, upper) = estimate.confidence_interval(0.95);
+
+        assert_eq!(estimate.hurst, estimate_hurst(&fbm_path(0.5, 4)).unwrap());
+        assert!(estimate.standard_error > 0.0);
+        assert!(lower < estimate.hurst && estimate.hurst < upper);
+        assert!(estimate.confidence_interval(0.99).0 < lower);
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let path = fbm_path(0.5, 5);
+
+        assert!(matches!(
+            estimate_hurst(&path[..HURST_MINIMUM_LENGTH - 1]),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+        assert!(estimate_hurst(&path[..HURST_MINIMUM_LENGTH]).is_ok());
+
+        let mut with_nan = path.clone();
+        with_nan[10] = f64::NAN;
+        assert!(matches!(
+            estimate_hurst(&with_nan),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+
+        assert!(matches!(
+            estimate_hurst(&vec![1.0; 256]),
+            Err(RustQuantError::ComputationError(_))
+        ));
+    }
+}
@@ -98,3 +98,11 @@ pub use sequences::*;
 /// Statistic trait.
 pub mod statistic;
 pub use statistic::*;
+
+/// Bootstrap and jackknife resampling.
+pub mod resampling;
+pub use resampling::*;
+
+/// Hurst exponent estimation.
+pub mod hurst;
+pub use hurst::*;
@@ -0,0 +1,219 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Differential evolution (Storn and Price, 1997), `rand/1/bin` variant.
+//!
+//! Each member $x_i$ of the population is challenged by a trial vector: a
+//! mutant $v = x_{r_1} + F (x_{r_2} - x_{r_3})$ from three other random
+//! members, crossed over coordinate-wise with $x_i$ with probability $CR$
+//! (and in at least one coordinate). The trial replaces $x_i$ if it is no
+//! worse. Mutant coordinates outside the bounds are redrawn between the
+//! member's coordinate and the bound it crossed.
+
+use super::global_optimizer::{
+    assert_bounds, optimizer_rng, BudgetedObjective, GlobalOptimizerResult,
+};
+use rand::Rng;
+use std::time::Instant;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Differential evolution minimizer over box bounds.
+#[derive(Debug, Clone)]
+pub struct DifferentialEvolution {
+    /// Lower and upper bound of each coordinate.
+    pub bounds: Vec<(f64, f64)>,
+
+    /// Maximum number of objective function evaluations.
+    pub max_evaluations: usize,
+
+    /// Number of members of the population (at least 4).
+    pub population_size: usize,
+
+    /// Differential weight ($F$), in $(0, 2]$.
+    pub mutation: f64,
+
+    /// Crossover probability ($CR$), in $[0, 1]$.
+    pub crossover: f64,
+
+    /// Stop once the standard deviation of the population's objective
+    /// values is below this tolerance.
+    pub tolerance: f64,
+
+    /// Refine the best member with Nelder-Mead, from the remaining budget.
+    pub polish: bool,
+
+    /// Seed for the random number generator (`None` for entropy).
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DifferentialEvolution {
+    /// Create a new differential evolution minimizer, with a population of
+    /// 15 members per dimension, $F = 0.8$, $CR = 0.9$, a tolerance of
+    /// $10^{-12}$, and no polish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no bounds, any bound is not finite with
+    /// `lower < upper`, or `max_evaluations` is zero.
+    #[must_use]
+    pub fn new(bounds: Vec<(f64, f64)>, max_evaluations: usize) -> Self {
+        assert_bounds(&bounds);
+        assert!(max_evaluations > 0);
+
+        Self {
+            population_size: (15 * bounds.len()).max(4),
+            bounds,
+            max_evaluations,
+            mutation: 0.8,
+            crossover: 0.9,
+            tolerance: 1e-12,
+            polish: false,
+            seed: None,
+        }
+    }
+
+    /// Use a seeded random number generator, for reproducible runs.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the population size, differential weight, and crossover probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the population has fewer than 4 members, or the weight or
+    /// probability are out of range.
+    #[must_use]
+    pub fn with_controls(mut self, population_size: usize, mutation: f64, crossover: f64) -> Self {
+        assert!(population_size >= 4);
+        assert!(mutation > 0.0 && mutation <= 2.0);
+        assert!((0.0..=1.0).contains(&crossover));
+
+        self.population_size = population_size;
+        self.mutation = mutation;
+        self.crossover = crossover;
+        self
+    }
+
+    /// Set the convergence tolerance on the population's objective values.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Refine the best member with Nelder-Mead at the end.
+    #[must_use]
+    pub fn with_polish(mut self, polish: bool) -> Self {
+        self.polish = polish;
+        self
+    }
+
+    /// Minimize `f` over the bounds. The objective is only ever evaluated
+    /// within the bounds, and at most `max_evaluations` times.
+    pub fn optimize<F>(&self, f: F) -> GlobalOptimizerResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let start = Instant::now();
+
+        let mut rng = optimizer_rng(self.seed);
+        let objective = BudgetedObjective::new(&f, &self.bounds, self.max_evaluations);
+        let (n, dimension) = (self.population_size, self.bounds.len());
+
+        let mut population: Vec<Vec<f64>> = (0..n)
+            .map(|_| {
+                self.bounds
+                    .iter()
+                    .map(|&(lower, upper)| rng.gen_range(lower..=upper))
+                    .collect()
+            })
+            .collect();
+        let mut values: Vec<f64> = population
+            .iter()
+            .map_while(|member| objective.evaluate(member))
+            .collect();
+
+        // Generations, until the budget is spent or the population converges.
+        'evolution: while values.len() == n && !Self::has_converged(&values, self.tolerance) {
+            for i in 0..n {
+                let [r1, r2, r3] = Self::distinct_others(i, n, &mut rng);
+                let j_rand = rng.gen_range(0..dimension);
+
+                let trial: Vec<f64> = (0..dimension)
+                    .map(|j| {
+                        if j != j_rand && rng.gen::<f64>() >= self.crossover {
+                            return population[i][j];
+                        }
+
+                        let (lower, upper) = self.bounds[j];
+                        let mutant = population[r1][j]
+                            + self.mutation * (population[r2][j] - population[r3][j]);
+
+                        if mutant < lower {
+                            lower + rng.gen::<f64>() * (population[i][j] - lower)
+                        } else if mutant > upper {
+                            upper - rng.gen::<f64>() * (upper - population[i][j])
+                        } else {
+                            mutant
+                        }
+                    })
+                    .collect();
+
+                let Some(value) = objective.evaluate(&trial) else {
+                    break 'evolution;
+                };
+
+                if value <= values[i] {
+                    population[i] = trial;
+                    values[i] = value;
+                }
+            }
+        }
+
+        if self.polish {
+            objective.polish();
+        }
+
+        objective.into_result(start.elapsed())
+    }
+
+    /// Three distinct population indices, all different from `i`.
+    fn distinct_others<R: Rng>(i: usize, n: usize, rng: &mut R) -> [usize; 3] {
+        let mut picks = [i; 3];
+
+        for k in 0..3 {
+            picks[k] = loop {
+                let r = rng.gen_range(0..n);
+
+                if r != i && !picks[..k].contains(&r) {
+                    break r;
+                }
+            };
+        }
+
+        picks
+    }
+
+    fn has_converged(values: &[f64], tolerance: f64) -> bool {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+
+        (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt() < tolerance
+    }
+}
@@ -0,0 +1,286 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Shared pieces of the box-bounded global optimizers
+//! ([`SimulatedAnnealing`](super::SimulatedAnnealing) and
+//! [`DifferentialEvolution`](super::DifferentialEvolution)): the result type,
+//! the evaluation budget, and the Nelder-Mead polish of the best point.
+
+use argmin::core::{CostFunction, Error, Executor};
+use argmin::solver::neldermead::NelderMead;
+use rand::{rngs::StdRng, SeedableRng};
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+/// Result of a global optimization.
+#[derive(Debug, Clone)]
+pub struct GlobalOptimizerResult {
+    /// Best point found (within the bounds).
+    pub minimizer: Vec<f64>,
+
+    /// Value of the objective at the minimizer.
+    pub minimum: f64,
+
+    /// Number of objective function evaluations used.
+    pub evaluations: usize,
+
+    /// Time elapsed during optimization.
+    pub elapsed: Duration,
+}
+
+/// Objective function with a budget of evaluations, that remembers the best
+/// point it has been evaluated at.
+pub(crate) struct BudgetedObjective<'a, F>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    objective: &'a F,
+    bounds: &'a [(f64, f64)],
+    max_evaluations: usize,
+    evaluations: Cell<usize>,
+    best: RefCell<(Vec<f64>, f64)>,
+}
+
+impl<'a, F> BudgetedObjective<'a, F>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    pub(crate) fn new(objective: &'a F, bounds: &'a [(f64, f64)], max_evaluations: usize) -> Self {
+        Self {
+            objective,
+            bounds,
+            max_evaluations,
+            evaluations: Cell::new(0),
+            best: RefCell::new((Vec::new(), f64::INFINITY)),
+        }
+    }
+
+    /// Objective value at `x`, or `None` once the budget is spent.
+    pub(crate) fn evaluate(&self, x: &[f64]) -> Option<f64> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        self.evaluations.set(self.evaluations.get() + 1);
+        let value = (self.objective)(x);
+
+        let mut best = self.best.borrow_mut();
+        if value < best.1 || best.0.is_empty() {
+            *best = (x.to_vec(), value);
+        }
+
+        Some(value)
+    }
+
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.evaluations.get() >= self.max_evaluations
+    }
+
+    /// Refine the best point so far with Nelder-Mead, on the objective
+    /// clamped to the bounds, until convergence or the budget is spent.
+    pub(crate) fn polish(&self) {
+        let start = self.best.borrow().0.clone();
+
+        if start.is_empty() || self.is_exhausted() {
+            return;
+        }
+
+        // Initial simplex: steps of 5% of each bound's width, inwards.
+        let mut simplex = vec![start.clone()];
+        for (j, &(lower, upper)) in self.bounds.iter().enumerate() {
+            let mut vertex = start.clone();
+            let step = 0.05 * (upper - lower);
+
+            vertex[j] = if vertex[j] + step <= upper {
+                vertex[j] + step
+            } else {
+                vertex[j] - step
+            };
+            simplex.push(vertex);
+        }
+
+        // Runs until the simplex collapses or the budget is spent (which
+        // ends the run with an error); the best point is tracked either way.
+        if let Ok(solver) = NelderMead::new(simplex).with_sd_tolerance(1e-12) {
+            let _ = Executor::new(self, solver)
+                .configure(|state| state.max_iters(u64::MAX))
+                .run();
+        }
+    }
+
+    pub(crate) fn into_result(self, elapsed: Duration) -> GlobalOptimizerResult {
+        let (minimizer, minimum) = self.best.into_inner();
+
+        GlobalOptimizerResult {
+            minimizer,
+            minimum,
+            evaluations: self.evaluations.get(),
+            elapsed,
+        }
+    }
+}
+
+impl<F> CostFunction for &BudgetedObjective<'_, F>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        let clamped: Vec<f64> = x
+            .iter()
+            .zip(self.bounds)
+            .map(|(&x, &(lower, upper))| x.clamp(lower, upper))
+            .collect();
+
+        self.evaluate(&clamped)
+            .ok_or_else(|| Error::msg("Evaluation budget exhausted."))
+    }
+}
+
+/// Check that the box bounds are finite and non-empty.
+pub(crate) fn assert_bounds(bounds: &[(f64, f64)]) {
+    assert!(!bounds.is_empty(), "At least one bound is required.");
+    assert!(
+        bounds
+            .iter()
+            .all(|&(lower, upper)| lower.is_finite() && upper.is_finite() && lower < upper),
+        "Bounds must be finite, with lower < upper."
+    );
+}
+
+/// Seeded, or entropy-seeded, random number generator.
+pub(crate) fn optimizer_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Reflect `x` back into `[lower, upper]` off the boundary it crossed,
+/// clamping if it overshoots by more than the width.
+pub(crate) fn reflect(x: f64, (lower, upper): (f64, f64)) -> f64 {
+    let reflected = if x < lower {
+        2.0 * lower - x
+    } else if x > upper {
+        2.0 * upper - x
+    } else {
+        x
+    };
+
+    reflected.clamp(lower, upper)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_global_optimizer {
+    use crate::math::optimization::{DifferentialEvolution, SimulatedAnnealing};
+    use std::cell::Cell;
+    use std::f64::consts::{E, PI};
+
+    const DIMENSION: usize = 5;
+
+    /// Global minimum of 0 at the origin, with a local minimum near
+    /// every integer point.
+    fn rastrigin(x: &[f64]) -> f64 {
+        10.0 * x.len() as f64
+            + x.iter()
+                .map(|x| x * x - 10.0 * (2.0 * PI * x).cos())
+                .sum::<f64>()
+    }
+
+    /// Global minimum of 0 at the origin, in a nearly flat, rippled landscape.
+    fn ackley(x: &[f64]) -> f64 {
+        let n = x.len() as f64;
+        let squares = x.iter().map(|x| x * x).sum::<f64>() / n;
+        let cosines = x.iter().map(|x| (2.0 * PI * x).cos()).sum::<f64>() / n;
+
+        -20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + E
+    }
+
+    /// Run `optimize` on `f`, checking that every evaluation is within the
+    /// (asymmetric) bounds and that the evaluations are counted.
+    fn check<F, O>(f: F, bounds: &[(f64, f64)], max_evaluations: usize, optimize: O)
+    where
+        F: Fn(&[f64]) -> f64,
+        O: FnOnce(&dyn Fn(&[f64]) -> f64) -> crate::math::GlobalOptimizerResult,
+    {
+        let evaluations = Cell::new(0);
+        let counted = |x: &[f64]| {
+            assert!(x
+                .iter()
+                .zip(bounds)
+                .all(|(x, &(lower, upper))| (lower..=upper).contains(x)));
+            evaluations.set(evaluations.get() + 1);
+            f(x)
+        };
+
+        let result = optimize(&counted);
+
+        assert_eq!(result.evaluations, evaluations.get());
+        assert!(result.evaluations <= max_evaluations);
+        assert!(result.minimum < 1e-6, "{result:?}");
+        assert!(result.minimizer.iter().all(|x| x.abs() < 1e-4));
+    }
+
+    #[test]
+    fn test_simulated_annealing() {
+        let bounds = vec![(-5.12, 4.0); DIMENSION];
+        let max_evaluations = 200_000;
+
+        for f in [rastrigin, ackley] {
+            check(f, &bounds, max_evaluations, |f| {
+                SimulatedAnnealing::new(bounds.clone(), max_evaluations)
+                    .with_polish(true)
+                    .with_seed(1)
+                    .optimize(f)
+            });
+        }
+    }
+
+    #[test]
+    fn test_differential_evolution() {
+        let bounds = vec![(-5.12, 4.0); DIMENSION];
+        let max_evaluations = 100_000;
+
+        for f in [rastrigin, ackley] {
+            check(f, &bounds, max_evaluations, |f| {
+                DifferentialEvolution::new(bounds.clone(), max_evaluations)
+                    .with_polish(true)
+                    .with_seed(1)
+                    .optimize(f)
+            });
+        }
+    }
+
+    #[test]
+    fn test_evaluation_budget() {
+        let bounds = vec![(-5.12, 4.0); DIMENSION];
+
+        for polish in [false, true] {
+            let annealing = SimulatedAnnealing::new(bounds.clone(), 500)
+                .with_polish(polish)
+                .with_seed(2)
+                .optimize(rastrigin);
+            let evolution = DifferentialEvolution::new(bounds.clone(), 500)
+                .with_polish(polish)
+                .with_seed(2)
+                .optimize(rastrigin);
+
+            for result in [annealing, evolution] {
+                assert_eq!(result.evaluations, 500);
+                assert_eq!(result.minimum, rastrigin(&result.minimizer));
+            }
+        }
+    }
+}
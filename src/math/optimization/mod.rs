@@ -10,3 +10,15 @@
 /// Gradient descent method.
 pub mod gradient_descent;
 pub use gradient_descent::*;
+
+/// Differential evolution global optimizer.
+pub mod differential_evolution;
+pub use differential_evolution::*;
+
+/// Shared result type and helpers of the global optimizers.
+pub mod global_optimizer;
+pub use global_optimizer::*;
+
+/// Simulated annealing global optimizer.
+pub mod simulated_annealing;
+pub use simulated_annealing::*;
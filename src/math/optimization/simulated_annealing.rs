@@ -0,0 +1,209 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Simulated annealing over a box.
+//!
+//! From the current point $x$, a move to a nearby point $y$ is accepted with
+//! the Metropolis probability $\min(1, e^{-(f(y) - f(x)) / T_k})$, so uphill
+//! moves are taken often while the temperature $T_k$ is high, and the search
+//! settles into the deepest basin as it cools. Moves change one coordinate
+//! at a time by a Gaussian step, reflected off the bounds. The step size is
+//! kept fixed, so late moves can still hop between neighbouring basins;
+//! the optional Nelder-Mead polish refines the final point.
+
+use super::global_optimizer::{
+    assert_bounds, optimizer_rng, reflect, BudgetedObjective, GlobalOptimizerResult,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use std::time::Instant;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Temperature $T_k$ after $k$ moves, from the initial temperature $T_0$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoolingSchedule {
+    /// Geometric cooling, $T_k = T_0 \rho^k$ with $0 < \rho < 1$.
+    Exponential(f64),
+
+    /// Logarithmic (Boltzmann) cooling, $T_k = T_0 / \ln(k + e)$.
+    Logarithmic,
+
+    /// Fast (Cauchy) cooling, $T_k = T_0 / (k + 1)$.
+    Fast,
+}
+
+/// Simulated annealing minimizer over box bounds.
+#[derive(Debug, Clone)]
+pub struct SimulatedAnnealing {
+    /// Lower and upper bound of each coordinate.
+    pub bounds: Vec<(f64, f64)>,
+
+    /// Maximum number of objective function evaluations.
+    pub max_evaluations: usize,
+
+    /// Initial temperature ($T_0$), on the scale of the objective.
+    pub initial_temperature: f64,
+
+    /// How the temperature decreases with each move.
+    pub cooling_schedule: CoolingSchedule,
+
+    /// Standard deviation of the moves, as a fraction of each bound's width.
+    pub step_size: f64,
+
+    /// Refine the best point with Nelder-Mead, from the remaining budget.
+    pub polish: bool,
+
+    /// Seed for the random number generator (`None` for entropy).
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CoolingSchedule {
+    /// Temperature after `k` moves, from `initial_temperature`.
+    #[must_use]
+    pub fn temperature(&self, initial_temperature: f64, k: usize) -> f64 {
+        match self {
+            Self::Exponential(rate) => initial_temperature * rate.powf(k as f64),
+            Self::Logarithmic => initial_temperature / (k as f64 + std::f64::consts::E).ln(),
+            Self::Fast => initial_temperature / (k as f64 + 1.0),
+        }
+    }
+}
+
+impl SimulatedAnnealing {
+    /// Create a new simulated annealing minimizer, with $T_0 = 10$,
+    /// exponential cooling reaching $10^{-6} T_0$ at 90% of the budget,
+    /// moves of 10% of the bounds' widths, and no polish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no bounds, any bound is not finite with
+    /// `lower < upper`, or `max_evaluations` is zero.
+    #[must_use]
+    pub fn new(bounds: Vec<(f64, f64)>, max_evaluations: usize) -> Self {
+        assert_bounds(&bounds);
+        assert!(max_evaluations > 0);
+
+        let rate = (1e-6_f64.ln() / (0.9 * max_evaluations as f64)).exp();
+
+        Self {
+            bounds,
+            max_evaluations,
+            initial_temperature: 10.0,
+            cooling_schedule: CoolingSchedule::Exponential(rate),
+            step_size: 0.1,
+            polish: false,
+            seed: None,
+        }
+    }
+
+    /// Use a seeded random number generator, for reproducible runs.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the initial temperature and cooling schedule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temperature is not positive, or an exponential
+    /// cooling rate is not in $(0, 1)$.
+    #[must_use]
+    pub fn with_cooling(mut self, initial_temperature: f64, schedule: CoolingSchedule) -> Self {
+        assert!(initial_temperature > 0.0);
+
+        if let CoolingSchedule::Exponential(rate) = schedule {
+            assert!(rate > 0.0 && rate < 1.0);
+        }
+
+        self.initial_temperature = initial_temperature;
+        self.cooling_schedule = schedule;
+        self
+    }
+
+    /// Set the move size, as a fraction of the bounds' widths.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step size is not positive.
+    #[must_use]
+    pub fn with_step_size(mut self, step_size: f64) -> Self {
+        assert!(step_size > 0.0);
+
+        self.step_size = step_size;
+        self
+    }
+
+    /// Refine the best point with Nelder-Mead at the end.
+    #[must_use]
+    pub fn with_polish(mut self, polish: bool) -> Self {
+        self.polish = polish;
+        self
+    }
+
+    /// Minimize `f` over the bounds. The objective is only ever evaluated
+    /// within the bounds, and at most `max_evaluations` times.
+    pub fn optimize<F>(&self, f: F) -> GlobalOptimizerResult
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let start = Instant::now();
+
+        let mut rng = optimizer_rng(self.seed);
+        let objective = BudgetedObjective::new(&f, &self.bounds, self.max_evaluations);
+
+        // Keep a share of the budget for the polish.
+        let annealing_budget = if self.polish {
+            self.max_evaluations - self.max_evaluations / 10
+        } else {
+            self.max_evaluations
+        };
+
+        let mut x: Vec<f64> = self
+            .bounds
+            .iter()
+            .map(|&(lower, upper)| rng.gen_range(lower..=upper))
+            .collect();
+        let mut fx = objective.evaluate(&x).unwrap_or(f64::INFINITY);
+
+        for k in 1..annealing_budget {
+            let temperature = self
+                .cooling_schedule
+                .temperature(self.initial_temperature, k);
+            let j = rng.gen_range(0..x.len());
+            let (lower, upper) = self.bounds[j];
+            let step = self.step_size * (upper - lower) * rng.sample::<f64, _>(StandardNormal);
+
+            let mut y = x.clone();
+            y[j] = reflect(x[j] + step, self.bounds[j]);
+
+            let Some(fy) = objective.evaluate(&y) else {
+                break;
+            };
+
+            if fy <= fx || rng.gen::<f64>() < (-(fy - fx) / temperature).exp() {
+                (x, fx) = (y, fy);
+            }
+        }
+
+        if self.polish {
+            objective.polish();
+        }
+
+        objective.into_result(start.elapsed())
+    }
+}
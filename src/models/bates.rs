@@ -0,0 +1,84 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::models::model_parameter::ModelParameter;
+
+/// Struct containing the Bates model parameters:
+/// the Heston parameters, plus lognormal jumps in the asset.
+pub struct Bates {
+    /// The asset's drift ($\mu$), zero unless set via [`Bates::with_drift`].
+    /// The jumps are compensated, so this is the drift of $E[S(t)]$.
+    pub mu: ModelParameter,
+
+    /// The initial variance ($v_0$).
+    pub initial_variance: ModelParameter,
+
+    /// The long-run variance ($\theta$).
+    pub long_run_variance: ModelParameter,
+
+    /// The mean reversion rate ($\kappa$).
+    pub mean_reversion_rate: ModelParameter,
+
+    /// The correlation between the asset and the variance Brownian motions ($\rho$).
+    pub correlation: ModelParameter,
+
+    /// The volatility of volatility ($\sigma$).
+    pub volatility_of_volatility: ModelParameter,
+
+    /// The jump intensity ($\lambda$).
+    pub lambda: ModelParameter,
+
+    /// The mean of the log jump size ($\mu_J$).
+    pub mu_j: ModelParameter,
+
+    /// The volatility of the log jump size ($\sigma_J$).
+    pub sigma_j: ModelParameter,
+}
+
+impl Bates {
+    /// Create a new Bates process.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        initial_variance: impl Into<ModelParameter>,
+        long_run_variance: impl Into<ModelParameter>,
+        mean_reversion_rate: impl Into<ModelParameter>,
+        correlation: impl Into<ModelParameter>,
+        volatility_of_volatility: impl Into<ModelParameter>,
+        lambda: impl Into<ModelParameter>,
+        mu_j: impl Into<ModelParameter>,
+        sigma_j: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            mu: 0.0.into(),
+            initial_variance: initial_variance.into(),
+            long_run_variance: long_run_variance.into(),
+            mean_reversion_rate: mean_reversion_rate.into(),
+            correlation: correlation.into(),
+            volatility_of_volatility: volatility_of_volatility.into(),
+            lambda: lambda.into(),
+            mu_j: mu_j.into(),
+            sigma_j: sigma_j.into(),
+        }
+    }
+
+    /// Set the asset's drift (e.g. $r - q$ under the risk-neutral measure).
+    #[must_use]
+    pub fn with_drift(mut self, mu: impl Into<ModelParameter>) -> Self {
+        self.mu = mu.into();
+        self
+    }
+
+    /// Mean relative jump size, $k = E[J - 1] = e^{\mu_J + \sigma_J^2 / 2} - 1$.
+    #[must_use]
+    pub fn mean_jump(&self, t: f64) -> f64 {
+        let (mu_j, sigma_j) = (self.mu_j.0(t), self.sigma_j.0(t));
+
+        (mu_j + 0.5 * sigma_j * sigma_j).exp() - 1.0
+    }
+}
@@ -24,7 +24,7 @@ pub struct ConstantElasticityOfVariance {
 }
 
 impl ConstantElasticityOfVariance {
-    /// Create a new Cox-Ingersoll-Ross process.
+    /// Create a new constant elasticity of variance process.
     pub fn new(
         mu: impl Into<ModelParameter>,
         sigma: impl Into<ModelParameter>,
@@ -21,6 +21,33 @@ pub struct CoxIngersollRoss {
     /// Mean reversion parameter ($\theta$).
     /// Defines the speed at which the process reverts to the long-run mean.
     pub theta: ModelParameter,
+
+    /// Scheme keeping the simulated paths non-negative.
+    pub scheme: CirScheme,
+}
+
+/// Fix to the Euler-Maruyama scheme of the square-root diffusion, which
+/// otherwise steps below zero, where $\sqrt{x}$ is undefined.
+///
+/// The scheme evolves an auxiliary state $\tilde{x}$, and the process value
+/// is its positive part, $x = \tilde{x}^+$ (Lord, Koekkoek and van Dijk, 2010):
+///
+/// - `FullTruncation`: $\tilde{x}' = \tilde{x} + \theta (\mu - \tilde{x}^+) \Delta t + \sigma \sqrt{\tilde{x}^+} \Delta W$.
+/// - `PartialTruncation`: $\tilde{x}' = \tilde{x} + \theta (\mu - \tilde{x}) \Delta t + \sigma \sqrt{\tilde{x}^+} \Delta W$.
+/// - `Reflection`: $\tilde{x}' = | \tilde{x} + \theta (\mu - \tilde{x}) \Delta t + \sigma \sqrt{\tilde{x}} \Delta W |$.
+///
+/// Full truncation has the smallest bias of the three.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CirScheme {
+    /// Truncate the state in the drift and the diffusion.
+    #[default]
+    FullTruncation,
+
+    /// Truncate the state in the diffusion only.
+    PartialTruncation,
+
+    /// Reflect the state at zero after each step.
+    Reflection,
 }
 
 impl CoxIngersollRoss {
@@ -34,6 +61,22 @@ impl CoxIngersollRoss {
             mu: mu.into(),
             sigma: sigma.into(),
             theta: theta.into(),
+            scheme: CirScheme::default(),
         }
     }
+
+    /// Use the given scheme to keep the simulated paths non-negative.
+    #[must_use]
+    pub fn with_scheme(mut self, scheme: CirScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Whether the Feller condition $2 \theta \mu \geq \sigma^2$ holds at
+    /// time `t`, so that the process stays strictly positive. When it fails,
+    /// the process can reach zero and the simulated paths depend on the
+    /// [CirScheme].
+    pub fn feller_condition(&self, t: f64) -> bool {
+        2.0 * self.theta.0(t) * self.mu.0(t) >= self.sigma.0(t).powi(2)
+    }
 }
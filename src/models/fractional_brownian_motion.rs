@@ -21,7 +21,7 @@ pub struct FractionalBrownianMotion {
 
 impl Default for FractionalBrownianMotion {
     fn default() -> Self {
-        Self::new(0.5, FractionalProcessGeneratorMethod::FFT)
+        Self::new(0.5, FractionalProcessGeneratorMethod::default())
     }
 }
 
@@ -30,10 +30,13 @@ impl FractionalBrownianMotion {
     ///
     /// # Panics
     ///
-    /// Will panic if Hurst parameter is not in [0, 1].
+    /// Will panic if Hurst parameter is not in (0, 1).
     #[must_use]
     pub fn new(hurst: f64, method: FractionalProcessGeneratorMethod) -> Self {
-        assert!((0.0..=1.0).contains(&hurst));
+        assert!(
+            0.0 < hurst && hurst < 1.0,
+            "Hurst parameter must be in (0, 1)."
+        );
 
         Self { hurst, method }
     }
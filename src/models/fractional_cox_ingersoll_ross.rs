@@ -8,6 +8,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use self::fractional_brownian_motion::FractionalProcessGeneratorMethod;
+use crate::models::cox_ingersoll_ross::CirScheme;
 use crate::models::model_parameter::ModelParameter;
 use crate::stochastics::*;
 
@@ -29,6 +30,9 @@ pub struct FractionalCoxIngersollRoss {
 
     /// Method to generate Fractional Gaussian Noise.
     pub method: FractionalProcessGeneratorMethod,
+
+    /// Scheme keeping the simulated paths non-negative.
+    pub scheme: CirScheme,
 }
 
 impl FractionalCoxIngersollRoss {
@@ -47,6 +51,20 @@ impl FractionalCoxIngersollRoss {
             theta: theta.into(),
             hurst,
             method,
+            scheme: CirScheme::default(),
         }
     }
+
+    /// Use the given scheme to keep the simulated paths non-negative.
+    #[must_use]
+    pub fn with_scheme(mut self, scheme: CirScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Whether the Feller condition $2 \theta \mu \geq \sigma^2$ holds at
+    /// time `t` (see [CoxIngersollRoss::feller_condition](crate::models::CoxIngersollRoss::feller_condition)).
+    pub fn feller_condition(&self, t: f64) -> bool {
+        2.0 * self.theta.0(t) * self.mu.0(t) >= self.sigma.0(t).powi(2)
+    }
 }
@@ -21,12 +21,50 @@ pub struct GeometricBrownianMotion {
 impl GeometricBrownianMotion {
     /// Create a new Geometric Brownian Motion process.
     pub fn new(mu: impl Into<ModelParameter>, sigma: impl Into<ModelParameter>) -> Self {
+        Self::new_with_functions(mu.into().0, sigma.into().0)
+    }
+
+    /// Create a new Geometric Brownian Motion process with time-dependent
+    /// drift $\mu(t)$ and volatility $\sigma(t)$.
+    #[allow(clippy::type_complexity)]
+    pub fn new_with_functions(
+        mu: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+        sigma: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+    ) -> Self {
         Self {
-            mu: mu.into(),
-            sigma: sigma.into(),
+            mu: ModelParameter(mu),
+            sigma: ModelParameter(sigma),
         }
     }
 
+    /// Create a risk-neutral Geometric Brownian Motion process whose drift is
+    /// the instantaneous forward growth rate $r(t) - q(t)$ implied by a
+    /// discount factor curve $P(0, t)$ and a dividend (or repo) factor curve
+    /// $Q(0, t)$, so that $E[X_t] = X_0 Q(0, t) / P(0, t)$ is the forward.
+    ///
+    /// The forward rates are taken by central finite differences of
+    /// $\ln(Q / P)$, falling back to a forward difference at $t = 0$.
+    pub fn new_from_curves<P, Q>(
+        discount_factor: P,
+        dividend_factor: Q,
+        sigma: impl Into<ModelParameter>,
+    ) -> Self
+    where
+        P: Fn(f64) -> f64 + Send + Sync + 'static,
+        Q: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        const H: f64 = 1e-5;
+
+        let mu = move |t: f64| {
+            let log_growth = |s: f64| (dividend_factor(s) / discount_factor(s)).ln();
+            let (lo, hi) = ((t - H).max(0.0), t + H);
+
+            (log_growth(hi) - log_growth(lo)) / (hi - lo)
+        };
+
+        Self::new_with_functions(Box::new(mu), sigma.into().0)
+    }
+
     /// Return the parameters as a Vec<f64>.
     pub fn parameters(&self) -> Vec<f64> {
         vec![self.mu.0(0.0), self.sigma.0(0.0)]
@@ -11,6 +11,9 @@ use crate::models::model_parameter::ModelParameter;
 
 /// Struct containing the Heston model parameters.
 pub struct Heston {
+    /// The asset's drift ($\mu$), zero unless set via [`Heston::with_drift`].
+    pub mu: ModelParameter,
+
     /// The initial variance ($v_0$).
     pub initial_variance: ModelParameter,
 
@@ -28,7 +31,7 @@ pub struct Heston {
 }
 
 impl Heston {
-    /// Create a new Arithmetic Brownian Motion process.
+    /// Create a new Heston process.
     pub fn new(
         initial_variance: impl Into<ModelParameter>,
         long_run_variance: impl Into<ModelParameter>,
@@ -37,6 +40,7 @@ impl Heston {
         volatility_of_volatility: impl Into<ModelParameter>,
     ) -> Self {
         Self {
+            mu: 0.0.into(),
             initial_variance: initial_variance.into(),
             long_run_variance: long_run_variance.into(),
             mean_reversion_rate: mean_reversion_rate.into(),
@@ -44,4 +48,11 @@ impl Heston {
             volatility_of_volatility: volatility_of_volatility.into(),
         }
     }
+
+    /// Set the asset's drift (e.g. $r - q$ under the risk-neutral measure).
+    #[must_use]
+    pub fn with_drift(mut self, mu: impl Into<ModelParameter>) -> Self {
+        self.mu = mu.into();
+        self
+    }
 }
@@ -20,6 +20,10 @@ pub struct HoLee {
 
 impl HoLee {
     /// Create a new Ho-Lee process.
+    ///
+    /// Each parameter may be a constant, a closure of time, or a
+    /// piecewise-constant term structure
+    /// ([`ModelParameter::piecewise_constant`]).
     pub fn new(sigma: impl Into<ModelParameter>, theta: impl Into<ModelParameter>) -> Self {
         Self {
             sigma: sigma.into(),
@@ -0,0 +1,82 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::models::model_parameter::ModelParameter;
+
+/// Struct containing the Kou (2002) double-exponential jump diffusion
+/// parameters. The Kou model is a Geometric Brownian Motion with jumps
+/// arriving as a Poisson process:
+/// $dS(t) = (\mu - \lambda \zeta) S(t) dt + \sigma S(t) dW(t) + (V - 1) S(t^-) dN(t)$,
+/// where the log jump size $Y = \ln V$ is asymmetric double-exponential:
+/// $Y \sim \text{Exp}(\eta_1)$ with probability $p$, and
+/// $-Y \sim \text{Exp}(\eta_2)$ with probability $1 - p$.
+/// $\zeta = E[V - 1]$ compensates the jumps.
+pub struct KouJumpDiffusion {
+    /// The drift ($\mu$).
+    pub mu: ModelParameter,
+
+    /// The volatility ($\sigma$).
+    pub sigma: ModelParameter,
+
+    /// The jump intensity ($\lambda$).
+    pub lambda: ModelParameter,
+
+    /// The probability of an upward jump ($p$).
+    pub p: f64,
+
+    /// The rate of the upward log jumps ($\eta_1$), with mean $1 / \eta_1$.
+    pub eta1: f64,
+
+    /// The rate of the downward log jumps ($\eta_2$), with mean $1 / \eta_2$.
+    pub eta2: f64,
+}
+
+impl KouJumpDiffusion {
+    /// Create a new Kou Jump Diffusion process.
+    /// # Arguments
+    /// * `mu` - The drift ($\mu$).
+    /// * `sigma` - The volatility ($\sigma$).
+    /// * `lambda` - The jump intensity ($\lambda$).
+    /// * `p` - The probability of an upward jump.
+    /// * `eta1` - The rate of the upward log jumps ($\eta_1$).
+    /// * `eta2` - The rate of the downward log jumps ($\eta_2$).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in $[0, 1]$, if `eta1 <= 1` (the mean jump,
+    /// and so the asset's mean, would be infinite), or if `eta2 <= 0`.
+    pub fn new(
+        mu: impl Into<ModelParameter>,
+        sigma: impl Into<ModelParameter>,
+        lambda: impl Into<ModelParameter>,
+        p: f64,
+        eta1: f64,
+        eta2: f64,
+    ) -> Self {
+        assert!((0.0..=1.0).contains(&p));
+        assert!(eta1 > 1.0 && eta2 > 0.0);
+
+        Self {
+            mu: mu.into(),
+            sigma: sigma.into(),
+            lambda: lambda.into(),
+            p,
+            eta1,
+            eta2,
+        }
+    }
+
+    /// Mean relative jump size,
+    /// $\zeta = E[V - 1] = \frac{p \eta_1}{\eta_1 - 1} + \frac{(1 - p) \eta_2}{\eta_2 + 1} - 1$.
+    #[must_use]
+    pub fn mean_jump(&self) -> f64 {
+        self.p * self.eta1 / (self.eta1 - 1.0) + (1.0 - self.p) * self.eta2 / (self.eta2 + 1.0)
+            - 1.0
+    }
+}
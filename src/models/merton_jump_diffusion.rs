@@ -7,12 +7,14 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::math::Gaussian;
+use crate::math::{Distribution, Gaussian};
 use crate::models::model_parameter::ModelParameter;
 
 /// Struct containing the Merton Jump Diffusion parameters.
-/// The Merton Jump Diffusion is a stochastic process that models a path-dependent option.
-/// It is a modification of the Geometric Brownian Motion where the end value is known.
+/// The Merton (1976) Jump Diffusion is a Geometric Brownian Motion with
+/// lognormal jumps arriving as a Poisson process:
+/// $dS(t) = (\mu - \lambda k) S(t) dt + \sigma S(t) dW(t) + (J - 1) S(t^-) dN(t)$,
+/// with $\ln J \sim N(m, v)$ and $k = E[J - 1]$ compensating the jumps.
 pub struct MertonJumpDiffusion {
     /// The drift ($\mu$) in percentage.
     pub mu: ModelParameter,
@@ -23,7 +25,7 @@ pub struct MertonJumpDiffusion {
     /// The jump intensity ($\lambda$) in percentage.
     pub lambda: ModelParameter,
 
-    /// The Gaussian distribution for the jump size.
+    /// The Gaussian distribution for the log jump size.
     pub gaussian: Gaussian,
 }
 
@@ -33,8 +35,8 @@ impl MertonJumpDiffusion {
     /// * `mu` - The drift ($\mu$) in percentage.
     /// * `sigma` - The volatility ($\sigma$) in percentage.
     /// * `lambda` - The jump intensity ($\lambda$) in percentage.
-    /// * `m` - The mean of the Gaussian distribution for the jump size.
-    /// * `v` - The variance of the Gaussian distribution for the jump size.
+    /// * `m` - The mean of the Gaussian distribution for the log jump size.
+    /// * `v` - The variance of the Gaussian distribution for the log jump size.
     pub fn new(
         mu: impl Into<ModelParameter>,
         sigma: impl Into<ModelParameter>,
@@ -49,4 +51,10 @@ impl MertonJumpDiffusion {
             gaussian: Gaussian::new(m, v),
         }
     }
+
+    /// Mean relative jump size, $k = E[J - 1] = e^{m + v / 2} - 1$.
+    #[must_use]
+    pub fn mean_jump(&self) -> f64 {
+        (self.gaussian.mean() + 0.5 * self.gaussian.variance()).exp_m1()
+    }
 }
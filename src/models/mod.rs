@@ -22,6 +22,10 @@ pub use model::*;
 pub mod arithmetic_brownian_motion;
 pub use arithmetic_brownian_motion::*;
 
+/// Bates stochastic volatility jump-diffusion model.
+pub mod bates;
+pub use bates::*;
+
 /// Black-Derman-Toy.
 pub mod black_derman_toy;
 pub use black_derman_toy::*;
@@ -74,6 +78,10 @@ pub use ho_lee::*;
 pub mod hull_white;
 pub use hull_white::*;
 
+/// Kou double-exponential Jump Diffusion.
+pub mod kou_jump_diffusion;
+pub use kou_jump_diffusion::*;
+
 /// Merton Jump Diffusion.
 pub mod merton_jump_diffusion;
 pub use merton_jump_diffusion::*;
@@ -94,6 +102,14 @@ pub use nelson_siegel_svensson::*;
 pub mod ornstein_uhlenbeck;
 pub use ornstein_uhlenbeck::*;
 
+/// Poisson and compound Poisson processes.
+pub mod poisson_process;
+pub use poisson_process::*;
+
 /// SABR: Stochastic Alpha, Beta, Rho.
 pub mod sabr;
 pub use sabr::*;
+
+/// Variance Gamma.
+pub mod variance_gamma;
+pub use variance_gamma::*;
@@ -21,6 +21,25 @@ pub struct ModelParameter(pub Box<dyn Fn(f64) -> f64 + Send + Sync>);
 // IMPLEMENTATIONS, AND FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl ModelParameter {
+    /// Piecewise-constant term structure: `values[0]` before `breakpoints[0]`,
+    /// `values[i]` on `[breakpoints[i - 1], breakpoints[i])`, and the last
+    /// value from the last breakpoint on.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there is one more value than breakpoints, and the
+    /// breakpoints are strictly increasing.
+    pub fn piecewise_constant(breakpoints: Vec<f64>, values: Vec<f64>) -> Self {
+        assert_eq!(values.len(), breakpoints.len() + 1);
+        assert!(breakpoints.windows(2).all(|w| w[0] < w[1]));
+
+        Self(Box::new(move |t| {
+            values[breakpoints.partition_point(|&breakpoint| breakpoint <= t)]
+        }))
+    }
+}
+
 impl fmt::Debug for ModelParameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "TimeDependent")
@@ -55,3 +74,27 @@ where
 //         x.partial_cmp(&y)
 //     }
 // }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_model_parameter {
+    use super::*;
+
+    #[test]
+    fn test_piecewise_constant() {
+        let parameter = ModelParameter::piecewise_constant(vec![1.0, 2.0], vec![0.1, 0.2, 0.3]);
+
+        assert_eq!(parameter.0(-1.0), 0.1);
+        assert_eq!(parameter.0(0.5), 0.1);
+        assert_eq!(parameter.0(1.0), 0.2);
+        assert_eq!(parameter.0(1.999), 0.2);
+        assert_eq!(parameter.0(2.0), 0.3);
+        assert_eq!(parameter.0(10.0), 0.3);
+
+        let constant = ModelParameter::piecewise_constant(vec![], vec![0.5]);
+        assert_eq!(constant.0(3.0), 0.5);
+    }
+}
@@ -7,7 +7,13 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::data::CurveModel;
+use crate::time::{today, DayCountConvention};
+use serde::{Deserialize, Serialize};
+use time::Date;
+
 /// Nelson-Siegel (1987) model parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct NelsonSiegel {
     /// $\beta_0$
     pub beta0: f64,
@@ -20,6 +26,28 @@ pub struct NelsonSiegel {
 
     /// $\lambda$
     pub lambda: f64,
+
+    /// Day count convention for the time to each date.
+    pub day_count_convention: DayCountConvention,
+}
+
+/// Labelled Nelson-Siegel parameters, e.g. for storing a fitted curve.
+///
+/// Serializes with named fields:
+/// `{"beta0": 4.0, "beta1": -1.5, "beta2": 1.0, "lambda": 2.0}`.
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct NelsonSiegelParams {
+    /// Long-run level, $\beta_0$.
+    pub beta0: f64,
+
+    /// Short-end slope, $\beta_1$.
+    pub beta1: f64,
+
+    /// Medium-term curvature, $\beta_2$.
+    pub beta2: f64,
+
+    /// Decay time, $\lambda$, in years.
+    pub lambda: f64,
 }
 
 impl NelsonSiegel {
@@ -31,6 +59,161 @@ impl NelsonSiegel {
             beta1,
             beta2,
             lambda,
+            day_count_convention: DayCountConvention::Actual_Actual_ISDA,
         }
     }
+
+    /// Set the day count convention (Actual/Actual ISDA by default).
+    #[must_use]
+    pub const fn with_day_count_convention(
+        mut self,
+        day_count_convention: DayCountConvention,
+    ) -> Self {
+        self.day_count_convention = day_count_convention;
+        self
+    }
+
+    /// Create a Nelson-Siegel model from labelled parameters, with the
+    /// default day count convention.
+    #[must_use]
+    pub const fn from_parameters(parameters: NelsonSiegelParams) -> Self {
+        Self::new(
+            parameters.beta0,
+            parameters.beta1,
+            parameters.beta2,
+            parameters.lambda,
+        )
+    }
+
+    /// Returns the labelled parameters of the model.
+    #[must_use]
+    pub const fn parameters(&self) -> NelsonSiegelParams {
+        NelsonSiegelParams {
+            beta0: self.beta0,
+            beta1: self.beta1,
+            beta2: self.beta2,
+            lambda: self.lambda,
+        }
+    }
+
+    /// Returns the model with all rates shifted by `bps` basis points.
+    /// The long-run level $\beta_0$ enters every spot and forward rate with
+    /// unit loading, so shifting it moves the whole curve in parallel,
+    /// keeping its shape (rates are in percent, so 1bp is 0.01).
+    #[must_use]
+    pub fn parallel_shifted(&self, bps: f64) -> Self {
+        Self {
+            beta0: self.beta0 + bps / 100.,
+            ..*self
+        }
+    }
+}
+
+impl CurveModel for NelsonSiegel {
+    /// Returns the forward rate for a given date.
+    fn forward_rate(&self, date: Date) -> f64 {
+        assert!(date > today(), "Date must be in the future.");
+
+        let tau = self.day_count_convention.day_count_factor(today(), date);
+
+        let term1 = f64::exp(-tau / self.lambda);
+        let term2 = (tau / self.lambda) * term1;
+
+        self.beta0 + self.beta1 * term1 + self.beta2 * term2
+    }
+
+    /// Returns the spot rate for a given date.
+    fn spot_rate(&self, date: Date) -> f64 {
+        assert!(date > today(), "Date must be in the future.");
+
+        let tau = self.day_count_convention.day_count_factor(today(), date);
+
+        let term1 = self.lambda * (1. - f64::exp(-tau / self.lambda)) / tau;
+        let term2 = term1 - f64::exp(-tau / self.lambda);
+
+        self.beta0 + self.beta1 * term1 + self.beta2 * term2
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        let tau = self.day_count_convention.day_count_factor(today(), date);
+
+        f64::exp(-self.spot_rate(date) * tau / 100.)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_nelson_siegel {
+    use super::*;
+    use crate::error::CurveError;
+    use time::Duration;
+
+    #[test]
+    fn test_parallel_shifted() {
+        let curve = NelsonSiegel::new(4.0, -1.5, 1.0, 2.0);
+        let shifted = curve.parallel_shifted(25.0);
+
+        for months in [1, 6, 12, 24, 60, 120, 360] {
+            let date = today() + Duration::days(30 * months);
+            let tau = DayCountConvention::default().day_count_factor(today(), date);
+
+            assert!((shifted.spot_rate(date) - curve.spot_rate(date) - 0.25).abs() < 1e-12);
+            assert!((shifted.forward_rate(date) - curve.forward_rate(date) - 0.25).abs() < 1e-12);
+
+            // Discount factors drop by exp(-25bp * tau).
+            let ratio = shifted.discount_factor(date) / curve.discount_factor(date);
+            assert!(ratio < 1.0);
+            assert!((ratio - (-0.0025 * tau).exp()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_parameters_round_trip() {
+        let curve = NelsonSiegel::new(4.0, -1.5, 1.0, 2.0);
+        let parameters = curve.parameters();
+
+        let json = serde_json::to_string(&parameters).unwrap();
+        assert_eq!(
+            json,
+            r#"{"beta0":4.0,"beta1":-1.5,"beta2":1.0,"lambda":2.0}"#
+        );
+
+        let restored = NelsonSiegel::from_parameters(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored, curve);
+
+        for months in [1, 6, 12, 24, 60, 120, 360] {
+            let date = today() + Duration::days(30 * months);
+
+            assert_eq!(restored.spot_rate(date), curve.spot_rate(date));
+            assert_eq!(restored.forward_rate(date), curve.forward_rate(date));
+            assert_eq!(restored.discount_factor(date), curve.discount_factor(date));
+        }
+    }
+
+    #[test]
+    fn test_validate_no_negative_forwards() {
+        let grid = (1..=30 * 12)
+            .map(|month| today() + Duration::days(30 * month))
+            .collect::<Vec<Date>>();
+
+        // Upward sloping curve, with a hump.
+        let well_behaved = NelsonSiegel::new(4.0, -1.5, 1.0, 2.0);
+        assert!(well_behaved.validate_no_negative_forwards(&grid).is_ok());
+
+        // The short end starts at beta0 + beta1 = -1%.
+        let pathological = NelsonSiegel::new(3.0, -4.0, 0.0, 1.0);
+
+        match pathological.validate_no_negative_forwards(&grid) {
+            Err(CurveError::NegativeForwardRate(date)) => assert_eq!(date, grid[0]),
+            other => panic!("Expected a negative forward rate, got {other:?}."),
+        }
+
+        assert!(matches!(
+            well_behaved.validate_no_negative_forwards(&[]),
+            Err(CurveError::NoPoints)
+        ));
+    }
 }
@@ -35,6 +35,9 @@ pub struct NelsonSiegelSvensson {
 
     /// $\lambda_2$
     pub lambda2: f64,
+
+    /// Day count convention for the time to each date.
+    pub day_count_convention: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -59,8 +62,19 @@ impl NelsonSiegelSvensson {
             beta3,
             lambda1,
             lambda2,
+            day_count_convention: DayCountConvention::Actual_Actual_ISDA,
         }
     }
+
+    /// Set the day count convention (Actual/Actual ISDA by default).
+    #[must_use]
+    pub const fn with_day_count_convention(
+        mut self,
+        day_count_convention: DayCountConvention,
+    ) -> Self {
+        self.day_count_convention = day_count_convention;
+        self
+    }
 }
 
 impl CurveModel for NelsonSiegelSvensson {
@@ -68,7 +82,7 @@ impl CurveModel for NelsonSiegelSvensson {
     fn forward_rate(&self, date: Date) -> f64 {
         assert!(date > today(), "Date must be in the future.");
 
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        let tau = self.day_count_convention.day_count_factor(today(), date);
 
         let term1 = f64::exp(-tau / self.lambda1);
         let term2 = (tau / self.lambda1) * term1;
@@ -81,7 +95,7 @@ impl CurveModel for NelsonSiegelSvensson {
     fn spot_rate(&self, date: Date) -> f64 {
         assert!(date > today(), "Date must be in the future.");
 
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        let tau = self.day_count_convention.day_count_factor(today(), date);
 
         let term1 = self.lambda1 * (1. - f64::exp(-tau / self.lambda1)) / tau;
         let term2 = term1 - f64::exp(-tau / self.lambda1);
@@ -92,7 +106,7 @@ impl CurveModel for NelsonSiegelSvensson {
     }
 
     fn discount_factor(&self, date: Date) -> f64 {
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        let tau = self.day_count_convention.day_count_factor(today(), date);
 
         f64::exp(-self.spot_rate(date) * tau / 100.)
     }
@@ -116,6 +130,7 @@ mod tests_nelson_siegel_svensson {
             beta3: -0.0198,
             lambda1: 1.58,
             lambda2: 0.15,
+            day_count_convention: DayCountConvention::default(),
         };
 
         let dates = (2..365 * 30)
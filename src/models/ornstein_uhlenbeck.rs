@@ -35,4 +35,88 @@ impl OrnsteinUhlenbeck {
             theta: theta.into(),
         }
     }
+
+    /// Zero-coupon bond price $P(t, T)$ in the Vasicek short rate model,
+    /// $dr = \theta (\mu - r) dt + \sigma dW$, given the short rate `r0` at `t`.
+    ///
+    /// The price is affine in the short rate:
+    ///
+    /// $$
+    /// P(t, T) = A(t, T) e^{-B(t, T) r_0}, \quad
+    /// B(t, T) = \frac{1 - e^{-\theta (T - t)}}{\theta},
+    /// $$
+    ///
+    /// $$
+    /// \ln A(t, T) = \left( \mu - \frac{\sigma^2}{2 \theta^2} \right) (B(t, T) - (T - t))
+    ///     - \frac{\sigma^2 B(t, T)^2}{4 \theta}.
+    /// $$
+    ///
+    /// The parameters are taken as constants, at their values at `t`.
+    /// Without mean reversion ($\theta = 0$) the limit
+    /// $B = T - t$, $\ln A = \sigma^2 (T - t)^3 / 6$ is used.
+    pub fn vasicek_zcb_price(&self, r0: f64, t: f64, T: f64) -> f64 {
+        assert!(T >= t, "Maturity must not precede the valuation time.");
+
+        let (mu, sigma, kappa) = (self.mu.0(t), self.sigma.0(t), self.theta.0(t));
+        let tau = T - t;
+
+        if kappa == 0.0 {
+            return (sigma * sigma * tau.powi(3) / 6.0 - tau * r0).exp();
+        }
+
+        let b = (1.0 - (-kappa * tau).exp()) / kappa;
+        let ln_a = (mu - sigma * sigma / (2.0 * kappa * kappa)) * (b - tau)
+            - sigma * sigma * b * b / (4.0 * kappa);
+
+        (ln_a - b * r0).exp()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_ornstein_uhlenbeck {
+    use super::*;
+
+    #[test]
+    fn test_vasicek_zcb_price() {
+        // (r0, reversion speed, long-run mean, sigma, T - t, price), computed by hand.
+        for (r0, kappa, mu, sigma, tau, expected) in [
+            (0.03, 0.3, 0.1, 0.03, 1.0, 0.961362489229),
+            (0.05, 0.5, 0.04, 0.01, 5.0, 0.804210699873),
+            (0.02, 1.0, 0.06, 0.02, 0.5, 0.985845725069),
+        ] {
+            let ou = OrnsteinUhlenbeck::new(mu, sigma, kappa);
+
+            assert_approx_equal!(ou.vasicek_zcb_price(r0, 0.0, tau), expected, 1e-12);
+            assert_approx_equal!(ou.vasicek_zcb_price(r0, 2.0, 2.0 + tau), expected, 1e-12);
+        }
+
+        assert_eq!(
+            OrnsteinUhlenbeck::new(0.1, 0.03, 0.3).vasicek_zcb_price(0.03, 1.0, 1.0),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_vasicek_zcb_price_deterministic_limit() {
+        let (r0, kappa, mu, t, T): (f64, f64, f64, f64, f64) = (0.05, 0.4, 0.02, 0.5, 4.0);
+        let tau = T - t;
+
+        // Without volatility the short rate decays deterministically to mu,
+        // r(s) = mu + (r0 - mu) exp(-kappa (s - t)), so P = exp(-int r).
+        let integral = mu * tau + (r0 - mu) * (1.0 - (-kappa * tau).exp()) / kappa;
+
+        for sigma in [1e-3, 1e-5, 0.0] {
+            let price = OrnsteinUhlenbeck::new(mu, sigma, kappa).vasicek_zcb_price(r0, t, T);
+
+            assert_approx_equal!(price, (-integral).exp(), 10.0 * sigma + 1e-15);
+        }
+
+        // Nor mean reversion: a constant rate.
+        let price = OrnsteinUhlenbeck::new(mu, 0.0, 0.0).vasicek_zcb_price(r0, t, T);
+        assert_approx_equal!(price, (-r0 * tau).exp(), 1e-15);
+    }
 }
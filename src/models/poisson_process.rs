@@ -0,0 +1,116 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::models::model_parameter::ModelParameter;
+use rand::{Rng, RngCore};
+use rand_distr::{Exp, Normal};
+
+/// Struct containing the Poisson process parameters:
+/// a counting process $N(t)$ with independent $\text{Poisson}(\lambda \Delta t)$
+/// increments.
+pub struct PoissonProcess {
+    /// The intensity ($\lambda$).
+    pub lambda: ModelParameter,
+}
+
+/// Struct containing the compound Poisson process parameters:
+/// $X(t) = \sum_{i=1}^{N(t)} J_i$, with $N$ a Poisson process and
+/// i.i.d. jump sizes $J_i$.
+pub struct CompoundPoissonProcess {
+    /// The intensity ($\lambda$).
+    pub lambda: ModelParameter,
+
+    /// The distribution of the jump sizes ($J_i$).
+    pub jump_distribution: JumpDistribution,
+}
+
+/// User-supplied jump size sampler.
+pub type JumpSampler = Box<dyn Fn(&mut dyn RngCore) -> f64 + Send + Sync>;
+
+/// Distribution of the jump sizes of a [`CompoundPoissonProcess`].
+pub enum JumpDistribution {
+    /// Every jump has the same size.
+    Constant(f64),
+
+    /// Normally distributed jumps.
+    Normal {
+        /// Mean jump size.
+        mean: f64,
+        /// Standard deviation of the jump sizes.
+        std_dev: f64,
+    },
+
+    /// Exponentially distributed (positive) jumps.
+    Exponential {
+        /// Rate, the inverse of the mean jump size.
+        rate: f64,
+    },
+
+    /// Asymmetric double-exponential jumps, as in the Kou model:
+    /// $\text{Exp}(\eta_1)$ with probability $p$, else $-\text{Exp}(\eta_2)$.
+    DoubleExponential {
+        /// Probability of an upward jump.
+        p: f64,
+        /// Rate of the upward jumps.
+        eta1: f64,
+        /// Rate of the downward jumps.
+        eta2: f64,
+    },
+
+    /// Jumps drawn by a user-supplied sampler.
+    Custom(JumpSampler),
+}
+
+impl PoissonProcess {
+    /// Create a new Poisson process.
+    /// # Arguments
+    /// * `lambda` - The intensity ($\lambda$).
+    pub fn new(lambda: impl Into<ModelParameter>) -> Self {
+        Self {
+            lambda: lambda.into(),
+        }
+    }
+}
+
+impl CompoundPoissonProcess {
+    /// Create a new compound Poisson process.
+    /// # Arguments
+    /// * `lambda` - The intensity ($\lambda$).
+    /// * `jump_distribution` - The distribution of the jump sizes.
+    pub fn new(lambda: impl Into<ModelParameter>, jump_distribution: JumpDistribution) -> Self {
+        Self {
+            lambda: lambda.into(),
+            jump_distribution,
+        }
+    }
+}
+
+impl JumpDistribution {
+    /// Draw a jump size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution's parameters are invalid
+    /// (e.g. a negative standard deviation or rate).
+    pub fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        match self {
+            Self::Constant(size) => *size,
+            Self::Normal { mean, std_dev } => rng.sample(Normal::new(*mean, *std_dev).unwrap()),
+            Self::Exponential { rate } => rng.sample(Exp::new(*rate).unwrap()),
+            Self::DoubleExponential { p, eta1, eta2 } => {
+                if rng.gen_bool(*p) {
+                    rng.sample(Exp::new(*eta1).unwrap())
+                } else {
+                    -rng.sample(Exp::new(*eta2).unwrap())
+                }
+            }
+            Self::Custom(sampler) => sampler(rng),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::models::model_parameter::ModelParameter;
+
+/// Struct containing the Variance Gamma (Madan, Carr, and Chang, 1998)
+/// parameters: a Brownian motion with drift $\theta$ and volatility
+/// $\sigma$, evaluated at a gamma time $G(t)$ with mean $t$ and variance $\nu t$.
+pub struct VarianceGamma {
+    /// The volatility of the Brownian motion ($\sigma$).
+    pub sigma: ModelParameter,
+
+    /// The drift of the Brownian motion ($\theta$), which controls the skew.
+    pub theta: ModelParameter,
+
+    /// The variance rate of the gamma time change ($\nu$),
+    /// which controls the kurtosis.
+    pub nu: ModelParameter,
+}
+
+impl VarianceGamma {
+    /// Create a new Variance Gamma process.
+    /// # Arguments
+    /// * `sigma` - The volatility of the Brownian motion ($\sigma$).
+    /// * `theta` - The drift of the Brownian motion ($\theta$).
+    /// * `nu` - The variance rate of the gamma time change ($\nu$).
+    pub fn new(
+        sigma: impl Into<ModelParameter>,
+        theta: impl Into<ModelParameter>,
+        nu: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            sigma: sigma.into(),
+            theta: theta.into(),
+            nu: nu.into(),
+        }
+    }
+}
@@ -73,12 +73,20 @@
 //! assert_approx_equal!(portfolio.profit(), 550.0 - portfolio.cost(), 1e-10);
 //! ```
 
+/// SPAN-style scenario margin for a book of options and futures.
+pub mod scenario_margin;
+pub use scenario_margin::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::{instruments::fx::currency::Currency, instruments::Instrument};
-use std::collections::HashMap;
+use crate::{
+    instruments::fx::currency::Currency, instruments::Instrument, math::Statistic,
+    stochastics::MultiTrajectories, time::Calendar,
+};
+use std::collections::{BTreeMap, HashMap};
+use time::Date;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
@@ -108,6 +116,40 @@ pub struct Position<I: Instrument> {
     pub currency: Option<Currency>,
 }
 
+/// Time decay ladder of a portfolio: the revaluation P&L of rolling the
+/// valuation date forward by 1 to N business days, holding markets fixed.
+pub struct ThetaLadder {
+    /// Horizon dates, one per business day rolled forward.
+    pub dates: Vec<Date>,
+
+    /// P&L of each position at each horizon date.
+    pub positions: HashMap<String, Vec<f64>>,
+
+    /// P&L aggregated by expiry month (keyed by the first day of the month),
+    /// with `None` for instruments without an expiry.
+    pub expiry_buckets: BTreeMap<Option<Date>, Vec<f64>>,
+
+    /// Total P&L of the portfolio at each horizon date.
+    pub total: Vec<f64>,
+}
+
+/// A market scenario to revalue a portfolio in, at a horizon date.
+#[derive(Debug, Clone)]
+pub struct MarketScenario {
+    /// Horizon (valuation) date of the scenario.
+    pub valuation_date: Date,
+
+    /// Underlying prices in the scenario, by position name.
+    /// Positions not listed keep their current underlying price.
+    pub underlying_prices: HashMap<String, f64>,
+
+    /// Parallel shift of all volatilities.
+    pub volatility_shift: f64,
+
+    /// Parallel shift of all interest rates.
+    pub rate_shift: f64,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -154,6 +196,38 @@ where
     }
 }
 
+impl MarketScenario {
+    /// Scenarios from simulated (e.g. correlated multi-asset) paths, one per
+    /// path, with the terminal value of asset `i` as the underlying price of
+    /// the position `names[i]`. Volatilities and rates are unshifted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not as many names as simulated assets.
+    pub fn from_trajectories(
+        names: &[&str],
+        trajectories: &MultiTrajectories,
+        valuation_date: Date,
+    ) -> Vec<Self> {
+        assert_eq!(names.len(), trajectories.paths.len());
+
+        let m_paths = trajectories.paths.first().map_or(0, Vec::len);
+
+        (0..m_paths)
+            .map(|j| Self {
+                valuation_date,
+                underlying_prices: names
+                    .iter()
+                    .zip(&trajectories.paths)
+                    .map(|(name, paths)| (name.to_string(), *paths[j].last().unwrap()))
+                    .collect(),
+                volatility_shift: 0.0,
+                rate_shift: 0.0,
+            })
+            .collect()
+    }
+}
+
 impl<I> Portfolio<I>
 where
     I: Instrument,
@@ -209,6 +283,169 @@ where
             .update_quantity(new_quantity);
     }
 
+    /// Returns the theta ladder of the portfolio: the P&L of rolling the
+    /// valuation date forward by 1 to `n_days` business days of the calendar,
+    /// holding markets fixed.
+    ///
+    /// Rolling over a weekend or holiday captures the decay of every calendar
+    /// day in between (e.g. Friday to Monday is three days of decay).
+    /// Positions expiring on or before a horizon date are valued by their
+    /// pricer at expiry (e.g. an option at its payoff).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the horizon runs past the last representable date, or if an
+    /// instrument cannot be repriced as of another date
+    /// (see [`Instrument::price_as_of`]).
+    pub fn theta_ladder<C: Calendar>(
+        &self,
+        valuation_date: Date,
+        calendar: &C,
+        n_days: usize,
+    ) -> ThetaLadder {
+        let mut dates = Vec::with_capacity(n_days);
+        let mut date = valuation_date;
+
+        while dates.len() < n_days {
+            date = date.next_day().unwrap();
+
+            if calendar.is_business_day(date) {
+                dates.push(date);
+            }
+        }
+
+        let positions: HashMap<String, Vec<f64>> = self
+            .positions
+            .iter()
+            .map(|(name, position)| {
+                let instrument = &position.instrument;
+                let quantity = position.quantity as f64;
+                let price_as_of = |date| {
+                    instrument.price_as_of(date).unwrap_or_else(|| {
+                        panic!(
+                            "{} cannot be repriced as of another date.",
+                            instrument.instrument_type()
+                        )
+                    })
+                };
+                let initial = price_as_of(valuation_date);
+
+                let pnl = dates
+                    .iter()
+                    .map(|&date| quantity * (price_as_of(date) - initial))
+                    .collect();
+
+                (name.clone(), pnl)
+            })
+            .collect();
+
+        let mut expiry_buckets: BTreeMap<Option<Date>, Vec<f64>> = BTreeMap::new();
+
+        for (name, pnl) in &positions {
+            let bucket = self.positions[name]
+                .instrument
+                .expiration_date()
+                .map(|expiry| expiry.replace_day(1).unwrap());
+
+            let bucket_pnl = expiry_buckets
+                .entry(bucket)
+                .or_insert_with(|| vec![0.0; n_days]);
+
+            for (total, value) in bucket_pnl.iter_mut().zip(pnl) {
+                *total += value;
+            }
+        }
+
+        let total = (0..n_days)
+            .map(|k| positions.values().map(|pnl| pnl[k]).sum())
+            .collect();
+
+        ThetaLadder {
+            dates,
+            positions,
+            expiry_buckets,
+            total,
+        }
+    }
+
+    /// Returns the P&L of the portfolio in each scenario: the change in
+    /// (model) value from repricing every position in the scenario's market.
+    /// Positions expiring on or before the scenario date are valued by
+    /// their pricer at expiry (e.g. an option at its payoff).
+    ///
+    /// # Panics
+    ///
+    /// Panics if an instrument cannot be repriced in a shocked market
+    /// (see [`Instrument::price_in_market`]).
+    pub fn scenario_pnl(&self, scenarios: &[MarketScenario]) -> Vec<f64> {
+        scenarios
+            .iter()
+            .map(|scenario| {
+                self.positions
+                    .iter()
+                    .map(|(name, position)| {
+                        let instrument = &position.instrument;
+
+                        let value = instrument
+                            .price_in_market(
+                                scenario.valuation_date,
+                                scenario.underlying_prices.get(name).copied(),
+                                scenario.volatility_shift,
+                                scenario.rate_shift,
+                            )
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "{} cannot be repriced in a market scenario.",
+                                    instrument.instrument_type()
+                                )
+                            });
+
+                        position.quantity as f64 * (value - instrument.price())
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Returns the value at risk at the given confidence level (e.g. 0.99):
+    /// the `confidence` quantile of the losses over the scenarios.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scenarios` is empty, `confidence` is not in [0, 1], or an
+    /// instrument cannot be repriced in a shocked market.
+    pub fn value_at_risk(&self, scenarios: &[MarketScenario], confidence: f64) -> f64 {
+        self.losses(scenarios).quantile(confidence)
+    }
+
+    /// Returns the expected shortfall at the given confidence level:
+    /// the average of the losses at or beyond the value at risk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scenarios` is empty, `confidence` is not in [0, 1], or an
+    /// instrument cannot be repriced in a shocked market.
+    pub fn expected_shortfall(&self, scenarios: &[MarketScenario], confidence: f64) -> f64 {
+        let losses = self.losses(scenarios);
+        let var = losses.quantile(confidence);
+
+        // As the mean excess over the VaR, so that ES >= VaR exactly.
+        let excess: Vec<f64> = losses
+            .into_iter()
+            .filter(|&loss| loss >= var)
+            .map(|loss| loss - var)
+            .collect();
+
+        var + excess.iter().sum::<f64>() / excess.len() as f64
+    }
+
+    fn losses(&self, scenarios: &[MarketScenario]) -> Vec<f64> {
+        self.scenario_pnl(scenarios)
+            .into_iter()
+            .map(|pnl| -pnl)
+            .collect()
+    }
+
     /// Returns the current weights of all positions
     pub fn position_weights(&self) -> HashMap<String, f32> {
         let current_value = self.value();
@@ -235,9 +472,12 @@ mod tests_portfolio {
     use crate::{
         instruments::fx::*,
         instruments::options::{BlackScholesMerton, TypeFlag},
+        stochastics::{MultiGeometricBrownianMotion, StochasticProcessConfig},
+        time::north_america::united_states::UnitedStatesCalendar,
         time::today,
     };
-    use time::Duration;
+    use nalgebra::DMatrix;
+    use time::{macros::date, Duration};
 
     fn setup_test_portfolio() -> Portfolio<BlackScholesMerton> {
         // Create a position of 100 call options.
@@ -309,4 +549,201 @@ mod tests_portfolio {
         assert_eq!(weights.get("Put Options"), Some(&0.36363637));
         assert_eq!(weights.get("Call Options"), Some(&0.6363636));
     }
+
+    fn call(evaluation_date: Date, expiration_date: Date) -> BlackScholesMerton {
+        BlackScholesMerton::new(
+            0.03,
+            100.0,
+            100.0,
+            0.2,
+            0.03,
+            Some(evaluation_date),
+            expiration_date,
+            TypeFlag::Call,
+        )
+    }
+
+    #[test]
+    fn test_value_at_risk_long_call() {
+        let valuation_date = date!(2024 - 03 - 04);
+        let horizon = valuation_date + Duration::days(10);
+        let option = call(valuation_date, date!(2025 - 03 - 04));
+        let premium = option.price();
+
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Call".to_string(),
+            Position::new(option, 10, premium, premium, None),
+        )]));
+
+        // Ten days of the underlying, from the multi-asset simulator.
+        let process =
+            MultiGeometricBrownianMotion::new(vec![0.03], vec![0.2], DMatrix::identity(1, 1))
+                .unwrap();
+        let config =
+            StochasticProcessConfig::new(100.0, 0.0, 10.0 / 365.0, 10, 5_000, false).with_seed(1);
+        let paths = process.euler_maruyama(&[100.0], &config);
+        let mut scenarios = MarketScenario::from_trajectories(&["Call"], &paths, horizon);
+
+        // Add crash scenarios, where the option becomes worthless.
+        for scenario in scenarios.iter_mut().take(100) {
+            scenario.underlying_prices.insert("Call".to_string(), 1.0);
+            scenario.volatility_shift = -0.1;
+        }
+
+        let var = portfolio.value_at_risk(&scenarios, 0.99);
+        let es = portfolio.expected_shortfall(&scenarios, 0.99);
+
+        // A long option can lose at most its premium.
+        assert!(var > 0.0);
+        assert!(var <= 10.0 * premium);
+        assert!(es >= var);
+        assert!(es <= 10.0 * premium);
+
+        // Without the crashes, the 99% loss is a ~2.3 sigma move over ten days.
+        let normal = &scenarios[100..];
+        let normal_var = portfolio.value_at_risk(normal, 0.99);
+
+        assert!(normal_var < var);
+        assert!(portfolio.expected_shortfall(normal, 0.99) >= normal_var);
+    }
+
+    #[test]
+    fn test_scenario_pnl_after_expiry() {
+        let valuation_date = date!(2024 - 03 - 04);
+        let option = call(valuation_date, date!(2024 - 03 - 15));
+        let premium = option.price();
+
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Call".to_string(),
+            Position::new(option, 10, premium, premium, None),
+        )]));
+
+        let scenario = |underlying_price: f64| MarketScenario {
+            valuation_date: date!(2024 - 03 - 18),
+            underlying_prices: HashMap::from([("Call".to_string(), underlying_price)]),
+            volatility_shift: 0.0,
+            rate_shift: 0.0,
+        };
+
+        // The expired option is worth its payoff, not nothing.
+        let pnl = portfolio.scenario_pnl(&[scenario(120.0), scenario(80.0)]);
+
+        assert_approx_equal!(pnl[0], 10.0 * (20.0 - premium), 1e-10);
+        assert_approx_equal!(pnl[1], -10.0 * premium, 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be repriced in a market scenario")]
+    fn test_scenario_pnl_unsupported_instrument() {
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Cash".to_string(),
+            Position::new(USD, 100, 1.0, 1.0, None),
+        )]));
+
+        let scenario = MarketScenario {
+            valuation_date: today(),
+            underlying_prices: HashMap::new(),
+            volatility_shift: 0.0,
+            rate_shift: 0.01,
+        };
+
+        portfolio.scenario_pnl(&[scenario]);
+    }
+
+    #[test]
+    fn test_theta_ladder_weekend() {
+        let expiry = date!(2024 - 06 - 21);
+        let theta_from = |valuation_date: Date| {
+            let portfolio = Portfolio::new(HashMap::from([(
+                "Call".to_string(),
+                Position::new(call(valuation_date, expiry), 1, 0.0, 0.0, None),
+            )]));
+
+            portfolio.theta_ladder(valuation_date, &UnitedStatesCalendar, 1)
+        };
+
+        // Friday to Monday is three calendar days of decay.
+        let friday = theta_from(date!(2024 - 03 - 08));
+        let tuesday = theta_from(date!(2024 - 03 - 05));
+
+        assert_eq!(friday.dates, vec![date!(2024 - 03 - 11)]);
+        assert_eq!(tuesday.dates, vec![date!(2024 - 03 - 06)]);
+        assert!(friday.total[0] < 0.0);
+        assert_approx_equal!(friday.total[0] / tuesday.total[0], 3.0, 0.05);
+    }
+
+    #[test]
+    fn test_theta_ladder_expiry_buckets() {
+        let valuation_date = date!(2024 - 03 - 04);
+
+        let near = call(valuation_date, date!(2024 - 03 - 07));
+        let near_value = near.price();
+        let near_itm = BlackScholesMerton {
+            strike_price: 90.0,
+            ..call(valuation_date, date!(2024 - 03 - 07))
+        };
+        let near_itm_value = near_itm.price();
+
+        let portfolio = Portfolio::new(HashMap::from([
+            ("Near".to_string(), Position::new(near, 10, 0.0, 0.0, None)),
+            (
+                "Near (ITM)".to_string(),
+                Position::new(near_itm, 4, 0.0, 0.0, None),
+            ),
+            (
+                "June".to_string(),
+                Position::new(
+                    call(valuation_date, date!(2024 - 06 - 21)),
+                    5,
+                    0.0,
+                    0.0,
+                    None,
+                ),
+            ),
+            (
+                "June (2)".to_string(),
+                Position::new(
+                    call(valuation_date, date!(2024 - 06 - 28)),
+                    2,
+                    0.0,
+                    0.0,
+                    None,
+                ),
+            ),
+        ]));
+
+        let ladder = portfolio.theta_ladder(valuation_date, &UnitedStatesCalendar, 5);
+
+        assert_eq!(ladder.dates.len(), 5);
+
+        // The near options expire on the third business day, and are then
+        // worth their payoff: nothing at the money, and 10 in the money.
+        for k in 2..5 {
+            assert_approx_equal!(ladder.positions["Near"][k], -10.0 * near_value, 1e-10);
+            assert_approx_equal!(
+                ladder.positions["Near (ITM)"][k],
+                4.0 * (10.0 - near_itm_value),
+                1e-10
+            );
+        }
+        assert!(ladder.positions["Near"][1] > -10.0 * near_value);
+
+        // Totals equal the sum of the position and of the bucket revaluations.
+        assert_eq!(ladder.expiry_buckets.len(), 2);
+
+        let june = &ladder.expiry_buckets[&Some(date!(2024 - 06 - 01))];
+
+        for k in 0..5 {
+            let by_position: f64 = ladder.positions.values().map(|pnl| pnl[k]).sum();
+            let by_bucket: f64 = ladder.expiry_buckets.values().map(|pnl| pnl[k]).sum();
+
+            assert_approx_equal!(ladder.total[k], by_position, 1e-10);
+            assert_approx_equal!(ladder.total[k], by_bucket, 1e-10);
+            assert_approx_equal!(
+                june[k],
+                ladder.positions["June"][k] + ladder.positions["June (2)"][k],
+                1e-10
+            );
+        }
+    }
 }
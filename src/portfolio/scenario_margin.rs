@@ -0,0 +1,411 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! SPAN-style scenario margin for a book of options and futures on one
+//! underlying.
+//!
+//! The book is fully revalued in 16 scenarios: the underlying price
+//! unchanged, or moved up or down by a third, two thirds, or all of the
+//! price scan range, each with the volatility up or down by the volatility
+//! scan range, plus two extreme moves (up and down by a multiple of the
+//! price scan range) of which only a fraction of the loss counts. The scan
+//! risk is the largest (weighted) loss.
+//!
+//! Scenarios move every contract month by the same amount, so spreads
+//! between months have no scan risk. The inter-month spread charge covers
+//! this: the net deltas of the months are paired off, longs against
+//! shorts, and each unit of delta spread is charged.
+
+use crate::instruments::options::BlackScholesMerton;
+use crate::instruments::Instrument;
+use std::collections::BTreeMap;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Instrument in a margin book.
+pub enum MarginInstrument {
+    /// Option on the underlying, revalued with its model.
+    Option(BlackScholesMerton),
+
+    /// Futures contract on the underlying, expiring on the given date,
+    /// whose price moves one for one with the underlying.
+    Future(Date),
+}
+
+/// Position in a margin book.
+pub struct MarginPosition {
+    /// The instrument.
+    pub instrument: MarginInstrument,
+
+    /// Signed quantity: positive if long, negative if short.
+    pub quantity: f64,
+}
+
+/// Book of options and futures on one underlying.
+pub struct MarginBook {
+    /// Positions in the book.
+    pub positions: Vec<MarginPosition>,
+}
+
+/// Parameters of the scenario margin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanParameters {
+    /// Price scan range, in units of the underlying price.
+    pub price_scan_range: f64,
+
+    /// Volatility scan range, as an absolute shift (e.g. 0.04 for four
+    /// volatility points).
+    pub volatility_scan_range: f64,
+
+    /// Size of the extreme moves, as a multiple of the price scan range.
+    pub extreme_move_multiple: f64,
+
+    /// Fraction of the loss in the extreme moves that counts.
+    pub extreme_move_weight: f64,
+
+    /// Charge per unit of delta spread between contract months.
+    pub intermonth_spread_charge: f64,
+}
+
+/// Scenario of the scan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanScenario {
+    /// Move in the underlying price, as a fraction of the price scan range.
+    pub price_move: f64,
+
+    /// Move in the volatility, as a fraction of the volatility scan range.
+    pub volatility_move: f64,
+
+    /// Fraction of the loss in the scenario that counts.
+    pub weight: f64,
+}
+
+/// Scenario margin of a book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioMargin {
+    /// Weighted loss of the book in each scenario.
+    pub scenario_losses: Vec<f64>,
+
+    /// Largest weighted loss over the scenarios (at least zero).
+    pub scan_risk: f64,
+
+    /// Net delta of each contract month (keyed by its first day).
+    pub month_deltas: BTreeMap<Date, f64>,
+
+    /// Inter-month spread charge.
+    pub spread_charge: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarginInstrument {
+    /// Expiry date of the instrument.
+    #[must_use]
+    pub fn expiry(&self) -> Date {
+        match self {
+            MarginInstrument::Option(option) => option.expiration_date,
+            MarginInstrument::Future(expiry) => *expiry,
+        }
+    }
+
+    /// Change in value of one unit when the underlying price moves by
+    /// `price_move` and the volatility by `volatility_shift`.
+    #[must_use]
+    pub fn revaluation(&self, price_move: f64, volatility_shift: f64) -> f64 {
+        match self {
+            MarginInstrument::Option(option) => {
+                option.price_in_market(
+                    option.valuation_date(),
+                    Some(option.underlying_price + price_move),
+                    volatility_shift,
+                    0.0,
+                ) - option.price()
+            }
+            MarginInstrument::Future(_) => price_move,
+        }
+    }
+
+    /// Delta of one unit of the instrument.
+    #[must_use]
+    pub fn delta(&self) -> f64 {
+        match self {
+            MarginInstrument::Option(option) => option.delta(),
+            MarginInstrument::Future(_) => 1.0,
+        }
+    }
+}
+
+impl MarginPosition {
+    /// Create a new position.
+    #[must_use]
+    pub fn new(instrument: MarginInstrument, quantity: f64) -> Self {
+        Self {
+            instrument,
+            quantity,
+        }
+    }
+}
+
+impl ScanParameters {
+    /// Scan parameters with the usual SPAN extreme moves: three times the
+    /// price scan range, with 35% of the loss counting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the parameters is negative.
+    #[must_use]
+    pub fn new(
+        price_scan_range: f64,
+        volatility_scan_range: f64,
+        intermonth_spread_charge: f64,
+    ) -> Self {
+        assert!(price_scan_range >= 0.0);
+        assert!(volatility_scan_range >= 0.0);
+        assert!(intermonth_spread_charge >= 0.0);
+
+        Self {
+            price_scan_range,
+            volatility_scan_range,
+            extreme_move_multiple: 3.0,
+            extreme_move_weight: 0.35,
+            intermonth_spread_charge,
+        }
+    }
+
+    /// The 16 scenarios of the scan, in the usual SPAN order.
+    #[must_use]
+    pub fn scenarios(&self) -> [ScanScenario; 16] {
+        let scenario = |price_move, volatility_move, weight| ScanScenario {
+            price_move,
+            volatility_move,
+            weight,
+        };
+
+        let mut scenarios = [scenario(0.0, 0.0, 1.0); 16];
+
+        for (i, price_move) in [0.0, 1.0, -1.0, 2.0, -2.0, 3.0, -3.0].iter().enumerate() {
+            scenarios[2 * i] = scenario(price_move / 3.0, 1.0, 1.0);
+            scenarios[2 * i + 1] = scenario(price_move / 3.0, -1.0, 1.0);
+        }
+
+        let (multiple, weight) = (self.extreme_move_multiple, self.extreme_move_weight);
+        scenarios[14] = scenario(multiple, 0.0, weight);
+        scenarios[15] = scenario(-multiple, 0.0, weight);
+
+        scenarios
+    }
+}
+
+impl MarginBook {
+    /// Create a new book.
+    #[must_use]
+    pub fn new(positions: Vec<MarginPosition>) -> Self {
+        Self { positions }
+    }
+
+    /// Weighted loss of the book in a scenario.
+    #[must_use]
+    pub fn scenario_loss(&self, scenario: &ScanScenario, parameters: &ScanParameters) -> f64 {
+        let price_move = scenario.price_move * parameters.price_scan_range;
+        let volatility_shift = scenario.volatility_move * parameters.volatility_scan_range;
+
+        let pnl: f64 = self
+            .positions
+            .iter()
+            .map(|position| {
+                position.quantity
+                    * position
+                        .instrument
+                        .revaluation(price_move, volatility_shift)
+            })
+            .sum();
+
+        -scenario.weight * pnl
+    }
+
+    /// Net delta of each contract month (keyed by its first day).
+    #[must_use]
+    pub fn month_deltas(&self) -> BTreeMap<Date, f64> {
+        let mut deltas = BTreeMap::new();
+
+        for position in &self.positions {
+            let month = position.instrument.expiry().replace_day(1).unwrap();
+
+            *deltas.entry(month).or_insert(0.0) += position.quantity * position.instrument.delta();
+        }
+
+        deltas
+    }
+
+    /// Scenario margin of the book: the scan risk plus the inter-month
+    /// spread charge.
+    #[must_use]
+    pub fn scenario_margin(&self, parameters: &ScanParameters) -> ScenarioMargin {
+        let scenario_losses: Vec<f64> = parameters
+            .scenarios()
+            .iter()
+            .map(|scenario| self.scenario_loss(scenario, parameters))
+            .collect();
+
+        let scan_risk = scenario_losses
+            .iter()
+            .fold(0.0_f64, |max, &loss| max.max(loss));
+
+        // Long month deltas are paired off against short ones.
+        let month_deltas = self.month_deltas();
+        let long: f64 = month_deltas.values().filter(|&&d| d > 0.0).sum();
+        let short: f64 = -month_deltas.values().filter(|&&d| d < 0.0).sum::<f64>();
+
+        ScenarioMargin {
+            scenario_losses,
+            scan_risk,
+            month_deltas,
+            spread_charge: long.min(short) * parameters.intermonth_spread_charge,
+        }
+    }
+}
+
+impl ScenarioMargin {
+    /// Total margin: the scan risk plus the spread charge.
+    #[must_use]
+    pub fn margin(&self) -> f64 {
+        self.scan_risk + self.spread_charge
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_scenario_margin {
+    use super::*;
+    use crate::instruments::options::TypeFlag;
+    use time::macros::date;
+
+    const VALUATION_DATE: Date = date!(2024 - 01 - 15);
+
+    fn option(strike: f64, expiry: Date, type_flag: TypeFlag) -> MarginInstrument {
+        MarginInstrument::Option(BlackScholesMerton::new(
+            0.0,
+            100.0,
+            strike,
+            0.25,
+            0.03,
+            Some(VALUATION_DATE),
+            expiry,
+            type_flag,
+        ))
+    }
+
+    fn parameters() -> ScanParameters {
+        ScanParameters::new(8.0, 0.04, 1.5)
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let scenarios = parameters().scenarios();
+
+        // Every combination of the price and volatility moves, once.
+        for price_move in [0.0, 1.0, -1.0, 2.0, -2.0, 3.0, -3.0] {
+            for volatility_move in [1.0, -1.0] {
+                let count = scenarios
+                    .iter()
+                    .filter(|s| {
+                        s.price_move == price_move / 3.0
+                            && s.volatility_move == volatility_move
+                            && s.weight == 1.0
+                    })
+                    .count();
+
+                assert_eq!(count, 1);
+            }
+        }
+
+        assert_eq!(scenarios[14].price_move, 3.0);
+        assert_eq!(scenarios[15].price_move, -3.0);
+        assert_eq!(scenarios[14].weight, 0.35);
+    }
+
+    #[test]
+    fn test_naked_short_option() {
+        let call = option(105.0, date!(2024 - 03 - 15), TypeFlag::Call);
+        let book = MarginBook::new(vec![MarginPosition::new(call, -10.0)]);
+        let parameters = parameters();
+
+        let margin = book.scenario_margin(&parameters);
+
+        // The worst loss, from revaluing the option in each scenario.
+        let MarginInstrument::Option(option) = &book.positions[0].instrument else {
+            unreachable!()
+        };
+        let worst = parameters
+            .scenarios()
+            .iter()
+            .map(|s| {
+                let shocked = option.price_in_market(
+                    VALUATION_DATE,
+                    Some(100.0 + s.price_move * 8.0),
+                    s.volatility_move * 0.04,
+                    0.0,
+                );
+
+                s.weight * 10.0 * (shocked - option.price())
+            })
+            .fold(f64::MIN, f64::max);
+
+        assert!(worst > 0.0);
+        assert_eq!(margin.spread_charge, 0.0);
+        assert_approx_equal!(margin.scan_risk, worst, 1e-10);
+        assert_approx_equal!(margin.margin(), worst, 1e-10);
+    }
+
+    #[test]
+    fn test_calendar_spread() {
+        let book = MarginBook::new(vec![
+            MarginPosition::new(MarginInstrument::Future(date!(2024 - 03 - 15)), 5.0),
+            MarginPosition::new(MarginInstrument::Future(date!(2024 - 06 - 21)), -5.0),
+        ]);
+
+        let margin = book.scenario_margin(&parameters());
+
+        assert_eq!(margin.scan_risk, 0.0);
+        assert_eq!(margin.month_deltas.len(), 2);
+        assert_approx_equal!(margin.margin(), 5.0 * 1.5, 1e-12);
+    }
+
+    #[test]
+    fn test_hedging_future() {
+        let expiry = date!(2024 - 03 - 15);
+        let call = option(100.0, expiry, TypeFlag::Call);
+        let delta = call.delta();
+
+        let naked = MarginBook::new(vec![MarginPosition::new(call, -10.0)]);
+
+        let hedged = MarginBook::new(vec![
+            MarginPosition::new(option(100.0, expiry, TypeFlag::Call), -10.0),
+            MarginPosition::new(MarginInstrument::Future(expiry), 10.0 * delta),
+        ]);
+
+        let (naked, hedged) = (
+            naked.scenario_margin(&parameters()),
+            hedged.scenario_margin(&parameters()),
+        );
+
+        // The hedge is in the same month, so there is no spread charge.
+        assert_eq!(hedged.spread_charge, 0.0);
+        assert_approx_equal!(hedged.month_deltas[&date!(2024 - 03 - 01)], 0.0, 1e-12);
+        assert!(hedged.margin() < naked.margin());
+    }
+}
@@ -0,0 +1,306 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Exposure profiles for counterparty credit risk (e.g. CVA).
+//!
+//! The instrument is marked to market on every simulated path at every
+//! time step. The exposure is the positive part of the mark-to-market,
+//! $E(t) = \max(V(t), 0)$, and its profile over time is summarised by
+//!
+//! - the expected exposure, $EE(t) = \mathbb{E}[E(t)]$, and
+//! - the potential future exposure, $PFE_q(t)$, the $q$-quantile of $E(t)$.
+//!
+//! Both are in time-$t$ money (undiscounted).
+//!
+//! The unilateral credit value adjustment integrates the discounted expected
+//! exposure against the counterparty's default density:
+//!
+//! $$
+//! CVA = (1 - R) \int_0^T P(t) \, EE(t) \, dQ^d(t)
+//!     \approx (1 - R) \sum_i \frac{P(t_{i-1}) EE(t_{i-1}) + P(t_i) EE(t_i)}{2}
+//!       \left( Q(t_{i-1}) - Q(t_i) \right)
+//! $$
+//!
+//! where $Q$ is the survival probability and $R$ the recovery rate, and
+//! exposure and default are assumed independent (no wrong-way risk).
+
+use super::MonteCarloEngine;
+use crate::data::HazardRateCurve;
+use crate::math::Statistic;
+use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Value of an instrument, given the underlying at a (simulation) time.
+///
+/// Implemented for closures `|underlying, time| value`, so analytic prices
+/// can be plugged in directly.
+pub trait MarkToMarket {
+    /// Mark-to-market value when the underlying is `underlying` at `time`.
+    fn mark_to_market(&self, underlying: f64, time: f64) -> f64;
+}
+
+/// Expected and potential future exposure at each simulation time.
+#[derive(Debug, Clone)]
+pub struct ExposureProfile {
+    /// Simulation times.
+    pub times: Vec<f64>,
+
+    /// Expected (positive) exposure at each time.
+    pub expected_exposure: Vec<f64>,
+
+    /// Potential future exposure (the quantile of the exposure) at each time.
+    pub potential_future_exposure: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F> MarkToMarket for F
+where
+    F: Fn(f64, f64) -> f64,
+{
+    fn mark_to_market(&self, underlying: f64, time: f64) -> f64 {
+        self(underlying, time)
+    }
+}
+
+impl MonteCarloEngine {
+    /// Exposure profile of an instrument over Euler-Maruyama paths of the
+    /// underlying, with the PFE at the given `quantile` (e.g. 0.95).
+    ///
+    /// # Arguments
+    ///
+    /// * `instrument` - Marks the instrument to market on each path.
+    /// * `process` - The [StochasticProcess] of the underlying.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `quantile` - Quantile of the exposure reported as the PFE.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quantile` is not between 0 and 1.
+    pub fn expected_exposure<I, S>(
+        &self,
+        instrument: &I,
+        process: &S,
+        config: &StochasticProcessConfig,
+        quantile: f64,
+    ) -> ExposureProfile
+    where
+        I: MarkToMarket + ?Sized,
+        S: StochasticProcess + ?Sized,
+    {
+        assert!(
+            (0.0..=1.0).contains(&quantile),
+            "Quantile must be between 0 and 1."
+        );
+
+        let trajectories = process.euler_maruyama(config);
+        let n_times = trajectories.times.len();
+
+        let mut expected_exposure = Vec::with_capacity(n_times);
+        let mut potential_future_exposure = Vec::with_capacity(n_times);
+
+        for (k, &time) in trajectories.times.iter().enumerate() {
+            let exposures: Vec<f64> = trajectories
+                .paths
+                .iter()
+                .map(|path| instrument.mark_to_market(path[k], time).max(0.0))
+                .collect();
+
+            expected_exposure.push(exposures.mean());
+            potential_future_exposure.push(exposures.quantile(quantile));
+        }
+
+        ExposureProfile {
+            times: trajectories.times,
+            expected_exposure,
+            potential_future_exposure,
+        }
+    }
+}
+
+/// Unilateral credit value adjustment of an exposure profile.
+///
+/// The discounted expected exposure is averaged over each simulation
+/// period (trapezoidal rule) and weighted by the probability of default
+/// in that period.
+///
+/// # Arguments
+///
+/// * `exposure_profile` - The [ExposureProfile] of the netting set.
+/// * `hazard_curve` - The counterparty's [HazardRateCurve].
+/// * `discount_curve` - Discount factor to each time of the profile.
+/// * `recovery` - Recovery rate, as a decimal.
+///
+/// # Panics
+///
+/// Panics if `recovery` is not between 0 and 1.
+pub fn credit_value_adjustment<D>(
+    exposure_profile: &ExposureProfile,
+    hazard_curve: &HazardRateCurve,
+    discount_curve: D,
+    recovery: f64,
+) -> f64
+where
+    D: Fn(f64) -> f64,
+{
+    assert!(
+        (0.0..=1.0).contains(&recovery),
+        "Recovery must be between 0 and 1."
+    );
+
+    let discounted_exposure: Vec<f64> = exposure_profile
+        .times
+        .iter()
+        .zip(&exposure_profile.expected_exposure)
+        .map(|(&t, &ee)| discount_curve(t) * ee)
+        .collect();
+
+    let expected_loss: f64 = exposure_profile
+        .times
+        .windows(2)
+        .zip(discounted_exposure.windows(2))
+        .map(|(t, exposure)| {
+            0.5 * (exposure[0] + exposure[1]) * hazard_curve.default_probability(t[0], t[1])
+        })
+        .sum();
+
+    (1.0 - recovery) * expected_loss
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_exposure {
+    use super::*;
+    use crate::math::distributions::{Distribution, Gaussian};
+    use crate::models::GeometricBrownianMotion;
+
+    #[test]
+    fn test_forward_exposure_profile() {
+        let (spot, rate, sigma, maturity) = (100.0, 0.03, 0.2, 1.0);
+        let strike = spot * f64::exp(rate * maturity);
+
+        // Long forward, struck at the initial forward price (worth zero).
+        let forward =
+            |underlying: f64, time: f64| underlying - strike * (-rate * (maturity - time)).exp();
+
+        let process = GeometricBrownianMotion::new(rate, sigma);
+        let config =
+            StochasticProcessConfig::new(spot, 0.0, maturity, 20, 20_000, true).with_seed(11);
+
+        let profile = MonteCarloEngine::new().expected_exposure(&forward, &process, &config, 0.95);
+
+        assert_eq!(profile.times.len(), 21);
+        assert_eq!(profile.expected_exposure[0], 0.0);
+
+        // The exposure is a call on S(t) struck at the forward S(0) e^{rt}:
+        // EE(t) = S(0) e^{rt} (2 N(sigma sqrt(t) / 2) - 1), about
+        // S(0) sigma sqrt(t / (2 pi)), so it grows with the square root of time.
+        let n = Gaussian::default();
+
+        for (k, &t) in profile.times.iter().enumerate().skip(1) {
+            let (ee, pfe) = (
+                profile.expected_exposure[k],
+                profile.potential_future_exposure[k],
+            );
+            let exact = spot * (rate * t).exp() * (2.0 * n.cdf(0.5 * sigma * t.sqrt()) - 1.0);
+
+            assert_approx_equal!(ee, exact, 0.03 * exact);
+            assert_approx_equal!(
+                ee / t.sqrt(),
+                spot * sigma / (2.0 * std::f64::consts::PI).sqrt(),
+                0.5
+            );
+            assert!(pfe > ee);
+        }
+
+        let ee_at = |t: f64| profile.expected_exposure[(t * 20.0).round() as usize];
+        assert_approx_equal!(ee_at(1.0) / ee_at(0.25), 2.0, 0.1);
+
+        // The 95% PFE at maturity is the 95% quantile of S(T) less the strike.
+        let quantile =
+            spot * ((rate - 0.5 * sigma * sigma) * maturity + sigma * 1.644_853_626_951).exp();
+        assert_approx_equal!(
+            profile.potential_future_exposure[20],
+            quantile - strike,
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_credit_value_adjustment() {
+        let (rate, recovery) = (0.03, 0.4);
+        let discount_curve = |t: f64| (-rate * t).exp();
+
+        // Constant exposure of 10 over 5 years: without discounting, the CVA
+        // is the loss given default times the default probability.
+        let times: Vec<f64> = (0..=20).map(|i| 0.25 * i as f64).collect();
+        let profile = ExposureProfile {
+            expected_exposure: vec![10.0; times.len()],
+            potential_future_exposure: vec![15.0; times.len()],
+            times: times.clone(),
+        };
+
+        let cva =
+            credit_value_adjustment(&profile, &HazardRateCurve::flat(0.02), |_| 1.0, recovery);
+        assert_approx_equal!(cva, 0.6 * 10.0 * (1.0 - (-0.1_f64).exp()), 1e-12);
+
+        // No default risk, no adjustment.
+        let cva = credit_value_adjustment(
+            &profile,
+            &HazardRateCurve::flat(0.0),
+            discount_curve,
+            recovery,
+        );
+        assert_eq!(cva, 0.0);
+
+        // Increasing in the hazard rate ...
+        let cvas: Vec<f64> = [0.005, 0.01, 0.02, 0.05, 0.1]
+            .iter()
+            .map(|&h| {
+                credit_value_adjustment(
+                    &profile,
+                    &HazardRateCurve::flat(h),
+                    discount_curve,
+                    recovery,
+                )
+            })
+            .collect();
+        assert!(cvas[0] > 0.0);
+        assert!(cvas.windows(2).all(|c| c[1] > c[0]), "{cvas:?}");
+
+        // ... and in the exposure.
+        let hazard_curve =
+            HazardRateCurve::new(vec![1.0, 3.0, 5.0], vec![0.01, 0.02, 0.03]).unwrap();
+        let cvas: Vec<f64> = [0.5, 1.0, 2.0, 4.0]
+            .iter()
+            .map(|&scale| {
+                let profile = ExposureProfile {
+                    expected_exposure: times.iter().map(|t| scale * t.sqrt()).collect(),
+                    potential_future_exposure: times
+                        .iter()
+                        .map(|t| 2.0 * scale * t.sqrt())
+                        .collect(),
+                    times: times.clone(),
+                };
+
+                credit_value_adjustment(&profile, &hazard_curve, discount_curve, recovery)
+            })
+            .collect();
+        assert!(cvas[0] > 0.0);
+        assert!(cvas.windows(2).all(|c| c[1] > c[0]), "{cvas:?}");
+    }
+}
@@ -28,6 +28,18 @@ pub use monte_carlo_pricer::*;
 pub mod analytic_pricer;
 pub use analytic_pricer::*;
 
+pub mod weighted_monte_carlo;
+pub use weighted_monte_carlo::*;
+
+pub mod model_implied_hedge;
+pub use model_implied_hedge::*;
+
+pub mod stochastic_mesh;
+pub use stochastic_mesh::*;
+
+pub mod exposure;
+pub use exposure::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // PRICER STRUCT
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
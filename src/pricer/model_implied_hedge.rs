@@ -0,0 +1,251 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Model-implied hedges: an exotic's model parameter risk, expressed as
+//! positions in the instruments the model was calibrated to.
+//!
+//! With $g = \partial E / \partial \theta$ the exotic's sensitivities to the
+//! model parameters and $J = \partial q / \partial \theta$ those of the
+//! calibration quotes, a least-squares calibration responds to quote moves
+//! by $d\theta = (J^T J)^{-1} J^T dq$. The exotic's sensitivities to the
+//! quotes are therefore
+//!
+//! $$w = J (J^T J)^{-1} g,$$
+//!
+//! the minimum-norm positions whose aggregate parameter sensitivities
+//! $J^T w$ match $g$. When there are as many quotes as parameters, this is
+//! the exact solution of $J^T w = g$.
+
+use crate::error::RustQuantError;
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Price (or quote) of an instrument as a function of the model parameters.
+pub type ModelPrice = dyn Fn(&[f64]) -> f64;
+
+/// An exotic's parameter risk, mapped onto the calibration instruments.
+pub struct ModelImpliedHedge {
+    /// The exotic's sensitivities to the model parameters.
+    parameter_sensitivities: DVector<f64>,
+
+    /// The quotes' sensitivities to the model parameters (one row per quote).
+    quote_sensitivities: DMatrix<f64>,
+
+    /// Positions in the calibration instruments.
+    positions: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ModelImpliedHedge {
+    /// Relative parameter bump for the central differences.
+    const BUMP: f64 = 1e-6;
+
+    /// Compute the model-implied hedge of an exotic at the calibrated
+    /// parameters, differentiating the prices by central differences.
+    ///
+    /// # Arguments:
+    /// * `parameters` - The calibrated model parameters.
+    /// * `exotic` - The exotic's price, given the model parameters.
+    /// * `quotes` - The calibration quotes, given the model parameters.
+    ///
+    /// # Errors
+    /// - `RustQuantError::MissingInput` if there are no parameters or quotes.
+    /// - `RustQuantError::InvalidArgument` if there are fewer quotes than
+    ///   parameters, so the calibration does not determine the parameters.
+    /// - `RustQuantError::MatrixInversionFailed` if the quotes are (numerically)
+    ///   insensitive to some combination of the parameters.
+    pub fn new(
+        parameters: &[f64],
+        exotic: &ModelPrice,
+        quotes: &[&ModelPrice],
+    ) -> Result<Self, RustQuantError> {
+        let (n, m) = (parameters.len(), quotes.len());
+
+        if n == 0 || m == 0 {
+            return Err(RustQuantError::MissingInput(
+                "No model parameters or calibration quotes.".to_string(),
+            ));
+        }
+        if m < n {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "{m} calibration quotes do not determine {n} model parameters."
+            )));
+        }
+
+        let parameter_sensitivities = DVector::from_vec(Self::gradient(parameters, exotic));
+        let mut quote_sensitivities = DMatrix::zeros(m, n);
+
+        for (j, quote) in quotes.iter().enumerate() {
+            let gradient = Self::gradient(parameters, *quote);
+
+            quote_sensitivities.set_row(j, &DVector::from_vec(gradient).transpose());
+        }
+
+        // With J = QR, J (J^T J)^{-1} g = Q R^{-T} g.
+        let qr = quote_sensitivities.clone().qr();
+        let r = qr.r();
+        let scale = r.diagonal().amax();
+
+        if r.diagonal().iter().any(|r_ii| r_ii.abs() <= 1e-12 * scale) {
+            return Err(RustQuantError::MatrixInversionFailed);
+        }
+
+        let y = r
+            .transpose()
+            .solve_lower_triangular(&parameter_sensitivities)
+            .ok_or(RustQuantError::MatrixInversionFailed)?;
+        let positions = qr.q() * y;
+
+        Ok(Self {
+            parameter_sensitivities,
+            quote_sensitivities,
+            positions,
+        })
+    }
+
+    /// Central difference gradient of a price in the model parameters.
+    fn gradient(parameters: &[f64], price: &ModelPrice) -> Vec<f64> {
+        let mut bumped = parameters.to_vec();
+
+        (0..parameters.len())
+            .map(|i| {
+                let h = Self::BUMP * (1.0 + parameters[i].abs());
+
+                bumped[i] = parameters[i] + h;
+                let up = price(&bumped);
+                bumped[i] = parameters[i] - h;
+                let down = price(&bumped);
+                bumped[i] = parameters[i];
+
+                (up - down) / (2.0 * h)
+            })
+            .collect()
+    }
+
+    /// Positions in the calibration instruments: the exotic's
+    /// sensitivity to each calibration quote.
+    #[must_use]
+    pub fn positions(&self) -> &[f64] {
+        self.positions.as_slice()
+    }
+
+    /// The exotic's sensitivities to the model parameters.
+    #[must_use]
+    pub fn parameter_sensitivities(&self) -> &[f64] {
+        self.parameter_sensitivities.as_slice()
+    }
+
+    /// Aggregate sensitivities of the hedge portfolio to the model parameters.
+    #[must_use]
+    pub fn hedge_sensitivities(&self) -> Vec<f64> {
+        (self.quote_sensitivities.transpose() * &self.positions)
+            .as_slice()
+            .to_vec()
+    }
+
+    /// Quote-space vega ladder: the exotic's vega to each calibration
+    /// instrument's implied volatility, given the instruments' vegas
+    /// (for quotes that are prices).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not one vega per calibration quote.
+    #[must_use]
+    pub fn vega_ladder(&self, quote_vegas: &[f64]) -> Vec<f64> {
+        assert_eq!(quote_vegas.len(), self.positions.len());
+
+        self.positions
+            .iter()
+            .zip(quote_vegas)
+            .map(|(position, vega)| position * vega)
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_model_implied_hedge {
+    use super::*;
+    use crate::instruments::options::TypeFlag;
+    use crate::models::SABR;
+
+    const FORWARD: f64 = 100.0;
+
+    /// SABR call price, with parameters (alpha, rho, nu) and beta = 0.5.
+    fn sabr_call(strike: f64, time: f64) -> impl Fn(&[f64]) -> f64 {
+        move |p: &[f64]| {
+            SABR::new(p[0], 0.5, p[1], p[2]).price(FORWARD, strike, time, 0.02, TypeFlag::Call)
+        }
+    }
+
+    #[test]
+    fn test_calibration_instrument_hedges_itself() {
+        let parameters = [2.0, -0.3, 0.5];
+
+        let quotes: Vec<_> = [90.0, 100.0, 110.0]
+            .iter()
+            .map(|&strike| sabr_call(strike, 1.0))
+            .collect();
+        let quotes: Vec<&ModelPrice> = quotes.iter().map(|q| q as &ModelPrice).collect();
+
+        let exotic = sabr_call(100.0, 1.0);
+        let hedge = ModelImpliedHedge::new(&parameters, &exotic, &quotes).unwrap();
+
+        let positions = hedge.positions();
+        assert!((positions[1] - 1.0).abs() < 1e-6, "{positions:?}");
+        assert!(positions[0].abs() < 1e-6, "{positions:?}");
+        assert!(positions[2].abs() < 1e-6, "{positions:?}");
+
+        // The vega ladder is the instrument's own vega, in its own bucket.
+        let ladder = hedge.vega_ladder(&[30.0, 40.0, 35.0]);
+        assert!((ladder[1] - 40.0).abs() < 1e-4);
+        assert!(ladder[0].abs() < 1e-4 && ladder[2].abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_hedge_matches_parameter_sensitivities() {
+        let parameters = [2.0, -0.3, 0.5];
+
+        let quotes: Vec<_> = [80.0, 90.0, 100.0, 110.0, 120.0]
+            .iter()
+            .map(|&strike| sabr_call(strike, 1.0))
+            .collect();
+        let quotes: Vec<&ModelPrice> = quotes.iter().map(|q| q as &ModelPrice).collect();
+
+        // A longer dated option, which is not a calibration instrument.
+        let exotic = sabr_call(105.0, 2.0);
+        let hedge = ModelImpliedHedge::new(&parameters, &exotic, &quotes).unwrap();
+
+        for (hedged, exotic) in hedge
+            .hedge_sensitivities()
+            .iter()
+            .zip(hedge.parameter_sensitivities())
+        {
+            assert!(
+                (hedged - exotic).abs() < 1e-8 * (1.0 + exotic.abs()),
+                "{hedged} != {exotic}"
+            );
+        }
+
+        // Too few quotes to determine the parameters.
+        assert!(matches!(
+            ModelImpliedHedge::new(&parameters, &exotic, &quotes[..2]),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+    }
+}
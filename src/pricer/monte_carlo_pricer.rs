@@ -7,12 +7,44 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-//! Monte-Carlo pricer trait.
+//! Monte-Carlo pricer trait, and the generic engine it is built on.
+//!
+//! [`MonteCarloEngine`] prices any discounted path functional, so bespoke
+//! payoffs do not need an instrument type:
+//!
+//! ```rust
+//! use RustQuant::models::GeometricBrownianMotion;
+//! use RustQuant::pricer::*;
+//! use RustQuant::stochastics::StochasticProcessConfig;
+//!
+//! let (rate, strike) = (0.05, 100.0);
+//! let process = GeometricBrownianMotion::new(rate, 0.2);
+//!
+//! // Twelve monthly closes over one year.
+//! let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 10_000, true).with_seed(1);
+//!
+//! // Call on the average of the three highest monthly closes.
+//! let top_three = |path: &Path| {
+//!     let mut closes = path.values[1..].to_vec();
+//!     closes.sort_by(|a, b| b.partial_cmp(a).unwrap());
+//!
+//!     (closes[..3].iter().sum::<f64>() / 3.0 - strike).max(0.0)
+//! };
+//! let discount = |path: &Path| (-rate * path.terminal_time()).exp();
+//!
+//! let output = MonteCarloEngine::new().estimate(&process, &config, top_three, discount);
+//!
+//! // Dominates the call on the terminal close.
+//! assert!(output.estimate.value > 10.0);
+//! assert!(output.estimate.standard_error < 0.5);
+//! ```
 
 use crate::{
-    instruments::Payoff,
-    stochastics::{StochasticProcess, StochasticProcessConfig},
+    error::SimulationError,
+    instruments::{ExerciseFlag, Payoff, VanillaOption},
+    stochastics::{StochasticProcess, StochasticProcessConfig, Trajectories},
 };
+use rayon::prelude::*;
 
 /// Monte-Carlo pricer trait.
 pub trait MonteCarloPricer<S>: Payoff
@@ -26,7 +58,371 @@ where
     /// * `process` - The [StochasticProcess] to use for the sample paths.
     /// * `config` - The [StochasticProcessConfig] for the simulation.
     /// * `rate` - The interest rate used to discount the payoff.
-    fn price_monte_carlo(&self, process: &S, config: &StochasticProcessConfig, rate: f64) -> f64;
+    fn price_monte_carlo(&self, process: &S, config: &StochasticProcessConfig, rate: f64) -> f64 {
+        self.price_monte_carlo_with(process, config, Discounting::FlatRate(rate))
+    }
+
+    /// Price the instrument using a Monte-Carlo method, with a choice of discounting.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `discounting` - How each path's payoff is discounted (see [Discounting]).
+    fn price_monte_carlo_with(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        discounting: Discounting,
+    ) -> f64;
+
+    /// Greeks by central finite differences of the Monte-Carlo price,
+    /// bumping the initial value, the volatility, and the rate.
+    ///
+    /// Every bump reuses the same random paths (common random numbers), from
+    /// the config's seed or, if it has none, from a single random seed.
+    /// Bumping the rate changes both the process and the discounting.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Builds the process from a rate and a volatility,
+    ///   e.g. `|r, v| GeometricBrownianMotion::new(r, v)`.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `rate` - The interest rate.
+    /// * `volatility` - The volatility.
+    fn greeks_finite_difference<F>(
+        &self,
+        process: F,
+        config: &StochasticProcessConfig,
+        rate: f64,
+        volatility: f64,
+    ) -> Greeks
+    where
+        Self: Sized,
+        F: Fn(f64, f64) -> S,
+    {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        let seed = config.seed.unwrap_or_else(rand::random);
+
+        let price = |x_0: f64, rate: f64, volatility: f64| {
+            let config = StochasticProcessConfig::new(x_0, t_0, t_n, n_steps, m_paths, parallel)
+                .with_seed(seed);
+
+            self.price_monte_carlo(&process(rate, volatility), &config, rate)
+        };
+
+        let (dx, dv, dr) = (0.01 * x_0.abs().max(1.0), 1e-3, 1e-4);
+
+        let base = price(x_0, rate, volatility);
+        let (up, down) = (
+            price(x_0 + dx, rate, volatility),
+            price(x_0 - dx, rate, volatility),
+        );
+
+        Greeks {
+            price: base,
+            delta: (up - down) / (2.0 * dx),
+            gamma: (up - 2.0 * base + down) / (dx * dx),
+            vega: (price(x_0, rate, volatility + dv) - price(x_0, rate, volatility - dv))
+                / (2.0 * dv),
+            rho: (price(x_0, rate + dr, volatility) - price(x_0, rate - dr, volatility))
+                / (2.0 * dr),
+        }
+    }
+}
+
+/// Price and first and second order sensitivities of an instrument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    /// Price.
+    pub price: f64,
+
+    /// Sensitivity to the underlying's initial value, $\partial V / \partial S$.
+    pub delta: f64,
+
+    /// Second order sensitivity to the initial value, $\partial^2 V / \partial S^2$.
+    pub gamma: f64,
+
+    /// Sensitivity to the volatility, $\partial V / \partial \sigma$.
+    pub vega: f64,
+
+    /// Sensitivity to the interest rate, $\partial V / \partial r$.
+    pub rho: f64,
+}
+
+/// How the payoff on each simulated path is discounted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Discounting {
+    /// Discount at a flat (continuously compounded) interest rate.
+    FlatRate(f64),
+
+    /// The simulated process is the short rate itself (e.g. Hull-White or
+    /// Cox-Ingersoll-Ross), so each path is discounted by its own money-market
+    /// account, $\exp(-\int r(t) dt)$, integrated with the trapezoidal rule.
+    ShortRate,
+}
+
+impl Discounting {
+    /// Discount factor from the start to the end of the path.
+    #[must_use]
+    pub fn discount_factor(&self, path: &Path) -> f64 {
+        match *self {
+            Discounting::FlatRate(rate) => (-rate * (path.terminal_time() - path.times[0])).exp(),
+            Discounting::ShortRate => {
+                let integral: f64 = path
+                    .times
+                    .windows(2)
+                    .zip(path.values.windows(2))
+                    .map(|(t, r)| 0.5 * (t[1] - t[0]) * (r[0] + r[1]))
+                    .sum();
+
+                (-integral).exp()
+            }
+        }
+    }
+}
+
+/// A Monte-Carlo estimate together with its sampling error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloEstimate {
+    /// Sample mean of the estimator.
+    pub value: f64,
+
+    /// Sample variance of the (per-path) estimator.
+    pub variance: f64,
+
+    /// Standard error of the sample mean.
+    pub standard_error: f64,
+}
+
+impl MonteCarloEstimate {
+    /// Create an estimate from the sum and sum of squares of `n` samples.
+    #[must_use]
+    pub fn from_sums(sum: f64, sum_of_squares: f64, n: usize) -> Self {
+        let n = n as f64;
+        let value = sum / n;
+        let variance = (sum_of_squares - n * value * value) / (n - 1.0);
+
+        Self {
+            value,
+            variance,
+            standard_error: (variance / n).sqrt(),
+        }
+    }
+}
+
+/// A single simulated path, as seen by a path functional.
+#[derive(Debug, Clone, Copy)]
+pub struct Path<'a> {
+    /// Time points of the path.
+    pub times: &'a [f64],
+
+    /// Values of the process at each time point.
+    pub values: &'a [f64],
+}
+
+impl Path<'_> {
+    /// Terminal value of the path.
+    #[must_use]
+    pub fn terminal(&self) -> f64 {
+        self.values.last().copied().unwrap_or(0.0)
+    }
+
+    /// Terminal time of the path.
+    #[must_use]
+    pub fn terminal_time(&self) -> f64 {
+        self.times.last().copied().unwrap_or(0.0)
+    }
+}
+
+/// Output of the [`MonteCarloEngine`].
+#[derive(Debug, Clone)]
+pub struct MonteCarloOutput {
+    /// Estimate of the discounted functional, with its standard error.
+    pub estimate: MonteCarloEstimate,
+
+    /// Discounted functional on each path, if requested.
+    pub sample: Option<Vec<f64>>,
+}
+
+/// Monte-Carlo engine for arbitrary discounted path functionals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonteCarloEngine {
+    /// Keep the per-path sample in the output.
+    pub keep_sample: bool,
+}
+
+impl MonteCarloEngine {
+    /// Create a new engine (without keeping the sample).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the per-path sample in the output.
+    #[must_use]
+    pub fn with_sample(mut self) -> Self {
+        self.keep_sample = true;
+        self
+    }
+
+    /// Estimate $E[D(X) F(X)]$ over Euler-Maruyama paths of the process.
+    ///
+    /// The paths are streamed (see [`StochasticProcess::simulate_path`]):
+    /// each one is simulated, valued, and dropped, so memory use is one path
+    /// per worker however many paths are simulated. Given a seed, the
+    /// estimate is the same in serial and in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The [StochasticProcess] to use for the sample paths.
+    /// * `config` - The [StochasticProcessConfig] for the simulation.
+    /// * `functional` - The (undiscounted) value $F$ of a path.
+    /// * `discount` - The discount factor $D$ applied to a path's value.
+    pub fn estimate<S, F, D>(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        functional: F,
+        discount: D,
+    ) -> MonteCarloOutput
+    where
+        S: StochasticProcess + ?Sized,
+        F: Fn(&Path) -> f64 + Sync,
+        D: Fn(&Path) -> f64 + Sync,
+    {
+        let times = config.times();
+
+        self.accumulate(config.m_paths, config.parallel, |index| {
+            let values = process.simulate_path(config, index);
+            let path = Path {
+                times: &times,
+                values: &values,
+            };
+
+            discount(&path) * functional(&path)
+        })
+    }
+
+    /// Estimate $E[D(X) F(X)]$, as [`Self::estimate`], but returning an
+    /// error instead of panicking (or averaging non-finite values) if a path
+    /// fails (see [`StochasticProcess::try_euler_maruyama`]).
+    ///
+    /// # Errors
+    ///
+    /// A [`SimulationError`] naming the path, time step, and state at which
+    /// the simulation failed.
+    pub fn try_estimate<S, F, D>(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        functional: F,
+        discount: D,
+    ) -> Result<MonteCarloOutput, SimulationError>
+    where
+        S: StochasticProcess + ?Sized,
+        F: Fn(&Path) -> f64,
+        D: Fn(&Path) -> f64,
+    {
+        let trajectories = process.try_euler_maruyama(config)?;
+
+        Ok(self.estimate_trajectories(&trajectories, functional, discount))
+    }
+
+    /// Estimate $E[D(X) F(X)]$ over already simulated trajectories.
+    pub fn estimate_trajectories<F, D>(
+        &self,
+        trajectories: &Trajectories,
+        functional: F,
+        discount: D,
+    ) -> MonteCarloOutput
+    where
+        F: Fn(&Path) -> f64,
+        D: Fn(&Path) -> f64,
+    {
+        let values = trajectories.paths.iter().map(|values| {
+            let path = Path {
+                times: &trajectories.times,
+                values,
+            };
+
+            discount(&path) * functional(&path)
+        });
+
+        let mut sample = self
+            .keep_sample
+            .then(|| Vec::with_capacity(trajectories.paths.len()));
+
+        let (sum, sum_of_squares) = values.fold((0.0, 0.0), |(sum, sum_of_squares), value| {
+            if let Some(sample) = sample.as_mut() {
+                sample.push(value);
+            }
+
+            (sum + value, sum_of_squares + value * value)
+        });
+
+        MonteCarloOutput {
+            estimate: MonteCarloEstimate::from_sums(sum, sum_of_squares, trajectories.paths.len()),
+            sample,
+        }
+    }
+
+    /// Accumulate the sum and sum of squares of `value(i)` over the paths
+    /// `i` in `0..m_paths`, without storing the values (unless the sample
+    /// is kept).
+    ///
+    /// The paths are summed in fixed chunks, which are then combined in
+    /// order, so the result does not depend on whether (or how) the chunks
+    /// are run in parallel.
+    pub(crate) fn accumulate<V>(&self, m_paths: usize, parallel: bool, value: V) -> MonteCarloOutput
+    where
+        V: Fn(usize) -> f64 + Sync,
+    {
+        const CHUNK_SIZE: usize = 1024;
+
+        let chunk = |c: usize| {
+            let paths = c * CHUNK_SIZE..((c + 1) * CHUNK_SIZE).min(m_paths);
+            let mut sample = self.keep_sample.then(|| Vec::with_capacity(paths.len()));
+
+            let (sum, sum_of_squares) =
+                paths.fold((0.0, 0.0), |(sum, sum_of_squares): (f64, f64), index| {
+                    let value = value(index);
+
+                    if let Some(sample) = sample.as_mut() {
+                        sample.push(value);
+                    }
+
+                    (sum + value, sum_of_squares + value * value)
+                });
+
+            (sum, sum_of_squares, sample)
+        };
+
+        let n_chunks = m_paths.div_ceil(CHUNK_SIZE);
+
+        let chunks: Vec<_> = if parallel {
+            (0..n_chunks).into_par_iter().map(chunk).collect()
+        } else {
+            (0..n_chunks).map(chunk).collect()
+        };
+
+        let mut sample = self.keep_sample.then(|| Vec::with_capacity(m_paths));
+        let (mut sum, mut sum_of_squares) = (0.0, 0.0);
+
+        for (chunk_sum, chunk_sum_of_squares, chunk_sample) in chunks {
+            sum += chunk_sum;
+            sum_of_squares += chunk_sum_of_squares;
+
+            if let (Some(sample), Some(chunk_sample)) = (sample.as_mut(), chunk_sample) {
+                sample.extend(chunk_sample);
+            }
+        }
+
+        MonteCarloOutput {
+            estimate: MonteCarloEstimate::from_sums(sum, sum_of_squares, m_paths),
+            sample,
+        }
+    }
 }
 
 /// Macro to implement `MonteCarloPricer` for a given instrument type.
@@ -36,29 +432,66 @@ macro_rules! impl_monte_carlo_pricer {
         where
             S: StochasticProcess,
         {
-            fn price_monte_carlo(
+            fn price_monte_carlo_with(
                 &self,
                 process: &S,
                 config: &StochasticProcessConfig,
-                rate: f64,
+                discounting: Discounting,
             ) -> f64 {
-                let out = process.euler_maruyama(&config);
-
-                let n = out.paths.len();
-
-                let df = (-rate * (config.t_n - config.t_0)).exp();
+                MonteCarloEngine::new()
+                    .estimate(
+                        process,
+                        config,
+                        |path| self.payoff($underlying(path.values)),
+                        |path| discounting.discount_factor(path),
+                    )
+                    .estimate
+                    .value
+            }
+        }
+    };
+}
 
-                let payoffs = out.paths.iter().fold(0.0, |acc, path| {
-                    let underlying = $underlying(&*path);
-                    let payoff = self.payoff(underlying);
+impl<S> MonteCarloPricer<S> for VanillaOption
+where
+    S: StochasticProcess,
+{
+    /// European options at a flat rate only depend on the terminal value,
+    /// so only that is simulated, one path at a time
+    /// (see [`StochasticProcess::simulate_terminal_value`]).
+    fn price_monte_carlo_with(
+        &self,
+        process: &S,
+        config: &StochasticProcessConfig,
+        discounting: Discounting,
+    ) -> f64 {
+        let engine = MonteCarloEngine::new();
 
-                    acc + payoff
-                });
+        match (&self.contract.exercise_flag, discounting) {
+            (ExerciseFlag::European { .. }, Discounting::FlatRate(rate)) => {
+                let discount_factor = (-rate * (config.t_n - config.t_0)).exp();
 
-                df * payoffs / n as f64
+                engine
+                    .accumulate(config.m_paths, config.parallel, |index| {
+                        discount_factor
+                            * self.payoff(process.simulate_terminal_value(config, index))
+                    })
+                    .estimate
+                    .value
+            }
+            _ => {
+                engine
+                    .estimate(
+                        process,
+                        config,
+                        |path| self.payoff(path.terminal()),
+                        |path| discounting.discount_factor(path),
+                    )
+                    .estimate
+                    .value
             }
         }
-    };
+    }
 }
 
 fn path_independent(path: &[f64]) -> f64 {
@@ -71,13 +504,218 @@ fn path_dependent(path: &[f64]) -> Vec<f64> {
 
 impl_monte_carlo_pricer!(crate::instruments::AsianOption, path_dependent);
 impl_monte_carlo_pricer!(crate::instruments::BinaryOption, path_independent);
-impl_monte_carlo_pricer!(crate::instruments::VanillaOption, path_independent);
+impl_monte_carlo_pricer!(crate::instruments::DigitalOption, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::PowerContract, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::PowerOption, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::SupershareOption, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::BarrierOption, path_dependent);
+impl_monte_carlo_pricer!(crate::instruments::LookbackOption, path_dependent);
 impl_monte_carlo_pricer!(crate::instruments::CappedPowerOption, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::PoweredOption, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::LogMoneynessContract, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::LogUnderlyingContract, path_independent);
 impl_monte_carlo_pricer!(crate::instruments::LogOption, path_independent);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_monte_carlo_engine {
+    use super::*;
+    use crate::instruments::{
+        AsianOption, AveragingMethod, OptionContractBuilder, StrikeFlag, TypeFlag,
+    };
+    use crate::math::Statistic;
+    use crate::models::GeometricBrownianMotion;
+    use time::macros::date;
+
+    fn contract(exercise_flag: ExerciseFlag) -> crate::instruments::OptionContract {
+        OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(exercise_flag)
+            .strike_flag(Some(StrikeFlag::Fixed))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_option_pricers_unchanged() {
+        let process = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 2000, true).with_seed(7);
+
+        let european = ExerciseFlag::European {
+            expiry: date!(2025 - 01 - 01),
+        };
+        let american = ExerciseFlag::American {
+            start: date!(2024 - 01 - 01),
+            end: date!(2025 - 01 - 01),
+        };
+
+        let vanilla = VanillaOption::new(contract(european.clone()), 100.0);
+        let vanilla_american = VanillaOption::new(contract(american), 100.0);
+        let asian = AsianOption::new(
+            contract(european),
+            AveragingMethod::ArithmeticDiscrete,
+            Some(100.0),
+        );
+
+        // Seeded prices from before the pricers were built on the engine.
+        let price = |option: &VanillaOption| option.price_monte_carlo(&process, &config, 0.05);
+
+        assert_approx_equal!(price(&vanilla), 10.586339627929712, 1e-12);
+        assert_approx_equal!(price(&vanilla_american), 10.588617267005969, 1e-12);
+        assert_approx_equal!(
+            asian.price_monte_carlo(&process, &config, 0.05),
+            5.706159378531665,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_short_rate_discounting() {
+        use crate::instruments::PowerContract;
+        use crate::models::HullWhite;
+
+        // dr = (theta - alpha r) dt + sigma dW.
+        let (alpha, theta, sigma, r_0, t_n) = (0.5, 0.02, 0.01, 0.03, 2.0_f64);
+        let hull_white = HullWhite::new(alpha, sigma, theta);
+
+        // Analytic zero-coupon bond price (constant theta).
+        let b = (1.0 - (-alpha * t_n).exp()) / alpha;
+        let ln_a = (theta / alpha - sigma * sigma / (2.0 * alpha * alpha)) * (b - t_n)
+            - sigma * sigma * b * b / (4.0 * alpha);
+        let zcb = (ln_a - b * r_0).exp();
+
+        // A contract paying 1.
+        let unit = PowerContract {
+            strike: 1.0,
+            power: 0.0,
+        };
+
+        let config = StochasticProcessConfig::new(r_0, 0.0, t_n, 100, 20_000, true).with_seed(5);
+        let price = unit.price_monte_carlo_with(&hull_white, &config, Discounting::ShortRate);
+
+        assert_approx_equal!(price, zcb, 5e-4);
+
+        // Discounting at the initial short rate misses the curve's slope.
+        assert!((unit.price_monte_carlo(&hull_white, &config, r_0) - zcb).abs() > 5e-3);
+    }
+
+    #[test]
+    fn test_greeks_finite_difference() {
+        use crate::instruments::options::BlackScholesMerton;
+
+        let (rate, volatility) = (0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 50_000, true).with_seed(3);
+
+        let option = VanillaOption::new(
+            contract(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            }),
+            100.0,
+        );
+
+        let greeks = option.greeks_finite_difference(
+            GeometricBrownianMotion::new,
+            &config,
+            rate,
+            volatility,
+        );
+
+        let analytic = BlackScholesMerton::new(
+            rate,
+            100.0,
+            100.0,
+            volatility,
+            rate,
+            Some(date!(2024 - 01 - 01)),
+            date!(2025 - 01 - 01),
+            TypeFlag::Call,
+        );
+
+        assert!((greeks.delta - analytic.delta()).abs() < 0.01);
+        assert!((greeks.gamma - analytic.gamma()).abs() < 0.003);
+        assert!((greeks.vega - analytic.vega()).abs() < 1.0);
+        assert!((greeks.rho - analytic.rho()).abs() < 1.0);
+
+        // Unseeded configs still reuse the paths across bumps.
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 50_000, true);
+        let greeks = option.greeks_finite_difference(
+            GeometricBrownianMotion::new,
+            &config,
+            rate,
+            volatility,
+        );
+
+        assert!((greeks.delta - analytic.delta()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_monte_carlo_engine_sample() {
+        let process = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 10, 1000, false).with_seed(3);
+
+        let output = MonteCarloEngine::new().with_sample().estimate(
+            &process,
+            &config,
+            |path| path.terminal(),
+            |path| (-0.05 * path.terminal_time()).exp(),
+        );
+
+        let sample = output.sample.unwrap();
+        assert_eq!(sample.len(), 1000);
+        assert_approx_equal!(output.estimate.value, sample.mean(), 1e-10);
+        assert_approx_equal!(
+            output.estimate.standard_error,
+            (sample.variance() / 1000.0).sqrt(),
+            1e-10
+        );
+
+        // The discounted GBM is a martingale.
+        assert!((output.estimate.value - 100.0).abs() < 3.0 * output.estimate.standard_error);
+        assert!(MonteCarloEngine::new()
+            .estimate(&process, &config, |_| 1.0, |_| 1.0)
+            .sample
+            .is_none());
+    }
+
+    #[test]
+    fn test_monte_carlo_engine_streaming() {
+        let process = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = |parallel| {
+            StochasticProcessConfig::new(100.0, 0.0, 1.0, 20, 2500, parallel).with_seed(9)
+        };
+
+        let average = |path: &Path| path.values.iter().sum::<f64>() / path.values.len() as f64;
+
+        let estimate = |parallel| {
+            MonteCarloEngine::new().with_sample().estimate(
+                &process,
+                &config(parallel),
+                average,
+                |path| (-0.05 * path.terminal_time()).exp(),
+            )
+        };
+
+        // Streamed paths are summed in the same order in serial and in parallel.
+        let (serial, parallel) = (estimate(false), estimate(true));
+        assert_eq!(serial.estimate, parallel.estimate);
+        assert_eq!(serial.sample, parallel.sample);
+
+        // The same estimate as from the stored trajectories.
+        let stored = MonteCarloEngine::new().with_sample().estimate_trajectories(
+            &process.euler_maruyama(&config(true)),
+            average,
+            |path| (-0.05 * path.terminal_time()).exp(),
+        );
+
+        assert_eq!(serial.sample, stored.sample);
+        assert_approx_equal!(serial.estimate.value, stored.estimate.value, 1e-12);
+        assert_approx_equal!(
+            serial.estimate.standard_error,
+            stored.estimate.standard_error,
+            1e-12
+        );
+    }
+}
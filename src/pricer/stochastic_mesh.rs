@@ -0,0 +1,537 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Stochastic mesh method for Bermudan options (Broadie and Glasserman, 2004).
+//!
+//! At each exercise date $t_1 < \dots < t_m$ the mesh has $b$ nodes: the
+//! states of $b$ independent paths from the initial state. The value at node
+//! $x_k^i$ is estimated by backward induction,
+//!
+//! $$
+//! \hat{Q}_k(x_k^i) = \max \left( h(x_k^i), \frac{D_k}{b} \sum_{j=1}^b w_k^{ij} \hat{Q}_{k+1}(x_{k+1}^j) \right),
+//! \quad
+//! w_k^{ij} = \frac{f_k(x_k^i, x_{k+1}^j)}{\frac{1}{b} \sum_{l=1}^b f_k(x_k^l, x_{k+1}^j)},
+//! $$
+//!
+//! where $D_k$ is the discount factor between the dates and the weights are
+//! the likelihood ratios of the transition density $f_k$ to the average
+//! density the next nodes were sampled from. By Jensen's inequality the
+//! estimator is biased high, with the bias vanishing as $b$ grows.
+//!
+//! It is paired with the low-biased Longstaff-Schwartz estimator: the
+//! continuation value is regressed on basis functions of the state over one
+//! set of paths, and the resulting exercise rule applied to independent
+//! paths, so its suboptimality can only lower the price.
+
+use crate::pricer::MonteCarloEstimate;
+use crate::stochastics::{StochasticProcessConfig, TransitionDensity};
+use nalgebra::{DMatrix, DVector};
+use rayon::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bermudan option pricer by stochastic mesh (high-biased) and
+/// Longstaff-Schwartz (low-biased) estimators.
+pub struct StochasticMesh<P: TransitionDensity> {
+    /// The (risk-neutral) process of the underlying assets.
+    pub process: P,
+
+    /// The assets' initial state.
+    pub initial_state: Vec<f64>,
+
+    /// Exercise times (in years), strictly increasing and positive.
+    /// The last one is the expiry.
+    pub exercise_times: Vec<f64>,
+
+    /// The risk-free interest rate.
+    pub rate: f64,
+
+    /// Number of nodes at each exercise date ($b$).
+    pub mesh_size: usize,
+
+    /// Seed for the random number generator.
+    pub seed: Option<u64>,
+}
+
+/// A simulated mesh.
+pub struct Mesh {
+    /// Nodes at each exercise date (`nodes[k][i]` is the state of node `i`
+    /// at date `k`).
+    pub nodes: Vec<Vec<Vec<f64>>>,
+
+    /// Mesh weights between consecutive exercise dates
+    /// (`weights[k][(i, j)]` is from node `i` at date `k` to node `j` at
+    /// date `k + 1`).
+    pub weights: Vec<DMatrix<f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<P: TransitionDensity> StochasticMesh<P> {
+    /// Create a new stochastic mesh pricer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the initial state does not match the process dimension, the
+    /// exercise times are not positive and strictly increasing, or the mesh
+    /// is empty.
+    pub fn new(
+        process: P,
+        initial_state: Vec<f64>,
+        exercise_times: Vec<f64>,
+        rate: f64,
+        mesh_size: usize,
+    ) -> Self {
+        assert_eq!(initial_state.len(), process.dimension());
+        assert!(!exercise_times.is_empty() && exercise_times[0] > 0.0);
+        assert!(exercise_times.windows(2).all(|w| w[0] < w[1]));
+        assert!(mesh_size > 0);
+
+        Self {
+            process,
+            initial_state,
+            exercise_times,
+            rate,
+            mesh_size,
+            seed: None,
+        }
+    }
+
+    /// Set the seed, for reproducible estimates.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Simulate the mesh: the nodes, and the weights between them.
+    pub fn generate(&self) -> Mesh {
+        let b = self.mesh_size;
+        let paths = self.simulate_paths(0, b);
+
+        // Transpose from [path][date] to [date][node].
+        let nodes: Vec<Vec<Vec<f64>>> = (0..self.exercise_times.len())
+            .map(|k| paths.iter().map(|path| path[k].clone()).collect())
+            .collect();
+
+        let weights = nodes
+            .windows(2)
+            .zip(self.exercise_times.windows(2))
+            .map(|(dates, times)| {
+                let (from, to) = (&dates[0], &dates[1]);
+                let (t, dt) = (times[0], times[1] - times[0]);
+
+                let densities: Vec<Vec<f64>> = from
+                    .par_iter()
+                    .map(|x| {
+                        to.iter()
+                            .map(|y| self.process.transition_density(x, y, t, dt))
+                            .collect()
+                    })
+                    .collect();
+
+                // Average density each node of the next date was sampled from.
+                let average: Vec<f64> = (0..b)
+                    .map(|j| densities.iter().map(|row| row[j]).sum::<f64>() / b as f64)
+                    .collect();
+
+                DMatrix::from_fn(b, b, |i, j| densities[i][j] / average[j])
+            })
+            .collect();
+
+        Mesh { nodes, weights }
+    }
+
+    /// High-biased estimate of the Bermudan option value, by backward
+    /// induction over a newly simulated mesh.
+    ///
+    /// The option can be exercised at the exercise times only, for
+    /// `payoff(state)`.
+    pub fn high_estimate<F>(&self, payoff: &F) -> f64
+    where
+        F: Fn(&[f64]) -> f64 + Sync,
+    {
+        let mesh = self.generate();
+        let (b, m) = (self.mesh_size, self.exercise_times.len());
+
+        let mut values: Vec<f64> = mesh.nodes[m - 1].iter().map(|x| payoff(x)).collect();
+
+        for k in (0..m - 1).rev() {
+            let discount = self.discount(k, k + 1);
+            let weights = &mesh.weights[k];
+
+            values = (0..b)
+                .into_par_iter()
+                .map(|i| {
+                    let continuation = discount
+                        * (0..b).map(|j| weights[(i, j)] * values[j]).sum::<f64>()
+                        / b as f64;
+
+                    payoff(&mesh.nodes[k][i]).max(continuation)
+                })
+                .collect();
+        }
+
+        // All first-date nodes are sampled from the initial state, so the
+        // weights from it are one.
+        (-self.rate * self.exercise_times[0]).exp() * values.iter().sum::<f64>() / b as f64
+    }
+
+    /// Low-biased (Longstaff-Schwartz) estimate of the Bermudan option value.
+    ///
+    /// The exercise rule is fitted by regressing realised continuation
+    /// values of in-the-money paths on quadratic polynomials in the
+    /// (normalised) state and the payoff, over `n_regression_paths` paths.
+    /// It is then applied to `n_paths` independent paths.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two paths are requested for the estimate.
+    pub fn low_estimate<F>(
+        &self,
+        payoff: &F,
+        n_regression_paths: usize,
+        n_paths: usize,
+    ) -> MonteCarloEstimate
+    where
+        F: Fn(&[f64]) -> f64 + Sync,
+    {
+        assert!(n_paths > 1);
+
+        let m = self.exercise_times.len();
+        let coefficients = self.regress_exercise_rule(payoff, n_regression_paths);
+
+        let paths = self.simulate_paths(self.mesh_size + n_regression_paths, n_paths);
+
+        let values: Vec<f64> = paths
+            .par_iter()
+            .map(|path| {
+                for k in 0..m {
+                    let exercise = payoff(&path[k]);
+
+                    let stop = exercise > 0.0
+                        && match &coefficients[k] {
+                            Some(beta) => exercise >= beta.dot(&self.basis(&path[k], exercise)),
+                            None => k == m - 1,
+                        };
+
+                    if stop {
+                        return (-self.rate * self.exercise_times[k]).exp() * exercise;
+                    }
+                }
+
+                0.0
+            })
+            .collect();
+
+        MonteCarloEstimate::from_sums(
+            values.iter().sum(),
+            values.iter().map(|v| v * v).sum(),
+            n_paths,
+        )
+    }
+
+    /// Regression coefficients of the continuation value at each exercise
+    /// date (`None` at expiry, or with too few in-the-money paths to fit).
+    fn regress_exercise_rule<F>(&self, payoff: &F, n_paths: usize) -> Vec<Option<DVector<f64>>>
+    where
+        F: Fn(&[f64]) -> f64 + Sync,
+    {
+        let m = self.exercise_times.len();
+        let paths = self.simulate_paths(self.mesh_size, n_paths);
+
+        let mut coefficients = vec![None; m];
+
+        // Realised value of each path, discounted to the current date.
+        let mut values: Vec<f64> = paths.iter().map(|path| payoff(&path[m - 1])).collect();
+
+        for k in (0..m - 1).rev() {
+            let discount = self.discount(k, k + 1);
+            values.iter_mut().for_each(|v| *v *= discount);
+
+            let in_the_money: Vec<(usize, f64)> = paths
+                .iter()
+                .enumerate()
+                .map(|(p, path)| (p, payoff(&path[k])))
+                .filter(|&(_, exercise)| exercise > 0.0)
+                .collect();
+
+            let n_basis = self.basis(&self.initial_state, 0.0).len();
+
+            if in_the_money.len() < 2 * n_basis {
+                continue;
+            }
+
+            let design = DMatrix::from_fn(in_the_money.len(), n_basis, |row, col| {
+                let (p, exercise) = in_the_money[row];
+                self.basis(&paths[p][k], exercise)[col]
+            });
+            let realised = DVector::from_iterator(
+                in_the_money.len(),
+                in_the_money.iter().map(|&(p, _)| values[p]),
+            );
+
+            let normal = design.transpose() * &design;
+            let Ok(beta) = normal
+                .svd(true, true)
+                .solve(&(design.transpose() * realised), 1e-12)
+            else {
+                continue;
+            };
+
+            for &(p, exercise) in &in_the_money {
+                if exercise >= beta.dot(&self.basis(&paths[p][k], exercise)) {
+                    values[p] = exercise;
+                }
+            }
+
+            coefficients[k] = Some(beta);
+        }
+
+        coefficients
+    }
+
+    /// Regression basis: one, the normalised state, its pairwise products,
+    /// and the normalised payoff.
+    fn basis(&self, x: &[f64], exercise: f64) -> DVector<f64> {
+        let n = x.len();
+        let scale = self.initial_state.iter().sum::<f64>() / n as f64;
+        let s: Vec<f64> = x
+            .iter()
+            .zip(&self.initial_state)
+            .map(|(x, x_0)| x / x_0)
+            .collect();
+
+        let mut basis = Vec::with_capacity(2 + n + n * (n + 1) / 2);
+        basis.push(1.0);
+        basis.extend(&s);
+
+        for i in 0..n {
+            for j in i..n {
+                basis.push(s[i] * s[j]);
+            }
+        }
+
+        basis.push(exercise / scale);
+
+        DVector::from_vec(basis)
+    }
+
+    /// Discount factor from exercise date `from` to `to`.
+    fn discount(&self, from: usize, to: usize) -> f64 {
+        (-self.rate * (self.exercise_times[to] - self.exercise_times[from])).exp()
+    }
+
+    /// `n_paths` paths of the state at the exercise dates, using the random
+    /// number streams from `first_stream` on.
+    fn simulate_paths(&self, first_stream: usize, n_paths: usize) -> Vec<Vec<Vec<f64>>> {
+        // The paths are sampled from the transition density between the
+        // exercise dates, so the configuration only supplies the streams.
+        let config = StochasticProcessConfig {
+            seed: self.seed,
+            ..StochasticProcessConfig::new(
+                0.0,
+                0.0,
+                self.exercise_times[self.exercise_times.len() - 1],
+                self.exercise_times.len(),
+                first_stream + n_paths,
+                true,
+            )
+        };
+
+        (first_stream..first_stream + n_paths)
+            .into_par_iter()
+            .map(|stream| {
+                let mut rng = config.path_rng(stream);
+                let (mut x, mut t) = (self.initial_state.clone(), 0.0);
+
+                self.exercise_times
+                    .iter()
+                    .map(|&time| {
+                        x = self.process.sample_transition(&x, t, time - t, &mut *rng);
+                        t = time;
+                        x.clone()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_stochastic_mesh {
+    use super::*;
+    use crate::math::Statistic;
+    use crate::models::GeometricBrownianMotion;
+    use crate::stochastics::MultiGeometricBrownianMotion;
+
+    /// Bermudan put on a Cox-Ross-Rubinstein tree, exercisable every
+    /// `steps_per_date` steps.
+    fn bermudan_put_binomial(
+        spot: f64,
+        strike: f64,
+        rate: f64,
+        volatility: f64,
+        exercise_times: &[f64],
+        steps_per_date: usize,
+    ) -> f64 {
+        let n_steps = exercise_times.len() * steps_per_date;
+        let dt = exercise_times[exercise_times.len() - 1] / n_steps as f64;
+
+        let u = (volatility * dt.sqrt()).exp();
+        let p = ((rate * dt).exp() - 1.0 / u) / (u - 1.0 / u);
+        let df = (-rate * dt).exp();
+
+        let node = |j: usize, i: usize| spot * u.powi(2 * i as i32 - j as i32);
+        let mut values: Vec<f64> = (0..=n_steps)
+            .map(|i| (strike - node(n_steps, i)).max(0.0))
+            .collect();
+
+        for j in (0..n_steps).rev() {
+            for i in 0..=j {
+                values[i] = df * (p * values[i + 1] + (1.0 - p) * values[i]);
+
+                if j > 0 && j % steps_per_date == 0 {
+                    values[i] = values[i].max(strike - node(j, i));
+                }
+            }
+        }
+
+        values[0]
+    }
+
+    #[test]
+    fn test_bermudan_put_bracketed() {
+        let (spot, strike, rate, volatility) = (36.0, 40.0, 0.06, 0.2);
+        let exercise_times: Vec<f64> = (1..=10).map(|k| k as f64 / 10.0).collect();
+        let put = |x: &[f64]| (strike - x[0]).max(0.0);
+
+        let tree = bermudan_put_binomial(spot, strike, rate, volatility, &exercise_times, 200);
+
+        let highs: Vec<f64> = (0..8)
+            .map(|seed| {
+                StochasticMesh::new(
+                    GeometricBrownianMotion::new(rate, volatility),
+                    vec![spot],
+                    exercise_times.clone(),
+                    rate,
+                    400,
+                )
+                .with_seed(seed)
+                .high_estimate(&put)
+            })
+            .collect();
+
+        let low = StochasticMesh::new(
+            GeometricBrownianMotion::new(rate, volatility),
+            vec![spot],
+            exercise_times.clone(),
+            rate,
+            400,
+        )
+        .with_seed(42)
+        .low_estimate(&put, 20_000, 50_000);
+
+        let high = highs.mean();
+        let high_error = (highs.variance() / highs.len() as f64).sqrt();
+
+        assert!(low.value - 3.0 * low.standard_error < tree);
+        assert!(tree < high + 3.0 * high_error);
+        assert!(low.value < high);
+    }
+
+    #[test]
+    fn test_mesh_weights_sum_to_mesh_size() {
+        let correlation = nalgebra::DMatrix::from_row_slice(2, 2, &[1.0, 0.3, 0.3, 1.0]);
+        let process =
+            MultiGeometricBrownianMotion::new(vec![0.02, 0.01], vec![0.2, 0.3], correlation)
+                .unwrap();
+
+        let b = 500;
+        let mesh = StochasticMesh::new(process, vec![100.0, 80.0], vec![0.25, 0.5, 0.75], 0.05, b)
+            .with_seed(1)
+            .generate();
+
+        assert_eq!(mesh.nodes.len(), 3);
+        assert_eq!(mesh.weights.len(), 2);
+
+        for weights in &mesh.weights {
+            // Against the average density, each column sums to b exactly ...
+            for j in 0..b {
+                assert!((weights.column(j).sum() - b as f64).abs() < 1e-9);
+            }
+
+            // ... and each row to b on average over the next nodes.
+            let row_sums: Vec<f64> = (0..b).map(|i| weights.row(i).sum() / b as f64).collect();
+            let deviation = row_sums.iter().map(|s| (s - 1.0).abs()).sum::<f64>() / b as f64;
+
+            assert!(deviation < 0.1, "{deviation}");
+        }
+    }
+
+    #[test]
+    #[ignore = "Slow: prices meshes of 500 and 1000 nodes in three dimensions."]
+    fn test_max_call_three_assets() {
+        // Broadie and Glasserman (2004): max-call on three independent assets,
+        // S_0 = K = 100, r = 5%, dividend yield 10%, volatility 20%, T = 3,
+        // exercisable at t = 1/3, 2/3, ..., 3. Binomial value: 18.69.
+        let (rate, dividend_yield) = (0.05, 0.1);
+        let exercise_times: Vec<f64> = (1..=9).map(|k| k as f64 / 3.0).collect();
+        let max_call = |x: &[f64]| (x.iter().cloned().fold(f64::MIN, f64::max) - 100.0).max(0.0);
+
+        let pricer = |mesh_size: usize, seed: u64| {
+            let process = MultiGeometricBrownianMotion::new(
+                vec![rate - dividend_yield; 3],
+                vec![0.2; 3],
+                nalgebra::DMatrix::identity(3, 3),
+            )
+            .unwrap();
+
+            StochasticMesh::new(
+                process,
+                vec![100.0; 3],
+                exercise_times.clone(),
+                rate,
+                mesh_size,
+            )
+            .with_seed(seed)
+        };
+
+        let highs = |mesh_size: usize| -> (f64, f64) {
+            let highs: Vec<f64> = (0..3)
+                .map(|seed| pricer(mesh_size, seed).high_estimate(&max_call))
+                .collect();
+
+            (highs.mean(), (highs.variance() / highs.len() as f64).sqrt())
+        };
+
+        let (coarse, coarse_error) = highs(500);
+        let (high, high_error) = highs(1_000);
+        let low = pricer(1_000, 99).low_estimate(&max_call, 20_000, 50_000);
+
+        // In three dimensions the mesh weights are volatile, so the high
+        // estimate is biased up by a few dollars: with b = 1000 it lies
+        // between 18.69 and 18.69 + 3.5, and the bias falls as the mesh grows.
+        assert!(18.69 < high - 3.0 * high_error);
+        assert!(high + 3.0 * high_error < 18.69 + 3.5);
+        assert!(high + 3.0 * high_error < coarse - 3.0 * coarse_error);
+
+        // The Longstaff-Schwartz exercise rule is close to optimal.
+        assert!(low.value - 3.0 * low.standard_error < 18.69);
+        assert!((low.value - 18.69).abs() < 0.25);
+    }
+}
@@ -0,0 +1,291 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! The Bates (1996) stochastic volatility jump-diffusion process,
+//! with factors $(S, v)$:
+//!
+//! - $dS(t) = (\mu - \lambda k) S(t) dt + \sqrt{v(t)} S(t) dW_1(t) + (J - 1) S(t^-) dN(t)$
+//! - $dv(t) = \kappa \left[ \theta - v(t) \right] dt + \xi \sqrt{v(t)} dW_2(t)$
+//!
+//! with $d\langle W_1, W_2 \rangle_t = \rho dt$, $N$ a Poisson process with
+//! intensity $\lambda$, $\ln J \sim N(\mu_J, \sigma_J^2)$, and
+//! $k = E[J - 1]$ compensating the jumps.
+//!
+//! As with Heston, the variance is truncated at zero (full truncation).
+
+use crate::models::Bates;
+use crate::stochastics::process::multifactor_path;
+use crate::stochastics::{
+    semi_definite_cholesky, MultifactorProcess, MultifactorScheme, StochasticProcess,
+    StochasticProcessConfig,
+};
+use nalgebra::DMatrix;
+use rand::{distributions::Distribution, RngCore};
+use rand_distr::{Poisson, StandardNormal};
+
+impl MultifactorProcess for Bates {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn drift(&self, x: &[f64], t: f64) -> Vec<f64> {
+        let v = x[1].max(0.0);
+
+        vec![
+            (self.mu.0(t) - self.lambda.0(t) * self.mean_jump(t)) * x[0],
+            self.mean_reversion_rate.0(t) * (self.long_run_variance.0(t) - v),
+        ]
+    }
+
+    fn diffusion(&self, x: &[f64], t: f64) -> Vec<f64> {
+        let v = x[1].max(0.0);
+
+        vec![
+            v.sqrt() * x[0],
+            self.volatility_of_volatility.0(t) * v.sqrt(),
+        ]
+    }
+
+    fn diffusion_jacobian(&self, x: &[f64], t: f64) -> DMatrix<f64> {
+        // The truncated diffusions are flat in the variance below zero.
+        if x[1] <= 0.0 {
+            return DMatrix::zeros(2, 2);
+        }
+
+        let sqrt_v = x[1].sqrt();
+
+        DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                sqrt_v,
+                0.5 * x[0] / sqrt_v,
+                0.0,
+                0.5 * self.volatility_of_volatility.0(t) / sqrt_v,
+            ],
+        )
+    }
+
+    fn correlation(&self) -> DMatrix<f64> {
+        let rho = self.correlation.0(0.0);
+
+        DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0])
+    }
+
+    fn jump(&self, x: &[f64], t: f64, dt: f64, rng: &mut dyn RngCore) -> Option<Vec<f64>> {
+        let arrivals = Poisson::new(self.lambda.0(t) * dt).ok()?;
+        let jumps: f64 = arrivals.sample(rng);
+
+        if jumps == 0.0 {
+            return None;
+        }
+
+        // The sum of the log jump sizes is N(n mu_J, n sigma_J^2).
+        let z: f64 = StandardNormal.sample(rng);
+        let log_jump = jumps * self.mu_j.0(t) + jumps.sqrt() * self.sigma_j.0(t) * z;
+
+        Some(vec![x[0] * log_jump.exp_m1(), 0.0])
+    }
+}
+
+/// As a one-factor process (e.g. for the
+/// [`MonteCarloPricer`](crate::pricer::MonteCarloPricer)), the Bates process
+/// is the asset price: the simulation methods evolve the full $(S, v)$ system
+/// with the Euler-Maruyama scheme and return the asset's paths.
+/// `drift` and `diffusion` are the asset's, at the initial variance.
+impl StochasticProcess for Bates {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        (self.mu.0(t) - self.lambda.0(t) * self.mean_jump(t)) * x
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.initial_variance.0(t).sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.mu.0(0.0),
+            self.initial_variance.0(0.0),
+            self.long_run_variance.0(0.0),
+            self.mean_reversion_rate.0(0.0),
+            self.correlation.0(0.0),
+            self.volatility_of_volatility.0(0.0),
+            self.lambda.0(0.0),
+            self.mu_j.0(0.0),
+            self.sigma_j.0(0.0),
+        ]
+    }
+
+    fn simulate_path_into(
+        &self,
+        config: &StochasticProcessConfig,
+        index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let cholesky = semi_definite_cholesky(&self.correlation())
+            .expect("Correlation matrix must be positive semi-definite.");
+
+        let mut paths = multifactor_path(
+            self,
+            MultifactorScheme::EulerMaruyama,
+            &[config.x_0, self.initial_variance.0(config.t_0)],
+            &config.times(),
+            (config.t_n - config.t_0) / (config.n_steps as f64),
+            &cholesky,
+            config.path_rng(index).as_mut(),
+        );
+
+        path.append(&mut paths[0]);
+    }
+
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        self.simulate_path(config, index)
+            .last()
+            .copied()
+            .unwrap_or(config.x_0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bates {
+    use super::*;
+    use crate::instruments::options::{
+        AsianOption, AveragingMethod, ExerciseFlag, OptionContractBuilder, StrikeFlag, TypeFlag,
+        VanillaOption,
+    };
+    use crate::math::integrate;
+    use crate::pricer::MonteCarloPricer;
+    use num::Complex;
+    use std::f64::consts::FRAC_1_PI;
+    use time::macros::date;
+
+    /// Bates call price via the characteristic function of $\ln S_T$
+    /// (the Heston one times the compensated jump part) and Gil-Pelaez inversion.
+    #[allow(clippy::too_many_arguments)]
+    fn bates_call_fourier(
+        s_0: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        (v_0, theta, kappa, rho, xi): (f64, f64, f64, f64, f64),
+        (lambda, mu_j, sigma_j): (f64, f64, f64),
+    ) -> f64 {
+        let i = Complex::i();
+        let mean_jump = (mu_j + 0.5 * sigma_j * sigma_j).exp() - 1.0;
+
+        let cf = |u: Complex<f64>| -> Complex<f64> {
+            let beta = kappa - rho * xi * i * u;
+            let d = (beta * beta + xi * xi * (i * u + u * u)).sqrt();
+            let g = (beta - d) / (beta + d);
+            let e = (-d * t).exp();
+
+            let c = (r - lambda * mean_jump) * i * u * t
+                + kappa * theta / (xi * xi)
+                    * ((beta - d) * t - 2.0 * ((1.0 - g * e) / (1.0 - g)).ln());
+            let dd = (beta - d) / (xi * xi) * (1.0 - e) / (1.0 - g * e);
+            let jumps = lambda * t * ((i * u * mu_j - 0.5 * sigma_j * sigma_j * u * u).exp() - 1.0);
+
+            (c + dd * v_0 + jumps + i * u * s_0.ln()).exp()
+        };
+
+        let forward = cf(-i);
+
+        let p_1 = 0.5
+            + FRAC_1_PI
+                * integrate(
+                    |u| {
+                        let u = Complex::new(u, 0.0);
+                        ((-i * u * k.ln()).exp() * cf(u - i) / (i * u * forward)).re
+                    },
+                    1e-5,
+                    100.0,
+                );
+        let p_2 = 0.5
+            + FRAC_1_PI
+                * integrate(
+                    |u| {
+                        let u = Complex::new(u, 0.0);
+                        ((-i * u * k.ln()).exp() * cf(u) / (i * u)).re
+                    },
+                    1e-5,
+                    100.0,
+                );
+
+        s_0 * p_1 - k * (-r * t).exp() * p_2
+    }
+
+    #[test]
+    fn test_bates_european_call() {
+        let (s_0, k, r, t) = (100.0, 100.0, 0.05, 1.0);
+        let heston = (0.04, 0.04, 2.0, -0.5, 0.3);
+        let jumps = (0.5, -0.1, 0.15);
+
+        let reference = bates_call_fourier(s_0, k, r, t, heston, jumps);
+
+        let (v_0, theta, kappa, rho, xi) = heston;
+        let (lambda, mu_j, sigma_j) = jumps;
+        let bates = Bates::new(v_0, theta, kappa, rho, xi, lambda, mu_j, sigma_j).with_drift(r);
+
+        let contract = |strike_flag| {
+            OptionContractBuilder::default()
+                .type_flag(TypeFlag::Call)
+                .exercise_flag(ExerciseFlag::European {
+                    expiry: date!(2025 - 01 - 01),
+                })
+                .strike_flag(strike_flag)
+                .build()
+                .unwrap()
+        };
+
+        let config = StochasticProcessConfig::new(s_0, 0.0, t, 100, 20_000, true).with_seed(11);
+
+        let european = VanillaOption::new(contract(None), k).price_monte_carlo(&bates, &config, r);
+
+        // Standard error of the estimate is about 0.1.
+        assert!(
+            (european - reference).abs() < 0.4,
+            "MC: {european}, reference: {reference}"
+        );
+
+        // Averaging reduces the optionality.
+        let asian = AsianOption::new(
+            contract(Some(StrikeFlag::Fixed)),
+            AveragingMethod::ArithmeticDiscrete,
+            Some(k),
+        )
+        .price_monte_carlo(&bates, &config, r);
+
+        assert!(asian > 0.0 && asian < european);
+    }
+
+    #[test]
+    fn test_bates_paths_match_across_apis() {
+        let bates = Bates::new(0.04, 0.04, 2.0, -0.5, 0.3, 0.5, -0.1, 0.15).with_drift(0.05);
+
+        // Both APIs draw path i from `config.path_rng(i)`, whatever the chunk size.
+        for (chunk_size, parallel) in [(1, false), (4, true), (7, false)] {
+            let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 40, parallel)
+                .with_seed(9)
+                .with_chunk_size(chunk_size);
+
+            let asset = StochasticProcess::euler_maruyama(&bates, &config);
+            let factors = bates.simulate(MultifactorScheme::EulerMaruyama, &[100.0, 0.04], &config);
+
+            assert_eq!(asset.paths, factors.paths[0]);
+        }
+    }
+}
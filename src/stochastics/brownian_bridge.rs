@@ -0,0 +1,238 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Brownian bridge path construction.
+//!
+//! Rather than summing sequential increments, the path is built by
+//! recursive bisection: the terminal value first (unless pinned), then the
+//! midpoint conditional on the endpoints, then the quarter points, and so on.
+//! The first normal draws therefore determine the coarse shape of the path,
+//! which is what makes the construction effective with low-discrepancy
+//! (e.g. Sobol) sequences.
+
+use std::collections::VecDeque;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Brownian bridge construction of Brownian paths on a uniform time grid.
+#[derive(Debug, Clone)]
+pub struct BrownianBridge {
+    /// Time grid, from `t_0` to `t_n` inclusive.
+    times: Vec<f64>,
+
+    /// Value of the path at `t_0`.
+    start: f64,
+
+    /// Value of the path at `t_n`, if pinned.
+    end: Option<f64>,
+
+    /// Construction steps, in the order the normal draws are consumed.
+    steps: Vec<BridgeStep>,
+}
+
+/// Fill `index` from its (already constructed) neighbours `left` and `right`.
+#[derive(Debug, Clone, Copy)]
+struct BridgeStep {
+    index: usize,
+    left: usize,
+    right: usize,
+    left_weight: f64,
+    right_weight: f64,
+    std_dev: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BrownianBridge {
+    /// Unconditional Brownian bridge construction, with $W(t_0) = 0$.
+    /// `build_path` takes `n_steps` normal draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t_0 >= t_n` or `n_steps` is zero.
+    #[must_use]
+    pub fn new(t_0: f64, t_n: f64, n_steps: usize) -> Self {
+        Self::construct(t_0, t_n, n_steps, 0.0, None)
+    }
+
+    /// Brownian bridge pinned at $W(t_0) = a$ and $W(t_n) = b$.
+    /// `build_path` takes `n_steps - 1` normal draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t_0 >= t_n` or `n_steps` is zero.
+    #[must_use]
+    pub fn pinned(t_0: f64, t_n: f64, n_steps: usize, a: f64, b: f64) -> Self {
+        Self::construct(t_0, t_n, n_steps, a, Some(b))
+    }
+
+    fn construct(t_0: f64, t_n: f64, n_steps: usize, start: f64, end: Option<f64>) -> Self {
+        assert!(t_0 < t_n);
+        assert!(n_steps > 0);
+
+        let dt = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let mut steps = Vec::with_capacity(n_steps);
+
+        if end.is_none() {
+            steps.push(BridgeStep {
+                index: n_steps,
+                left: 0,
+                right: 0,
+                left_weight: 1.0,
+                right_weight: 0.0,
+                std_dev: (t_n - t_0).sqrt(),
+            });
+        }
+
+        // Breadth-first bisection, so coarse points come first.
+        let mut intervals = VecDeque::from([(0, n_steps)]);
+
+        while let Some((left, right)) = intervals.pop_front() {
+            if right - left < 2 {
+                continue;
+            }
+
+            let index = (left + right) / 2;
+            let (t_l, t_m, t_r) = (times[left], times[index], times[right]);
+
+            steps.push(BridgeStep {
+                index,
+                left,
+                right,
+                left_weight: (t_r - t_m) / (t_r - t_l),
+                right_weight: (t_m - t_l) / (t_r - t_l),
+                std_dev: ((t_m - t_l) * (t_r - t_m) / (t_r - t_l)).sqrt(),
+            });
+
+            intervals.push_back((left, index));
+            intervals.push_back((index, right));
+        }
+
+        Self {
+            times,
+            start,
+            end,
+            steps,
+        }
+    }
+
+    /// The time grid of the constructed paths.
+    #[must_use]
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Number of normal draws consumed by `build_path`.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Build a path on the time grid from independent standard normal draws.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z.len()` is not equal to `self.dimension()`.
+    #[must_use]
+    pub fn build_path(&self, z: &[f64]) -> Vec<f64> {
+        assert_eq!(z.len(), self.dimension());
+
+        let n_steps = self.times.len() - 1;
+        let mut path = vec![0.0; n_steps + 1];
+
+        path[0] = self.start;
+        if let Some(end) = self.end {
+            path[n_steps] = end;
+        }
+
+        for (step, z) in self.steps.iter().zip(z) {
+            path[step.index] = step.left_weight * path[step.left]
+                + step.right_weight * path[step.right]
+                + step.std_dev * z;
+        }
+
+        path
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_brownian_bridge {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    fn sample_paths(bridge: &BrownianBridge, m_paths: usize) -> Vec<Vec<f64>> {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        (0..m_paths)
+            .map(|_| {
+                let z: Vec<f64> = (0..bridge.dimension())
+                    .map(|_| rng.sample(StandardNormal))
+                    .collect();
+
+                bridge.build_path(&z)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_brownian_bridge_covariance() {
+        // Not a power of two, to exercise uneven bisection.
+        let bridge = BrownianBridge::new(0.0, 1.5, 6);
+        let paths = sample_paths(&bridge, 50_000);
+        let times = bridge.times();
+
+        assert!(paths.iter().all(|path| path[0] == 0.0));
+
+        for i in 1..times.len() {
+            for j in i..times.len() {
+                let covariance =
+                    paths.iter().map(|path| path[i] * path[j]).sum::<f64>() / paths.len() as f64;
+
+                assert!(
+                    (covariance - times[i].min(times[j])).abs() < 0.03,
+                    "Cov(W({}), W({})) = {covariance}",
+                    times[i],
+                    times[j]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_brownian_bridge_pinned() {
+        let (a, b) = (1.0, -0.5);
+        let bridge = BrownianBridge::pinned(0.0, 1.0, 4, a, b);
+
+        assert_eq!(bridge.dimension(), 3);
+
+        let paths = sample_paths(&bridge, 50_000);
+
+        assert!(paths.iter().all(|path| path[0] == a && path[4] == b));
+
+        // W(1/2) ~ N((a + b) / 2, 1/4).
+        let midpoints: Vec<f64> = paths.iter().map(|path| path[2]).collect();
+        let mean = midpoints.iter().sum::<f64>() / midpoints.len() as f64;
+        let variance =
+            midpoints.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / midpoints.len() as f64;
+
+        assert!((mean - 0.25).abs() < 0.01);
+        assert!((variance - 0.25).abs() < 0.01);
+    }
+}
@@ -8,7 +8,10 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::models::brownian_motion::BrownianMotion;
-use crate::stochastics::process::StochasticProcess;
+use crate::stochastics::brownian_bridge::BrownianBridge;
+use crate::stochastics::process::{StochasticProcess, StochasticProcessConfig, Trajectories};
+use rand::Rng;
+use rand_distr::StandardNormal;
 
 impl StochasticProcess for BrownianMotion {
     fn drift(&self, _x: f64, _t: f64) -> f64 {
@@ -28,6 +31,38 @@ impl StochasticProcess for BrownianMotion {
     }
 }
 
+impl BrownianMotion {
+    /// Brownian bridge from `config.x_0` at `config.t_0` to `wt` at
+    /// `config.t_n`, built with a [`BrownianBridge`] pinned at both ends,
+    /// so the interior points have variance $(t - t_0)(t_n - t)/(t_n - t_0)$.
+    ///
+    /// Path `i` draws from `config`'s generator for that path, so a seeded
+    /// configuration gives the same bridges in serial or in parallel.
+    ///
+    /// # Arguments:
+    /// * `config` - The grid, number of paths, starting value and seed.
+    /// * `wt` - The value at `config.t_n`.
+    #[must_use]
+    pub fn bridge(config: &StochasticProcessConfig, wt: f64) -> Trajectories {
+        let bridge = BrownianBridge::pinned(config.t_0, config.t_n, config.n_steps, config.x_0, wt);
+
+        let paths = config.map_paths(|i| {
+            let z: Vec<f64> = config
+                .path_rng(i)
+                .sample_iter(StandardNormal)
+                .take(bridge.dimension())
+                .collect();
+
+            bridge.build_path(&z)
+        });
+
+        Trajectories {
+            times: bridge.times().to_vec(),
+            paths,
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -91,4 +126,31 @@ mod sde_tests {
         // V[X_T] = T
         assert_approx_equal!(V_XT, 0.5, 0.5);
     }
+
+    #[test]
+    fn test_brownian_bridge() {
+        let (w0, wt, t0, tn) = (1.0, -0.5, 0.0, 2.0);
+        let config = StochasticProcessConfig::new(w0, t0, tn, 10, 50_000, false).with_seed(258);
+
+        let output = BrownianMotion::bridge(&config, wt);
+
+        // Seeded bridges are reproducible, in serial or in parallel.
+        let parallel = StochasticProcessConfig::new(w0, t0, tn, 10, 50_000, true).with_seed(258);
+        assert_eq!(BrownianMotion::bridge(&parallel, wt).paths, output.paths);
+
+        assert!(output
+            .paths
+            .iter()
+            .all(|path| path[0] == w0 && path[10] == wt));
+
+        // The midpoint has mean (w0 + wt) / 2 and variance (t - t0)(tn - t)/(tn - t0).
+        let midpoint: Vec<f64> = output.paths.iter().map(|path| path[5]).collect();
+
+        assert_approx_equal!(midpoint.mean(), 0.25, 0.02);
+        assert_approx_equal!(midpoint.variance(), 0.5, 0.02);
+
+        // Interior points near the ends have smaller variance.
+        let near_end: Vec<f64> = output.paths.iter().map(|path| path[9]).collect();
+        assert_approx_equal!(near_end.variance(), 1.8 * 0.2 / 2.0, 0.01);
+    }
 }
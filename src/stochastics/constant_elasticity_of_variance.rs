@@ -7,8 +7,20 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! Constant elasticity of variance (CEV) process:
+//!
+//! $dX(t) = \mu X(t) dt + \sigma X(t)^{\gamma} dW(t)$
+//!
+//! For $\gamma < 1$ the process can reach zero, which is absorbing: a step
+//! that would cross zero stops there, and the path stays at zero.
+//! Otherwise the steps are plain Euler-Maruyama, so $\gamma = 1$ gives the
+//! same paths as Geometric Brownian Motion, and $\gamma = \mu = 0$ the same
+//! paths as (driftless) Arithmetic Brownian Motion, for the same seed.
+
 use crate::models::constant_elasticity_of_variance::ConstantElasticityOfVariance;
 use crate::stochastics::process::StochasticProcess;
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
 
 impl StochasticProcess for ConstantElasticityOfVariance {
     fn drift(&self, x: f64, t: f64) -> f64 {
@@ -29,6 +41,23 @@ impl StochasticProcess for ConstantElasticityOfVariance {
     fn parameters(&self) -> Vec<f64> {
         vec![self.mu.0(0.0), self.sigma.0(0.0), self.elasticity.0(0.0)]
     }
+
+    /// Euler-Maruyama increment, absorbed at zero.
+    fn simulate_increment(&self, x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+
+        if x <= 0.0 {
+            return -x;
+        }
+
+        let increment = self.drift(x, t) * dt + self.diffusion(x, t) * dW;
+
+        if x + increment > 0.0 {
+            increment
+        } else {
+            -x
+        }
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -71,4 +100,67 @@ mod tests_cev {
         //     0.5
         // );
     }
+
+    #[test]
+    fn test_cev_unit_elasticity_is_gbm() {
+        use crate::models::GeometricBrownianMotion;
+
+        let cev = ConstantElasticityOfVariance::new(0.05, 0.3, 1.0);
+        let gbm = GeometricBrownianMotion::new(0.05, 0.3);
+
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 100, 100, false).with_seed(5);
+
+        assert_eq!(
+            cev.euler_maruyama(&config).paths,
+            gbm.euler_maruyama(&config).paths
+        );
+    }
+
+    #[test]
+    fn test_cev_zero_elasticity_is_abm() {
+        use crate::models::ArithmeticBrownianMotion;
+
+        // Far enough from zero that no path is absorbed.
+        let cev = ConstantElasticityOfVariance::new(0.0, 2.0, 0.0);
+        let abm = ArithmeticBrownianMotion::new(0.0, 2.0);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 100, false).with_seed(5);
+
+        assert_eq!(
+            cev.euler_maruyama(&config).paths,
+            abm.euler_maruyama(&config).paths
+        );
+        assert_eq!(
+            cev.simulate_terminal(&config),
+            abm.simulate_terminal(&config)
+        );
+    }
+
+    #[test]
+    fn test_cev_absorbed_at_zero() {
+        let cev = ConstantElasticityOfVariance::new(0.0, 1.5, 0.5);
+        let config = StochasticProcessConfig::new(0.5, 0.0, 1.0, 200, 1000, true).with_seed(11);
+
+        let output = cev.euler_maruyama(&config);
+
+        assert!(output.paths.iter().flatten().all(|&x| x >= 0.0));
+
+        // Some paths are absorbed, and stay at zero.
+        let absorbed: Vec<&Vec<f64>> = output
+            .paths
+            .iter()
+            .filter(|path| path[200] == 0.0)
+            .collect();
+        assert!(!absorbed.is_empty());
+
+        for path in absorbed {
+            let hit = path.iter().position(|&x| x == 0.0).unwrap();
+            assert!(path[hit..].iter().all(|&x| x == 0.0));
+        }
+
+        // No NaNs or negative values at the terminal time either.
+        let terminal = cev.simulate_terminal(&config);
+        assert!(terminal.iter().all(|&x| x >= 0.0));
+        assert_eq!(terminal, output.terminal_values());
+    }
 }
@@ -0,0 +1,225 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Convergence diagnostics for the Euler-Maruyama scheme.
+//!
+//! The scheme is run at a ladder of step counts $n, 2n, 4n, \dots$, all
+//! driven by the same Brownian increments (summed from the finest grid),
+//! so that each level can be compared path by path with the exact solution.
+//! For step size $h$ the errors at the terminal time $T$ are
+//!
+//! - strong: $\mathbb{E} |X_T^h - X_T|$, against the exact solution, and
+//! - weak: $|\mathbb{E}[X_T^h] - \mathbb{E}[X_T]|$, against the analytic mean.
+//!
+//! The orders of convergence are the least-squares slopes of the log errors
+//! against the log step sizes. The weak error is estimated by the sample
+//! mean, so it cannot be resolved below its standard error: use enough
+//! paths that the errors of interest lie well above it.
+
+use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors of the Euler-Maruyama scheme at a ladder of step counts, and the
+/// estimated orders of convergence.
+#[derive(Debug, Clone)]
+pub struct ConvergenceReport {
+    /// Number of time steps at each level.
+    pub n_steps: Vec<usize>,
+
+    /// Step size at each level.
+    pub step_sizes: Vec<f64>,
+
+    /// Strong error at each level.
+    pub strong_errors: Vec<f64>,
+
+    /// Weak error at each level.
+    pub weak_errors: Vec<f64>,
+
+    /// Standard error of the simulated mean at each level, which bounds
+    /// the weak errors that can be resolved.
+    pub weak_standard_errors: Vec<f64>,
+
+    /// Estimated strong order of convergence.
+    pub strong_order: f64,
+
+    /// Estimated weak order of convergence.
+    pub weak_order: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Run the Euler-Maruyama scheme of the process's drift and diffusion at
+/// `levels` step counts, doubling from `config.n_steps`, and compare it
+/// with the exact solution.
+///
+/// # Arguments:
+/// * `process` - The process to simulate (jumps are ignored).
+/// * `config` - Simulation config; `n_steps` is the coarsest level.
+/// * `exact_solution` - The exact $X_t$ at time `t`, given the increment
+///   $W_t - W_{t_0}$ of the driving Brownian motion.
+/// * `exact_mean` - The analytic mean $\mathbb{E}[X_{t_n}]$.
+/// * `levels` - Number of levels in the ladder.
+///
+/// # Panics
+///
+/// Panics if there are fewer than two levels or paths, or no steps.
+pub fn convergence_report<P, S>(
+    process: &P,
+    config: &StochasticProcessConfig,
+    exact_solution: S,
+    exact_mean: f64,
+    levels: usize,
+) -> ConvergenceReport
+where
+    P: StochasticProcess,
+    S: Fn(f64, f64) -> f64 + Sync,
+{
+    let (x_0, t_0, t_n, n_steps, m_paths, _) = config.unpack();
+
+    assert!(levels >= 2 && n_steps > 0 && m_paths > 1);
+    assert!(t_0 < t_n);
+
+    let ladder: Vec<usize> = (0..levels).map(|level| n_steps << level).collect();
+    let step_sizes: Vec<f64> = ladder.iter().map(|&n| (t_n - t_0) / n as f64).collect();
+    let finest = ladder[levels - 1];
+    let sqrt_dt = (step_sizes[levels - 1]).sqrt();
+
+    // Terminal values at each level, and the exact terminal value.
+    let sample = |i: usize| -> (Vec<f64>, f64) {
+        let mut rng = config.path_rng(i);
+        let increments: Vec<f64> = (0..finest)
+            .map(|_| sqrt_dt * rng.sample::<f64, _>(StandardNormal))
+            .collect();
+
+        let approximations = ladder
+            .iter()
+            .zip(&step_sizes)
+            .map(|(&n, &dt)| {
+                let mut x = x_0;
+
+                for (k, fine) in increments.chunks(finest / n).enumerate() {
+                    let t = t_0 + k as f64 * dt;
+                    let dw: f64 = fine.iter().sum();
+
+                    x += process.drift(x, t) * dt + process.diffusion(x, t) * dw;
+                }
+
+                x
+            })
+            .collect();
+
+        (approximations, exact_solution(t_n, increments.iter().sum()))
+    };
+
+    let samples: Vec<(Vec<f64>, f64)> = config.map_paths(sample);
+
+    let m = m_paths as f64;
+    let mut strong_errors = Vec::with_capacity(levels);
+    let mut weak_errors = Vec::with_capacity(levels);
+    let mut weak_standard_errors = Vec::with_capacity(levels);
+
+    for level in 0..levels {
+        let (mut sum, mut sum_of_squares, mut absolute_error) = (0.0, 0.0, 0.0);
+
+        for (approximations, exact) in &samples {
+            let x = approximations[level];
+
+            sum += x;
+            sum_of_squares += x * x;
+            absolute_error += (x - exact).abs();
+        }
+
+        let mean = sum / m;
+        let variance = (sum_of_squares - m * mean * mean) / (m - 1.0);
+
+        strong_errors.push(absolute_error / m);
+        weak_errors.push((mean - exact_mean).abs());
+        weak_standard_errors.push((variance / m).sqrt());
+    }
+
+    ConvergenceReport {
+        strong_order: convergence_order(&step_sizes, &strong_errors),
+        weak_order: convergence_order(&step_sizes, &weak_errors),
+        n_steps: ladder,
+        step_sizes,
+        strong_errors,
+        weak_errors,
+        weak_standard_errors,
+    }
+}
+
+/// Least-squares slope of `ln(error)` against `ln(step size)`.
+fn convergence_order(step_sizes: &[f64], errors: &[f64]) -> f64 {
+    let n = step_sizes.len() as f64;
+    let x: Vec<f64> = step_sizes.iter().map(|h| h.ln()).collect();
+    let y: Vec<f64> = errors.iter().map(|e| e.ln()).collect();
+
+    let x_mean = x.iter().sum::<f64>() / n;
+    let y_mean = y.iter().sum::<f64>() / n;
+
+    let covariance: f64 = x
+        .iter()
+        .zip(&y)
+        .map(|(x, y)| (x - x_mean) * (y - y_mean))
+        .sum();
+    let variance: f64 = x.iter().map(|x| (x - x_mean).powi(2)).sum();
+
+    covariance / variance
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_convergence {
+    use super::*;
+    use crate::models::GeometricBrownianMotion;
+
+    fn gbm_report(mu: f64, sigma: f64, m_paths: usize) -> ConvergenceReport {
+        let (x_0, t_n) = (1.0, 1.0);
+        let gbm = GeometricBrownianMotion::new(mu, sigma);
+        let config = StochasticProcessConfig::new(x_0, 0.0, t_n, 8, m_paths, true).with_seed(17);
+
+        convergence_report(
+            &gbm,
+            &config,
+            |t, w| x_0 * ((mu - 0.5 * sigma * sigma) * t + sigma * w).exp(),
+            x_0 * (mu * t_n).exp(),
+            5,
+        )
+    }
+
+    #[test]
+    fn test_euler_maruyama_orders_for_gbm() {
+        // The strong error is dominated by the diffusion when the volatility
+        // is large relative to the drift ...
+        let report = gbm_report(0.1, 1.0, 5_000);
+
+        assert_eq!(report.n_steps, vec![8, 16, 32, 64, 128]);
+        assert!(report.strong_errors.windows(2).all(|e| e[1] < e[0]));
+        assert!((report.strong_order - 0.5).abs() < 0.1, "{report:?}");
+
+        // ... while the weak error, E[X_T^h] = (1 + mu h)^n, needs a drift
+        // large enough for it to stand out from the sampling error.
+        let report = gbm_report(2.0, 0.1, 20_000);
+
+        for (error, standard_error) in report.weak_errors.iter().zip(&report.weak_standard_errors) {
+            assert!(*error > 10.0 * standard_error);
+        }
+        assert!((report.weak_order - 1.0).abs() < 0.15, "{report:?}");
+    }
+}
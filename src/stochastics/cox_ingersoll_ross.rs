@@ -7,8 +7,33 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::models::cox_ingersoll_ross::CoxIngersollRoss;
-use crate::stochastics::process::StochasticProcess;
+use crate::math::distributions::NonCentralChiSquared;
+use crate::models::cox_ingersoll_ross::{CirScheme, CoxIngersollRoss};
+use crate::stochastics::process::{
+    terminal_value_with, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
+
+impl CirScheme {
+    /// One step of the auxiliary state `x`, given the Brownian increment `dW`.
+    pub(crate) fn step(self, x: f64, (mu, sigma, theta): (f64, f64, f64), dt: f64, dW: f64) -> f64 {
+        match self {
+            CirScheme::FullTruncation => {
+                x + theta * (mu - x.max(0.0)) * dt + sigma * x.max(0.0).sqrt() * dW
+            }
+            CirScheme::PartialTruncation => {
+                x + theta * (mu - x) * dt + sigma * x.max(0.0).sqrt() * dW
+            }
+            CirScheme::Reflection => {
+                let x = x.abs();
+
+                (x + theta * (mu - x) * dt + sigma * x.sqrt() * dW).abs()
+            }
+        }
+    }
+}
 
 impl StochasticProcess for CoxIngersollRoss {
     fn drift(&self, x: f64, t: f64) -> f64 {
@@ -27,6 +52,118 @@ impl StochasticProcess for CoxIngersollRoss {
     fn parameters(&self) -> Vec<f64> {
         vec![self.mu.0(0.0), self.sigma.0(0.0), self.theta.0(0.0)]
     }
+
+    /// Euler-Maruyama with the process's [CirScheme], so the paths are
+    /// non-negative even when the Feller condition fails
+    /// (see [CoxIngersollRoss::feller_condition]).
+    fn simulate_path_into(
+        &self,
+        config: &StochasticProcessConfig,
+        index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let (x_0, t_0, t_n, n_steps, _, _) = config.unpack();
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let sqrt_dt = dt.sqrt();
+
+        let mut rng = config.path_rng(index);
+        let mut x = x_0;
+
+        path.push(x_0);
+
+        for t in 0..n_steps {
+            let dW = rng.sample::<f64, _>(StandardNormal) * sqrt_dt;
+
+            x = self
+                .scheme
+                .step(x, self.parameters_at(t_0 + dt * (t as f64)), dt, dW);
+            path.push(x.max(0.0));
+        }
+    }
+
+    /// Terminal value of [StochasticProcess::simulate_path].
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        terminal_value_with(config, index, |x, t, dt, z| {
+            self.scheme
+                .step(x, self.parameters_at(t), dt, z * dt.sqrt())
+        })
+        .max(0.0)
+    }
+
+    /// Increment of one step of the [CirScheme], restarted from the
+    /// (non-negative) process value `x`.
+    fn simulate_increment(&self, x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+
+        self.scheme.step(x, self.parameters_at(t), dt, dW).max(0.0) - x
+    }
+}
+
+impl CoxIngersollRoss {
+    /// The parameters $(\mu, \sigma, \theta)$ at time `t`.
+    fn parameters_at(&self, t: f64) -> (f64, f64, f64) {
+        (self.mu.0(t), self.sigma.0(t), self.theta.0(t))
+    }
+
+    /// Exact simulation, sampling each transition from the scaled
+    /// non-central chi-squared distribution:
+    ///
+    /// $$
+    /// X_{t + \Delta t} = c \chi'^2_d(\lambda), \quad
+    /// c = \frac{\sigma^2 (1 - e^{-\theta \Delta t})}{4 \theta}, \quad
+    /// d = \frac{4 \theta \mu}{\sigma^2}, \quad
+    /// \lambda = \frac{X_t e^{-\theta \Delta t}}{c}
+    /// $$
+    ///
+    /// Unlike the Euler-Maruyama scheme, the paths are non-negative and
+    /// unbiased for any step size, even when the Feller condition fails.
+    /// Time-dependent parameters are frozen at the start of each step.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    pub fn exact(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(t_0 < t_n);
+        assert!(x_0 >= 0.0);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let (mu, sigma, theta) = (
+                    self.mu.0(times[t]),
+                    self.sigma.0(times[t]),
+                    self.theta.0(times[t]),
+                );
+
+                let decay = (-theta * dt).exp();
+                let c = sigma * sigma * (1.0 - decay) / (4.0 * theta);
+                let d = 4.0 * theta * mu / (sigma * sigma);
+
+                path[t + 1] =
+                    c * NonCentralChiSquared::new(d, path[t] * decay / c).sample_with_rng(&mut rng);
+            }
+        };
+
+        if parallel {
+            paths
+                .par_iter_mut()
+                .with_min_len(config.chunk_size)
+                .enumerate()
+                .for_each(path_generator);
+        } else {
+            paths.iter_mut().enumerate().for_each(path_generator);
+        }
+
+        Trajectories { times, paths }
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -75,4 +212,75 @@ mod tests_cir {
         // let file2 = "./images/CIR2.png";
         // plot_vector((&output.trajectories[1]).clone(), file2)
     }
+
+    #[test]
+    fn test_cox_ingersoll_ross_exact() {
+        // 2 * theta * mu < sigma^2, so the Feller condition is violated.
+        let (mu, sigma, theta, x_0, T) = (0.05, 0.3, 0.5, 0.03, 2.0_f64);
+
+        let cir = CoxIngersollRoss::new(mu, sigma, theta);
+
+        let config = StochasticProcessConfig::new(x_0, 0.0, T, 10, 50_000, true).with_seed(1234);
+
+        let output = cir.exact(&config);
+
+        assert!(output.paths.iter().flatten().all(|&x| x >= 0.0));
+
+        let X_T: Vec<f64> = output
+            .paths
+            .iter()
+            .filter_map(|v| v.last().copied())
+            .collect();
+
+        let decay = (-theta * T).exp();
+        let mean = x_0 * decay + mu * (1.0 - decay);
+        let variance = x_0 * sigma * sigma / theta * (decay - decay * decay)
+            + mu * sigma * sigma / (2.0 * theta) * (1.0 - decay).powi(2);
+
+        assert_approx_equal!(X_T.mean(), mean, 1e-3);
+        assert_approx_equal!(X_T.variance(), variance, 1e-4);
+
+        // Reproducible given a seed.
+        assert_eq!(cir.exact(&config).paths, output.paths);
+    }
+
+    #[test]
+    fn test_positivity_preserving_schemes() {
+        // 2 * theta * mu = 0.005 < sigma^2 = 0.25, with a coarse step.
+        let (mu, sigma, theta, x_0) = (0.05, 0.5, 0.05, 0.01);
+        let config = StochasticProcessConfig::new(x_0, 0.0, 5.0, 50, 2_000, true).with_seed(7);
+
+        let cir = CoxIngersollRoss::new(mu, sigma, theta);
+        assert!(!cir.feller_condition(0.0));
+        assert!(CoxIngersollRoss::new(0.05, 0.1, 1.0).feller_condition(0.0));
+
+        for scheme in [
+            CirScheme::FullTruncation,
+            CirScheme::PartialTruncation,
+            CirScheme::Reflection,
+        ] {
+            let cir = CoxIngersollRoss::new(mu, sigma, theta).with_scheme(scheme);
+            let output = cir.euler_maruyama(&config);
+
+            assert!(
+                output.paths.iter().flatten().all(|&x| x >= 0.0),
+                "{scheme:?}"
+            );
+            // The truncation schemes hold paths at zero when they cross it.
+            if scheme != CirScheme::Reflection {
+                assert!(output.paths.iter().flatten().any(|&x| x == 0.0));
+            }
+
+            // The terminal values agree with the paths, and the restarted
+            // increments stay well-defined.
+            assert_eq!(cir.simulate_terminal(&config), output.terminal_values());
+            assert!(cir
+                .try_euler_maruyama(&config)
+                .unwrap()
+                .paths
+                .iter()
+                .flatten()
+                .all(|&x| x >= 0.0));
+        }
+    }
 }
@@ -0,0 +1,174 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Deterministic (time-dependent) volatility calibrated to a term structure
+//! of ATM implied volatilities.
+//!
+//! Under GBM with volatility $\sigma(t)$, the implied volatility $\Sigma(T)$
+//! of an option expiring at $T$ matches the integrated variance:
+//!
+//! $$
+//! \Sigma^2(T) \, T = \int_0^T \sigma^2(t) \, dt
+//! $$
+//!
+//! With $\sigma$ constant between the maturities $T_{i-1} < T_i$, it is the
+//! forward volatility
+//!
+//! $$
+//! \sigma_i^2 = \frac{\Sigma^2(T_i) T_i - \Sigma^2(T_{i-1}) T_{i-1}}{T_i - T_{i-1}}
+//! $$
+
+use crate::error::RustQuantError;
+use crate::models::ModelParameter;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bootstrap the piecewise-constant instantaneous volatility that reprices
+/// the given ATM implied volatilities, as `(maturity, volatility)` pairs.
+///
+/// The volatility of the period $[T_{i-1}, T_i)$ (with $T_0 = 0$) applies
+/// up to $T_i$ exclusive, so a simulation grid containing the maturities
+/// freezes each step at the volatility of the period it covers. The last
+/// volatility is extended flat after the last maturity.
+///
+/// The result can be used directly as a [ModelParameter], e.g. the
+/// volatility of a `GeometricBrownianMotion`.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if there are no volatilities, the
+///   maturities are not positive and strictly increasing, a volatility is
+///   negative, or the total implied variance decreases with maturity
+///   (calendar arbitrage), so no real forward volatility exists.
+pub fn calibrate_time_dependent_vol(
+    atm_vols: &[(f64, f64)],
+) -> Result<impl Fn(f64) -> f64 + Send + Sync + 'static, RustQuantError> {
+    if atm_vols.is_empty() {
+        return Err(RustQuantError::InvalidArgument(
+            "At least one ATM volatility is required.".to_string(),
+        ));
+    }
+
+    if atm_vols[0].0 <= 0.0 || atm_vols.windows(2).any(|w| w[0].0 >= w[1].0) {
+        return Err(RustQuantError::InvalidArgument(
+            "Maturities must be positive and strictly increasing.".to_string(),
+        ));
+    }
+
+    if atm_vols.iter().any(|&(_, vol)| vol < 0.0 || vol.is_nan()) {
+        return Err(RustQuantError::InvalidArgument(
+            "Volatilities must be non-negative.".to_string(),
+        ));
+    }
+
+    let mut forward_vols = Vec::with_capacity(atm_vols.len());
+    let (mut start, mut total_variance) = (0.0, 0.0);
+
+    for &(maturity, vol) in atm_vols {
+        let variance = vol * vol * maturity;
+        let forward_variance = (variance - total_variance) / (maturity - start);
+
+        if forward_variance < 0.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Total implied variance decreases at maturity {maturity} (calendar arbitrage)."
+            )));
+        }
+
+        forward_vols.push(forward_variance.sqrt());
+        (start, total_variance) = (maturity, variance);
+    }
+
+    let breakpoints = atm_vols[..atm_vols.len() - 1]
+        .iter()
+        .map(|&(maturity, _)| maturity)
+        .collect();
+
+    Ok(ModelParameter::piecewise_constant(breakpoints, forward_vols).0)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_deterministic_volatility {
+    use super::*;
+    use crate::math::distributions::{Distribution, Gaussian};
+    use crate::math::Statistic;
+    use crate::models::GeometricBrownianMotion;
+    use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+
+    const ATM_VOLS: [(f64, f64); 4] = [(0.25, 0.30), (0.5, 0.26), (1.0, 0.22), (2.0, 0.24)];
+
+    #[test]
+    fn test_integrated_variance_matches() {
+        let sigma = calibrate_time_dependent_vol(&ATM_VOLS).unwrap();
+
+        // sigma(t)^2 integrated with the midpoint rule on a fine grid.
+        let n = 80_000;
+        let dt = 2.0 / n as f64;
+        let mut variance = 0.0;
+        let mut k = 0;
+
+        for i in 0..n {
+            variance += sigma((i as f64 + 0.5) * dt).powi(2) * dt;
+
+            if ((i + 1) as f64 * dt - ATM_VOLS[k].0).abs() < 1e-9 {
+                let (maturity, vol) = ATM_VOLS[k];
+                assert_approx_equal!(variance, vol * vol * maturity, 1e-10);
+                k += 1;
+            }
+        }
+        assert_eq!(k, ATM_VOLS.len());
+
+        // Flat after the last maturity, and the new period starts at each maturity.
+        assert_eq!(sigma(5.0), sigma(1.5));
+        assert_eq!(sigma(0.0), 0.30);
+        assert_ne!(sigma(0.25), 0.30);
+    }
+
+    #[test]
+    fn test_gbm_reprices_atm_options() {
+        let (spot, rate) = (100.0, 0.02);
+        let sigma = calibrate_time_dependent_vol(&ATM_VOLS).unwrap();
+        let gbm = GeometricBrownianMotion::new(rate, sigma);
+        let n = Gaussian::default();
+
+        for (i, &(maturity, vol)) in ATM_VOLS.iter().enumerate() {
+            // Steps of 0.05 years land on each maturity.
+            let n_steps = (maturity / 0.05).round() as usize;
+            let config = StochasticProcessConfig::new(spot, 0.0, maturity, n_steps, 100_000, true)
+                .with_seed(i as u64);
+
+            let discount = (-rate * maturity).exp();
+            let payoffs: Vec<f64> = gbm
+                .simulate_terminal(&config)
+                .iter()
+                .map(|s_t| discount * (s_t - spot).max(0.0))
+                .collect();
+
+            let standard_error = (payoffs.variance() / payoffs.len() as f64).sqrt();
+
+            let d1 = (rate + 0.5 * vol * vol) * maturity / (vol * maturity.sqrt());
+            let d2 = d1 - vol * maturity.sqrt();
+            let black_scholes = spot * n.cdf(d1) - spot * discount * n.cdf(d2);
+
+            assert_approx_equal!(payoffs.mean(), black_scholes, 3.0 * standard_error);
+        }
+    }
+
+    #[test]
+    fn test_calendar_arbitrage_is_rejected() {
+        // Total variance 0.09 at 1y, but only 0.08 at 2y.
+        assert!(calibrate_time_dependent_vol(&[(1.0, 0.3), (2.0, 0.2)]).is_err());
+        assert!(calibrate_time_dependent_vol(&[(1.0, 0.3), (1.0, 0.3)]).is_err());
+        assert!(calibrate_time_dependent_vol(&[]).is_err());
+    }
+}
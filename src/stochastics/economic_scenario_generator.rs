@@ -0,0 +1,490 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Economic scenario generator (ESG).
+//!
+//! Jointly simulates an equity index, a short rate, and a credit spread
+//! with correlated Brownian drivers:
+//!
+//! - Equity: Geometric Brownian Motion (log-Euler scheme).
+//! - Short rate: Hull-White (Euler scheme).
+//! - Credit spread: Cox-Ingersoll-Ross (full-truncation Euler scheme).
+//!
+//! Scenarios can be generated under the risk-neutral measure, where the
+//! equity drifts at the simulated short rate, or under the real-world
+//! measure, where the equity drifts at the GBM's $\mu$ and the rate and
+//! spread drifts are shifted by their market prices of risk.
+
+use crate::data::{Data, DataFormat, DataWriter};
+use crate::error::RustQuantError;
+use crate::models::{CoxIngersollRoss, GeometricBrownianMotion, HullWhite};
+use crate::stochastics::{StochasticProcess, StochasticProcessConfig};
+use nalgebra::{DMatrix, DVector};
+use polars::prelude::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use time::{Date, Duration};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Probability measure under which scenarios are generated.
+#[derive(Debug, Clone, Copy)]
+pub enum ScenarioMeasure {
+    /// Risk-neutral measure.
+    /// The equity drifts at the short rate, and the rate and spread
+    /// models are used with their parameters as given.
+    RiskNeutral,
+
+    /// Real-world (physical) measure.
+    /// The equity drifts at the GBM's $\mu$, and the rate and spread
+    /// drifts are shifted by $\lambda \sigma(x, t)$.
+    RealWorld {
+        /// Market price of risk for the short rate ($\lambda_r$).
+        rate_market_price_of_risk: f64,
+
+        /// Market price of risk for the credit spread ($\lambda_s$).
+        spread_market_price_of_risk: f64,
+    },
+}
+
+/// Economic variables produced by the scenario generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioVariable {
+    /// Equity index level.
+    Equity,
+
+    /// Instantaneous short rate.
+    ShortRate,
+
+    /// Instantaneous credit spread.
+    CreditSpread,
+
+    /// Stochastic discount factor $\exp(-\int_0^t r_s ds)$.
+    DiscountFactor,
+}
+
+/// Economic scenario generator.
+///
+/// The correlation matrix is ordered as (equity, short rate, credit spread).
+pub struct EconomicScenarioGenerator {
+    /// Equity model. The volatility is always used, the drift only
+    /// under the real-world measure.
+    pub equity: GeometricBrownianMotion,
+
+    /// Short rate model.
+    pub rates: HullWhite,
+
+    /// Credit spread model.
+    pub credit: CoxIngersollRoss,
+
+    /// Initial equity level.
+    pub initial_equity: f64,
+
+    /// Initial short rate.
+    pub initial_rate: f64,
+
+    /// Initial credit spread.
+    pub initial_spread: f64,
+
+    /// Correlation matrix of the Brownian drivers.
+    pub correlation: [[f64; 3]; 3],
+
+    /// Measure under which to simulate.
+    pub measure: ScenarioMeasure,
+
+    /// Date corresponding to $t = 0$.
+    pub start_date: Date,
+
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+/// Output of the economic scenario generator.
+pub struct EconomicScenarios {
+    /// Vector of time points (in years).
+    pub times: Vec<f64>,
+
+    /// Vector of dates, one per time point.
+    pub dates: Vec<Date>,
+
+    /// Equity paths.
+    pub equity: Vec<Vec<f64>>,
+
+    /// Short rate paths.
+    pub short_rate: Vec<Vec<f64>>,
+
+    /// Credit spread paths.
+    pub credit_spread: Vec<Vec<f64>>,
+
+    /// Stochastic discount factor paths.
+    pub discount_factor: Vec<Vec<f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl EconomicScenarioGenerator {
+    /// Create a new economic scenario generator.
+    ///
+    /// Returns an error if the correlation matrix is not symmetric,
+    /// does not have a unit diagonal, or is not positive definite.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        equity: GeometricBrownianMotion,
+        rates: HullWhite,
+        credit: CoxIngersollRoss,
+        initial_equity: f64,
+        initial_rate: f64,
+        initial_spread: f64,
+        correlation: [[f64; 3]; 3],
+        measure: ScenarioMeasure,
+        start_date: Date,
+    ) -> Result<Self, RustQuantError> {
+        for (i, row) in correlation.iter().enumerate() {
+            if (row[i] - 1.0).abs() > f64::EPSILON {
+                return Err(RustQuantError::InvalidArgument(
+                    "Correlation matrix must have a unit diagonal.".to_string(),
+                ));
+            }
+
+            for (j, column) in correlation.iter().enumerate().take(i) {
+                if (row[j] - column[i]).abs() > f64::EPSILON {
+                    return Err(RustQuantError::InvalidArgument(
+                        "Correlation matrix must be symmetric.".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let matrix = DMatrix::from_fn(3, 3, |i, j| correlation[i][j]);
+
+        let cholesky = matrix
+            .cholesky()
+            .ok_or(RustQuantError::InvalidArgument(
+                "Correlation matrix must be positive definite.".to_string(),
+            ))?
+            .l();
+
+        Ok(Self {
+            equity,
+            rates,
+            credit,
+            initial_equity,
+            initial_rate,
+            initial_spread,
+            correlation,
+            measure,
+            start_date,
+            cholesky,
+        })
+    }
+
+    /// Generate economic scenarios.
+    ///
+    /// The time grid, number of scenarios, parallelism and seed are taken
+    /// from `config`. Its `x_0` is unused: the initial equity, rate and
+    /// spread are those of the generator. Scenario `i` draws from `config`'s
+    /// generator for path `i`, so a seeded configuration gives the same
+    /// scenarios in serial or in parallel.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration (times in years from the start date).
+    pub fn generate(&self, config: &StochasticProcessConfig) -> EconomicScenarios {
+        assert!(config.t_n > config.t_0);
+
+        let (_, t_0, t_n, n_steps, m_paths, _) = config.unpack();
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let sqrt_dt = dt.sqrt();
+
+        let times: Vec<f64> = config.times();
+        let dates: Vec<Date> = times
+            .iter()
+            .map(|t| self.start_date + Duration::days((t * 365.0).round() as i64))
+            .collect();
+
+        let (lambda_r, lambda_s) = match self.measure {
+            ScenarioMeasure::RiskNeutral => (0.0, 0.0),
+            ScenarioMeasure::RealWorld {
+                rate_market_price_of_risk,
+                spread_market_price_of_risk,
+            } => (rate_market_price_of_risk, spread_market_price_of_risk),
+        };
+
+        let scenario_generator = |path: usize| {
+            let mut rng = config.path_rng(path);
+
+            let mut s = vec![self.initial_equity; n_steps + 1];
+            let mut r = vec![self.initial_rate; n_steps + 1];
+            let mut c = vec![self.initial_spread; n_steps + 1];
+            let mut d = vec![1.0; n_steps + 1];
+
+            for i in 0..n_steps {
+                let t = times[i];
+
+                let z = DVector::from_fn(3, |_, _| rng.sample::<f64, _>(StandardNormal));
+                let w = &self.cholesky * z;
+
+                // Equity (log-Euler, exact for constant coefficients).
+                let sigma = self.equity.sigma.0(t);
+                let mu = match self.measure {
+                    ScenarioMeasure::RiskNeutral => r[i],
+                    ScenarioMeasure::RealWorld { .. } => self.equity.mu.0(t),
+                };
+                s[i + 1] = s[i] * ((mu - 0.5 * sigma * sigma) * dt + sigma * sqrt_dt * w[0]).exp();
+
+                // Short rate (Euler).
+                let vol_r = self.rates.diffusion(r[i], t);
+                r[i + 1] = r[i]
+                    + (self.rates.drift(r[i], t) + lambda_r * vol_r) * dt
+                    + vol_r * sqrt_dt * w[1];
+
+                // Credit spread (full truncation Euler).
+                let c_plus = c[i].max(0.0);
+                let vol_c = self.credit.diffusion(c_plus, t);
+                c[i + 1] = c[i]
+                    + (self.credit.drift(c_plus, t) + lambda_s * vol_c) * dt
+                    + vol_c * sqrt_dt * w[2];
+
+                // Discount factor (left-point rule, keeping S * D a martingale).
+                d[i + 1] = d[i] * (-r[i] * dt).exp();
+            }
+
+            (s, r, c, d)
+        };
+
+        let scenarios = config.map_paths(scenario_generator);
+
+        let mut output = EconomicScenarios {
+            times,
+            dates,
+            equity: Vec::with_capacity(m_paths),
+            short_rate: Vec::with_capacity(m_paths),
+            credit_spread: Vec::with_capacity(m_paths),
+            discount_factor: Vec::with_capacity(m_paths),
+        };
+
+        for (s, r, c, d) in scenarios {
+            output.equity.push(s);
+            output.short_rate.push(r);
+            output.credit_spread.push(c);
+            output.discount_factor.push(d);
+        }
+
+        output
+    }
+}
+
+impl EconomicScenarios {
+    /// Get the simulated paths of a given variable.
+    pub fn paths(&self, variable: ScenarioVariable) -> &Vec<Vec<f64>> {
+        match variable {
+            ScenarioVariable::Equity => &self.equity,
+            ScenarioVariable::ShortRate => &self.short_rate,
+            ScenarioVariable::CreditSpread => &self.credit_spread,
+            ScenarioVariable::DiscountFactor => &self.discount_factor,
+        }
+    }
+
+    /// Convert the paths of a given variable to a `DataFrame`.
+    ///
+    /// The `DataFrame` has one row per time point, with columns
+    /// `date`, `time`, and `path_0`, ..., `path_{m-1}`.
+    pub fn to_dataframe(&self, variable: ScenarioVariable) -> Result<DataFrame, RustQuantError> {
+        let unix_epoch = time::OffsetDateTime::UNIX_EPOCH.date();
+
+        let days = self
+            .dates
+            .iter()
+            .map(|date| (*date - unix_epoch).whole_days() as i32)
+            .collect::<Vec<i32>>();
+
+        let mut columns = vec![
+            Series::new("date", days).cast(&DataType::Date)?,
+            Series::new("time", self.times.clone()),
+        ];
+
+        for (i, path) in self.paths(variable).iter().enumerate() {
+            columns.push(Series::new(&format!("path_{}", i), path.clone()));
+        }
+
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Write the paths of a given variable to a file (CSV, JSON, or Parquet).
+    pub fn write(
+        &self,
+        variable: ScenarioVariable,
+        format: DataFormat,
+        path: &str,
+    ) -> Result<(), RustQuantError> {
+        let mut data = Data::new(format, path.to_string());
+        data.data = self.to_dataframe(variable)?;
+
+        data.write()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_economic_scenario_generator {
+    use super::*;
+    use crate::data::DataReader;
+    use crate::math::Statistic;
+    use time::macros::date;
+
+    const CORRELATION: [[f64; 3]; 3] = [[1.0, -0.3, 0.5], [-0.3, 1.0, -0.2], [0.5, -0.2, 1.0]];
+
+    fn esg(measure: ScenarioMeasure) -> EconomicScenarioGenerator {
+        EconomicScenarioGenerator::new(
+            GeometricBrownianMotion::new(0.08, 0.2),
+            HullWhite::new(0.5, 0.01, 0.02),
+            CoxIngersollRoss::new(0.02, 0.05, 1.0),
+            100.0,
+            0.03,
+            0.015,
+            CORRELATION,
+            measure,
+            date!(2024 - 01 - 01),
+        )
+        .unwrap()
+    }
+
+    fn config(t_n: f64, n_steps: usize, m_paths: usize, parallel: bool) -> StochasticProcessConfig {
+        StochasticProcessConfig::new(0.0, 0.0, t_n, n_steps, m_paths, parallel).with_seed(254)
+    }
+
+    fn terminal(paths: &[Vec<f64>]) -> Vec<f64> {
+        paths.iter().filter_map(|p| p.last().copied()).collect()
+    }
+
+    #[test]
+    fn test_invalid_correlation() {
+        let not_psd = [[1.0, 0.9, -0.9], [0.9, 1.0, 0.9], [-0.9, 0.9, 1.0]];
+
+        let result = EconomicScenarioGenerator::new(
+            GeometricBrownianMotion::new(0.08, 0.2),
+            HullWhite::new(0.5, 0.01, 0.02),
+            CoxIngersollRoss::new(0.02, 0.05, 1.0),
+            100.0,
+            0.03,
+            0.015,
+            not_psd,
+            ScenarioMeasure::RiskNeutral,
+            date!(2024 - 01 - 01),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_risk_neutral_martingales() {
+        let t_n = 2.0;
+        let m_paths = 20_000;
+        let scenarios =
+            esg(ScenarioMeasure::RiskNeutral).generate(&config(t_n, 200, m_paths, true));
+
+        let d_t = terminal(&scenarios.discount_factor);
+        let s_t = terminal(&scenarios.equity);
+        let deflated = s_t
+            .iter()
+            .zip(&d_t)
+            .map(|(s, d)| s * d)
+            .collect::<Vec<f64>>();
+
+        // Discounted equity is a martingale.
+        let se = deflated.standard_deviation() / (m_paths as f64).sqrt();
+        assert!((deflated.mean() - 100.0).abs() < 4.0 * se);
+
+        // Hull-White zero-coupon bond price with constant parameters.
+        let (a, sigma, theta, r0) = (0.5_f64, 0.01_f64, 0.02_f64, 0.03_f64);
+        let b = (1.0 - (-a * t_n).exp()) / a;
+        let mean = theta / a * t_n + (r0 - theta / a) * b;
+        let variance =
+            sigma * sigma / (a * a) * (t_n - 2.0 * b + (1.0 - (-2.0 * a * t_n).exp()) / (2.0 * a));
+        let bond = (-mean + 0.5 * variance).exp();
+
+        // Deflated bond (discount factor) is a martingale.
+        let se = d_t.standard_deviation() / (m_paths as f64).sqrt();
+        assert!((d_t.mean() - bond).abs() < 4.0 * se + 1e-4);
+    }
+
+    #[test]
+    fn test_real_world_drift() {
+        let scenarios = esg(ScenarioMeasure::RealWorld {
+            rate_market_price_of_risk: 0.0,
+            spread_market_price_of_risk: 0.0,
+        })
+        .generate(&config(1.0, 50, 20_000, true));
+
+        let s_t = terminal(&scenarios.equity);
+        let se = s_t.standard_deviation() / (s_t.len() as f64).sqrt();
+
+        assert!((s_t.mean() - 100.0 * 0.08_f64.exp()).abs() < 4.0 * se);
+    }
+
+    #[test]
+    fn test_correlations() {
+        let scenarios = esg(ScenarioMeasure::RiskNeutral).generate(&config(1.0, 10, 20_000, true));
+
+        // Seeded scenarios are the same in serial.
+        let serial = esg(ScenarioMeasure::RiskNeutral).generate(&config(1.0, 10, 20_000, false));
+        assert_eq!(serial.short_rate, scenarios.short_rate);
+
+        let increments = |paths: &[Vec<f64>], log: bool| {
+            paths
+                .iter()
+                .map(|p| match log {
+                    true => (p[1] / p[0]).ln(),
+                    false => p[1] - p[0],
+                })
+                .collect::<Vec<f64>>()
+        };
+
+        let equity = increments(&scenarios.equity, true);
+        let rates = increments(&scenarios.short_rate, false);
+        let spreads = increments(&scenarios.credit_spread, false);
+
+        assert!((equity.correlation(&rates) - CORRELATION[0][1]).abs() < 0.03);
+        assert!((equity.correlation(&spreads) - CORRELATION[0][2]).abs() < 0.03);
+        assert!((rates.correlation(&spreads) - CORRELATION[1][2]).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_write_round_trip() -> Result<(), RustQuantError> {
+        let scenarios = esg(ScenarioMeasure::RiskNeutral).generate(&config(1.0, 12, 5, false));
+
+        let path = std::env::temp_dir().join("rustquant_esg_short_rate.csv");
+        let path = path.to_str().unwrap();
+
+        scenarios.write(ScenarioVariable::ShortRate, DataFormat::CSV, path)?;
+
+        let mut data = Data::new(DataFormat::CSV, path.to_string());
+        data.read()?;
+        std::fs::remove_file(path)?;
+
+        assert_eq!(data.data.shape(), (13, 7));
+
+        for (i, expected) in scenarios.short_rate.iter().enumerate() {
+            let column = data.data.column(&format!("path_{}", i))?.f64()?;
+
+            for (x, y) in column.into_no_null_iter().zip(expected) {
+                assert!((x - y).abs() < 1e-12);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@
 
 use crate::{
     models::fractional_brownian_motion::FractionalBrownianMotion,
-    stochastics::process::{StochasticProcess, Trajectories},
+    stochastics::process::StochasticProcess,
 };
 use nalgebra::{DMatrix, DVector, Dim, Dyn, RowDVector};
 use ndarray::{concatenate, prelude::*};
@@ -19,19 +19,26 @@ use num::{complex::ComplexDistribution, Complex};
 use rand::Rng;
 use rand::{rngs::StdRng, SeedableRng};
 use rand_distr::StandardNormal;
-use rayon::prelude::*;
 
 use super::StochasticProcessConfig;
 
 /// Method used to generate the Fractional Brownian Motion.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FractionalProcessGeneratorMethod {
-    /// Chooses the Cholesky decomposition method.
+    /// Chooses the Cholesky decomposition method,
+    /// $O(n^3)$ in the number of steps.
     CHOLESKY,
-    /// Chooses the Davies-Harte method.
+    /// Chooses the Davies-Harte (circulant embedding) method,
+    /// $O(n \log n)$ in the number of steps. Falls back to the Cholesky
+    /// method if the circulant embedding is not non-negative definite.
+    #[default]
     FFT,
 }
 
+// Relative size of the negative eigenvalues of the circulant embedding
+// that are put down to rounding (and set to zero).
+const EMBEDDING_TOLERANCE: f64 = 1e-10;
+
 impl FractionalBrownianMotion {
     /// Autocovariance function (ACF).
     fn acf_vector(&self, n: usize) -> RowDVector<f64> {
@@ -71,21 +78,19 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise.
     pub fn fgn_cholesky(&self, n: usize, t_n: f64) -> Vec<f64> {
-        let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = rand::thread_rng()
-            .sample_iter::<f64, StandardNormal>(StandardNormal)
-            .take(n)
-            .collect();
-        let noise = DVector::<f64>::from_vec(noise);
-        let noise = (acf_sqrt * noise).transpose() * (1.0 * t_n / n as f64).powf(self.hurst);
-
-        noise.data.as_vec().clone()
+        self.fgn_cholesky_with_rng(n, t_n, &mut rand::thread_rng())
     }
 
     /// Seedable Fractional Gaussian noise.
     pub fn seedable_fgn_cholesky(&self, n: usize, t_n: f64, seed: u64) -> Vec<f64> {
+        self.fgn_cholesky_with_rng(n, t_n, &mut StdRng::seed_from_u64(seed))
+    }
+
+    fn fgn_cholesky_with_rng<R: Rng + ?Sized>(&self, n: usize, t_n: f64, rng: &mut R) -> Vec<f64> {
+        self.assert_hurst();
+
         let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = StdRng::seed_from_u64(seed)
+        let noise = rng
             .sample_iter::<f64, StandardNormal>(StandardNormal)
             .take(n)
             .collect();
@@ -97,9 +102,37 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise via FFT.
     pub fn fgn_fft(&self, n: usize, t_n: f64) -> Vec<f64> {
-        if !(0.0..=1.0).contains(&self.hurst) {
-            panic!("Hurst parameter must be between 0 and 1");
-        }
+        self.fgn_fft_with_rng(n, t_n, &mut rand::thread_rng())
+    }
+
+    fn fgn_fft_with_rng<R: Rng + ?Sized>(&self, n: usize, t_n: f64, rng: &mut R) -> Vec<f64> {
+        self.assert_hurst();
+
+        let Some(sqrt_eigenvalues) = self.circulant_sqrt_eigenvalues(n) else {
+            return self.fgn_cholesky_with_rng(n, t_n, rng);
+        };
+
+        let rnd = Array1::<Complex<f64>>::random_using(
+            2 * n,
+            ComplexDistribution::new(StandardNormal, StandardNormal),
+            rng,
+        );
+        let fgn = &sqrt_eigenvalues * &rnd;
+        let fft_handler = FftHandler::new(2 * n);
+        let mut fgn_fft = Array1::<Complex<f64>>::zeros(2 * n);
+        ndfft_par(&fgn, &mut fgn_fft, &fft_handler, 0);
+        let fgn = fgn_fft
+            .slice(s![1..n + 1])
+            .mapv(|x: Complex<f64>| (x.re * (n as f64).powf(-self.hurst)) * t_n.powf(self.hurst));
+        fgn.to_vec()
+    }
+
+    /// Square roots of the eigenvalues of the circulant embedding (of size
+    /// $2n$) of the covariance of $n$ steps of fractional Gaussian noise,
+    /// scaled by $1 / \sqrt{2n}$.
+    ///
+    /// `None` if the embedding is not non-negative definite, beyond rounding.
+    fn circulant_sqrt_eigenvalues(&self, n: usize) -> Option<Array1<Complex<f64>>> {
         let mut r = Array1::linspace(0.0, n as f64, n + 1);
         r.par_mapv_inplace(|x| {
             if x == 0.0 {
@@ -115,26 +148,27 @@ impl FractionalBrownianMotion {
             &[r.view(), r.slice(s![..;-1]).slice(s![1..-1]).view()],
         )
         .unwrap();
-        let mut data = Array1::<Complex<f64>>::zeros(r.len());
-        for (i, v) in r.iter().enumerate() {
-            data[i] = Complex::new(*v, 0.0);
-        }
+        let data = r.mapv(|v| Complex::new(v, 0.0));
         let r_fft = FftHandler::new(r.len());
-        let mut sqrt_eigenvalues = Array1::<Complex<f64>>::zeros(r.len());
-        ndfft_par(&data, &mut sqrt_eigenvalues, &r_fft, 0);
-        sqrt_eigenvalues.par_mapv_inplace(|x| Complex::new((x.re / (2.0 * n as f64)).sqrt(), x.im));
-        let rnd = Array1::<Complex<f64>>::random(
-            2 * n,
-            ComplexDistribution::new(StandardNormal, StandardNormal),
+        let mut eigenvalues = Array1::<Complex<f64>>::zeros(r.len());
+        ndfft_par(&data, &mut eigenvalues, &r_fft, 0);
+
+        let largest = eigenvalues.iter().fold(0.0_f64, |m, x| m.max(x.re));
+        if eigenvalues
+            .iter()
+            .any(|x| x.re < -EMBEDDING_TOLERANCE * largest)
+        {
+            return None;
+        }
+
+        Some(eigenvalues.mapv(|x| Complex::new((x.re.max(0.0) / (2.0 * n as f64)).sqrt(), 0.0)))
+    }
+
+    fn assert_hurst(&self) {
+        assert!(
+            0.0 < self.hurst && self.hurst < 1.0,
+            "Hurst parameter must be in (0, 1)."
         );
-        let fgn = &sqrt_eigenvalues * &rnd;
-        let fft_handler = FftHandler::new(2 * n);
-        let mut fgn_fft = Array1::<Complex<f64>>::zeros(2 * n);
-        ndfft_par(&fgn, &mut fgn_fft, &fft_handler, 0);
-        let fgn = fgn_fft
-            .slice(s![1..n + 1])
-            .mapv(|x: Complex<f64>| (x.re * (n as f64).powf(-self.hurst)) * t_n.powf(self.hurst));
-        fgn.to_vec()
     }
 }
 
@@ -155,74 +189,44 @@ impl StochasticProcess for FractionalBrownianMotion {
         vec![self.hurst]
     }
 
-    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
-        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
-
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = match self.method {
-                FractionalProcessGeneratorMethod::FFT => self.fgn_fft(n_steps, t_n),
-                FractionalProcessGeneratorMethod::CHOLESKY => self.fgn_cholesky(n_steps, t_n),
-            };
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        self.simulate_path(config, index)
+            .last()
+            .copied()
+            .unwrap_or(config.x_0)
     }
 
-    fn seedable_euler_maruyama(
+    fn simulate_path_into(
         &self,
-        x_0: f64,
-        t_0: f64,
-        t_n: f64,
-        n_steps: usize,
-        m_paths: usize,
-        parallel: bool,
-        seed: u64,
-    ) -> Trajectories {
+        config: &StochasticProcessConfig,
+        index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let (x_0, t_0, t_n, n_steps, _, _) = config.unpack();
+
         assert!(t_0 < t_n);
 
         let dt: f64 = (t_n - t_0) / (n_steps as f64);
 
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let times = config.times();
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = self.seedable_fgn_cholesky(n_steps, t_n, seed);
+        let mut rng = config.path_rng(index);
 
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
+        let fgn = match self.method {
+            FractionalProcessGeneratorMethod::FFT => self.fgn_fft_with_rng(n_steps, t_n, &mut rng),
+            FractionalProcessGeneratorMethod::CHOLESKY => {
+                self.fgn_cholesky_with_rng(n_steps, t_n, &mut rng)
             }
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+        let mut x = x_0;
+
+        path.push(x);
 
-        Trajectories { times, paths }
+        for t in 0..n_steps {
+            x = x + self.drift(x, times[t]) * dt + self.diffusion(x, times[t]) * fgn[t];
+            path.push(x);
+        }
     }
 }
 
@@ -309,4 +313,54 @@ mod test_fractional_brownian_motion {
         // V[X_T] = T
         assert_approx_equal!(X_T.clone().variance(), 0.5, 0.5);
     }
+
+    // Autocovariance of fractional Gaussian noise with unit variance.
+    fn fgn_autocovariance(hurst: f64, lag: f64) -> f64 {
+        let h2 = 2.0 * hurst;
+
+        0.5 * ((lag + 1.0).powf(h2) - 2.0 * lag.powf(h2) + (lag - 1.0).abs().powf(h2))
+    }
+
+    #[test]
+    fn test_fft_autocovariance() {
+        let (n, m_paths, max_lag) = (1024, 400, 5);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for hurst in [0.3, 0.7] {
+            let fbm = FractionalBrownianMotion::new(hurst, FractionalProcessGeneratorMethod::FFT);
+            assert!(fbm.circulant_sqrt_eigenvalues(n).is_some());
+
+            // Unit steps, so the noise has unit variance.
+            let mut autocovariance = vec![0.0; max_lag + 1];
+            for _ in 0..m_paths {
+                let fgn = fbm.fgn_fft_with_rng(n, n as f64, &mut rng);
+
+                for (lag, sum) in autocovariance.iter_mut().enumerate() {
+                    *sum += (0..n - lag).map(|i| fgn[i] * fgn[i + lag]).sum::<f64>()
+                        / ((n - lag) * m_paths) as f64;
+                }
+            }
+
+            for (lag, &sample) in autocovariance.iter().enumerate() {
+                assert_approx_equal!(sample, fgn_autocovariance(hurst, lag as f64), 0.02);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_long_path() {
+        let fbm = FractionalBrownianMotion::new(0.3, FractionalProcessGeneratorMethod::default());
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 1 << 16, 1, false).with_seed(3);
+
+        let path = fbm.simulate_path(&config, 0);
+
+        assert_eq!(path.len(), (1 << 16) + 1);
+        assert!(path.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Hurst parameter must be in (0, 1).")]
+    fn test_hurst_validation() {
+        let _ = FractionalBrownianMotion::new(1.0, FractionalProcessGeneratorMethod::FFT);
+    }
 }
@@ -40,8 +40,36 @@ impl StochasticProcess for FractionalCoxIngersollRoss {
         ]
     }
 
+    fn simulate_terminal(&self, config: &StochasticProcessConfig) -> Vec<f64> {
+        self.euler_maruyama(config).terminal_values()
+    }
+
+    /// A single-path run of [`StochasticProcess::euler_maruyama`] (which
+    /// does not use the seed, so neither does this).
+    fn simulate_path_into(
+        &self,
+        config: &StochasticProcessConfig,
+        _index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let single = StochasticProcessConfig {
+            m_paths: 1,
+            parallel: false,
+            ..*config
+        };
+
+        path.append(&mut self.euler_maruyama(&single).paths[0]);
+    }
+
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        self.simulate_path(config, index)
+            .last()
+            .copied()
+            .unwrap_or(config.x_0)
+    }
+
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
-        let (t_0, x_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
 
         let fgn = match self.method {
             FractionalProcessGeneratorMethod::CHOLESKY => {
@@ -66,11 +94,20 @@ impl StochasticProcess for FractionalCoxIngersollRoss {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
+        // The noise is applied through the process's `CirScheme`, so the
+        // paths stay non-negative.
         let path_generator = |path: &mut Vec<f64>| {
+            let mut x = x_0;
+
             for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
+                let parameters = (
+                    self.mu.0(times[t]),
+                    self.sigma.0(times[t]),
+                    self.theta.0(times[t]),
+                );
+
+                x = self.scheme.step(x, parameters, dt, fgn[t]);
+                path[t + 1] = x.max(0.0);
             }
         };
 
@@ -111,4 +148,35 @@ mod test_fractional_cir {
 
         std::result::Result::Ok(())
     }
+
+    #[test]
+    fn test_fractional_cir_stays_non_negative() {
+        use crate::models::cox_ingersoll_ross::CirScheme;
+
+        // 2 * theta * mu = 0.005 < sigma^2 = 0.25.
+        for scheme in [
+            CirScheme::FullTruncation,
+            CirScheme::PartialTruncation,
+            CirScheme::Reflection,
+        ] {
+            let fcir = FractionalCoxIngersollRoss::new(
+                0.05,
+                0.5,
+                0.05,
+                0.7,
+                FractionalProcessGeneratorMethod::FFT,
+            )
+            .with_scheme(scheme);
+            assert!(!fcir.feller_condition(0.0));
+
+            let config = StochasticProcessConfig::new(0.01, 0.0, 5.0, 64, 20, false);
+            let output = fcir.euler_maruyama(&config);
+
+            assert_eq!(output.paths[0][0], 0.01);
+            assert!(
+                output.paths.iter().flatten().all(|&x| x >= 0.0),
+                "{scheme:?}"
+            );
+        }
+    }
 }
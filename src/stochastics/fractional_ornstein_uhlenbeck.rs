@@ -42,6 +42,34 @@ impl StochasticProcess for FractionalOrnsteinUhlenbeck {
         ]
     }
 
+    fn simulate_terminal(&self, config: &StochasticProcessConfig) -> Vec<f64> {
+        self.euler_maruyama(config).terminal_values()
+    }
+
+    /// A single-path run of [`StochasticProcess::euler_maruyama`] (which
+    /// does not use the seed, so neither does this).
+    fn simulate_path_into(
+        &self,
+        config: &StochasticProcessConfig,
+        _index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let single = StochasticProcessConfig {
+            m_paths: 1,
+            parallel: false,
+            ..*config
+        };
+
+        path.append(&mut self.euler_maruyama(&single).paths[0]);
+    }
+
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        self.simulate_path(config, index)
+            .last()
+            .copied()
+            .unwrap_or(config.x_0)
+    }
+
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
         let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
 
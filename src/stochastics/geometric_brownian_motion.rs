@@ -9,8 +9,12 @@
 
 use crate::{
     models::geometric_brownian_motion::GeometricBrownianMotion,
-    stochastics::process::StochasticProcess,
+    stochastics::process::{terminal_value_with, StochasticProcess, StochasticProcessConfig},
+    stochastics::TransitionDensity,
 };
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+use std::f64::consts::PI;
 
 impl StochasticProcess for GeometricBrownianMotion {
     fn drift(&self, x: f64, t: f64) -> f64 {
@@ -31,6 +35,44 @@ impl StochasticProcess for GeometricBrownianMotion {
     fn parameters(&self) -> Vec<f64> {
         vec![self.mu.0(0.0), self.sigma.0(0.0)]
     }
+
+    /// Terminal value via the exact lognormal step, so that a single
+    /// step suffices for constant parameters (time-dependent parameters
+    /// are frozen at the start of each step).
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        terminal_value_with(config, index, |x, t, dt, z| {
+            let (mu, sigma) = (self.mu.0(t), self.sigma.0(t));
+
+            x * ((mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z).exp()
+        })
+    }
+}
+
+/// Lognormal transition density, with the parameters frozen at the start
+/// of the step.
+impl TransitionDensity for GeometricBrownianMotion {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn sample_transition(&self, x: &[f64], t: f64, dt: f64, rng: &mut dyn RngCore) -> Vec<f64> {
+        let (mu, sigma) = (self.mu.0(t), self.sigma.0(t));
+        let z: f64 = rng.sample(StandardNormal);
+
+        vec![x[0] * ((mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z).exp()]
+    }
+
+    fn transition_density(&self, x: &[f64], y: &[f64], t: f64, dt: f64) -> f64 {
+        if y[0] <= 0.0 {
+            return 0.0;
+        }
+
+        let (mu, sigma) = (self.mu.0(t), self.sigma.0(t));
+        let std_dev = sigma * dt.sqrt();
+        let z = ((y[0] / x[0]).ln() - (mu - 0.5 * sigma * sigma) * dt) / std_dev;
+
+        (-0.5 * z * z).exp() / ((2.0 * PI).sqrt() * std_dev * y[0])
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -73,4 +115,56 @@ mod tests_gbm {
         // let file2 = "./images/GBM2.png";
         // plot_vector((&output.trajectories[1]).clone(), file2)
     }
+
+    #[test]
+    fn test_time_dependent_volatility() {
+        // Piecewise constant volatility: 10% then 30%.
+        let gbm = GeometricBrownianMotion::new_with_functions(
+            Box::new(|_| 0.05),
+            Box::new(|t| if t < 0.5 { 0.1 } else { 0.3 }),
+        );
+
+        assert_eq!(gbm.diffusion(1.0, 0.25), 0.1);
+        assert_eq!(gbm.diffusion(1.0, 0.75), 0.3);
+
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 200, 20000, true).with_seed(11);
+        let output = gbm.euler_maruyama(&config);
+
+        let log_X_T: Vec<f64> = output
+            .paths
+            .iter()
+            .filter_map(|v| v.last().map(|x| x.ln()))
+            .collect();
+
+        // V[ln X_T] = int_0^T sigma(t)^2 dt = 0.5 * 0.1^2 + 0.5 * 0.3^2.
+        assert_approx_equal!(log_X_T.variance(), 0.5 * 0.01 + 0.5 * 0.09, 0.003);
+    }
+
+    #[test]
+    fn test_forward_curve_drift() {
+        // Sloped discount curve: r(t) = 0.02 + 0.02 t, and dividend yield q = 1%.
+        let discount_factor = |t: f64| (-(0.02 * t + 0.01 * t * t)).exp();
+        let dividend_factor = |t: f64| (-0.01 * t).exp();
+
+        let gbm = GeometricBrownianMotion::new_from_curves(discount_factor, dividend_factor, 0.2);
+
+        assert_approx_equal!(gbm.drift(1.0, 0.0), 0.01, 1e-6);
+        assert_approx_equal!(gbm.drift(1.0, 1.5), 0.04, 1e-6);
+
+        let (s_0, t_n) = (100.0, 2.0);
+        let config = StochasticProcessConfig::new(s_0, 0.0, t_n, 200, 20000, true).with_seed(3);
+
+        let discounted_X_T: Vec<f64> = gbm
+            .simulate_terminal(&config)
+            .iter()
+            .map(|x| discount_factor(t_n) * x)
+            .collect();
+
+        // E[P(0, T) X_T] = X_0 Q(0, T), i.e. today's forward, discounted.
+        let expected = s_0 * dividend_factor(t_n);
+        assert_approx_equal!(discounted_X_T.mean(), expected, 0.5);
+
+        // A constant drift frozen at r(0) - q misses the forward by about 4%.
+        assert!((s_0 * (0.01 * t_n).exp() * discount_factor(t_n) - expected).abs() > 3.0);
+    }
 }
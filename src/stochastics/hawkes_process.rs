@@ -0,0 +1,231 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Hawkes self-exciting point process, with an exponential kernel.
+//!
+//! The intensity jumps by $\alpha$ at each event and decays back to the
+//! baseline $\mu$ at rate $\beta$:
+//!
+//! $$
+//! \lambda(t) = \mu + \sum_{t_i < t} \alpha e^{-\beta (t - t_i)}
+//! $$
+//!
+//! Each event triggers on average $\alpha / \beta$ further events (the
+//! branching ratio), so the process is stationary only if this is below one,
+//! with mean intensity $\mu / (1 - \alpha / \beta)$.
+//!
+//! Paths are simulated exactly by Ogata's thinning algorithm, starting with
+//! no event history at $t_0$.
+
+use crate::error::RustQuantError;
+use crate::stochastics::{StochasticProcessConfig, Trajectories};
+use rand::{Rng, RngCore};
+use rand_distr::Exp1;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Hawkes process with an exponential excitation kernel.
+pub struct HawkesProcess {
+    /// Baseline intensity ($\mu$).
+    pub mu: f64,
+
+    /// Jump in the intensity at each event ($\alpha$).
+    pub alpha: f64,
+
+    /// Decay rate of the excitation ($\beta$).
+    pub beta: f64,
+}
+
+/// Simulated Hawkes process paths.
+pub struct HawkesTrajectories {
+    /// Event times of each path, in increasing order.
+    pub event_times: Vec<Vec<f64>>,
+
+    /// Intensity of each path, sampled on the time grid.
+    pub intensity: Trajectories,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HawkesProcess {
+    /// Create a new Hawkes process.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if `mu` or `beta` is not positive,
+    ///   `alpha` is negative, or the branching ratio `alpha / beta` is not
+    ///   below one (the process would explode).
+    pub fn new(mu: f64, alpha: f64, beta: f64) -> Result<Self, RustQuantError> {
+        if !(mu > 0.0 && beta > 0.0 && alpha >= 0.0) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Hawkes parameters must satisfy mu > 0, alpha >= 0, beta > 0 \
+                 (got mu = {mu}, alpha = {alpha}, beta = {beta})."
+            )));
+        }
+
+        if alpha >= beta {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Hawkes branching ratio alpha / beta = {} must be below 1.",
+                alpha / beta
+            )));
+        }
+
+        Ok(Self { mu, alpha, beta })
+    }
+
+    /// Branching ratio $\alpha / \beta$: the mean number of events
+    /// directly triggered by each event.
+    pub fn branching_ratio(&self) -> f64 {
+        self.alpha / self.beta
+    }
+
+    /// Mean intensity of the stationary process, $\mu / (1 - \alpha / \beta)$.
+    pub fn stationary_intensity(&self) -> f64 {
+        self.mu / (1.0 - self.branching_ratio())
+    }
+
+    /// Simulate event times and intensity paths on `[t_0, t_n]`.
+    ///
+    /// `config.x_0` is ignored: the intensity starts at the baseline.
+    pub fn simulate(&self, config: &StochasticProcessConfig) -> HawkesTrajectories {
+        let (_, t_0, t_n, n_steps, _, _) = config.unpack();
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |i: usize| self.thinning(&times, &mut config.path_rng(i));
+
+        let simulated: Vec<(Vec<f64>, Vec<f64>)> = config.map_paths(path_generator);
+
+        let (event_times, paths) = simulated.into_iter().unzip();
+
+        HawkesTrajectories {
+            event_times,
+            intensity: Trajectories { times, paths },
+        }
+    }
+
+    /// Ogata's thinning: candidates arrive at the current intensity (an upper
+    /// bound until the next event, as the intensity decays) and are accepted
+    /// with probability $\lambda(t) / \bar{\lambda}$.
+    ///
+    /// Returns the event times, and the intensity at each of `times`.
+    fn thinning(&self, times: &[f64], rng: &mut dyn RngCore) -> (Vec<f64>, Vec<f64>) {
+        let t_n = times[times.len() - 1];
+
+        let mut events = Vec::new();
+        let mut intensity = Vec::with_capacity(times.len());
+
+        // Current time, and excitation above the baseline just after it.
+        let (mut t, mut excitation) = (times[0], 0.0);
+
+        loop {
+            let bound = self.mu + excitation;
+            let candidate = t + rng.sample::<f64, _>(Exp1) / bound;
+
+            // Record the grid points passed on the way to the candidate.
+            for &s in &times[intensity.len()..] {
+                if s >= candidate {
+                    break;
+                }
+
+                intensity.push(self.mu + excitation * (-self.beta * (s - t)).exp());
+            }
+
+            if candidate > t_n {
+                break;
+            }
+
+            excitation *= (-self.beta * (candidate - t)).exp();
+            t = candidate;
+
+            if rng.gen::<f64>() * bound <= self.mu + excitation {
+                events.push(t);
+                excitation += self.alpha;
+            }
+        }
+
+        (events, intensity)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hawkes_process {
+    use super::*;
+    use crate::math::Statistic;
+
+    #[test]
+    fn test_explosive_parameters() {
+        assert!(HawkesProcess::new(1.0, 0.5, 1.0).is_ok());
+
+        for (mu, alpha, beta) in [(1.0, 1.0, 1.0), (1.0, 2.0, 1.0), (0.0, 0.5, 1.0)] {
+            assert!(matches!(
+                HawkesProcess::new(mu, alpha, beta),
+                Err(RustQuantError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_hawkes_mean_event_count() {
+        let (mu, alpha, beta, t_n) = (1.0, 0.5, 1.0, 100.0);
+        let hawkes = HawkesProcess::new(mu, alpha, beta).unwrap();
+        let config = StochasticProcessConfig::new(0.0, 0.0, t_n, 100, 2_000, true).with_seed(1);
+
+        let output = hawkes.simulate(&config);
+
+        let counts: Vec<f64> = output
+            .event_times
+            .iter()
+            .map(|events| events.len() as f64)
+            .collect();
+
+        // mu T / (1 - alpha / beta), up to the start-up from no history.
+        assert_approx_equal!(
+            counts.mean() / (hawkes.stationary_intensity() * t_n),
+            1.0,
+            0.02
+        );
+    }
+
+    #[test]
+    fn test_hawkes_intensity_matches_events() {
+        let hawkes = HawkesProcess::new(2.0, 1.5, 2.0).unwrap();
+        let config = StochasticProcessConfig::new(0.0, 1.0, 11.0, 50, 20, false).with_seed(2);
+
+        let output = hawkes.simulate(&config);
+        let times = &output.intensity.times;
+
+        for (events, path) in output.event_times.iter().zip(&output.intensity.paths) {
+            assert!(events.windows(2).all(|w| w[0] < w[1]));
+            assert!(events.iter().all(|&t| t > 1.0 && t <= 11.0));
+            assert_eq!(path.len(), times.len());
+            assert_eq!(path[0], 2.0);
+
+            for (&s, &value) in times.iter().zip(path) {
+                let expected = 2.0
+                    + events
+                        .iter()
+                        .filter(|&&t| t < s)
+                        .map(|&t| 1.5 * (-2.0 * (s - t)).exp())
+                        .sum::<f64>();
+
+                assert_approx_equal!(value, expected, 1e-10);
+            }
+        }
+    }
+}
@@ -7,28 +7,154 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::{models::Heston, stochastics::StochasticProcess};
+//! The Heston (1993) stochastic volatility process, with factors $(S, v)$:
+//!
+//! - $dS(t) = \mu S(t) dt + \sqrt{v(t)} S(t) dW_1(t)$
+//! - $dv(t) = \kappa \left[ \theta - v(t) \right] dt + \xi \sqrt{v(t)} dW_2(t)$
+//!
+//! with $d\langle W_1, W_2 \rangle_t = \rho dt$.
+//!
+//! The variance is truncated at zero in the drift and diffusion,
+//! so the Euler-Maruyama scheme is the full-truncation scheme.
 
-impl StochasticProcess for Heston {
-    fn drift(&self, _x: f64, _t: f64) -> f64 {
-        todo!()
-    }
+use crate::{models::Heston, stochastics::MultifactorProcess};
+use nalgebra::DMatrix;
 
-    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
-        todo!()
+impl MultifactorProcess for Heston {
+    fn dimension(&self) -> usize {
+        2
     }
 
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
-        todo!()
+    fn drift(&self, x: &[f64], t: f64) -> Vec<f64> {
+        let v = x[1].max(0.0);
+
+        vec![
+            self.mu.0(t) * x[0],
+            self.mean_reversion_rate.0(t) * (self.long_run_variance.0(t) - v),
+        ]
     }
 
-    fn parameters(&self) -> Vec<f64> {
+    fn diffusion(&self, x: &[f64], t: f64) -> Vec<f64> {
+        let v = x[1].max(0.0);
+
         vec![
-            self.initial_variance.0(0.0),
-            self.long_run_variance.0(0.0),
-            self.mean_reversion_rate.0(0.0),
-            self.correlation.0(0.0),
-            self.volatility_of_volatility.0(0.0),
+            v.sqrt() * x[0],
+            self.volatility_of_volatility.0(t) * v.sqrt(),
         ]
     }
+
+    fn diffusion_jacobian(&self, x: &[f64], t: f64) -> DMatrix<f64> {
+        // The truncated diffusions are flat in the variance below zero.
+        if x[1] <= 0.0 {
+            return DMatrix::zeros(2, 2);
+        }
+
+        let sqrt_v = x[1].sqrt();
+
+        DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                sqrt_v,
+                0.5 * x[0] / sqrt_v,
+                0.0,
+                0.5 * self.volatility_of_volatility.0(t) / sqrt_v,
+            ],
+        )
+    }
+
+    fn correlation(&self) -> DMatrix<f64> {
+        let rho = self.correlation.0(0.0);
+
+        DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0])
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use crate::math::Statistic;
+    use crate::stochastics::{MultifactorScheme, StochasticProcessConfig};
+
+    #[test]
+    fn test_heston_mean_reversion() {
+        let (v_0, theta, kappa): (f64, f64, f64) = (0.09, 0.04, 2.0);
+
+        let heston = Heston::new(v_0, theta, kappa, -0.7, 0.3).with_drift(0.05);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 2.0, 200, 10000, true).with_seed(42);
+        let output = heston.simulate(MultifactorScheme::EulerMaruyama, &[100.0, v_0], &config);
+
+        assert_eq!(output.paths.len(), 2);
+
+        let mean_at = |factor: usize, step: usize| -> f64 {
+            output.paths[factor]
+                .iter()
+                .map(|path| path[step])
+                .collect::<Vec<f64>>()
+                .mean()
+        };
+
+        // E[v_t] = theta + (v_0 - theta) * exp(-kappa * t).
+        for (step, t) in [(50, 0.5), (100, 1.0), (200, 2.0)] {
+            let expected = theta + (v_0 - theta) * (-kappa * t).exp();
+            assert_approx_equal!(mean_at(1, step), expected, 1e-3);
+        }
+
+        // The variance reverts monotonically towards theta.
+        assert!(mean_at(1, 50) > mean_at(1, 100) && mean_at(1, 100) > mean_at(1, 200));
+
+        // E[S_T] = S_0 * exp(mu * T).
+        assert_approx_equal!(mean_at(0, 200), 100.0 * (0.05 * 2.0_f64).exp(), 1.0);
+    }
+
+    #[test]
+    fn test_heston_milstein_closer_to_fourier() {
+        use crate::instruments::options::heston as heston_fourier;
+        use time::macros::date;
+
+        // The Feller condition is violated, so the variance often hits zero.
+        let (s_0, k, r, n_steps) = (100.0, 100.0, 0.03, 4);
+        let (v_0, theta, kappa, rho, xi) = (0.04, 0.04, 1.5, -0.7, 0.8);
+
+        let (reference, _) = heston_fourier(
+            s_0,
+            v_0,
+            k,
+            r,
+            0.0,
+            rho,
+            xi,
+            kappa,
+            theta,
+            Some(date!(2024 - 01 - 01)),
+            date!(2025 - 01 - 01),
+        );
+
+        let heston = Heston::new(v_0, theta, kappa, rho, xi).with_drift(r);
+
+        let price = |scheme| {
+            let config =
+                StochasticProcessConfig::new(s_0, 0.0, 1.0, n_steps, 50_000, true).with_seed(17);
+            let output = heston.simulate(scheme, &[s_0, v_0], &config);
+
+            let payoffs: Vec<f64> = output.paths[0]
+                .iter()
+                .map(|path| (path[n_steps] - k).max(0.0))
+                .collect();
+
+            (-r).exp() * payoffs.mean()
+        };
+
+        let euler = price(MultifactorScheme::EulerMaruyama);
+        let milstein = price(MultifactorScheme::Milstein {
+            levy_area_substeps: n_steps,
+        });
+
+        assert!((milstein - reference).abs() < (euler - reference).abs());
+    }
 }
@@ -70,4 +70,71 @@ mod tests_ho_lee {
         // V[X_T] = sigma^2 * T
         assert_approx_equal!(V_XT, 1.6 * 1.6 * 1.0, 0.5);
     }
+
+    #[test]
+    fn test_piecewise_theta_reproduces_discount_curve() {
+        use crate::models::ModelParameter;
+
+        let (r_0, sigma) = (0.03, 0.01);
+        let maturities = [1.0, 2.0, 3.0];
+        let spot_rates = [0.03, 0.035, 0.04];
+
+        // With r(t) = r_0 + int_0^t theta + sigma W(t), the bond price is
+        // P(0, T) = exp(-r_0 T - int_0^T int_0^s theta(u) du ds + sigma^2 T^3 / 6),
+        // which is linear in the pieces of theta: solve for them in turn.
+        let weight = |k: usize, t: f64| {
+            let start = if k == 0 { 0.0 } else { maturities[k - 1] };
+            let end = maturities[k];
+
+            if t <= start {
+                0.0
+            } else if t <= end {
+                0.5 * (t - start).powi(2)
+            } else {
+                0.5 * (end - start).powi(2) + (end - start) * (t - end)
+            }
+        };
+
+        let mut theta = Vec::with_capacity(3);
+
+        for (j, (&t, &rate)) in maturities.iter().zip(&spot_rates).enumerate() {
+            let target = rate * t - r_0 * t + sigma * sigma * t.powi(3) / 6.0;
+            let known: f64 = (0..j).map(|k| theta[k] * weight(k, t)).sum();
+
+            theta.push((target - known) / weight(j, t));
+        }
+
+        let hl = HoLee::new(
+            sigma,
+            ModelParameter::piecewise_constant(
+                vec![maturities[0], maturities[1]],
+                vec![theta[0], theta[1], theta[2]],
+            ),
+        );
+
+        // A binary time step, so that the breakpoints lie exactly on the grid.
+        let n_steps = 384;
+        let dt = 3.0 / n_steps as f64;
+        let config = StochasticProcessConfig::new(r_0, 0.0, 3.0, n_steps, 4000, true).with_seed(5);
+        let output = hl.euler_maruyama(&config);
+
+        for (&t, &rate) in maturities.iter().zip(&spot_rates) {
+            let steps = (t / dt).round() as usize;
+
+            let bond_prices: Vec<f64> = output
+                .paths
+                .iter()
+                .map(|path| {
+                    let integral: f64 = path[..=steps]
+                        .windows(2)
+                        .map(|w| 0.5 * (w[0] + w[1]) * dt)
+                        .sum();
+
+                    (-integral).exp()
+                })
+                .collect();
+
+            assert_approx_equal!(bond_prices.mean(), (-rate * t).exp(), 2e-3);
+        }
+    }
 }
@@ -0,0 +1,252 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! The Kou (2002) double-exponential jump diffusion: a Geometric Brownian
+//! Motion, with compensated jumps arriving as a Poisson process, whose log
+//! sizes are asymmetric double-exponential.
+//!
+//! Each step adds the Euler-Maruyama increment of the diffusion, then the
+//! $N \sim \text{Poisson}(\lambda \Delta t)$ jumps arriving in the step,
+//! each an upward $\text{Exp}(\eta_1)$ log jump with probability $p$ and a
+//! downward $\text{Exp}(\eta_2)$ one otherwise.
+//!
+//! The diffusion is lognormal over any horizon, so the terminal value can
+//! also be sampled exactly, in a single step
+//! (see [`KouJumpDiffusion::sample_terminal`]).
+
+use crate::models::KouJumpDiffusion;
+use crate::stochastics::process::{terminal_value_by, StochasticProcess, StochasticProcessConfig};
+use rand::{Rng, RngCore};
+use rand_distr::{Exp, Poisson, StandardNormal};
+
+impl StochasticProcess for KouJumpDiffusion {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        (self.mu.0(t) - self.lambda.0(t) * self.mean_jump()) * x
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        assert!(self.sigma.0(t) >= 0.0);
+        self.sigma.0(t) * x
+    }
+
+    /// Samples a log jump size.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(self.sample_log_jump(&mut rand::thread_rng()))
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.mu.0(0.0),
+            self.sigma.0(0.0),
+            self.lambda.0(0.0),
+            self.p,
+            self.eta1,
+            self.eta2,
+        ]
+    }
+
+    fn simulate_increment(&self, x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+        let increment = self.drift(x, t) * dt + self.diffusion(x, t) * dW;
+
+        // No draws without jumps, so with zero intensity this is exactly GBM.
+        let arrivals = match Poisson::new(self.lambda.0(t) * dt) {
+            Ok(arrivals) => rng.sample(arrivals) as usize,
+            Err(_) => 0,
+        };
+
+        if arrivals > 0 {
+            let log_jump: f64 = (0..arrivals).map(|_| self.sample_log_jump(rng)).sum();
+
+            increment + x * log_jump.exp_m1()
+        } else {
+            increment
+        }
+    }
+
+    /// Terminal value via the exact step, so that a single step suffices
+    /// for constant parameters (time-dependent parameters are frozen at the
+    /// start of each step).
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        terminal_value_by(config, index, |x, t, dt, rng| {
+            self.exact_step(x, t, dt, rng)
+        })
+    }
+}
+
+impl KouJumpDiffusion {
+    /// Exact sample of $S(t)$ given $S(0) = x_0$, without time stepping,
+    /// with the parameters frozen at time zero.
+    ///
+    /// Draws the number of jumps $N \sim \text{Poisson}(\lambda t)$ and their
+    /// log sizes $Y_i$, then the normal diffusion part of the log return:
+    /// $\ln \frac{S(t)}{x_0} = (\mu - \lambda \zeta - \frac{\sigma^2}{2}) t + \sigma W(t) + \sum_{i=1}^N Y_i$.
+    pub fn sample_terminal<R: Rng + ?Sized>(&self, x0: f64, t: f64, rng: &mut R) -> f64 {
+        self.exact_step(x0, 0.0, t, rng)
+    }
+
+    fn exact_step<R: Rng + ?Sized>(&self, x: f64, t: f64, dt: f64, rng: &mut R) -> f64 {
+        let (mu, sigma, lambda) = (self.mu.0(t), self.sigma.0(t), self.lambda.0(t));
+
+        let arrivals = match Poisson::new(lambda * dt) {
+            Ok(arrivals) => rng.sample(arrivals) as usize,
+            Err(_) => 0,
+        };
+        let log_jump: f64 = (0..arrivals).map(|_| self.sample_log_jump(rng)).sum();
+
+        let drift = (mu - lambda * self.mean_jump() - 0.5 * sigma * sigma) * dt;
+        let diffusion = sigma * dt.sqrt() * rng.sample::<f64, _>(StandardNormal);
+
+        x * (drift + diffusion + log_jump).exp()
+    }
+
+    fn sample_log_jump<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if rng.gen_bool(self.p) {
+            rng.sample(Exp::new(self.eta1).unwrap())
+        } else {
+            -rng.sample(Exp::new(self.eta2).unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_kou_jump_diffusion {
+    use super::*;
+    use crate::instruments::options::{
+        ExerciseFlag, OptionContractBuilder, TypeFlag, VanillaOption,
+    };
+    use crate::math::Statistic;
+    use crate::models::GeometricBrownianMotion;
+    use crate::pricer::MonteCarloPricer;
+    use crate::stochastics::StochasticProcessConfig;
+    use rand::{rngs::StdRng, SeedableRng};
+    use time::macros::date;
+
+    #[test]
+    fn test_kou_jump_diffusion_moments() {
+        let (mu, sigma, lambda, p, eta1, eta2, t) = (0.05, 0.2, 3.0, 0.3, 8.0, 4.0, 1.0);
+        let kou = KouJumpDiffusion::new(mu, sigma, lambda, p, eta1, eta2);
+        let config = StochasticProcessConfig::new(1.0, 0.0, t, 100, 20_000, true).with_seed(5);
+
+        let X_T = kou.euler_maruyama(&config).terminal_values();
+
+        // The jumps are compensated, so E[X_T] = x_0 e^{mu t} as for GBM.
+        let mean = (mu * t).exp();
+        assert!((X_T.mean() - mean).abs() < 0.01, "E[X_T] = {}", X_T.mean());
+
+        // Downward jumps are larger and more frequent: the log returns are skewed left.
+        let log_returns: Vec<f64> = X_T.iter().map(|x| x.ln()).collect();
+        let m = log_returns.mean();
+        let third_moment =
+            log_returns.iter().map(|x| (x - m).powi(3)).sum::<f64>() / log_returns.len() as f64;
+        assert!(third_moment < 0.0);
+    }
+
+    #[test]
+    fn test_kou_european_calls() {
+        // Parameters from Kou (2002), with semi-closed-form prices
+        // from Fourier inversion of the characteristic function.
+        let (s_0, r, sigma, t) = (100.0, 0.05, 0.16, 0.5);
+        let kou = KouJumpDiffusion::new(r, sigma, 1.0, 0.4, 10.0, 5.0);
+
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        let config = StochasticProcessConfig::new(s_0, 0.0, t, 25, 40_000, true).with_seed(2);
+
+        for (strike, reference) in [
+            (90.0, 14.811_890_545),
+            (100.0, 7.959_429_203),
+            (110.0, 3.599_649_815),
+        ] {
+            let price =
+                VanillaOption::new(contract.clone(), strike).price_monte_carlo(&kou, &config, r);
+
+            // Standard errors of the estimates are below 0.06.
+            assert!(
+                (price - reference).abs() < 0.2,
+                "K = {strike}: MC {price}, reference {reference}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_intensity_is_gbm() {
+        let kou = KouJumpDiffusion::new(0.05, 0.2, 0.0, 0.4, 10.0, 5.0);
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 100, false).with_seed(3);
+
+        assert_eq!(
+            kou.euler_maruyama(&config).paths,
+            gbm.euler_maruyama(&config).paths
+        );
+    }
+
+    #[test]
+    fn test_sample_terminal_moments() {
+        let (x_0, mu, sigma, lambda, p, eta1, eta2, t) = (1.0, 0.05, 0.2, 3.0, 0.3, 8.0, 4.0, 1.0);
+        let kou = KouJumpDiffusion::new(mu, sigma, lambda, p, eta1, eta2);
+        let mut rng = StdRng::seed_from_u64(13);
+
+        let X_T: Vec<f64> = (0..200_000)
+            .map(|_| kou.sample_terminal(x_0, t, &mut rng))
+            .collect();
+        let log_returns: Vec<f64> = X_T.iter().map(|x| (x / x_0).ln()).collect();
+
+        // The jumps are compensated, so E[X_T] = x_0 e^{mu t} as for GBM.
+        assert_approx_equal!(X_T.mean(), x_0 * (mu * t).exp(), 0.004);
+
+        // Log jumps have E[Y] = p / eta1 - (1 - p) / eta2,
+        // and E[Y^2] = 2 p / eta1^2 + 2 (1 - p) / eta2^2.
+        let jump_mean = p / eta1 - (1.0 - p) / eta2;
+        let jump_second_moment = 2.0 * p / (eta1 * eta1) + 2.0 * (1.0 - p) / (eta2 * eta2);
+        let log_mean =
+            (mu - lambda * kou.mean_jump() - 0.5 * sigma * sigma) * t + lambda * t * jump_mean;
+        let log_variance = sigma * sigma * t + lambda * t * jump_second_moment;
+        assert_approx_equal!(log_returns.mean(), log_mean, 0.004);
+        assert_approx_equal!(log_returns.variance(), log_variance, 0.004);
+    }
+
+    #[test]
+    fn test_sample_terminal_european_calls() {
+        // As in `test_kou_european_calls`, with a single exact step.
+        let (s_0, r, sigma, t) = (100.0, 0.05, 0.16, 0.5);
+        let kou = KouJumpDiffusion::new(r, sigma, 1.0, 0.4, 10.0, 5.0);
+
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        let config = StochasticProcessConfig::new(s_0, 0.0, t, 1, 200_000, true).with_seed(2);
+
+        for (strike, reference) in [
+            (90.0, 14.811_890_545),
+            (100.0, 7.959_429_203),
+            (110.0, 3.599_649_815),
+        ] {
+            let price =
+                VanillaOption::new(contract.clone(), strike).price_monte_carlo(&kou, &config, r);
+
+            // Standard errors of the estimates are below 0.03.
+            assert!(
+                (price - reference).abs() < 0.1,
+                "K = {strike}: MC {price}, reference {reference}"
+            );
+        }
+    }
+}
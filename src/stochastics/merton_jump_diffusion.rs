@@ -7,18 +7,26 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::math::{Distribution as LocalDistribution, Poisson};
+//! The Merton (1976) jump diffusion: a Geometric Brownian Motion, with
+//! compensated lognormal jumps arriving as a Poisson process.
+//!
+//! Each step adds the Euler-Maruyama increment of the diffusion, then the
+//! $N \sim \text{Poisson}(\lambda \Delta t)$ jumps arriving in the step,
+//! whose log sizes sum to $N(N m, N v)$.
+//!
+//! Given the number of jumps, the log return over any horizon is normal, so
+//! the terminal value can also be sampled exactly, in a single step
+//! (see [`MertonJumpDiffusion::sample_terminal`]).
+
+use crate::math::Distribution as LocalDistribution;
 use crate::models::merton_jump_diffusion::MertonJumpDiffusion;
-use crate::stochastics::process::{StochasticProcess, Trajectories};
-use rand_distr::Distribution;
-use rayon::prelude::*;
-
-use super::StochasticProcessConfig;
-// use statrs::distribution::Normal;
+use crate::stochastics::process::{terminal_value_by, StochasticProcess, StochasticProcessConfig};
+use rand::{Rng, RngCore};
+use rand_distr::{Poisson, StandardNormal};
 
 impl StochasticProcess for MertonJumpDiffusion {
     fn drift(&self, x: f64, t: f64) -> f64 {
-        self.mu.0(t) * x
+        (self.mu.0(t) - self.lambda.0(t) * self.mean_jump()) * x
     }
 
     fn diffusion(&self, x: f64, t: f64) -> f64 {
@@ -26,6 +34,7 @@ impl StochasticProcess for MertonJumpDiffusion {
         self.sigma.0(t) * x
     }
 
+    /// Samples a log jump size.
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
         self.gaussian.sample(1).unwrap().first().copied()
     }
@@ -34,85 +43,205 @@ impl StochasticProcess for MertonJumpDiffusion {
         vec![self.mu.0(0.0), self.sigma.0(0.0), self.lambda.0(0.0)]
     }
 
-    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
-        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
-
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
-
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
-
-            let jumps = Poisson::new(self.lambda.0(0.0) * dt)
-                .sample(n_steps)
-                .unwrap();
-
-            for t in 0..n_steps {
-                if jumps[t] > 0.0 {
-                    path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
-                        + self.diffusion(path[t], times[t]) * dW[t]
-                        + self.jump(path[t], times[t]).unwrap_or(0.0);
-                } else {
-                    path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
-                        + self.diffusion(path[t], times[t]) * dW[t];
-                }
-            }
+    fn simulate_increment(&self, x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+        let increment = self.drift(x, t) * dt + self.diffusion(x, t) * dW;
+
+        // No draws without jumps, so with zero intensity this is exactly GBM.
+        let arrivals = match Poisson::new(self.lambda.0(t) * dt) {
+            Ok(arrivals) => rng.sample(arrivals),
+            Err(_) => 0.0,
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+        if arrivals > 0.0 {
+            let (m, v) = (self.gaussian.mean(), self.gaussian.variance());
+            let log_jump =
+                arrivals * m + (arrivals * v).sqrt() * rng.sample::<f64, _>(StandardNormal);
+
+            increment + x * log_jump.exp_m1()
         } else {
-            paths.iter_mut().for_each(path_generator);
+            increment
         }
+    }
+
+    /// Terminal value via the exact step, so that a single step suffices
+    /// for constant parameters (time-dependent parameters are frozen at the
+    /// start of each step).
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        terminal_value_by(config, index, |x, t, dt, rng| {
+            self.exact_step(x, t, dt, rng)
+        })
+    }
+}
+
+impl MertonJumpDiffusion {
+    /// Exact sample of $S(t)$ given $S(0) = x_0$, without time stepping,
+    /// with the parameters frozen at time zero.
+    ///
+    /// Draws the number of jumps $N \sim \text{Poisson}(\lambda t)$, then the
+    /// log return, which given $N$ is normal:
+    /// $\ln \frac{S(t)}{x_0} \sim N\left((\mu - \lambda k - \frac{\sigma^2}{2}) t + N m, \ \sigma^2 t + N v\right)$.
+    pub fn sample_terminal<R: Rng + ?Sized>(&self, x0: f64, t: f64, rng: &mut R) -> f64 {
+        self.exact_step(x0, 0.0, t, rng)
+    }
+
+    fn exact_step<R: Rng + ?Sized>(&self, x: f64, t: f64, dt: f64, rng: &mut R) -> f64 {
+        let (mu, sigma, lambda) = (self.mu.0(t), self.sigma.0(t), self.lambda.0(t));
+        let (m, v) = (self.gaussian.mean(), self.gaussian.variance());
+
+        let arrivals = match Poisson::new(lambda * dt) {
+            Ok(arrivals) => rng.sample(arrivals),
+            Err(_) => 0.0,
+        };
+
+        let mean = (mu - lambda * self.mean_jump() - 0.5 * sigma * sigma) * dt + arrivals * m;
+        let std_dev = (sigma * sigma * dt + arrivals * v).sqrt();
 
-        Trajectories { times, paths }
+        x * (mean + std_dev * rng.sample::<f64, _>(StandardNormal)).exp()
     }
 }
 
 #[cfg(test)]
-mod tests_gbm_bridge {
+mod tests_merton_jump_diffusion {
     use super::*;
-    use crate::{assert_approx_equal, math::*};
+    use crate::instruments::options::{
+        BlackScholesMerton, ExerciseFlag, OptionContractBuilder, TypeFlag, VanillaOption,
+    };
+    use crate::math::*;
+    use crate::models::GeometricBrownianMotion;
+    use crate::pricer::MonteCarloPricer;
+    use rand::{rngs::StdRng, SeedableRng};
+    use time::macros::date;
 
     #[test]
-    fn test_geometric_brownian_motion_bridge() {
-        let mjd = MertonJumpDiffusion::new(0.05, 0.9, 1.0, 0.0, 0.3);
-        let config = StochasticProcessConfig::new(10.0, 0.0, 0.5, 125, 10000, false);
+    fn test_merton_jump_diffusion_moments() {
+        let (mu, sigma, lambda, m, v, t): (f64, f64, f64, f64, f64, f64) =
+            (0.05, 0.9, 1.0, 0.0, 0.3, 0.5);
+        let mjd = MertonJumpDiffusion::new(mu, sigma, lambda, m, v);
+        let config = StochasticProcessConfig::new(10.0, 0.0, t, 125, 10000, false).with_seed(1);
         let output = mjd.euler_maruyama(&config);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().copied())
-            .collect();
-
-        println!("X_T = {:?}", X_T);
+        let X_T = output.terminal_values();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
-        // E[X_T] = https://en.wikipedia.org/wiki/Geometric_Brownian_motion
-        assert_approx_equal!(E_XT, 10. * (0.05 * 0.5_f64).exp(), 0.5);
-        // V[X_T] = https://en.wikipedia.org/wiki/Geometric_Brownian_motion
-        assert_approx_equal!(
-            V_XT,
-            10. * 10. * (2. * 0.05 * 0.5_f64).exp() * ((0.9 * 0.9 * 0.5_f64).exp() - 1.),
-            5.0
+
+        // The jumps are compensated, so E[X_T] = x_0 e^{mu t} as for GBM.
+        let mean = 10. * (mu * t).exp();
+        assert!((E_XT - mean).abs() < 0.5, "E[X_T] = {E_XT}");
+
+        // E[X_T^2] = x_0^2 exp((2 (mu - lambda k) + sigma^2) t + lambda t (E[J^2] - 1)).
+        let k = mjd.mean_jump();
+        let second_moment = 100.
+            * ((2. * (mu - lambda * k) + sigma * sigma) * t
+                + lambda * t * ((2. * m + 2. * v).exp() - 1.))
+                .exp();
+        let variance = second_moment - mean * mean;
+        assert!(
+            (V_XT - variance).abs() < 0.1 * variance,
+            "V[X_T] = {V_XT}, expected {variance}"
+        );
+    }
+
+    #[test]
+    fn test_zero_intensity_is_gbm() {
+        let mjd = MertonJumpDiffusion::new(0.05, 0.2, 0.0, -0.1, 0.04);
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 100, false).with_seed(3);
+
+        assert_eq!(
+            mjd.euler_maruyama(&config).paths,
+            gbm.euler_maruyama(&config).paths
         );
     }
+
+    #[test]
+    fn test_sample_terminal_moments() {
+        let (x_0, mu, sigma, lambda, m, v, t) = (10.0, 0.05, 0.2, 1.5, -0.1, 0.04, 1.0);
+        let mjd = MertonJumpDiffusion::new(mu, sigma, lambda, m, v);
+        let mut rng = StdRng::seed_from_u64(11);
+
+        let X_T: Vec<f64> = (0..200_000)
+            .map(|_| mjd.sample_terminal(x_0, t, &mut rng))
+            .collect();
+        let log_returns: Vec<f64> = X_T.iter().map(|x| (x / x_0).ln()).collect();
+
+        // E[X_T] = x_0 e^{mu t} and E[X_T^2] as in the Euler-Maruyama test.
+        let k = mjd.mean_jump();
+        let mean = x_0 * (mu * t).exp();
+        let second_moment = x_0
+            * x_0
+            * ((2. * (mu - lambda * k) + sigma * sigma) * t
+                + lambda * t * ((2. * m + 2. * v).exp() - 1.))
+                .exp();
+        let variance = second_moment - mean * mean;
+        assert_approx_equal!(X_T.mean(), mean, 0.04);
+        assert_approx_equal!(X_T.variance(), variance, 0.02 * variance);
+
+        // The log return is a Poisson mixture of normals.
+        let log_mean = (mu - lambda * k - 0.5 * sigma * sigma) * t + lambda * t * m;
+        let log_variance = sigma * sigma * t + lambda * t * (v + m * m);
+        assert_approx_equal!(log_returns.mean(), log_mean, 0.003);
+        assert_approx_equal!(log_returns.variance(), log_variance, 0.003);
+    }
+
+    #[test]
+    fn test_sample_terminal_european_call() {
+        let (s_0, r, sigma, lambda, m, v) = (100.0, 0.05, 0.2, 1.5, -0.1, 0.04);
+        let mjd = MertonJumpDiffusion::new(r, sigma, lambda, m, v);
+
+        let (start, expiry) = (date!(2024 - 01 - 01), date!(2025 - 01 - 01));
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European { expiry })
+            .build()
+            .unwrap();
+
+        // Merton (1976): a Poisson-weighted sum of Black-Scholes prices.
+        let merton_1976 = |strike: f64| {
+            let mut bsm = BlackScholesMerton::new(
+                r,
+                s_0,
+                strike,
+                sigma,
+                r,
+                Some(start),
+                expiry,
+                TypeFlag::Call,
+            );
+            let t = bsm.year_fraction();
+            let k = mjd.mean_jump();
+            let intensity = lambda * (1.0 + k) * t;
+
+            let mut weight = (-intensity).exp();
+            let mut price = 0.0;
+            for n in 0..50 {
+                let n = n as f64;
+                let r_n = r - lambda * k + n * (m + 0.5 * v) / t;
+
+                bsm.volatility = (sigma * sigma + n * v / t).sqrt();
+                (bsm.risk_free_rate, bsm.cost_of_carry) = (r_n, r_n);
+                price += weight * bsm.price();
+                weight *= intensity / (n + 1.0);
+            }
+
+            (price, t)
+        };
+
+        for strike in [90.0, 100.0, 110.0] {
+            let (reference, t) = merton_1976(strike);
+
+            // A single exact step.
+            let config = StochasticProcessConfig::new(s_0, 0.0, t, 1, 200_000, true).with_seed(4);
+            let price =
+                VanillaOption::new(contract.clone(), strike).price_monte_carlo(&mjd, &config, r);
+
+            // Standard errors of the estimates are below 0.04.
+            assert!(
+                (price - reference).abs() < 0.15,
+                "K = {strike}: MC {price}, Merton {reference}"
+            );
+        }
+    }
 }
@@ -44,18 +44,37 @@
 /// Arithmetic Brownian Motion.
 pub mod arithmetic_brownian_motion;
 
+/// Bates model process.
+pub mod bates;
+
 /// Black-Derman-Toy short rate model.
 pub mod black_derman_toy;
 
+/// Brownian bridge path construction.
+pub mod brownian_bridge;
+pub use brownian_bridge::*;
+
 /// Standard Brownian Motion.
 pub mod brownian_motion;
 
 /// Constant Elasticity of Variance process.
 pub mod constant_elasticity_of_variance;
 
+/// Convergence diagnostics for discretisation schemes.
+pub mod convergence;
+pub use convergence::*;
+
 /// Cox-Ingersoll-Ross process.
 pub mod cox_ingersoll_ross;
 
+/// Deterministic volatility calibrated to ATM implied volatilities.
+pub mod deterministic_volatility;
+pub use deterministic_volatility::*;
+
+/// Economic scenario generator (equity, rates, and credit).
+pub mod economic_scenario_generator;
+pub use economic_scenario_generator::*;
+
 /// Extended Vasicek process.
 pub mod extended_vasicek;
 
@@ -74,6 +93,10 @@ pub mod geometric_brownian_bridge;
 /// Geometric Brownian Motion.
 pub mod geometric_brownian_motion;
 
+/// Hawkes self-exciting point process.
+pub mod hawkes_process;
+pub use hawkes_process::*;
+
 /// Heston model process.
 pub mod heston;
 
@@ -83,15 +106,36 @@ pub mod ho_lee;
 /// Hull-White model process.
 pub mod hull_white;
 
+/// Kou jump diffusion process.
+pub mod kou_jump_diffusion;
+
 /// Merton jump diffusion process.
 pub mod merton_jump_diffusion;
 
+/// Correlated multi-asset Geometric Brownian Motion.
+pub mod multi_geometric_brownian_motion;
+pub use multi_geometric_brownian_motion::*;
+
 /// Ornstein-Uhlenbeck process.
 pub mod ornstein_uhlenbeck;
 
+/// Poisson and compound Poisson processes.
+pub mod poisson_process;
+
+/// Regime-switching Geometric Brownian Motion.
+pub mod regime_switching_gbm;
+pub use regime_switching_gbm::*;
+
 /// SABR model process.
 pub mod sabr;
 
+/// Markov processes with known transition densities.
+pub mod transition_density;
+pub use transition_density::*;
+
+/// Variance Gamma process.
+pub mod variance_gamma;
+
 /// Defines `Trajectories` and `StochasticProcess`.
 pub mod process;
 pub use process::*;
@@ -0,0 +1,503 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Correlated multi-asset Geometric Brownian Motion.
+//!
+//! Each asset follows
+//! $dS_i(t) = \mu_i S_i(t) dt + \sigma_i S_i(t) dW_i(t)$,
+//! with $d\langle W_i, W_j \rangle_t = \rho_{ij} dt$.
+//! Paths are simulated with the (exact) log-Euler scheme.
+//!
+//! Separately parameterised [GeometricBrownianMotion]s can also be driven by
+//! correlated Brownian motions with [correlated_gbm_paths].
+
+use crate::error::RustQuantError;
+use crate::models::GeometricBrownianMotion;
+use crate::stochastics::{
+    MultiTrajectories, StochasticProcessConfig, Trajectories, TransitionDensity,
+};
+use nalgebra::{DMatrix, DVector};
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+use std::f64::consts::PI;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Correlated multi-asset Geometric Brownian Motion.
+pub struct MultiGeometricBrownianMotion {
+    /// The drifts ($\mu_i$).
+    pub mu: Vec<f64>,
+
+    /// The volatilities ($\sigma_i$).
+    pub sigma: Vec<f64>,
+
+    /// The correlation matrix ($\rho_{ij}$).
+    pub correlation: DMatrix<f64>,
+
+    /// Lower-triangular factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MultiGeometricBrownianMotion {
+    /// Create a new correlated multi-asset Geometric Brownian Motion.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the dimensions do not match.
+    /// - `RustQuantError::InvalidArgument` if the correlation matrix does not
+    ///   have a unit diagonal, is not symmetric, or is not positive semi-definite.
+    pub fn new(
+        mu: Vec<f64>,
+        sigma: Vec<f64>,
+        correlation: DMatrix<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let n = mu.len();
+
+        if sigma.len() != n {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let cholesky = correlation_cholesky(&correlation, n)?;
+
+        Ok(Self {
+            mu,
+            sigma,
+            correlation,
+            cholesky,
+        })
+    }
+
+    /// Number of assets.
+    pub fn dimension(&self) -> usize {
+        self.mu.len()
+    }
+
+    /// Simulate correlated paths with the exact log-Euler step.
+    ///
+    /// Path `i` of every asset is driven by `config.path_rng(i)`, so a seeded
+    /// config gives reproducible paths.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The assets' initial values at `config.t_0`. These replace
+    ///   the scalar `config.x_0`, which is not used.
+    /// * `config` - The simulation configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_0` does not have one value per asset.
+    pub fn euler_maruyama(
+        &self,
+        x_0: &[f64],
+        config: &StochasticProcessConfig,
+    ) -> MultiTrajectories {
+        assert_eq!(x_0.len(), self.dimension());
+
+        simulate_correlated_paths(x_0, &self.cholesky, config, |i, _| {
+            (self.mu[i], self.sigma[i])
+        })
+    }
+}
+
+/// Multivariate lognormal transition density.
+///
+/// The density only exists for a positive definite correlation matrix,
+/// so evaluating it panics otherwise.
+impl TransitionDensity for MultiGeometricBrownianMotion {
+    fn dimension(&self) -> usize {
+        self.mu.len()
+    }
+
+    fn sample_transition(&self, x: &[f64], _t: f64, dt: f64, rng: &mut dyn RngCore) -> Vec<f64> {
+        let n = self.dimension();
+        let z = DVector::from_fn(n, |_, _| rng.sample::<f64, _>(StandardNormal));
+        let w = &self.cholesky * z;
+
+        (0..n)
+            .map(|i| {
+                let (mu, sigma) = (self.mu[i], self.sigma[i]);
+
+                x[i] * ((mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * w[i]).exp()
+            })
+            .collect()
+    }
+
+    fn transition_density(&self, x: &[f64], y: &[f64], _t: f64, dt: f64) -> f64 {
+        let n = self.dimension();
+
+        assert!(
+            (0..n).all(|i| self.cholesky[(i, i)] > 0.0),
+            "The transition density requires a positive definite correlation matrix."
+        );
+
+        if y.iter().any(|&y_i| y_i <= 0.0) {
+            return 0.0;
+        }
+
+        // Standardised log-returns, decorrelated by forward substitution.
+        let mut w = vec![0.0; n];
+        let mut normalisation = (2.0 * PI).powf(0.5 * n as f64);
+
+        for i in 0..n {
+            let (mu, sigma) = (self.mu[i], self.sigma[i]);
+            let std_dev = sigma * dt.sqrt();
+            let z = ((y[i] / x[i]).ln() - (mu - 0.5 * sigma * sigma) * dt) / std_dev;
+
+            w[i] = (z - (0..i).map(|k| self.cholesky[(i, k)] * w[k]).sum::<f64>())
+                / self.cholesky[(i, i)];
+            normalisation *= self.cholesky[(i, i)] * std_dev * y[i];
+        }
+
+        (-0.5 * w.iter().map(|w_i| w_i * w_i).sum::<f64>()).exp() / normalisation
+    }
+}
+
+/// Validate an `n` by `n` correlation matrix and return its Cholesky factor.
+///
+/// # Errors
+/// - `RustQuantError::UnequalLength` if the matrix is not `n` by `n`.
+/// - `RustQuantError::InvalidArgument` if the matrix does not have a unit
+///   diagonal, is not symmetric, or is not positive semi-definite.
+pub(crate) fn correlation_cholesky(
+    correlation: &DMatrix<f64>,
+    n: usize,
+) -> Result<DMatrix<f64>, RustQuantError> {
+    if correlation.nrows() != n || correlation.ncols() != n {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    for i in 0..n {
+        if (correlation[(i, i)] - 1.0).abs() > f64::EPSILON {
+            return Err(RustQuantError::InvalidArgument(
+                "Correlation matrix must have a unit diagonal.".to_string(),
+            ));
+        }
+
+        for j in 0..i {
+            if (correlation[(i, j)] - correlation[(j, i)]).abs() > f64::EPSILON {
+                return Err(RustQuantError::InvalidArgument(
+                    "Correlation matrix must be symmetric.".to_string(),
+                ));
+            }
+        }
+    }
+
+    semi_definite_cholesky(correlation).ok_or(RustQuantError::InvalidArgument(
+        "Correlation matrix must be positive semi-definite.".to_string(),
+    ))
+}
+
+/// Simulate Geometric Brownian Motions driven by correlated Brownian motions,
+/// one set of [Trajectories] per process, with the exact lognormal step
+/// (time-dependent parameters are frozen at the start of each step).
+///
+/// The independent normals of each step are correlated through the
+/// Cholesky factor of `correlation`. With a seed, path `i` of every
+/// process is driven by the same random number stream.
+///
+/// # Arguments:
+/// * `processes` - The processes to simulate.
+/// * `x_0` - The processes' initial values at `config.t_0`. These replace
+///   the scalar `config.x_0`, which is not used.
+/// * `correlation` - Correlation matrix of the driving Brownian motions.
+/// * `config` - The simulation configuration, shared by all the processes.
+///
+/// # Errors
+/// - `RustQuantError::UnequalLength` if the dimensions do not match.
+/// - `RustQuantError::InvalidArgument` if the correlation matrix does not
+///   have a unit diagonal, is not symmetric, or is not positive semi-definite.
+pub fn correlated_gbm_paths(
+    processes: &[GeometricBrownianMotion],
+    x_0: &[f64],
+    correlation: &DMatrix<f64>,
+    config: &StochasticProcessConfig,
+) -> Result<Vec<Trajectories>, RustQuantError> {
+    let n = processes.len();
+
+    if x_0.len() != n {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    let cholesky = correlation_cholesky(correlation, n)?;
+
+    let simulated = simulate_correlated_paths(x_0, &cholesky, config, |i, t| {
+        (processes[i].mu.0(t), processes[i].sigma.0(t))
+    });
+
+    Ok(simulated
+        .paths
+        .into_iter()
+        .map(|paths| Trajectories {
+            times: simulated.times.clone(),
+            paths,
+        })
+        .collect())
+}
+
+/// Simulate lognormal paths driven by Brownian motions correlated through
+/// the factor `cholesky`, with the exact step and the drift and volatility
+/// of asset `i` at time `t` given by `coefficients(i, t)` (frozen over
+/// each step). Path `index` draws from `config.path_rng(index)`.
+fn simulate_correlated_paths<F>(
+    x_0: &[f64],
+    cholesky: &DMatrix<f64>,
+    config: &StochasticProcessConfig,
+    coefficients: F,
+) -> MultiTrajectories
+where
+    F: Fn(usize, f64) -> (f64, f64) + Sync,
+{
+    let n = x_0.len();
+
+    let (_, t_0, t_n, n_steps, m_paths, _) = config.unpack();
+    assert!(t_0 < t_n);
+
+    let dt: f64 = (t_n - t_0) / (n_steps as f64);
+    let sqrt_dt = dt.sqrt();
+
+    let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+    // Returns one path per asset.
+    let path_generator = |index: usize| {
+        let mut rng = config.path_rng(index);
+        let mut paths: Vec<Vec<f64>> = x_0.iter().map(|&x| vec![x; n_steps + 1]).collect();
+
+        for t in 0..n_steps {
+            let z = DVector::from_fn(n, |_, _| rng.sample::<f64, _>(StandardNormal));
+            let w = cholesky * z;
+
+            for (i, path) in paths.iter_mut().enumerate() {
+                let (mu, sigma) = coefficients(i, times[t]);
+
+                path[t + 1] =
+                    path[t] * ((mu - 0.5 * sigma * sigma) * dt + sigma * sqrt_dt * w[i]).exp();
+            }
+        }
+
+        paths
+    };
+
+    let simulated: Vec<Vec<Vec<f64>>> = config.map_paths(path_generator);
+
+    // Transpose from [path][asset] to [asset][path].
+    let mut paths = vec![Vec::with_capacity(m_paths); n];
+
+    for path in simulated {
+        for (asset, values) in path.into_iter().enumerate() {
+            paths[asset].push(values);
+        }
+    }
+
+    MultiTrajectories { times, paths }
+}
+
+/// Cholesky factorisation that also accepts singular positive
+/// semi-definite matrices, by zeroing columns with a (numerically) zero pivot.
+/// Returns `None` if the matrix is not positive semi-definite.
+pub(crate) fn semi_definite_cholesky(matrix: &DMatrix<f64>) -> Option<DMatrix<f64>> {
+    const TOLERANCE: f64 = 1e-12;
+
+    let n = matrix.nrows();
+    let mut l = DMatrix::<f64>::zeros(n, n);
+
+    for j in 0..n {
+        let pivot = matrix[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+
+        if pivot < -TOLERANCE {
+            return None;
+        }
+
+        if pivot <= TOLERANCE {
+            // The remaining entries of the column must then vanish too.
+            for i in (j + 1)..n {
+                let residual = matrix[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+
+                if residual.abs() > TOLERANCE.sqrt() {
+                    return None;
+                }
+            }
+
+            continue;
+        }
+
+        l[(j, j)] = pivot.sqrt();
+
+        for i in (j + 1)..n {
+            l[(i, j)] =
+                (matrix[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>()) / l[(j, j)];
+        }
+    }
+
+    Some(l)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_multi_geometric_brownian_motion {
+    use super::*;
+    use crate::math::Statistic;
+
+    #[test]
+    fn test_correlated_log_returns() {
+        let rho = 0.8;
+        let correlation = DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0]);
+
+        let gbm = MultiGeometricBrownianMotion::new(vec![0.05, 0.02], vec![0.2, 0.4], correlation)
+            .unwrap();
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 50, 1000, true).with_seed(3);
+        let output = gbm.euler_maruyama(&[100.0, 50.0], &config);
+
+        assert_eq!(output.paths.len(), 2);
+        assert_eq!(output.paths[0].len(), 1000);
+        assert_eq!(output.paths[0][0].len(), 51);
+
+        let log_returns = |asset: usize| -> Vec<f64> {
+            output.paths[asset]
+                .iter()
+                .flat_map(|path| path.windows(2).map(|w| (w[1] / w[0]).ln()))
+                .collect()
+        };
+
+        let empirical = log_returns(0).correlation(&log_returns(1));
+
+        assert!((empirical - rho).abs() < 0.02);
+
+        // The seed makes the paths reproducible, serially or in parallel.
+        let serial = StochasticProcessConfig {
+            parallel: false,
+            ..config
+        };
+        assert_eq!(
+            gbm.euler_maruyama(&[100.0, 50.0], &serial).paths,
+            output.paths
+        );
+    }
+
+    #[test]
+    fn test_invalid_correlation() {
+        let not_psd =
+            DMatrix::from_row_slice(3, 3, &[1.0, 0.9, -0.9, 0.9, 1.0, 0.9, -0.9, 0.9, 1.0]);
+        assert!(MultiGeometricBrownianMotion::new(vec![0.0; 3], vec![0.2; 3], not_psd).is_err());
+
+        let mismatched = DMatrix::identity(3, 3);
+        assert!(MultiGeometricBrownianMotion::new(vec![0.0; 2], vec![0.2; 2], mismatched).is_err());
+
+        // Perfect correlation is singular, but still positive semi-definite.
+        let singular = DMatrix::from_element(2, 2, 1.0);
+        let gbm = MultiGeometricBrownianMotion::new(vec![0.0; 2], vec![0.2; 2], singular).unwrap();
+
+        let output = gbm.euler_maruyama(
+            &[1.0, 1.0],
+            &StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 10, false),
+        );
+        assert_eq!(output.paths[0], output.paths[1]);
+    }
+
+    #[test]
+    fn test_transition_density() {
+        use crate::models::GeometricBrownianMotion;
+
+        let (x, y, dt) = ([100.0, 50.0], [105.0, 47.0], 0.25);
+        let (mu, sigma) = ([0.05, 0.02], [0.2, 0.4]);
+
+        // Independent assets: the product of the marginal densities.
+        let independent =
+            MultiGeometricBrownianMotion::new(mu.to_vec(), sigma.to_vec(), DMatrix::identity(2, 2))
+                .unwrap();
+        let marginal = |i: usize| {
+            GeometricBrownianMotion::new(mu[i], sigma[i]).transition_density(
+                &[x[i]],
+                &[y[i]],
+                0.0,
+                dt,
+            )
+        };
+
+        assert!(
+            (independent.transition_density(&x, &y, 0.0, dt) - marginal(0) * marginal(1)).abs()
+                < 1e-15
+        );
+
+        // Correlated assets: the bivariate normal density of the log-returns.
+        let rho = -0.6;
+        let correlated = MultiGeometricBrownianMotion::new(
+            mu.to_vec(),
+            sigma.to_vec(),
+            DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0]),
+        )
+        .unwrap();
+
+        let z: Vec<f64> = (0..2)
+            .map(|i| {
+                ((y[i] / x[i]).ln() - (mu[i] - 0.5 * sigma[i] * sigma[i]) * dt)
+                    / (sigma[i] * dt.sqrt())
+            })
+            .collect();
+        let quadratic = (z[0] * z[0] - 2.0 * rho * z[0] * z[1] + z[1] * z[1]) / (1.0 - rho * rho);
+        let expected = (-0.5 * quadratic).exp()
+            / (2.0 * PI * (1.0 - rho * rho).sqrt() * sigma[0] * sigma[1] * dt * y[0] * y[1]);
+
+        let density = correlated.transition_density(&x, &y, 0.0, dt);
+        assert!((density - expected).abs() < 1e-12 * expected);
+    }
+
+    #[test]
+    fn test_correlated_gbm_paths() {
+        let rho = [[1.0, 0.6, -0.3], [0.6, 1.0, 0.2], [-0.3, 0.2, 1.0]];
+        let correlation = DMatrix::from_fn(3, 3, |i, j| rho[i][j]);
+
+        let processes = [
+            GeometricBrownianMotion::new(0.05, 0.2),
+            GeometricBrownianMotion::new(0.02, 0.4),
+            GeometricBrownianMotion::new(0.0, 0.1),
+        ];
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 50, 2000, true).with_seed(5);
+
+        let output =
+            correlated_gbm_paths(&processes, &[100.0, 50.0, 10.0], &correlation, &config).unwrap();
+
+        assert_eq!(output.len(), 3);
+        assert_eq!(output[1].paths.len(), 2000);
+        assert_eq!(output[1].paths[0].len(), 51);
+        assert_eq!(output[2].paths[7][0], 10.0);
+
+        let log_returns = |asset: usize| -> Vec<f64> {
+            output[asset]
+                .paths
+                .iter()
+                .flat_map(|path| path.windows(2).map(|w| (w[1] / w[0]).ln()))
+                .collect()
+        };
+
+        for (i, j) in [(1, 0), (2, 0), (2, 1)] {
+            let empirical = log_returns(i).correlation(&log_returns(j));
+
+            assert!((empirical - rho[i][j]).abs() < 0.01, "{i} {j} {empirical}");
+        }
+
+        // The seed makes the paths reproducible.
+        let again =
+            correlated_gbm_paths(&processes, &[100.0, 50.0, 10.0], &correlation, &config).unwrap();
+        assert_eq!(again[2].paths, output[2].paths);
+
+        // An indefinite correlation matrix is rejected.
+        let not_psd =
+            DMatrix::from_row_slice(3, 3, &[1.0, 0.9, -0.9, 0.9, 1.0, 0.9, -0.9, 0.9, 1.0]);
+        assert!(correlated_gbm_paths(&processes, &[1.0; 3], &not_psd, &config).is_err());
+        assert!(correlated_gbm_paths(&processes, &[1.0; 2], &correlation, &config).is_err());
+    }
+}
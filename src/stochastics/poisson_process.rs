@@ -0,0 +1,154 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! The Poisson and compound Poisson processes.
+//!
+//! Both are pure-jump, $dX(t) = J dN(t)$, with no drift or diffusion.
+//! They are simulated exactly on the time grid: each step samples the
+//! $\text{Poisson}(\lambda \Delta t)$ number of arrivals, and (for the
+//! compound process) adds that many jump sizes. The paths are therefore
+//! piecewise constant between arrivals.
+
+use crate::models::{CompoundPoissonProcess, PoissonProcess};
+use crate::stochastics::StochasticProcess;
+use rand::{Rng, RngCore};
+use rand_distr::Poisson;
+
+/// Number of arrivals in a step of length `dt` at intensity `lambda`.
+fn arrivals(lambda: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+    match Poisson::new(lambda * dt) {
+        Ok(arrivals) => rng.sample(arrivals),
+        Err(_) => 0.0,
+    }
+}
+
+impl StochasticProcess for PoissonProcess {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Every jump has unit size.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(1.0)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda.0(0.0)]
+    }
+
+    fn simulate_increment(&self, _x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        arrivals(self.lambda.0(t), dt, rng)
+    }
+}
+
+impl StochasticProcess for CompoundPoissonProcess {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Samples a jump size.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(self.jump_distribution.sample(&mut rand::thread_rng()))
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda.0(0.0)]
+    }
+
+    fn simulate_increment(&self, _x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let n = arrivals(self.lambda.0(t), dt, rng) as usize;
+
+        (0..n).map(|_| self.jump_distribution.sample(rng)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests_poisson_process {
+    use super::*;
+    use crate::math::Statistic;
+    use crate::models::JumpDistribution;
+    use crate::stochastics::StochasticProcessConfig;
+
+    #[test]
+    fn test_poisson_process_moments() {
+        let lambda = 3.0;
+        let poisson = PoissonProcess::new(lambda);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 2.0, 20, 20_000, true).with_seed(1);
+
+        let output = poisson.euler_maruyama(&config);
+
+        // Non-decreasing integer counts.
+        assert!(output.paths.iter().all(|path| {
+            path[0] == 0.0
+                && path.iter().all(|n| n.fract() == 0.0)
+                && path.windows(2).all(|w| w[1] >= w[0])
+        }));
+
+        // E[N(t)] = V[N(t)] = lambda t, at each time.
+        for step in [5, 10, 20] {
+            let t = output.times[step];
+            let counts: Vec<f64> = output.paths.iter().map(|path| path[step]).collect();
+
+            assert!(
+                (counts.mean() - lambda * t).abs() < 0.05,
+                "E[N({t})] = {}",
+                counts.mean()
+            );
+            assert!(
+                (counts.variance() - lambda * t).abs() < 0.15,
+                "V[N({t})] = {}",
+                counts.variance()
+            );
+        }
+    }
+
+    #[test]
+    fn test_compound_poisson_process_moments() {
+        let (lambda, mean, std_dev, t) = (2.0, -0.5, 1.0, 1.0);
+        let compound =
+            CompoundPoissonProcess::new(lambda, JumpDistribution::Normal { mean, std_dev });
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, 50_000, true).with_seed(2);
+
+        let X_T = compound.euler_maruyama(&config).terminal_values();
+
+        // E[X(t)] = lambda t E[J] and V[X(t)] = lambda t E[J^2].
+        let (expected_mean, expected_variance) = (
+            lambda * t * mean,
+            lambda * t * (mean * mean + std_dev * std_dev),
+        );
+
+        assert!((X_T.mean() - expected_mean).abs() < 0.03);
+        assert!((X_T.variance() - expected_variance).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_compound_poisson_custom_sampler() {
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 100, false).with_seed(3);
+
+        // Jumps that draw no random numbers leave the arrivals unchanged.
+        let doubled = CompoundPoissonProcess::new(
+            1.5,
+            JumpDistribution::Custom(Box::new(|_: &mut dyn RngCore| 2.0)),
+        )
+        .euler_maruyama(&config);
+        let counts = PoissonProcess::new(1.5).euler_maruyama(&config);
+
+        for (x, n) in doubled.paths.iter().zip(&counts.paths) {
+            assert!(x.iter().zip(n).all(|(x, n)| *x == 2.0 * n));
+        }
+    }
+}
@@ -14,9 +14,20 @@
 //! Autonomous refers to processes where the drift and diffusion
 //! do not explicitly depend on the time `t`.
 
+use crate::error::{RustQuantError, SimulationError};
+use crate::math::Statistic;
+use crate::stochastics::semi_definite_cholesky;
+use nalgebra::{DMatrix, DVector};
+use polars::prelude::{DataFrame, NamedFrom, Series};
 use rand::prelude::Distribution;
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rand_distr::StandardNormal;
 use rayon::prelude::*;
+use std::any::Any;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::panic::{self, AssertUnwindSafe};
 // use statrs::distribution::Normal;
 
 /// Struct to contain the time points and path values of the process.
@@ -28,6 +39,222 @@ pub struct Trajectories {
     pub paths: Vec<Vec<f64>>,
 }
 
+impl Trajectories {
+    /// Terminal value of each path.
+    #[must_use]
+    pub fn terminal_values(&self) -> Vec<f64> {
+        self.paths
+            .iter()
+            .filter_map(|path| path.last().copied())
+            .collect()
+    }
+
+    /// Mean across the paths at each time point.
+    #[must_use]
+    pub fn mean_path(&self) -> Vec<f64> {
+        self.cross_sections().map(|values| values.mean()).collect()
+    }
+
+    /// Sample standard deviation across the paths at each time point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two paths.
+    #[must_use]
+    pub fn std_path(&self) -> Vec<f64> {
+        self.cross_sections()
+            .map(|values| values.standard_deviation())
+            .collect()
+    }
+
+    /// Quantile `p` across the paths at each time point, interpolating
+    /// linearly between the order statistics (as [`Statistic::quantile`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `[0, 1]`, or if there are no paths.
+    #[must_use]
+    pub fn quantile_path(&self, p: f64) -> Vec<f64> {
+        self.cross_sections()
+            .map(|values| values.quantile(p))
+            .collect()
+    }
+
+    /// Quantile `p` of the terminal values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `[0, 1]`, or if there are no paths.
+    #[must_use]
+    pub fn terminal_quantile(&self, p: f64) -> f64 {
+        self.terminal_values().quantile(p)
+    }
+
+    /// Sample autocorrelation of each path's increments, at lags
+    /// `0..=max_lag`, averaged across the paths.
+    ///
+    /// For each path with increments $d_i$ and mean increment $\bar{d}$,
+    ///
+    /// $$
+    /// \hat{\rho}(k) = \frac{\sum_i (d_i - \bar{d})(d_{i+k} - \bar{d})}{\sum_i (d_i - \bar{d})^2}
+    /// $$
+    ///
+    /// Paths with constant increments (no variation) are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_lag` is not less than the number of increments, or if
+    /// no path varies.
+    #[must_use]
+    pub fn increment_autocorrelation(&self, max_lag: usize) -> Vec<f64> {
+        assert!(
+            max_lag + 1 < self.times.len(),
+            "max_lag must be less than the number of increments."
+        );
+
+        let mut sums = vec![0.0; max_lag + 1];
+        let mut count = 0;
+
+        for path in &self.paths {
+            let increments: Vec<f64> = path.windows(2).map(|w| w[1] - w[0]).collect();
+            let mean = increments.mean();
+            let deviations: Vec<f64> = increments.iter().map(|d| d - mean).collect();
+
+            let denominator: f64 = deviations.iter().map(|d| d * d).sum();
+            if denominator == 0.0 {
+                continue;
+            }
+
+            for (lag, sum) in sums.iter_mut().enumerate() {
+                let numerator: f64 = deviations
+                    .iter()
+                    .zip(&deviations[lag..])
+                    .map(|(a, b)| a * b)
+                    .sum();
+
+                *sum += numerator / denominator;
+            }
+            count += 1;
+        }
+
+        assert!(count > 0, "No path varies.");
+
+        sums.iter().map(|sum| sum / count as f64).collect()
+    }
+
+    /// Write the trajectories to a CSV file, with a `time` column and one
+    /// column per path (`sim_0`, `sim_1`, ...), one row per time point.
+    /// The values are written in full precision, so [`Self::from_csv`]
+    /// reads them back exactly.
+    ///
+    /// # Errors
+    /// - `RustQuantError::IoError` if the file cannot be written.
+    pub fn to_csv(&self, path: &str) -> Result<(), RustQuantError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        write!(writer, "time")?;
+        for i in 0..self.paths.len() {
+            write!(writer, ",sim_{i}")?;
+        }
+        writeln!(writer)?;
+
+        for (i, time) in self.times.iter().enumerate() {
+            write!(writer, "{time}")?;
+            for path in &self.paths {
+                write!(writer, ",{}", path[i])?;
+            }
+            writeln!(writer)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Read trajectories from a CSV file written by [`Self::to_csv`].
+    ///
+    /// # Errors
+    /// - `RustQuantError::IoError` if the file cannot be read.
+    /// - `RustQuantError::FileOperationFailed` if the header is missing, a
+    ///   value is not a number, or a row has the wrong number of values.
+    pub fn from_csv(path: &str) -> Result<Self, RustQuantError> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+
+        let header = lines.next().ok_or_else(|| {
+            RustQuantError::FileOperationFailed(format!("{path} has no header."))
+        })??;
+        let n_columns = header.split(',').count();
+
+        let mut times = Vec::new();
+        let mut paths = vec![Vec::new(); n_columns - 1];
+
+        for (i, line) in lines.enumerate() {
+            let values = line?
+                .split(',')
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|error| {
+                    RustQuantError::FileOperationFailed(format!("{path}, row {}: {error}", i + 1))
+                })?;
+
+            if values.len() != n_columns {
+                return Err(RustQuantError::FileOperationFailed(format!(
+                    "{path}, row {}: expected {n_columns} values, found {}.",
+                    i + 1,
+                    values.len()
+                )));
+            }
+
+            times.push(values[0]);
+            for (path, value) in paths.iter_mut().zip(&values[1..]) {
+                path.push(*value);
+            }
+        }
+
+        Ok(Self { times, paths })
+    }
+
+    /// Convert the trajectories to a long-format `DataFrame`, with one row
+    /// per path and time point and columns `time` (float), `sim` (the path
+    /// index), and `value` (float).
+    ///
+    /// # Errors
+    /// - `RustQuantError::PolarsError` if the `DataFrame` cannot be created.
+    pub fn to_dataframe(&self) -> Result<DataFrame, RustQuantError> {
+        let n = self.paths.len() * self.times.len();
+
+        let mut times = Vec::with_capacity(n);
+        let mut sims = Vec::with_capacity(n);
+        let mut values = Vec::with_capacity(n);
+
+        for (sim, path) in self.paths.iter().enumerate() {
+            times.extend_from_slice(&self.times);
+            sims.resize(sims.len() + path.len(), sim as u64);
+            values.extend_from_slice(path);
+        }
+
+        Ok(DataFrame::new(vec![
+            Series::new("time", times),
+            Series::new("sim", sims),
+            Series::new("value", values),
+        ])?)
+    }
+
+    /// Values of all the paths at each time point.
+    fn cross_sections(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        (0..self.times.len()).map(|i| self.paths.iter().map(|path| path[i]).collect())
+    }
+}
+
+/// Struct to contain the time points and path values of a multi-factor process.
+pub struct MultiTrajectories {
+    /// Vector of time points.
+    pub times: Vec<f64>,
+
+    /// Process trajectories, indexed as `paths[factor][path][time]`.
+    pub paths: Vec<Vec<Vec<f64>>>,
+}
+
 /// Trait to implement stochastic volatility processes.
 pub trait StochasticVolatilityProcess: Sync {
     /// Base method for the asset's drift.
@@ -101,6 +328,300 @@ pub trait StochasticVolatilityProcess: Sync {
     }
 }
 
+/// A stochastic process under a changed measure.
+/// See [`StochasticProcess::change_measure`].
+pub struct ChangedMeasureProcess<'a, P, F>
+where
+    P: StochasticProcess,
+    F: Fn(f64, f64) -> f64 + Sync,
+{
+    /// The process under the original measure.
+    pub process: &'a P,
+
+    /// The market price of risk $\lambda(x, t)$.
+    pub market_price_of_risk: F,
+}
+
+impl<P, F> StochasticProcess for ChangedMeasureProcess<'_, P, F>
+where
+    P: StochasticProcess,
+    F: Fn(f64, f64) -> f64 + Sync,
+{
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.process.drift(x, t) - self.process.diffusion(x, t) * (self.market_price_of_risk)(x, t)
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.process.diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        self.process.jump(x, t)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        self.process.parameters()
+    }
+}
+
+/// Discretisation schemes for multi-factor processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultifactorScheme {
+    /// Euler-Maruyama scheme (strong order 0.5).
+    EulerMaruyama,
+
+    /// Milstein scheme, including the Levy area terms (strong order 1.0).
+    ///
+    /// The Levy areas are approximated by subsampling each step's
+    /// Brownian increments; strong order 1.0 requires around
+    /// $1 / \Delta t$ sub-steps.
+    Milstein {
+        /// Number of sub-steps used to approximate the Levy areas.
+        levy_area_substeps: usize,
+    },
+}
+
+/// Trait to implement multi-factor stochastic processes,
+/// driven by correlated Brownian motions:
+///
+/// $dX_i(t) = \mu_i(X, t) dt + \sigma_i(X, t) dW_i(t)$,
+/// with $d\langle W_i, W_j \rangle_t = \rho_{ij} dt$.
+pub trait MultifactorProcess: Sync {
+    /// Number of factors.
+    fn dimension(&self) -> usize;
+
+    /// Base method for the factors' drifts.
+    fn drift(&self, x: &[f64], t: f64) -> Vec<f64>;
+
+    /// Base method for the factors' diffusions.
+    fn diffusion(&self, x: &[f64], t: f64) -> Vec<f64>;
+
+    /// Correlation matrix of the Brownian drivers.
+    fn correlation(&self) -> DMatrix<f64>;
+
+    /// Jacobian of the diffusions, $J_{il} = \partial \sigma_i / \partial x_l$,
+    /// used by the Milstein scheme.
+    ///
+    /// Defaults to central finite differences.
+    fn diffusion_jacobian(&self, x: &[f64], t: f64) -> DMatrix<f64> {
+        let n = self.dimension();
+        let mut jacobian = DMatrix::<f64>::zeros(n, n);
+        let mut bumped = x.to_vec();
+
+        for l in 0..n {
+            let h = 1e-6 * x[l].abs().max(1.0);
+
+            bumped[l] = x[l] + h;
+            let up = self.diffusion(&bumped, t);
+            bumped[l] = x[l] - h;
+            let down = self.diffusion(&bumped, t);
+            bumped[l] = x[l];
+
+            for i in 0..n {
+                jacobian[(i, l)] = (up[i] - down[i]) / (2.0 * h);
+            }
+        }
+
+        jacobian
+    }
+
+    /// Jumps in the factors over the step of length `dt` from time `t`,
+    /// given the state `x` after the diffusion part of the step.
+    /// The jumps are added to the state.
+    ///
+    /// Defaults to no jumps (a pure diffusion).
+    fn jump(&self, _x: &[f64], _t: f64, _dt: f64, _rng: &mut dyn RngCore) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Euler-Maruyama discretisation scheme.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The factors' initial values at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    fn euler_maruyama(
+        &self,
+        x_0: &[f64],
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> MultiTrajectories {
+        let config = StochasticProcessConfig::new(x_0[0], t_0, t_n, n_steps, m_paths, parallel);
+
+        self.simulate(MultifactorScheme::EulerMaruyama, x_0, &config)
+    }
+
+    /// Simulate with the given discretisation scheme.
+    ///
+    /// Path `i` draws from `config.path_rng(i)`, as in
+    /// [`StochasticProcess::euler_maruyama`], so a seeded config gives the
+    /// same random number streams, whatever its chunk size.
+    ///
+    /// # Arguments:
+    /// * `scheme` - The discretisation scheme.
+    /// * `x_0` - The factors' initial values at `config.t_0`. These replace
+    ///   the scalar `config.x_0`, which is not used.
+    /// * `config` - The simulation configuration.
+    fn simulate(
+        &self,
+        scheme: MultifactorScheme,
+        x_0: &[f64],
+        config: &StochasticProcessConfig,
+    ) -> MultiTrajectories {
+        let n = self.dimension();
+
+        let (_, t_0, t_n, n_steps, m_paths, _) = config.unpack();
+        assert!(t_0 < t_n);
+        assert_eq!(x_0.len(), n);
+
+        let cholesky = semi_definite_cholesky(&self.correlation())
+            .expect("Correlation matrix must be positive semi-definite.");
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times = config.times();
+
+        let simulated: Vec<Vec<Vec<f64>>> = config.map_paths(|index| {
+            let mut rng = config.path_rng(index);
+
+            multifactor_path(self, scheme, x_0, &times, dt, &cholesky, rng.as_mut())
+        });
+
+        // Transpose from [path][factor] to [factor][path].
+        let mut paths = vec![Vec::with_capacity(m_paths); n];
+
+        for path in simulated {
+            for (factor, values) in path.into_iter().enumerate() {
+                paths[factor].push(values);
+            }
+        }
+
+        MultiTrajectories { times, paths }
+    }
+}
+
+/// A path of [`MultifactorProcess::simulate`], one path per factor,
+/// drawing from `rng`.
+pub(crate) fn multifactor_path<P: MultifactorProcess + ?Sized>(
+    process: &P,
+    scheme: MultifactorScheme,
+    x_0: &[f64],
+    times: &[f64],
+    dt: f64,
+    cholesky: &DMatrix<f64>,
+    rng: &mut dyn RngCore,
+) -> Vec<Vec<f64>> {
+    let (n, n_steps) = (x_0.len(), times.len() - 1);
+    let sqrt_dt = dt.sqrt();
+
+    let mut paths: Vec<Vec<f64>> = x_0.iter().map(|&x| vec![x; n_steps + 1]).collect();
+    let mut x = x_0.to_vec();
+
+    for t in 0..n_steps {
+        // Independent Brownian increments (and their Levy areas).
+        let (dW, levy_area) = match scheme {
+            MultifactorScheme::EulerMaruyama => (
+                DVector::from_fn(n, |_, _| rng.sample::<f64, _>(StandardNormal)) * sqrt_dt,
+                None,
+            ),
+            MultifactorScheme::Milstein { levy_area_substeps } => {
+                let substeps = levy_area_substeps.max(1);
+                let scale = (dt / substeps as f64).sqrt();
+
+                let increments: Vec<DVector<f64>> = (0..substeps)
+                    .map(|_| {
+                        DVector::from_fn(n, |_, _| rng.sample::<f64, _>(StandardNormal)) * scale
+                    })
+                    .collect();
+
+                let (dW, area) = levy_area(&increments);
+
+                (dW, Some(area))
+            }
+        };
+
+        x = multifactor_step(process, &x, times[t], dt, cholesky, &dW, levy_area.as_ref());
+
+        if let Some(jump) = process.jump(&x, times[t], dt, rng) {
+            x.iter_mut().zip(jump).for_each(|(x, jump)| *x += jump);
+        }
+
+        for (i, path) in paths.iter_mut().enumerate() {
+            path[t + 1] = x[i];
+        }
+    }
+
+    paths
+}
+
+/// Total increment and Levy areas
+/// $A_{jk} = \frac{1}{2} \int (W_j dW_k - W_k dW_j)$
+/// of independent Brownian motions over a step, approximated by the
+/// discrete sums over the given sub-step increments (Kloeden-Platen subsampling).
+fn levy_area(increments: &[DVector<f64>]) -> (DVector<f64>, DMatrix<f64>) {
+    let n = increments.first().map_or(0, DVector::len);
+
+    let mut W = DVector::<f64>::zeros(n);
+    let mut area = DMatrix::<f64>::zeros(n, n);
+
+    for dW in increments {
+        let outer = &W * dW.transpose();
+        area += 0.5 * (&outer - outer.transpose());
+        W += dW;
+    }
+
+    (W, area)
+}
+
+/// One step of the Euler-Maruyama scheme, or of the Milstein scheme
+/// if the Levy areas of the (independent) increments `dW` are given.
+///
+/// With $b^i_k = \sigma_i L_{ik}$, where $L$ is the Cholesky factor of the
+/// correlation matrix, the Milstein correction to factor $i$ is
+/// $\sum_{j,k} \sum_l b^l_j \partial_l b^i_k I_{(j,k)}$ with the iterated integrals
+/// $I_{(j,k)} = \frac{1}{2} (\Delta W_j \Delta W_k - \delta_{jk} \Delta t) + A_{jk}$.
+fn multifactor_step<P: MultifactorProcess + ?Sized>(
+    process: &P,
+    x: &[f64],
+    t: f64,
+    dt: f64,
+    cholesky: &DMatrix<f64>,
+    dW: &DVector<f64>,
+    levy_area: Option<&DMatrix<f64>>,
+) -> Vec<f64> {
+    let n = x.len();
+
+    let drift = process.drift(x, t);
+    let diffusion = process.diffusion(x, t);
+    let correlated = cholesky * dW;
+
+    let mut next: Vec<f64> = (0..n)
+        .map(|i| x[i] + drift[i] * dt + diffusion[i] * correlated[i])
+        .collect();
+
+    if let Some(area) = levy_area {
+        // c_ij = sum_l J_il sigma_l L_lj.
+        let c = process.diffusion_jacobian(x, t)
+            * DMatrix::from_diagonal(&DVector::from_column_slice(&diffusion))
+            * cholesky;
+
+        let iterated = 0.5 * (dW * dW.transpose() - DMatrix::<f64>::identity(n, n) * dt) + area;
+
+        let correction = c * iterated * cholesky.transpose();
+
+        for (i, value) in next.iter_mut().enumerate() {
+            *value += correction[(i, i)];
+        }
+    }
+
+    next
+}
+
 /// Configuration parameters for simulating a stochastic process.
 ///
 /// # Arguments:
@@ -110,6 +631,9 @@ pub trait StochasticVolatilityProcess: Sync {
 /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
 /// * `m_paths` - How many process trajectories to simulate.
 /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+/// * `seed` - Optional seed for reproducible paths (see [`StochasticProcessConfig::with_seed`]).
+/// * `chunk_size` - Paths per chunk of work and random number stream
+///   (see [`StochasticProcessConfig::with_chunk_size`]).
 pub struct StochasticProcessConfig {
     /// Initial value of the process.
     pub x_0: f64,
@@ -128,6 +652,12 @@ pub struct StochasticProcessConfig {
 
     /// Run in parallel or not (recommended for > 1000 paths).
     pub parallel: bool,
+
+    /// Seed for the random number generator (`None` for entropy seeding).
+    pub seed: Option<u64>,
+
+    /// Number of paths in each chunk of work, sharing one random number stream.
+    pub chunk_size: usize,
 }
 
 impl StochasticProcessConfig {
@@ -147,9 +677,79 @@ impl StochasticProcessConfig {
             n_steps,
             m_paths,
             parallel,
+            seed: None,
+            chunk_size: 1,
+        }
+    }
+
+    /// Seed the random number generator.
+    ///
+    /// The paths are split into fixed-size chunks (see
+    /// [`StochasticProcessConfig::with_chunk_size`]), each with its own
+    /// random number stream derived from the seed and the chunk's index.
+    /// The same seed therefore gives bitwise-identical trajectories
+    /// whether the simulation runs in serial or in parallel, and whatever
+    /// the number of threads.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the number of paths in each chunk (one by default).
+    ///
+    /// Parallel simulations hand out whole chunks to the threads, and each
+    /// chunk draws from one stream, keyed by the seed and the chunk's index,
+    /// with a sub-stream per path within it. Paths can still be simulated
+    /// one at a time, in any order. A chunk size of one gives every path
+    /// its own seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "Chunk size must be positive.");
+
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Random number generator for path `index`: the path's sub-stream
+    /// of its chunk's stream if seeded, otherwise the thread-local generator.
+    pub(crate) fn path_rng(&self, index: usize) -> Box<dyn RngCore> {
+        match self.seed {
+            Some(seed) => {
+                let mut rng = ChaCha12Rng::seed_from_u64(path_seed(seed, index / self.chunk_size));
+                rng.set_stream((index % self.chunk_size) as u64);
+
+                Box::new(rng)
+            }
+            None => Box::new(rand::thread_rng()),
+        }
+    }
+
+    /// `generate(i)` for each path index `i`, in parallel (one chunk
+    /// of paths at a time per thread) if the configuration is parallel.
+    pub(crate) fn map_paths<T, F>(&self, generate: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync + Send,
+    {
+        if self.parallel {
+            self.par_indices().map(generate).collect()
+        } else {
+            (0..self.m_paths).map(generate).collect()
         }
     }
 
+    /// Path indices, split between threads in whole chunks.
+    pub(crate) fn par_indices(&self) -> impl IndexedParallelIterator<Item = usize> {
+        (0..self.m_paths)
+            .into_par_iter()
+            .with_min_len(self.chunk_size)
+    }
+
     pub(crate) fn unpack(&self) -> (f64, f64, f64, usize, usize, bool) {
         (
             self.x_0,
@@ -160,6 +760,96 @@ impl StochasticProcessConfig {
             self.parallel,
         )
     }
+
+    /// Time points of the simulation grid.
+    pub(crate) fn times(&self) -> Vec<f64> {
+        let dt: f64 = (self.t_n - self.t_0) / (self.n_steps as f64);
+
+        (0..=self.n_steps)
+            .map(|t| self.t_0 + dt * (t as f64))
+            .collect()
+    }
+}
+
+/// Seed of the sub-stream for path `index`, derived from `seed`
+/// via the SplitMix64 finaliser so that nearby seeds give unrelated streams.
+pub(crate) fn path_seed(seed: u64, index: usize) -> u64 {
+    let mut z = seed.wrapping_add((index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Terminal value of path `index`, evolved in place by
+/// `step(x, t, dt, z)` given a standard normal draw `z`.
+pub(crate) fn terminal_value_with<F>(config: &StochasticProcessConfig, index: usize, step: F) -> f64
+where
+    F: Fn(f64, f64, f64, f64) -> f64,
+{
+    terminal_value_by(config, index, |x, t, dt, rng| {
+        step(x, t, dt, rng.sample(StandardNormal))
+    })
+}
+
+/// Terminal value of path `index`, evolved in place by
+/// `step(x, t, dt, rng)`, drawing from the path's generator.
+pub(crate) fn terminal_value_by<F>(config: &StochasticProcessConfig, index: usize, step: F) -> f64
+where
+    F: Fn(f64, f64, f64, &mut dyn RngCore) -> f64,
+{
+    let (x_0, t_0, t_n, n_steps, _, _) = config.unpack();
+    assert!(t_0 < t_n);
+
+    let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+    // Seeded paths use the same sub-streams as `euler_maruyama`.
+    let mut rng = config.path_rng(index);
+    let mut x = x_0;
+
+    for k in 0..n_steps {
+        x = step(x, t_0 + dt * (k as f64), dt, rng.as_mut());
+    }
+
+    x
+}
+
+/// Iterator over the paths of a process, simulated one at a time
+/// (see [`StochasticProcess::path_iter`]).
+pub struct PathIter<'a, P> {
+    process: &'a P,
+    config: &'a StochasticProcessConfig,
+    index: usize,
+}
+
+impl<P: StochasticProcess> Iterator for PathIter<'_, P> {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.index < self.config.m_paths).then(|| {
+            self.index += 1;
+            self.process.simulate_path(self.config, self.index - 1)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.config.m_paths.saturating_sub(self.index);
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<P: StochasticProcess> ExactSizeIterator for PathIter<'_, P> {}
+
+/// Message of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => format!("panicked: {message}"),
+        (_, Some(message)) => format!("panicked: {message}"),
+        _ => "panicked".to_string(),
+    }
 }
 
 /// Trait to implement stochastic processes.
@@ -179,51 +869,346 @@ pub trait StochasticProcess: Sync {
         vec![]
     }
 
+    /// Change of measure via Girsanov's theorem.
+    ///
+    /// Returns the process under the measure with Brownian motion
+    /// $dW^{\mathbb{Q}} = dW^{\mathbb{P}} + \lambda(x, t) dt$, i.e. with drift
+    /// $\mu(x, t) - \sigma(x, t) \lambda(x, t)$ and the same diffusion.
+    ///
+    /// # Arguments:
+    /// * `market_price_of_risk` - The market price of risk $\lambda(x, t)$.
+    fn change_measure<F>(&self, market_price_of_risk: F) -> ChangedMeasureProcess<'_, Self, F>
+    where
+        Self: Sized,
+        F: Fn(f64, f64) -> f64 + Sync,
+    {
+        ChangedMeasureProcess {
+            process: self,
+            market_price_of_risk,
+        }
+    }
+
     /// Euler-Maruyama discretisation scheme.
     ///
     /// # Arguments:
-    /// * `x_0` - The process' initial value at `t_0`.
-    /// * `t_0` - The initial time point.
-    /// * `t_n` - The terminal time point.
-    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
-    /// * `m_paths` - How many process trajectories to simulate.
-    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    /// * `config` - The simulation configuration (see [`StochasticProcessConfig`]).
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
-        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(config.t_0 < config.t_n);
+
+        let paths = config.map_paths(|i| self.simulate_path(config, i));
+
+        Trajectories {
+            times: config.times(),
+            paths,
+        }
+    }
+
+    /// Simulate a single path of [`StochasticProcess::euler_maruyama`].
+    ///
+    /// Given a seed, this is the path with index `index` in the full
+    /// simulation, so paths can be generated one at a time (and in any
+    /// order) without storing the others. Processes with their own scheme
+    /// override [`StochasticProcess::simulate_path_into`], and the other
+    /// simulation methods then use it.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    /// * `index` - The index of the path, in `0..config.m_paths`.
+    fn simulate_path(&self, config: &StochasticProcessConfig, index: usize) -> Vec<f64> {
+        let mut path = Vec::with_capacity(config.n_steps + 1);
+        self.simulate_path_into(config, index, &mut path);
+
+        path
+    }
+
+    /// Simulate the path of [`StochasticProcess::simulate_path`] into the
+    /// (empty) `path`, pushing each state as soon as it is computed.
+    ///
+    /// If a step panics, the states before it are left in `path`, which
+    /// [`StochasticProcess::try_euler_maruyama`] uses to report the step
+    /// that failed. Processes with their own scheme override this.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    /// * `index` - The index of the path, in `0..config.m_paths`.
+    /// * `path` - The path to push the states to.
+    fn simulate_path_into(
+        &self,
+        config: &StochasticProcessConfig,
+        index: usize,
+        path: &mut Vec<f64>,
+    ) {
+        let (x_0, t_0, t_n, n_steps, _, _) = config.unpack();
         assert!(t_0 < t_n);
 
         let dt: f64 = (t_n - t_0) / (n_steps as f64);
 
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let mut rng = config.path_rng(index);
+        let mut x = x_0;
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
+        path.push(x);
+
+        for t in 0..n_steps {
+            x += self.simulate_increment(x, t_0 + dt * (t as f64), dt, rng.as_mut());
+            path.push(x);
+        }
+    }
+
+    /// Stream the paths of [`StochasticProcess::euler_maruyama`], simulating
+    /// each one only when it is requested.
+    ///
+    /// Only one path is held in memory at a time, so the number of paths is
+    /// not limited by memory. The iterator is `Send`, so it can also be
+    /// shared between threads with rayon's `par_bridge`, although
+    /// [`StochasticProcess::par_path_iter`] splits the work better.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    fn path_iter<'a>(&'a self, config: &'a StochasticProcessConfig) -> PathIter<'a, Self>
+    where
+        Self: Sized,
+    {
+        PathIter {
+            process: self,
+            config,
+            index: 0,
+        }
+    }
+
+    /// Stream the paths of [`StochasticProcess::euler_maruyama`] in parallel.
+    ///
+    /// Rayon hands out whole chunks of paths (see
+    /// [`StochasticProcessConfig::with_chunk_size`]) to its workers, each of
+    /// which simulates one path at a time. Given a seed, the paths are the
+    /// same whatever the number of threads, and collecting them gives the
+    /// trajectories of the full simulation.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    fn par_path_iter<'a>(
+        &'a self,
+        config: &'a StochasticProcessConfig,
+    ) -> impl IndexedParallelIterator<Item = Vec<f64>> + 'a
+    where
+        Self: Sized,
+    {
+        config
+            .par_indices()
+            .map(move |i| self.simulate_path(config, i))
+    }
+
+    /// Predictor-corrector discretisation scheme, with drift and diffusion
+    /// weights of one half (see [`StochasticProcess::predictor_corrector_with`]).
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    fn predictor_corrector(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.predictor_corrector_with(config, 0.5, 0.5)
+    }
+
+    /// Predictor-corrector discretisation scheme.
+    ///
+    /// An Euler step predicts $\bar{X}_{n+1}$, and the step is then redone
+    /// with the coefficients averaged between the start and the prediction:
+    ///
+    /// $$
+    /// X_{n+1} = X_n + \left( \alpha \bar{a}(\bar{X}_{n+1}, t_{n+1}) + (1 - \alpha) \bar{a}(X_n, t_n) \right) \Delta t
+    ///     + \left( \eta b(\bar{X}_{n+1}, t_{n+1}) + (1 - \eta) b(X_n, t_n) \right) \Delta W_n,
+    /// $$
+    ///
+    /// with the same Brownian increment $\Delta W_n$ in both steps, and the
+    /// drift corrected for the weight on the diffusion,
+    /// $\bar{a} = a - \eta b \, \partial b / \partial x$ (differentiated
+    /// numerically). Weights of zero give back the Euler-Maruyama scheme.
+    /// The jump term is ignored.
+    ///
+    /// With a seed, the paths use the same random numbers as
+    /// [`StochasticProcess::euler_maruyama`].
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    /// * `alpha` - Weight of the drift at the predicted state, in $[0, 1]$.
+    /// * `eta` - Weight of the diffusion at the predicted state, in $[0, 1]$.
+    fn predictor_corrector_with(
+        &self,
+        config: &StochasticProcessConfig,
+        alpha: f64,
+        eta: f64,
+    ) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(t_0 < t_n);
+        assert!((0.0..=1.0).contains(&alpha) && (0.0..=1.0).contains(&eta));
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let corrected_drift = |x: f64, t: f64| {
+            if eta == 0.0 {
+                return self.drift(x, t);
+            }
+
+            let h = 1e-6 * x.abs().max(1.0);
+            let slope = (self.diffusion(x + h, t) - self.diffusion(x - h, t)) / (2.0 * h);
+
+            self.drift(x, t) - eta * self.diffusion(x, t) * slope
+        };
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
 
             for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
+                let (x, s, s_next) = (path[t], times[t], times[t + 1]);
+                let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+
+                let predictor = x + (self.drift(x, s) * dt + self.diffusion(x, s) * dW);
+
+                let drift = alpha * corrected_drift(predictor, s_next)
+                    + (1.0 - alpha) * corrected_drift(x, s);
+                let diffusion =
+                    eta * self.diffusion(predictor, s_next) + (1.0 - eta) * self.diffusion(x, s);
+
+                path[t + 1] = x + (drift * dt + diffusion * dW);
             }
         };
 
         if parallel {
-            paths.par_iter_mut().for_each(path_generator);
+            paths
+                .par_iter_mut()
+                .with_min_len(config.chunk_size)
+                .enumerate()
+                .for_each(path_generator);
         } else {
-            paths.iter_mut().for_each(path_generator);
+            paths.iter_mut().enumerate().for_each(path_generator);
         }
 
         Trajectories { times, paths }
     }
 
+    /// Euler-Maruyama discretisation scheme, failing gracefully.
+    ///
+    /// As [`StochasticProcess::euler_maruyama`] (the same paths, from
+    /// [`StochasticProcess::simulate_path`], so processes with their own
+    /// scheme keep it), but a panic in the process, or a non-finite value,
+    /// fails the simulation instead of aborting it or propagating silently.
+    /// The failure on the lowest path index is reported, in both the serial
+    /// and the parallel modes.
+    ///
+    /// # Errors
+    ///
+    /// A [`SimulationError`] naming the path, time step, and state at which
+    /// the path failed. A panic is reported at the step after the last
+    /// state that [`StochasticProcess::simulate_path_into`] completed.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    fn try_euler_maruyama(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> Result<Trajectories, SimulationError> {
+        assert!(config.t_0 < config.t_n);
+
+        let times = config.times();
+
+        let path_generator = |i: usize| -> Result<Vec<f64>, SimulationError> {
+            let failure = |step: usize, state: f64, reason: String| SimulationError {
+                path: i,
+                step,
+                time: times[step],
+                state,
+                reason,
+            };
+
+            let mut path = Vec::with_capacity(config.n_steps + 1);
+
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                self.simulate_path_into(config, i, &mut path);
+            }))
+            .map_err(|payload| {
+                // The panicking step starts from the last completed state.
+                failure(
+                    path.len().saturating_sub(1),
+                    path.last().copied().unwrap_or(config.x_0),
+                    panic_message(payload.as_ref()),
+                )
+            })?;
+
+            match path.iter().position(|x| !x.is_finite()) {
+                Some(0) => Err(failure(0, path[0], format!("non-finite value {}", path[0]))),
+                Some(k) => Err(failure(
+                    k - 1,
+                    path[k - 1],
+                    format!("non-finite value {}", path[k]),
+                )),
+                None => Ok(path),
+            }
+        };
+
+        let paths = if config.parallel {
+            config
+                .par_indices()
+                .map(path_generator)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            (0..config.m_paths)
+                .map(path_generator)
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Trajectories { times, paths })
+    }
+
+    /// Simulate only the terminal values $X(T)$ of the paths.
+    ///
+    /// Each path's state is evolved in place (see
+    /// [`StochasticProcess::simulate_terminal_value`]), rather than storing
+    /// the full trajectories, which is all that is needed for European
+    /// payoffs. Given a seed, the values match the last points of
+    /// [`StochasticProcess::euler_maruyama`].
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    fn simulate_terminal(&self, config: &StochasticProcessConfig) -> Vec<f64> {
+        config.map_paths(|i| self.simulate_terminal_value(config, i))
+    }
+
+    /// Simulate only the terminal value $X(T)$ of path `index`.
+    ///
+    /// By default the state is evolved in place with the Euler-Maruyama
+    /// scheme. Processes with an exact or more accurate terminal step
+    /// override this; processes that override
+    /// [`StochasticProcess::simulate_path`] should override this too.
+    ///
+    /// # Arguments:
+    /// * `config` - The simulation configuration.
+    /// * `index` - The index of the path, in `0..config.m_paths`.
+    fn simulate_terminal_value(&self, config: &StochasticProcessConfig, index: usize) -> f64 {
+        terminal_value_by(config, index, |x, t, dt, rng| {
+            x + self.simulate_increment(x, t, dt, rng)
+        })
+    }
+
+    /// Simulate the increment $X(t + dt) - X(t)$ given $X(t) = x$.
+    ///
+    /// By default this is the Euler-Maruyama step, from the drift and the
+    /// diffusion. Processes that are not driven by a Brownian motion (such
+    /// as pure-jump Levy processes) override this instead, and the default
+    /// simulation methods then use their increments.
+    ///
+    /// # Arguments:
+    /// * `x` - The process' value at `t`.
+    /// * `t` - The time point.
+    /// * `dt` - The time step.
+    /// * `rng` - The random number generator to draw from.
+    fn simulate_increment(&self, x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+
+        self.drift(x, t) * dt + self.diffusion(x, t) * dW
+    }
+
     /// Euler-Maruyama discretisation scheme with a choice of random seed.
     ///
     /// # Arguments:
@@ -244,46 +1229,46 @@ pub trait StochasticProcess: Sync {
         parallel: bool,
         seed: u64,
     ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+        let config =
+            StochasticProcessConfig::new(x_0, t_0, t_n, n_steps, m_paths, parallel).with_seed(seed);
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = StdRng::seed_from_u64(seed);
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
+        self.euler_maruyama(&config)
     }
 }
 
+/// Simulate an ensemble of processes (e.g. a parameter sweep) with the same
+/// configuration, in parallel across the processes, and across the paths of
+/// each process if `config.parallel` is set.
+///
+/// With a seed, every process is driven by the same random numbers
+/// (common random numbers), so differences between the ensemble members
+/// are down to their parameters rather than sampling noise.
+///
+/// # Arguments:
+/// * `processes` - The processes to simulate.
+/// * `config` - The simulation configuration, shared by all the processes.
+pub fn simulate_ensemble<P>(processes: &[P], config: &StochasticProcessConfig) -> Vec<Trajectories>
+where
+    P: StochasticProcess,
+{
+    processes
+        .par_iter()
+        .map(|process| process.euler_maruyama(config))
+        .collect()
+}
+
 #[cfg(test)]
 mod test_process {
+    use super::{levy_area, multifactor_step, MultifactorProcess};
+    use crate::math::Statistic;
+    use crate::models::cox_ingersoll_ross::CoxIngersollRoss;
     use crate::models::geometric_brownian_motion::GeometricBrownianMotion;
+    use crate::models::{Bates, FractionalBrownianMotion};
     use crate::stochastics::process::StochasticProcess;
-    use crate::stochastics::StochasticProcessConfig;
+    use crate::stochastics::{StochasticProcessConfig, Trajectories};
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
     use std::time::Instant;
 
     #[test]
@@ -330,7 +1315,592 @@ mod test_process {
         // Check that using a different seed gives a different output.
         assert_ne!(output_first_seed.paths, output_different_seed.paths);
 
+        // Check that each path draws from its own sub-stream.
+        assert_ne!(output_first_seed.paths[0], output_first_seed.paths[1]);
+
         // To see the output of this "test", run:
         // cargo test test_process -- --nocapture
     }
+
+    #[test]
+    fn test_seeded_config_reproducible() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.9);
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 0.5);
+
+        let config = |x_0, parallel| {
+            StochasticProcessConfig::new(x_0, 0.0, 1.0, 50, 1000, parallel).with_seed(1234)
+        };
+
+        for (process, x_0) in [
+            (&gbm as &dyn StochasticProcess, 10.0),
+            (&cir as &dyn StochasticProcess, 0.04),
+        ] {
+            let serial = process.euler_maruyama(&config(x_0, false));
+            let parallel = process.euler_maruyama(&config(x_0, true));
+
+            assert_eq!(
+                serial.paths,
+                process.euler_maruyama(&config(x_0, false)).paths
+            );
+            assert_eq!(
+                parallel.paths,
+                process.euler_maruyama(&config(x_0, true)).paths
+            );
+            assert_eq!(serial.paths, parallel.paths);
+            assert_eq!(serial.times, parallel.times);
+
+            let other = StochasticProcessConfig::new(x_0, 0.0, 1.0, 50, 1000, true).with_seed(4321);
+            assert_ne!(serial.paths, process.euler_maruyama(&other).paths);
+        }
+    }
+
+    #[test]
+    fn test_chunked_streams_serial_equals_parallel() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.9);
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 0.5);
+
+        let config = |x_0, parallel, chunk_size| {
+            StochasticProcessConfig::new(x_0, 0.0, 1.0, 50, 1000, parallel)
+                .with_seed(1234)
+                .with_chunk_size(chunk_size)
+        };
+
+        let simulate =
+            |process: &dyn StochasticProcess, config: &StochasticProcessConfig, threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap()
+                    .install(|| process.euler_maruyama(config).paths)
+            };
+
+        for (process, x_0) in [
+            (&gbm as &dyn StochasticProcess, 10.0),
+            (&cir as &dyn StochasticProcess, 0.04),
+        ] {
+            // A chunk size of one is the default.
+            assert_eq!(
+                simulate(process, &config(x_0, false, 1), 1),
+                process
+                    .euler_maruyama(
+                        &StochasticProcessConfig::new(x_0, 0.0, 1.0, 50, 1000, false)
+                            .with_seed(1234)
+                    )
+                    .paths
+            );
+
+            // The last chunk is partial.
+            for chunk_size in [1, 64, 1000] {
+                let serial = simulate(process, &config(x_0, false, chunk_size), 1);
+
+                assert_eq!(serial, simulate(process, &config(x_0, true, chunk_size), 1));
+                assert_eq!(serial, simulate(process, &config(x_0, true, chunk_size), 4));
+
+                // Paths can still be simulated one at a time.
+                let chunked = config(x_0, false, chunk_size);
+                for i in [0, 63, 64, 999] {
+                    assert_eq!(serial[i], process.simulate_path(&chunked, i));
+                }
+            }
+
+            // The streams are keyed by chunk.
+            assert_ne!(
+                simulate(process, &config(x_0, false, 1), 1),
+                simulate(process, &config(x_0, false, 64), 1)
+            );
+        }
+
+        // The exact CIR scheme splits the paths the same way.
+        let serial = cir.exact(&config(0.04, false, 64)).paths;
+        assert_eq!(serial, cir.exact(&config(0.04, true, 64)).paths);
+    }
+
+    #[test]
+    fn test_seeded_terminal_values_thread_count_independent() {
+        use crate::models::{FractionalBrownianMotion, MertonJumpDiffusion};
+        use crate::stochastics::fractional_brownian_motion::FractionalProcessGeneratorMethod;
+
+        let mjd = MertonJumpDiffusion::new(0.05, 0.2, 3.0, -0.1, 0.04);
+        let fbm = FractionalBrownianMotion::new(0.7, FractionalProcessGeneratorMethod::FFT);
+
+        let terminal = |process: &dyn StochasticProcess, seed, threads| {
+            let config = StochasticProcessConfig::new(1.0, 0.0, 1.0, 32, 200, true).with_seed(seed);
+
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| process.euler_maruyama(&config).terminal_values())
+        };
+
+        for process in [&mjd as &dyn StochasticProcess, &fbm] {
+            let first = terminal(process, 11, 1);
+
+            // Bitwise identical for the same seed, however the paths are
+            // split across threads.
+            assert_eq!(first, terminal(process, 11, 1));
+            assert_eq!(first, terminal(process, 11, 3));
+
+            // Different seeds diverge.
+            let other = terminal(process, 12, 1);
+            assert!(first.iter().zip(&other).all(|(a, b)| a != b));
+        }
+    }
+
+    #[test]
+    fn test_simulate_ensemble() {
+        use super::simulate_ensemble;
+
+        let processes: Vec<GeometricBrownianMotion> = [0.1, 0.2, 0.3, 0.4, 0.5]
+            .iter()
+            .map(|&sigma| GeometricBrownianMotion::new(0.05, sigma))
+            .collect();
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 50, 2000, true).with_seed(21);
+
+        let ensemble = simulate_ensemble(&processes, &config);
+
+        assert_eq!(ensemble.len(), processes.len());
+
+        let variances: Vec<f64> = ensemble
+            .iter()
+            .map(|trajectories| trajectories.terminal_values().variance())
+            .collect();
+
+        assert!(
+            variances.windows(2).all(|pair| pair[0] < pair[1]),
+            "{variances:?}"
+        );
+
+        // Each member is the process' own simulation.
+        for (process, trajectories) in processes.iter().zip(&ensemble) {
+            assert_eq!(trajectories.paths, process.euler_maruyama(&config).paths);
+        }
+    }
+
+    #[test]
+    fn test_trajectory_statistics() {
+        let trajectories = Trajectories {
+            times: vec![0.0, 0.5, 1.0],
+            paths: vec![
+                vec![1.0, 2.0, 4.0],
+                vec![1.0, 3.0, 1.0],
+                vec![1.0, 4.0, 7.0],
+            ],
+        };
+
+        assert_eq!(trajectories.mean_path(), vec![1.0, 3.0, 4.0]);
+        assert_eq!(trajectories.std_path(), vec![0.0, 1.0, 3.0]);
+        assert_eq!(trajectories.quantile_path(0.5), vec![1.0, 3.0, 4.0]);
+        assert_eq!(trajectories.quantile_path(0.25), vec![1.0, 2.5, 2.5]);
+        assert_eq!(trajectories.terminal_values(), vec![4.0, 1.0, 7.0]);
+        assert_eq!(trajectories.terminal_quantile(0.75), 5.5);
+        assert_eq!(trajectories.terminal_quantile(1.0), 7.0);
+
+        // Serial and parallel simulations store the paths alike.
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let summary = |parallel| {
+            let config =
+                StochasticProcessConfig::new(10.0, 0.0, 1.0, 20, 500, parallel).with_seed(3);
+            let output = gbm.euler_maruyama(&config);
+
+            (
+                output.mean_path(),
+                output.std_path(),
+                output.quantile_path(0.9),
+            )
+        };
+
+        assert_eq!(summary(false), summary(true));
+    }
+
+    #[test]
+    fn test_increment_autocorrelation() {
+        use crate::models::FractionalBrownianMotion;
+        use crate::stochastics::fractional_brownian_motion::FractionalProcessGeneratorMethod;
+
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 250, 1000, true).with_seed(5);
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2).euler_maruyama(&config);
+        let acf = gbm.increment_autocorrelation(5);
+
+        assert_eq!(acf.len(), 6);
+        assert_approx_equal!(acf[0], 1.0, 1e-12);
+        assert!(acf[1..].iter().all(|rho| rho.abs() < 0.02), "{acf:?}");
+
+        // Fractional Gaussian noise has lag-1 autocorrelation 2^{2H - 1} - 1.
+        let hurst = 0.7_f64;
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 250, 200, false);
+        let fbm = FractionalBrownianMotion::new(hurst, FractionalProcessGeneratorMethod::FFT)
+            .euler_maruyama(&config);
+        let acf = fbm.increment_autocorrelation(3);
+
+        assert_approx_equal!(acf[1], 2_f64.powf(2.0 * hurst - 1.0) - 1.0, 0.05);
+        assert!(
+            acf[1] > acf[2] && acf[2] > acf[3] && acf[3] > 0.0,
+            "{acf:?}"
+        );
+    }
+
+    #[test]
+    fn test_trajectories_csv_round_trip() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 12, 5, false).with_seed(8);
+        let trajectories = gbm.euler_maruyama(&config);
+
+        let path = std::env::temp_dir().join("rustquant_trajectories.csv");
+        let path = path.to_str().unwrap();
+
+        trajectories.to_csv(path).unwrap();
+        let read = Trajectories::from_csv(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(read.times, trajectories.times);
+        assert_eq!(read.paths, trajectories.paths);
+    }
+
+    #[test]
+    fn test_trajectories_to_dataframe() {
+        use polars::prelude::DataType;
+
+        let trajectories = Trajectories {
+            times: vec![0.0, 0.5, 1.0],
+            paths: vec![vec![1.0, 2.0, 4.0], vec![1.0, 3.0, 1.0]],
+        };
+
+        let df = trajectories.to_dataframe().unwrap();
+
+        assert_eq!(df.shape(), (6, 3));
+        assert_eq!(df.column("sim").unwrap().dtype(), &DataType::UInt64);
+
+        let column = |name| -> Vec<f64> {
+            df.column(name)
+                .unwrap()
+                .cast(&DataType::Float64)
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect()
+        };
+
+        assert_eq!(column("time"), vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0]);
+        assert_eq!(column("sim"), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(column("value"), vec![1.0, 2.0, 4.0, 1.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn test_path_iter() {
+        use crate::models::FractionalBrownianMotion;
+        use crate::stochastics::fractional_brownian_motion::FractionalProcessGeneratorMethod;
+        use rayon::prelude::*;
+
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 0.5);
+        let fbm = FractionalBrownianMotion::new(0.7, FractionalProcessGeneratorMethod::FFT);
+
+        let config = |x_0| StochasticProcessConfig::new(x_0, 0.0, 1.0, 50, 500, true).with_seed(8);
+
+        // Paths simulated one at a time are those of the full simulation,
+        // also for processes with their own scheme.
+        for (process, x_0) in [
+            (&gbm as &dyn StochasticProcess, 10.0),
+            (&cir, 0.04),
+            (&fbm, 0.0),
+        ] {
+            let config = config(x_0);
+            let paths = process.euler_maruyama(&config).paths;
+
+            assert!((0..config.m_paths).all(|i| process.simulate_path(&config, i) == paths[i]));
+        }
+
+        let config = config(10.0);
+        let paths = gbm.euler_maruyama(&config).paths;
+
+        let mut stream = gbm.path_iter(&config);
+        assert_eq!(stream.len(), 500);
+        assert_eq!(stream.next().as_ref(), Some(&paths[0]));
+        assert_eq!(stream.len(), 499);
+
+        assert_eq!(gbm.path_iter(&config).collect::<Vec<_>>(), paths);
+        assert_eq!(gbm.par_path_iter(&config).collect::<Vec<_>>(), paths);
+
+        // Bridged into rayon, the same paths arrive in any order.
+        let sorted = |mut paths: Vec<Vec<f64>>| {
+            paths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            paths
+        };
+
+        assert_eq!(
+            sorted(gbm.path_iter(&config).par_bridge().collect()),
+            sorted(paths)
+        );
+    }
+
+    #[test]
+    fn test_simulate_terminal() {
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 0.8);
+
+        for parallel in [false, true] {
+            let config =
+                StochasticProcessConfig::new(0.03, 0.0, 1.0, 50, 1000, parallel).with_seed(99);
+
+            let terminal = cir.simulate_terminal(&config);
+
+            assert_eq!(terminal, cir.euler_maruyama(&config).terminal_values());
+        }
+    }
+
+    #[test]
+    fn test_predictor_corrector_reduces_mean_reversion_bias() {
+        use crate::models::OrnsteinUhlenbeck;
+
+        // Fast mean reversion to zero, with only ten steps.
+        let (kappa, x_0) = (5.0, 1.0);
+        let ou = OrnsteinUhlenbeck::new(0.0, 0.1, kappa);
+        let config = StochasticProcessConfig::new(x_0, 0.0, 1.0, 10, 20_000, true).with_seed(8);
+
+        let exact = x_0 * (-kappa * 1.0_f64).exp();
+        let error = |output: Trajectories| (output.terminal_values().mean() - exact).abs();
+
+        let euler = error(ou.euler_maruyama(&config));
+        let predictor_corrector = error(ou.predictor_corrector(&config));
+
+        // The means of the schemes are (1 - k dt)^n and (1 - k dt + (k dt)^2 / 2)^n.
+        assert!(predictor_corrector < 0.5 * euler);
+        assert_approx_equal!(euler, exact - 0.5_f64.powi(10), 1e-3);
+        assert_approx_equal!(predictor_corrector, 0.625_f64.powi(10) - exact, 1e-3);
+
+        // With zero weights, the scheme is Euler-Maruyama.
+        assert_eq!(
+            ou.predictor_corrector_with(&config, 0.0, 0.0).paths,
+            ou.euler_maruyama(&config).paths
+        );
+    }
+
+    /// Unit drift, with a diffusion that is NaN (or panics) above a level.
+    struct Breaking {
+        level: f64,
+        panics: bool,
+    }
+
+    impl StochasticProcess for Breaking {
+        fn drift(&self, _x: f64, _t: f64) -> f64 {
+            1.0
+        }
+
+        fn diffusion(&self, x: f64, _t: f64) -> f64 {
+            match (x > self.level, self.panics) {
+                (false, _) => 0.0,
+                (true, false) => f64::NAN,
+                (true, true) => panic!("diffusion above {}", self.level),
+            }
+        }
+
+        fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_try_euler_maruyama_reports_failures() {
+        for parallel in [false, true] {
+            let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 100, parallel);
+
+            // The state passes 0.5 after six steps of 0.1.
+            let error = Breaking {
+                level: 0.55,
+                panics: false,
+            }
+            .try_euler_maruyama(&config)
+            .err()
+            .unwrap();
+
+            assert_eq!((error.path, error.step), (0, 6));
+            assert_approx_equal!(error.time, 0.6, 1e-12);
+            assert_approx_equal!(error.state, 0.6, 1e-12);
+            assert_eq!(error.reason, "non-finite value NaN");
+
+            let error = Breaking {
+                level: 0.55,
+                panics: true,
+            }
+            .try_euler_maruyama(&config)
+            .err()
+            .unwrap();
+
+            // The panic is in the step from 0.6, as for the NaN.
+            assert_eq!((error.path, error.step), (0, 6));
+            assert_approx_equal!(error.time, 0.6, 1e-12);
+            assert_approx_equal!(error.state, 0.6, 1e-12);
+            assert_eq!(error.reason, "panicked: diffusion above 0.55");
+            assert!(error.to_string().contains("path 0 at step 6"));
+        }
+
+        // Below the level, nothing fails.
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 100, true);
+        let output = Breaking {
+            level: 2.0,
+            panics: true,
+        }
+        .try_euler_maruyama(&config)
+        .unwrap();
+
+        assert_approx_equal!(output.paths[99][10], 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_try_euler_maruyama_matches_euler_maruyama() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.9);
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 125, 2000, false).with_seed(3);
+
+        // Same paths.
+        assert_eq!(
+            gbm.euler_maruyama(&config).paths,
+            gbm.try_euler_maruyama(&config).unwrap().paths
+        );
+
+        // Processes with their own scheme keep it.
+        let bates = Bates::new(0.04, 0.04, 2.0, -0.5, 0.3, 0.5, -0.1, 0.15).with_drift(0.05);
+        let fbm = FractionalBrownianMotion::new(0.7, Default::default());
+
+        for parallel in [false, true] {
+            let config =
+                StochasticProcessConfig::new(100.0, 0.0, 1.0, 64, 200, parallel).with_seed(5);
+
+            assert_eq!(
+                StochasticProcess::euler_maruyama(&bates, &config).paths,
+                bates.try_euler_maruyama(&config).unwrap().paths
+            );
+            assert_eq!(
+                fbm.euler_maruyama(&config).paths,
+                fbm.try_euler_maruyama(&config).unwrap().paths
+            );
+        }
+    }
+
+    #[test]
+    fn test_change_measure() {
+        let (mu, sigma, r) = (0.12, 0.2, 0.03);
+
+        let real_world = GeometricBrownianMotion::new(mu, sigma);
+        let risk_neutral = real_world.change_measure(|_x, _t| (mu - r) / sigma);
+
+        // The risk-neutral drift is r * x.
+        for x in [1.0, 10.0, 100.0] {
+            assert!((risk_neutral.drift(x, 0.5) - r * x).abs() < 1e-12);
+            assert_eq!(risk_neutral.diffusion(x, 0.5), real_world.diffusion(x, 0.5));
+        }
+
+        // Discounted prices are martingales.
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 10000, true).with_seed(7);
+        let terminal: Vec<f64> = risk_neutral
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|path| (-r * 1.0_f64).exp() * path[100])
+            .collect();
+
+        let mean = terminal.mean();
+        let standard_error = (terminal.variance() / terminal.len() as f64).sqrt();
+
+        assert!((mean - 100.0).abs() < 4.0 * standard_error);
+    }
+
+    /// $dX_1 = dW_1$, $dX_2 = X_1 dW_2$, with non-commutative noise, so that
+    /// $X_2(T) = X_2(0) + X_1(0) W_2(T) + \int_0^T W_1 dW_2$ depends on the Levy area.
+    struct IteratedIntegral;
+
+    impl MultifactorProcess for IteratedIntegral {
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn drift(&self, _x: &[f64], _t: f64) -> Vec<f64> {
+            vec![0.0, 0.0]
+        }
+
+        fn diffusion(&self, x: &[f64], _t: f64) -> Vec<f64> {
+            vec![1.0, x[0]]
+        }
+
+        fn correlation(&self) -> DMatrix<f64> {
+            DMatrix::identity(2, 2)
+        }
+    }
+
+    #[test]
+    fn test_milstein_strong_order() {
+        const FINE_STEPS: usize = 2048;
+        const M_PATHS: usize = 300;
+
+        let process = IteratedIntegral;
+        let cholesky = DMatrix::<f64>::identity(2, 2);
+        let x_0 = vec![1.0, 0.0];
+        let fine_dt = 1.0 / FINE_STEPS as f64;
+
+        let n_steps = [2, 4, 8, 16];
+        let mut euler_errors = [0.0; 4];
+        let mut milstein_errors = [0.0; 4];
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..M_PATHS {
+            let fine: Vec<DVector<f64>> = (0..FINE_STEPS)
+                .map(|_| {
+                    DVector::from_fn(2, |_, _| rng.sample::<f64, _>(StandardNormal))
+                        * fine_dt.sqrt()
+                })
+                .collect();
+
+            let reference = fine.iter().fold(x_0.clone(), |x, dW| {
+                multifactor_step(&process, &x, 0.0, fine_dt, &cholesky, dW, None)
+            });
+
+            for (level, &n) in n_steps.iter().enumerate() {
+                let dt = 1.0 / n as f64;
+
+                // Sub-sample each step's Levy area on n sub-steps, i.e. ~1 / dt.
+                let (mut euler, mut milstein) = (x_0.clone(), x_0.clone());
+
+                for step in fine.chunks(FINE_STEPS / n) {
+                    let substeps: Vec<DVector<f64>> = step
+                        .chunks(FINE_STEPS / (n * n))
+                        .map(|chunk| chunk.iter().sum())
+                        .collect();
+
+                    let (dW, area) = levy_area(&substeps);
+
+                    euler = multifactor_step(&process, &euler, 0.0, dt, &cholesky, &dW, None);
+                    milstein =
+                        multifactor_step(&process, &milstein, 0.0, dt, &cholesky, &dW, Some(&area));
+                }
+
+                euler_errors[level] += (euler[1] - reference[1]).powi(2) / M_PATHS as f64;
+                milstein_errors[level] += (milstein[1] - reference[1]).powi(2) / M_PATHS as f64;
+            }
+        }
+
+        // Least-squares slope of log(RMS error) against log(dt).
+        let order = |errors: &[f64]| -> f64 {
+            let x: Vec<f64> = n_steps.iter().map(|&n| -(n as f64).ln()).collect();
+            let y: Vec<f64> = errors.iter().map(|e| 0.5 * e.ln()).collect();
+            let (x_bar, y_bar) = (x.mean(), y.mean());
+
+            x.iter()
+                .zip(&y)
+                .map(|(x, y)| (x - x_bar) * (y - y_bar))
+                .sum::<f64>()
+                / x.iter().map(|x| (x - x_bar).powi(2)).sum::<f64>()
+        };
+
+        let (euler_order, milstein_order) = (order(&euler_errors), order(&milstein_errors));
+
+        assert!(
+            (euler_order - 0.5).abs() < 0.15,
+            "Euler order: {euler_order}"
+        );
+        assert!(
+            (milstein_order - 1.0).abs() < 0.15,
+            "Milstein order: {milstein_order}"
+        );
+    }
 }
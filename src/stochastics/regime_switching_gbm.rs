@@ -0,0 +1,350 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Regime-switching Geometric Brownian Motion.
+//!
+//! The asset follows
+//! $dS(t) = \mu_{Z(t)} S(t) dt + \sigma_{Z(t)} S(t) dW(t)$,
+//! where the regime $Z$ is a continuous-time Markov chain with generator
+//! $Q$: it leaves regime $i$ at rate $-q_{ii}$, for regime $j$ with
+//! probability $q_{ij} / (-q_{ii})$.
+//!
+//! Each regime path is sampled first, from exponential holding times. Given
+//! the time $\tau_j$ spent in each regime over a step, the log-return is
+//! Gaussian with mean $\sum_j (\mu_j - \sigma_j^2 / 2) \tau_j$ and variance
+//! $\sum_j \sigma_j^2 \tau_j$, so the prices are sampled exactly.
+
+use crate::error::RustQuantError;
+use crate::stochastics::{StochasticProcessConfig, Trajectories};
+use nalgebra::DMatrix;
+use rand::{Rng, RngCore};
+use rand_distr::{Exp1, StandardNormal};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Geometric Brownian Motion whose drift and volatility switch between
+/// regimes, following a continuous-time Markov chain.
+pub struct RegimeSwitchingGBM {
+    /// The drift in each regime ($\mu_i$).
+    pub mu: Vec<f64>,
+
+    /// The volatility in each regime ($\sigma_i$).
+    pub sigma: Vec<f64>,
+
+    /// Generator matrix of the regime chain ($Q$).
+    pub generator: DMatrix<f64>,
+
+    /// Regime at the initial time.
+    pub initial_regime: usize,
+}
+
+/// Simulated regime-switching paths.
+pub struct RegimeSwitchingTrajectories {
+    /// Asset price paths.
+    pub paths: Trajectories,
+
+    /// Regime of each path at each time point.
+    pub regimes: Vec<Vec<usize>>,
+
+    /// Regime switches of each path, as (time, new regime).
+    pub switches: Vec<Vec<(f64, usize)>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RegimeSwitchingGBM {
+    /// Create a new regime-switching Geometric Brownian Motion.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the drifts, volatilities, and
+    ///   generator do not have one entry (row and column) per regime.
+    /// - `RustQuantError::InvalidArgument` if the generator has a negative
+    ///   off-diagonal entry or a row that does not sum to zero, a volatility
+    ///   is negative, or the initial regime does not exist.
+    pub fn new(
+        mu: Vec<f64>,
+        sigma: Vec<f64>,
+        generator: DMatrix<f64>,
+        initial_regime: usize,
+    ) -> Result<Self, RustQuantError> {
+        const TOLERANCE: f64 = 1e-12;
+
+        let n = mu.len();
+
+        if sigma.len() != n || generator.nrows() != n || generator.ncols() != n {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        for i in 0..n {
+            if (0..n).any(|j| j != i && generator[(i, j)] < 0.0) {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Generator row {i} has a negative off-diagonal rate."
+                )));
+            }
+
+            let row = generator.row(i);
+            let scale = row.iter().map(|q| q.abs()).sum::<f64>().max(1.0);
+
+            if row.sum().abs() > TOLERANCE * scale {
+                return Err(RustQuantError::InvalidArgument(format!(
+                    "Generator row {i} sums to {}, not zero.",
+                    row.sum()
+                )));
+            }
+        }
+
+        if sigma.iter().any(|&s| s < 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Volatilities must be non-negative.".to_string(),
+            ));
+        }
+
+        if initial_regime >= n {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Initial regime {initial_regime} does not exist (there are {n} regimes)."
+            )));
+        }
+
+        Ok(Self {
+            mu,
+            sigma,
+            generator,
+            initial_regime,
+        })
+    }
+
+    /// Number of regimes.
+    pub fn n_regimes(&self) -> usize {
+        self.mu.len()
+    }
+
+    /// Simulate price paths, with their regime paths.
+    pub fn simulate(&self, config: &StochasticProcessConfig) -> RegimeSwitchingTrajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, _) = config.unpack();
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |i: usize| {
+            let mut rng = config.path_rng(i);
+            let switches = self.regime_path(t_0, t_n, &mut rng);
+            let (path, regimes) = self.diffuse(x_0, &times, &switches, &mut rng);
+
+            (path, regimes, switches)
+        };
+
+        let simulated: Vec<_> = config.map_paths(path_generator);
+
+        let mut output = RegimeSwitchingTrajectories {
+            paths: Trajectories {
+                times,
+                paths: Vec::with_capacity(m_paths),
+            },
+            regimes: Vec::with_capacity(m_paths),
+            switches: Vec::with_capacity(m_paths),
+        };
+
+        for (path, regimes, switches) in simulated {
+            output.paths.paths.push(path);
+            output.regimes.push(regimes);
+            output.switches.push(switches);
+        }
+
+        output
+    }
+
+    /// Regime switches on `(t_0, t_n]`, from exponential holding times.
+    fn regime_path(&self, t_0: f64, t_n: f64, rng: &mut dyn RngCore) -> Vec<(f64, usize)> {
+        let mut switches = Vec::new();
+        let (mut t, mut regime) = (t_0, self.initial_regime);
+
+        loop {
+            let exit_rate = -self.generator[(regime, regime)];
+
+            if exit_rate <= 0.0 {
+                break;
+            }
+
+            t += rng.sample::<f64, _>(Exp1) / exit_rate;
+
+            if t > t_n {
+                break;
+            }
+
+            // Next regime, with probability proportional to its rate.
+            let mut u = rng.gen::<f64>() * exit_rate;
+            regime = (0..self.n_regimes())
+                .filter(|&j| j != regime && self.generator[(regime, j)] > 0.0)
+                .find(|&j| {
+                    u -= self.generator[(regime, j)];
+                    u <= 0.0
+                })
+                .unwrap_or_else(|| {
+                    // Rounding: take the last reachable regime.
+                    (0..self.n_regimes())
+                        .rev()
+                        .find(|&j| j != regime && self.generator[(regime, j)] > 0.0)
+                        .unwrap()
+                });
+
+            switches.push((t, regime));
+        }
+
+        switches
+    }
+
+    /// Price path on `times`, and the regime at each time, given the switches.
+    fn diffuse(
+        &self,
+        x_0: f64,
+        times: &[f64],
+        switches: &[(f64, usize)],
+        rng: &mut dyn RngCore,
+    ) -> (Vec<f64>, Vec<usize>) {
+        let mut path = Vec::with_capacity(times.len());
+        let mut regimes = Vec::with_capacity(times.len());
+
+        let (mut x, mut regime) = (x_0, self.initial_regime);
+        let mut next = 0;
+
+        path.push(x);
+        regimes.push(regime);
+
+        for window in times.windows(2) {
+            let (mut t, end) = (window[0], window[1]);
+            let (mut mean, mut variance) = (0.0, 0.0);
+
+            // Occupation of each regime over the step.
+            loop {
+                let switch = switches.get(next).filter(|&&(time, _)| time <= end);
+                let until = switch.map_or(end, |&(time, _)| time);
+                let (mu, sigma) = (self.mu[regime], self.sigma[regime]);
+
+                mean += (mu - 0.5 * sigma * sigma) * (until - t);
+                variance += sigma * sigma * (until - t);
+                t = until;
+
+                match switch {
+                    Some(&(_, to)) => {
+                        regime = to;
+                        next += 1;
+                    }
+                    None => break,
+                }
+            }
+
+            if variance > 0.0 {
+                mean += variance.sqrt() * rng.sample::<f64, _>(StandardNormal);
+            }
+
+            x *= mean.exp();
+            path.push(x);
+            regimes.push(regime);
+        }
+
+        (path, regimes)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_regime_switching_gbm {
+    use super::*;
+    use nalgebra::dmatrix;
+
+    #[test]
+    fn test_invalid_generator() {
+        let mu = vec![0.05, 0.0];
+        let sigma = vec![0.2, 0.4];
+
+        // Row does not sum to zero.
+        let q = dmatrix![-1.0, 0.5; 1.0, -1.0];
+        assert!(RegimeSwitchingGBM::new(mu.clone(), sigma.clone(), q, 0).is_err());
+
+        // Negative off-diagonal rate.
+        let q = dmatrix![1.0, -1.0; 1.0, -1.0];
+        assert!(RegimeSwitchingGBM::new(mu.clone(), sigma.clone(), q, 0).is_err());
+
+        // Wrong dimensions.
+        let q = dmatrix![-1.0, 0.5, 0.5; 1.0, -1.0, 0.0; 0.0, 1.0, -1.0];
+        assert!(RegimeSwitchingGBM::new(mu.clone(), sigma.clone(), q, 0).is_err());
+
+        // Non-existent initial regime.
+        let q = dmatrix![-1.0, 1.0; 2.0, -2.0];
+        assert!(RegimeSwitchingGBM::new(mu.clone(), sigma.clone(), q.clone(), 2).is_err());
+
+        assert!(RegimeSwitchingGBM::new(mu, sigma, q, 1).is_ok());
+    }
+
+    #[test]
+    fn test_zero_volatility_regime_is_flat() {
+        // Regime 1 is calm: no drift, no volatility.
+        let q = dmatrix![-2.0, 2.0; 3.0, -3.0];
+        let rsgbm = RegimeSwitchingGBM::new(vec![0.1, 0.0], vec![0.3, 0.0], q, 0).unwrap();
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 5.0, 500, 20, false).with_seed(42);
+        let output = rsgbm.simulate(&config);
+        let times = &output.paths.times;
+
+        let (mut flat, mut moving) = (0, 0);
+
+        for ((path, regimes), switches) in output
+            .paths
+            .paths
+            .iter()
+            .zip(&output.regimes)
+            .zip(&output.switches)
+        {
+            assert_eq!(path.len(), times.len());
+            assert_eq!(regimes.len(), times.len());
+
+            for k in 0..times.len() - 1 {
+                let switched = switches
+                    .iter()
+                    .any(|&(time, _)| time > times[k] && time <= times[k + 1]);
+
+                if regimes[k] == 1 && !switched {
+                    // Whole step in the calm regime.
+                    assert_eq!(path[k + 1], path[k]);
+                    flat += 1;
+                } else {
+                    assert_ne!(path[k + 1], path[k]);
+                    moving += 1;
+                }
+            }
+        }
+
+        // Stationary distribution is (0.6, 0.4), so both kinds of step occur.
+        let calm_fraction = flat as f64 / (flat + moving) as f64;
+        assert!((calm_fraction - 0.4).abs() < 0.1, "{calm_fraction}");
+    }
+
+    #[test]
+    fn test_seeded_reproducibility() {
+        let q = dmatrix![-1.0, 0.5, 0.5; 0.2, -0.4, 0.2; 1.0, 1.0, -2.0];
+        let rsgbm =
+            RegimeSwitchingGBM::new(vec![0.05, 0.0, -0.1], vec![0.1, 0.2, 0.5], q, 0).unwrap();
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, 1.0, 100, 50, true).with_seed(7);
+        let a = rsgbm.simulate(&config);
+        let b = rsgbm.simulate(&config);
+
+        assert_eq!(a.paths.paths, b.paths.paths);
+        assert_eq!(a.regimes, b.regimes);
+        assert!(a.regimes.iter().flatten().all(|&r| r < 3));
+    }
+}
@@ -0,0 +1,31 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Markov processes with known transition densities.
+//!
+//! Likelihood-ratio methods, such as the stochastic mesh, need to both sample
+//! a step of the process and evaluate the density of where it lands.
+
+use rand::RngCore;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// (Possibly multi-dimensional) Markov process with a known transition density.
+pub trait TransitionDensity: Sync {
+    /// Dimension of the state.
+    fn dimension(&self) -> usize;
+
+    /// Sample the state at `t + dt`, given the state `x` at `t`.
+    fn sample_transition(&self, x: &[f64], t: f64, dt: f64, rng: &mut dyn RngCore) -> Vec<f64>;
+
+    /// Density of the state at `t + dt` being `y`, given the state `x` at `t`.
+    fn transition_density(&self, x: &[f64], y: &[f64], t: f64, dt: f64) -> f64;
+}
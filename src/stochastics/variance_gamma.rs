@@ -0,0 +1,114 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! The Variance Gamma process, $X(t) = \theta G(t) + \sigma W(G(t))$,
+//! where $G$ is a gamma process with $E[G(t)] = t$ and $V[G(t)] = \nu t$.
+//!
+//! The process is pure-jump, so it has no drift/diffusion form to
+//! discretise. Instead it is simulated exactly, as gamma time-changed
+//! Brownian motion: each step samples the gamma time increment
+//! $\Delta G \sim \Gamma(\Delta t / \nu, \nu)$, then the Brownian increment
+//! conditional on it, $\theta \Delta G + \sigma \sqrt{\Delta G} Z$.
+//!
+//! Used as the log-return of an asset, $S(t) = S(0) e^{(r + \omega) t + X(t)}$
+//! with $\omega = \ln(1 - \theta \nu - \sigma^2 \nu / 2) / \nu$ is a
+//! risk-neutral price process.
+
+use crate::models::VarianceGamma;
+use crate::stochastics::StochasticProcess;
+use rand::{Rng, RngCore};
+use rand_distr::{Gamma, StandardNormal};
+
+impl StochasticProcess for VarianceGamma {
+    /// The mean rate, $E[dX(t)] = \theta dt$.
+    fn drift(&self, _x: f64, t: f64) -> f64 {
+        self.theta.0(t)
+    }
+
+    /// The process has no diffusion term.
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.sigma.0(0.0), self.theta.0(0.0), self.nu.0(0.0)]
+    }
+
+    fn simulate_increment(&self, _x: f64, t: f64, dt: f64, rng: &mut dyn RngCore) -> f64 {
+        let (sigma, theta, nu) = (self.sigma.0(t), self.theta.0(t), self.nu.0(t));
+
+        assert!(sigma >= 0.0 && nu > 0.0);
+
+        let dG: f64 = rng.sample(Gamma::new(dt / nu, nu).unwrap());
+        let z: f64 = rng.sample(StandardNormal);
+
+        theta * dG + sigma * dG.sqrt() * z
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_variance_gamma {
+    use super::*;
+    use crate::stochastics::StochasticProcessConfig;
+
+    #[test]
+    fn test_variance_gamma_moments() {
+        let (sigma, theta, nu): (f64, f64, f64) = (0.2, -0.15, 0.3);
+        let vg = VarianceGamma::new(sigma, theta, nu);
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 4, 100_000, false).with_seed(7);
+        let paths = vg.euler_maruyama(&config);
+
+        assert!(paths.paths.iter().all(|path| path[0] == 0.0));
+
+        // Log-returns over [0, 1].
+        let x = paths.terminal_values();
+        let n = x.len() as f64;
+
+        let mean = x.iter().sum::<f64>() / n;
+        let central = |k: i32| x.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n;
+        let variance = central(2);
+        let skewness = central(3) / variance.powf(1.5);
+        let excess_kurtosis = central(4) / variance.powi(2) - 3.0;
+
+        // Analytic moments at t = 1, from the cumulants of the VG law.
+        let vg_variance = sigma.powi(2) + nu * theta.powi(2);
+        let vg_skewness = (2.0 * theta.powi(3) * nu.powi(2) + 3.0 * sigma.powi(2) * theta * nu)
+            / vg_variance.powf(1.5);
+        let vg_excess_kurtosis = (3.0 * sigma.powi(4) * nu
+            + 12.0 * sigma.powi(2) * theta.powi(2) * nu.powi(2)
+            + 6.0 * theta.powi(4) * nu.powi(3))
+            / vg_variance.powi(2);
+
+        assert!((mean - theta).abs() < 0.002, "mean = {mean}");
+        assert!(
+            (variance - vg_variance).abs() < 0.001,
+            "variance = {variance}"
+        );
+        assert!(
+            (skewness - vg_skewness).abs() < 0.05,
+            "skewness = {skewness}"
+        );
+        assert!(
+            (excess_kurtosis - vg_excess_kurtosis).abs() < 0.15,
+            "excess kurtosis = {excess_kurtosis}, expected {vg_excess_kurtosis}"
+        );
+
+        // Exact simulation: the terminal values match the paths' end points.
+        assert_eq!(vg.simulate_terminal(&config), x);
+    }
+}
@@ -34,7 +34,7 @@ use time::{util::is_leap_year, Date, Duration, Month};
 /// payment dates, the seller is eligible to some fraction of the coupon amount.
 /// """
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DayCountConvention {
     /// The '1/1' day count, which always returns a day count of 1.
     One_One,
@@ -80,6 +80,7 @@ pub enum DayCountConvention {
     No_Leap_365,
 
     /// The '30/360 ISDA' day count, which treats input day-of-month 31 specially.
+    /// Also known as '30/360 Bond Basis' (ISDA 2006, section 4.16(f)).
     Thirty_360_ISDA,
 
     /// The '30E/360' day count, which treats input day-of-month 31 specially.
@@ -98,6 +99,12 @@ pub enum DayCountConvention {
     Thirty_U_360,
 }
 
+impl DayCountConvention {
+    /// The '30/360 Bond Basis' day count, the same as [`Self::Thirty_360_ISDA`].
+    #[allow(non_upper_case_globals)]
+    pub const Thirty_360_Bond_Basis: Self = Self::Thirty_360_ISDA;
+}
+
 /// `DayCounter` trait.
 ///
 /// This trait is used to compute:
@@ -572,7 +579,10 @@ impl DayCountConvention {
             d1 = 30;
         }
 
-        if d2 == 31 && d1 == 30 || is_last_day_of_february(end_date) {
+        // The end of February only rolls to the 30th if the period also starts on it.
+        if d2 == 31 && d1 == 30
+            || is_last_day_of_february(start_date) && is_last_day_of_february(end_date)
+        {
             d2 = 30;
         }
 
@@ -631,6 +641,70 @@ mod TESTS_thirty_360 {
 
         assert_approx_equal!(dcf, 1.515_068_493, RUSTQUANT_EPSILON);
     }
+
+    #[test]
+    fn thirty_360_bond_basis() {
+        let convention = DayCountConvention::Thirty_360_Bond_Basis;
+
+        // (start, end, 30/360 days)
+        let cases = [
+            (date!(2007 - 01 - 15), date!(2007 - 01 - 31), 16.0),
+            (date!(2007 - 01 - 31), date!(2007 - 02 - 28), 28.0),
+            (date!(2007 - 01 - 30), date!(2008 - 03 - 31), 420.0),
+            (date!(2007 - 02 - 28), date!(2008 - 02 - 29), 361.0),
+            (date!(2007 - 08 - 31), date!(2008 - 02 - 29), 179.0),
+            (date!(2008 - 02 - 29), date!(2008 - 03 - 31), 32.0),
+        ];
+
+        assert_eq!(convention, DayCountConvention::Thirty_360_ISDA);
+
+        for (start, end, days) in cases {
+            assert_approx_equal!(
+                convention.day_count_factor(start, end),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn thirty_e_360() {
+        // (start, end, 30E/360 days)
+        let cases = [
+            (date!(2007 - 01 - 15), date!(2007 - 01 - 31), 15.0),
+            (date!(2007 - 01 - 31), date!(2007 - 03 - 31), 60.0),
+            (date!(2007 - 02 - 28), date!(2008 - 02 - 29), 361.0),
+            (date!(2007 - 08 - 31), date!(2008 - 02 - 29), 179.0),
+            (date!(2008 - 02 - 29), date!(2008 - 03 - 31), 31.0),
+        ];
+
+        for (start, end, days) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Thirty_E_360.day_count_factor(start, end),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn thirty_u_360() {
+        // (start, end, 30U/360 days)
+        let cases = [
+            (date!(2007 - 02 - 28), date!(2008 - 02 - 29), 360.0),
+            (date!(2007 - 01 - 15), date!(2008 - 02 - 29), 404.0),
+            (date!(2008 - 02 - 29), date!(2008 - 03 - 31), 30.0),
+            (date!(2007 - 01 - 15), date!(2007 - 01 - 31), 16.0),
+        ];
+
+        for (start, end, days) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Thirty_U_360.day_count_factor(start, end),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -688,6 +762,30 @@ mod TESTS_actual_constant {
         }
     }
 
+    #[test]
+    fn actual_360_and_actual_365_fixed() {
+        // (start, end, actual days), across the 2008 leap day.
+        let cases = [
+            (date!(2008 - 02 - 28), date!(2008 - 03 - 31), 32.0),
+            (date!(2007 - 12 - 31), date!(2008 - 12 - 31), 366.0),
+            (date!(2008 - 01 - 01), date!(2009 - 01 - 01), 366.0),
+            (date!(2009 - 01 - 01), date!(2010 - 01 - 01), 365.0),
+        ];
+
+        for (start, end, days) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Actual_360.day_count_factor(start, end),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+            assert_approx_equal!(
+                DayCountConvention::Actual_365_Fixed.day_count_factor(start, end),
+                days / 365.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
     #[test]
     fn actual_366() {
         // Test cases from QuantLib.
@@ -755,6 +853,38 @@ mod TESTS_actual_actual {
         );
     }
 
+    #[test]
+    fn actual_actual_isda_leap_years() {
+        // (start, end, expected): days in each calendar year over its length.
+        let cases = [
+            (
+                date!(2007 - 12 - 28),
+                date!(2008 - 02 - 28),
+                4.0 / 365.0 + 58.0 / 366.0,
+            ),
+            (
+                date!(2007 - 12 - 28),
+                date!(2008 - 02 - 29),
+                4.0 / 365.0 + 59.0 / 366.0,
+            ),
+            (
+                date!(2008 - 02 - 29),
+                date!(2009 - 02 - 28),
+                307.0 / 366.0 + 58.0 / 365.0,
+            ),
+            (date!(2008 - 01 - 01), date!(2008 - 12 - 31), 365.0 / 366.0),
+            (date!(2006 - 01 - 01), date!(2010 - 01 - 01), 4.0),
+        ];
+
+        for (start, end, expected) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Actual_Actual_ISDA.day_count_factor(start, end),
+                expected,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
     #[test]
     fn actual_actual_icma() {
         // Test cases from QuantLib.